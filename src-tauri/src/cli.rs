@@ -0,0 +1,175 @@
+// Command-line interface
+// Lets IDE tasks, plugins and shells query the active profile and control instances
+// without opening the window. See docs/IDE_INTEGRATION.md for the documented output.
+
+use std::path::PathBuf;
+
+use crate::commands::environment::{shell_alias_snippet, ShellKind};
+use crate::commands::ide::{active_environment, write_ide_config, IdeTarget};
+use crate::commands::instance::{load_instances, start_instance, stop_instance};
+use crate::commands::lock::check_command_allowed;
+use crate::commands::profile::{list_profiles, switch_profile};
+
+const USAGE: &str = "Usage: aem-env-manager <command> [args]
+
+Commands:
+  env                          Active profile, JAVA_HOME and instances as JSON
+  java-home                    Print the active profile's JAVA_HOME
+  switch <profile>             Switch to a profile (id or name)
+  start <instance>             Start an instance (id or name)
+  stop <instance>              Stop an instance (id or name)
+  ide-config <dir> [vscode] [intellij]
+                               Write IDE settings for a project from the active profile
+  list <profiles|instances>    Print profile or instance names, one per line
+  aliases [bash|zsh|fish|powershell]
+                               Print shell functions (aemstart, aemswitch, ...) with completion
+  help                         Show this help
+
+Running without a command opens the application window.";
+
+/// Subcommands recognized on the command line
+const COMMANDS: [&str; 10] = [
+    "env", "java-home", "switch", "start", "stop", "ide-config", "list", "aliases", "help", "--help",
+];
+
+/// Run a CLI command when one was given; returns the exit code,
+/// or None to continue with the normal GUI startup
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let command = args.get(1)?;
+    if !COMMANDS.contains(&command.as_str()) {
+        return None;
+    }
+
+    let result = tauri::async_runtime::block_on(execute(command, &args[2..]));
+    Some(match result {
+        Ok(output) => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    })
+}
+
+async fn execute(command: &str, args: &[String]) -> Result<String, String> {
+    match command {
+        "env" => {
+            let env = active_environment().await?;
+            serde_json::to_string_pretty(&env).map_err(|e| e.to_string())
+        }
+        "java-home" => active_environment()
+            .await?
+            .java_home
+            .ok_or_else(|| "The active profile has no resolvable JDK".to_string()),
+        "switch" => {
+            let id = resolve_profile(required(args, "profile")?).await?;
+            let result = switch_profile(id).await?;
+            for warning in &result.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            if result.success {
+                Ok(result.message.unwrap_or_default())
+            } else {
+                Err(result.error.unwrap_or_else(|| "Profile switch failed".to_string()))
+            }
+        }
+        "start" => {
+            check_command_allowed("start_instance")?;
+            start_instance(resolve_instance(required(args, "instance")?)?).await?;
+            Ok(String::new())
+        }
+        "stop" => {
+            // The CLI goes around the invoke handler, so the persisted lock is checked here
+            check_command_allowed("stop_instance")?;
+            stop_instance(resolve_instance(required(args, "instance")?)?).await?;
+            Ok(String::new())
+        }
+        "ide-config" => {
+            let dir = PathBuf::from(required(args, "project directory")?);
+            let targets = parse_ide_targets(&args[1..])?;
+            let result = write_ide_config(&dir, &targets, &active_environment().await?)?;
+            for warning in &result.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            Ok(result.files_written.join("\n"))
+        }
+        "list" => {
+            let names: Vec<String> = match required(args, "profiles or instances")? {
+                "profiles" => list_profiles().await?.into_iter().map(|p| p.name).collect(),
+                "instances" => load_instances()?.into_iter().map(|i| i.name).collect(),
+                other => return Err(format!("Unknown list '{}' (expected profiles or instances)", other)),
+            };
+            Ok(names.join("\n"))
+        }
+        "aliases" => {
+            let shell = match args.first() {
+                Some(name) => ShellKind::from_name(name).ok_or_else(|| format!("Unsupported shell: {}", name))?,
+                None => ShellKind::detect(),
+            };
+            let binary = std::env::current_exe().map_err(|e| e.to_string())?;
+            Ok(shell_alias_snippet(shell, &binary.to_string_lossy()))
+        }
+        _ => Ok(USAGE.to_string()),
+    }
+}
+
+fn required<'a>(args: &'a [String], name: &str) -> Result<&'a str, String> {
+    args.first()
+        .map(String::as_str)
+        .ok_or_else(|| format!("Missing {}\n\n{}", name, USAGE))
+}
+
+/// IDE targets from the arguments (both when none are given)
+fn parse_ide_targets(args: &[String]) -> Result<Vec<IdeTarget>, String> {
+    if args.is_empty() {
+        return Ok(vec![IdeTarget::Vscode, IdeTarget::Intellij]);
+    }
+    args.iter()
+        .map(|a| match a.as_str() {
+            "vscode" => Ok(IdeTarget::Vscode),
+            "intellij" => Ok(IdeTarget::Intellij),
+            other => Err(format!("Unknown IDE '{}' (expected vscode or intellij)", other)),
+        })
+        .collect()
+}
+
+/// Find a profile id by id or (case-insensitive) name
+async fn resolve_profile(key: &str) -> Result<String, String> {
+    list_profiles()
+        .await?
+        .into_iter()
+        .find(|p| p.id == key || p.name.eq_ignore_ascii_case(key))
+        .map(|p| p.id)
+        .ok_or_else(|| format!("Profile not found: {}", key))
+}
+
+/// Find an instance id by id or (case-insensitive) name
+fn resolve_instance(key: &str) -> Result<String, String> {
+    load_instances()?
+        .into_iter()
+        .find(|i| i.id == key || i.name.eq_ignore_ascii_case(key))
+        .map(|i| i.id)
+        .ok_or_else(|| format!("Instance not found: {}", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_cli_passthrough() {
+        // No command, or arguments the OS/launcher adds, start the GUI
+        assert_eq!(run_cli(&["aem-env-manager".to_string()]), None);
+        assert_eq!(run_cli(&["aem-env-manager".to_string(), "-psn_0_123".to_string()]), None);
+
+        assert_eq!(
+            parse_ide_targets(&["intellij".to_string()]),
+            Ok(vec![IdeTarget::Intellij])
+        );
+        assert!(parse_ide_targets(&["eclipse".to_string()]).is_err());
+    }
+}
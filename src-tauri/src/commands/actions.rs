@@ -0,0 +1,108 @@
+// Action Catalog Commands
+// Every command the frontend can invoke, with a title, category and argument list, so the
+// command palette and automations can enumerate what the backend offers
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionArgumentType {
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionArgument {
+    /// Argument name as passed to `invoke` (camelCase)
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ActionArgumentType,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionDescriptor {
+    /// Command name, e.g. start_instance
+    pub id: String,
+    pub title: String,
+    pub category: String,
+    pub arguments: Vec<ActionArgument>,
+    /// Deletes, resets, stops or reveals secrets; rejected while the app is locked
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub destructive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ActionCatalog {
+    actions: Vec<ActionDescriptor>,
+}
+
+/// Catalog shipped with the app; every command registered in lib.rs has an entry
+const ACTION_CATALOG: &str = include_str!("../../resources/actions.json");
+
+// ============================================
+// Helpers
+// ============================================
+
+pub(crate) fn load_actions() -> Vec<ActionDescriptor> {
+    let catalog: ActionCatalog = serde_json::from_str(ACTION_CATALOG).expect("bundled action catalog is valid JSON");
+    catalog.actions
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Actions in registration order, optionally only those of one category (case-insensitive)
+#[command]
+pub async fn list_actions(category: Option<String>) -> Result<Vec<ActionDescriptor>, String> {
+    let actions = load_actions();
+    Ok(match category {
+        Some(category) => actions
+            .into_iter()
+            .filter(|a| a.category.eq_ignore_ascii_case(category.trim()))
+            .collect(),
+        None => actions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_catalog_covers_registered_commands() {
+        let lib = include_str!("../lib.rs");
+        let handler = lib
+            .split_once("generate_handler![")
+            .and_then(|(_, rest)| rest.split_once("])"))
+            .map(|(handler, _)| handler)
+            .expect("lib.rs registers commands");
+        let registered: Vec<&str> = handler
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("//"))
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        let actions = load_actions();
+        let ids: HashSet<&str> = actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids.len(), actions.len(), "duplicate action ids");
+        for name in &registered {
+            assert!(ids.contains(name), "{} has no action catalog entry", name);
+        }
+        assert_eq!(registered.len(), actions.len(), "catalog lists commands that are not registered");
+        assert!(actions.iter().all(|a| !a.title.is_empty() && !a.category.is_empty()));
+    }
+}
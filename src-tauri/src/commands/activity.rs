@@ -0,0 +1,187 @@
+// Instance Activity Feed
+// Timeline of notable per-instance events: starts and stops, package installs, status
+// changes seen by the health scheduler and repository snapshots/resets
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    Started,
+    Stopped,
+    PackageInstalled,
+    /// Status changed without a start or stop from the app, e.g. a crash
+    StatusChanged,
+    SnapshotCreated,
+    SnapshotRestored,
+    RepositoryReset,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub instance_id: String,
+    pub kind: ActivityKind,
+    pub message: String,
+    pub timestamp: String,
+}
+
+/// Entries kept across all instances
+const MAX_ENTRIES: usize = 2000;
+
+/// Serializes read-modify-write of the activity log
+static ACTIVITY_LOCK: Mutex<()> = Mutex::new(());
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_activity_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("activity.json")
+}
+
+fn load_activity() -> Vec<ActivityEntry> {
+    fs::read_to_string(get_activity_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_activity(entries: &[ActivityEntry]) -> Result<(), String> {
+    let file_path = get_activity_file();
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(entries).map_err(|e| format!("Failed to serialize activity: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write activity: {}", e))
+}
+
+/// Append an entry to the activity log; failures are only logged
+pub(crate) fn record_activity(instance_id: &str, kind: ActivityKind, message: impl Into<String>) {
+    let _guard = ACTIVITY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_activity();
+    entries.push(ActivityEntry {
+        instance_id: instance_id.to_string(),
+        kind,
+        message: message.into(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    });
+    if entries.len() > MAX_ENTRIES {
+        entries.drain(..entries.len() - MAX_ENTRIES);
+    }
+    if let Err(e) = save_activity(&entries) {
+        println!("[AEM] Failed to record activity: {}", e);
+    }
+}
+
+/// Most recent entry of an instance
+pub(crate) fn latest_activity(instance_id: &str) -> Option<ActivityEntry> {
+    load_activity().into_iter().rev().find(|e| e.instance_id == instance_id)
+}
+
+/// Remove the activity of a deleted instance
+pub fn delete_activity(instance_id: &str) -> Result<(), String> {
+    let _guard = ACTIVITY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_activity();
+    let before = entries.len();
+    entries.retain(|e| e.instance_id != instance_id);
+    if entries.len() == before {
+        return Ok(());
+    }
+    save_activity(&entries)
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Entries matching the filters, newest first
+fn query_activity(
+    entries: Vec<ActivityEntry>,
+    instance_id: Option<&str>,
+    kinds: &[ActivityKind],
+    since: Option<&str>,
+    limit: usize,
+) -> Vec<ActivityEntry> {
+    // RFC 3339 UTC timestamps sort chronologically as strings
+    entries
+        .into_iter()
+        .rev()
+        .filter(|e| instance_id.is_none_or(|id| e.instance_id == id))
+        .filter(|e| kinds.is_empty() || kinds.contains(&e.kind))
+        .filter(|e| since.is_none_or(|since| e.timestamp.as_str() >= since))
+        .take(limit)
+        .collect()
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Activity of one instance (or all), newest first. `since` is an RFC 3339 timestamp;
+/// `limit` defaults to 100.
+#[command]
+pub async fn get_instance_activity(
+    instance_id: Option<String>,
+    kinds: Option<Vec<ActivityKind>>,
+    since: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ActivityEntry>, String> {
+    let since = since
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|t| t.with_timezone(&chrono::Utc).to_rfc3339())
+                .map_err(|e| format!("Invalid timestamp '{}': {}", s, e))
+        })
+        .transpose()?;
+    Ok(query_activity(
+        load_activity(),
+        instance_id.as_deref(),
+        &kinds.unwrap_or_default(),
+        since.as_deref(),
+        limit.unwrap_or(100),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_activity() {
+        let entry = |instance_id: &str, kind, timestamp: &str| ActivityEntry {
+            instance_id: instance_id.to_string(),
+            kind,
+            message: String::new(),
+            timestamp: timestamp.to_string(),
+        };
+        let entries = vec![
+            entry("author", ActivityKind::Started, "2025-01-01T10:00:00+00:00"),
+            entry("publish", ActivityKind::Started, "2025-01-01T10:01:00+00:00"),
+            entry("author", ActivityKind::PackageInstalled, "2025-01-01T11:00:00+00:00"),
+            entry("author", ActivityKind::Stopped, "2025-01-01T12:00:00+00:00"),
+        ];
+
+        let author = query_activity(entries.clone(), Some("author"), &[], None, 10);
+        assert_eq!(author.len(), 3);
+        assert_eq!(author[0].kind, ActivityKind::Stopped);
+
+        let recent = query_activity(entries.clone(), None, &[], Some("2025-01-01T10:30:00+00:00"), 10);
+        assert_eq!(recent.len(), 2);
+
+        let starts = query_activity(entries, None, &[ActivityKind::Started], None, 1);
+        assert_eq!(starts.len(), 1);
+        assert_eq!(starts[0].instance_id, "publish");
+    }
+}
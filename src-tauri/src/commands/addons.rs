@@ -0,0 +1,260 @@
+// AEM Add-on Commands
+// Detects installed add-ons (Forms, Screens) from an instance's bundles or install folder;
+// add-ons change the JVM arguments used at startup and how long a start is expected to take
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::command;
+
+use crate::commands::instance::{
+    get_instance_credentials, load_instances, save_instances, AemInstance, AemInstanceType,
+};
+use crate::i18n::{Entity, Message};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum AemAddon {
+    Forms,
+    Screens,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddonDetectionResult {
+    pub instance_id: String,
+    pub addons: Vec<AemAddon>,
+    /// "bundles" (running instance) or "install-folder" (crx-quickstart/install)
+    pub source: String,
+    /// JVM arguments the add-ons add at startup
+    pub jvm_args: Vec<String>,
+    pub expected_startup_secs: u64,
+}
+
+/// Opens required by the Forms add-on on Java 11 and later
+const FORMS_JVM_ARGS: [&str; 9] = [
+    "-XX:+UseParallelGC",
+    "--add-opens=java.desktop/com.sun.imageio.plugins.jpeg=ALL-UNNAMED",
+    "--add-opens=java.base/sun.net.www.protocol.jrt=ALL-UNNAMED",
+    "--add-opens=java.naming/javax.naming.spi=ALL-UNNAMED",
+    "--add-opens=java.xml/com.sun.org.apache.xerces.internal.dom=ALL-UNNAMED",
+    "--add-opens=java.base/java.lang=ALL-UNNAMED",
+    "--add-opens=java.base/jdk.internal.loader=ALL-UNNAMED",
+    "--add-opens=java.base/java.net=ALL-UNNAMED",
+    "-Dnashorn.args=--no-deprecation-warning",
+];
+
+impl AemAddon {
+    /// Bundle symbolic name prefixes that identify the add-on
+    fn bundle_prefixes(self) -> &'static [&'static str] {
+        match self {
+            AemAddon::Forms => &["com.adobe.aemfd.", "com.adobe.aemds.", "com.adobe.forms."],
+            AemAddon::Screens => &["com.adobe.cq.screens."],
+        }
+    }
+
+    /// Substrings of package file names in crx-quickstart/install
+    fn package_markers(self) -> &'static [&'static str] {
+        match self {
+            AemAddon::Forms => &["aemfd", "forms-addon", "aem-forms"],
+            AemAddon::Screens => &["screens"],
+        }
+    }
+
+    /// JVM arguments the add-on needs with the given Java major version
+    pub fn jvm_args(self, java_major: Option<u32>) -> Vec<String> {
+        match (self, java_major) {
+            (AemAddon::Forms, Some(major)) if major >= 11 => {
+                FORMS_JVM_ARGS.iter().map(|a| a.to_string()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Additional startup time on top of the instance's base expectation
+    fn extra_startup(self) -> Duration {
+        match self {
+            AemAddon::Forms => Duration::from_secs(180),
+            AemAddon::Screens => Duration::from_secs(60),
+        }
+    }
+
+    const ALL: [AemAddon; 2] = [AemAddon::Forms, AemAddon::Screens];
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// How long a start of the instance is expected to take, including its add-ons
+pub(crate) fn expected_startup(instance: &AemInstance) -> Duration {
+    let base = match instance.instance_type {
+        AemInstanceType::Author => Duration::from_secs(240),
+        AemInstanceType::Publish => Duration::from_secs(180),
+        AemInstanceType::Dispatcher => Duration::from_secs(10),
+    };
+    instance.addons.iter().map(|a| a.extra_startup()).sum::<Duration>() + base
+}
+
+/// Whether a JVM argument selects a garbage collector, e.g. -XX:+UseG1GC
+fn selects_gc(arg: &str) -> bool {
+    arg.starts_with("-XX:+Use") && arg.ends_with("GC")
+}
+
+/// JVM arguments of the instance's add-ons that are not already part of java_opts. The
+/// add-on's collector is left out when java_opts already selects one.
+pub(crate) fn addon_jvm_args(instance: &AemInstance, java_major: Option<u32>) -> Vec<String> {
+    let existing: Vec<&str> = instance.java_opts.as_deref().unwrap_or_default().split_whitespace().collect();
+    let gc_selected = existing.iter().any(|a| selects_gc(a));
+    let mut args: Vec<String> = Vec::new();
+    for arg in instance.addons.iter().flat_map(|a| a.jvm_args(java_major)) {
+        if existing.contains(&arg.as_str()) || args.contains(&arg) || (gc_selected && selects_gc(&arg)) {
+            continue;
+        }
+        args.push(arg);
+    }
+    args
+}
+
+/// Add-ons identified by bundle symbolic names
+fn addons_from_bundles<'a>(names: impl Iterator<Item = &'a str>) -> Vec<AemAddon> {
+    let names: Vec<&str> = names.collect();
+    AemAddon::ALL
+        .into_iter()
+        .filter(|addon| {
+            names
+                .iter()
+                .any(|n| addon.bundle_prefixes().iter().any(|p| n.starts_with(p)))
+        })
+        .collect()
+}
+
+/// Add-ons identified by package file names
+fn addons_from_packages<'a>(names: impl Iterator<Item = &'a str>) -> Vec<AemAddon> {
+    let names: Vec<String> = names.map(|n| n.to_lowercase()).collect();
+    AemAddon::ALL
+        .into_iter()
+        .filter(|addon| {
+            names
+                .iter()
+                .any(|n| addon.package_markers().iter().any(|m| n.contains(m)))
+        })
+        .collect()
+}
+
+/// Symbolic names from /system/console/bundles.json (None when not reachable)
+async fn fetch_bundle_names(instance: &AemInstance) -> Option<Vec<String>> {
+    if instance.probe_without_auth {
+        return None;
+    }
+    let (username, password, _) = get_instance_credentials(&instance.id).ok()?;
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(10)))
+        .build()
+        .ok()?;
+
+    let json: serde_json::Value = client
+        .get(format!("{}/system/console/bundles.json", instance.base_url()))
+        .basic_auth(&username, Some(&password))
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    Some(
+        json.get("data")?
+            .as_array()?
+            .iter()
+            .filter_map(|b| b.get("symbolicName")?.as_str().map(str::to_string))
+            .collect(),
+    )
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Detect add-ons of an instance and record them on it. Uses the bundle list of a
+/// running instance, otherwise the packages in crx-quickstart/install.
+#[command]
+pub async fn detect_instance_addons(id: String) -> Result<AddonDetectionResult, String> {
+    let mut instances = load_instances()?;
+    let index = instances
+        .iter()
+        .position(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+
+    let (addons, source) = match fetch_bundle_names(&instances[index]).await {
+        Some(bundles) => (addons_from_bundles(bundles.iter().map(String::as_str)), "bundles"),
+        None => {
+            let install_dir = instances[index].crx_quickstart_dir().join("install");
+            let packages: Vec<String> = std::fs::read_dir(&install_dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect();
+            (addons_from_packages(packages.iter().map(String::as_str)), "install-folder")
+        }
+    };
+
+    let instance = &mut instances[index];
+    instance.addons = addons.clone();
+    instance.updated_at = chrono::Utc::now().to_rfc3339();
+    let result = AddonDetectionResult {
+        instance_id: id,
+        addons,
+        source: source.to_string(),
+        // Shown for Java 11+, where the Forms opens apply
+        jvm_args: addon_jvm_args(instance, Some(11)),
+        expected_startup_secs: expected_startup(instance).as_secs(),
+    };
+    save_instances(&instances)?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addon_detection() {
+        let bundles = ["org.apache.sling.api", "com.adobe.aemfd.docmanager", "com.adobe.cq.screens.device"];
+        assert_eq!(
+            addons_from_bundles(bundles.into_iter()),
+            vec![AemAddon::Forms, AemAddon::Screens]
+        );
+        assert!(addons_from_bundles(["com.adobe.cq.wcm.core"].into_iter()).is_empty());
+
+        let packages = ["AEM-FORMS-6.5.21.0-LX.far", "cq-6.5.0-hotfix.zip"];
+        assert_eq!(addons_from_packages(packages.into_iter()), vec![AemAddon::Forms]);
+
+        assert!(AemAddon::Forms.jvm_args(Some(8)).is_empty());
+        assert_eq!(AemAddon::Forms.jvm_args(Some(11)).len(), FORMS_JVM_ARGS.len());
+    }
+
+    #[test]
+    fn test_addon_jvm_args() {
+        let mut instance: AemInstance = serde_json::from_value(serde_json::json!({
+            "name": "Author", "instance_type": "author", "host": "localhost", "port": 4502
+        }))
+        .unwrap();
+        instance.addons = vec![AemAddon::Forms];
+        instance.java_opts = Some("-XX:+UseG1GC --add-opens=java.base/java.lang=ALL-UNNAMED -Xmx4g".into());
+        let args = addon_jvm_args(&instance, Some(11));
+        assert!(!args.iter().any(|a| a == "-XX:+UseParallelGC"));
+        assert!(!args.iter().any(|a| a == "--add-opens=java.base/java.lang=ALL-UNNAMED"));
+        assert!(args.iter().any(|a| a == "--add-opens=java.base/java.net=ALL-UNNAMED"));
+        assert_eq!(args.len(), FORMS_JVM_ARGS.len() - 2);
+
+        instance.java_opts = None;
+        assert_eq!(addon_jvm_args(&instance, Some(11)).len(), FORMS_JVM_ARGS.len());
+    }
+}
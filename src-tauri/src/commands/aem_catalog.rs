@@ -0,0 +1,322 @@
+// AEM Release Catalog
+// Known AEM 6.5 service packs and AEM SDK builds (bundled, optionally refreshed from a URL),
+// and an overview of which release each instance runs and whether a newer one exists
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{command, AppHandle};
+
+use crate::commands::downloads::{download, download_fresh, DownloadEntry};
+use crate::commands::instance::{
+    check_port_open, fetch_version_info, find_quickstart_jar, get_instance_credentials, load_instances, AemInstance,
+};
+use crate::commands::progress::ProgressReporter;
+use crate::commands::version::compare_versions;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseKind {
+    /// AEM as a Cloud Service SDK quickstart (year.month.build)
+    Sdk,
+    /// AEM 6.x with service packs
+    OnPremise,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AemRelease {
+    pub kind: ReleaseKind,
+    /// "2024.11.18598" for SDKs, "6.5.21" for service packs
+    pub version: String,
+    #[serde(default)]
+    pub service_pack: Option<u32>,
+    /// Release month (YYYY-MM)
+    #[serde(default)]
+    pub released: Option<String>,
+    /// Where the SDK or service pack can be downloaded without signing in (e.g. a company
+    /// mirror of the Software Distribution portal)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// Hex-encoded SHA-256 of the download
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AemCatalog {
+    pub updated: String,
+    pub releases: Vec<AemRelease>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceReleaseInfo {
+    pub instance_id: String,
+    pub instance_name: String,
+    /// Version as reported by the instance or read from the quickstart JAR name
+    pub detected_version: Option<String>,
+    /// "product_info" (running instance), "recorded" (last version read) or "jar_name"
+    pub version_source: Option<String>,
+    pub kind: Option<ReleaseKind>,
+    pub service_pack: Option<u32>,
+    /// Catalog entry of the detected release
+    pub release: Option<AemRelease>,
+    /// Newest catalog release of the same line
+    pub latest: Option<AemRelease>,
+    /// None when the installed service pack cannot be told (stopped 6.5 instance)
+    pub update_available: Option<bool>,
+}
+
+/// Catalog shipped with the app
+const BUNDLED_CATALOG: &str = include_str!("../../resources/aem-releases.json");
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_catalog_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("aem_catalog.json")
+}
+
+fn bundled_catalog() -> AemCatalog {
+    serde_json::from_str(BUNDLED_CATALOG).expect("bundled AEM release catalog is valid JSON")
+}
+
+/// Bundled catalog merged with the last refreshed one
+fn load_catalog() -> AemCatalog {
+    let bundled = bundled_catalog();
+    let refreshed: Option<AemCatalog> = std::fs::read_to_string(get_catalog_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    match refreshed {
+        Some(refreshed) => merge_catalogs(bundled, refreshed),
+        None => bundled,
+    }
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Releases of both catalogs (the newer catalog wins on duplicates), newest first
+fn merge_catalogs(a: AemCatalog, b: AemCatalog) -> AemCatalog {
+    let (older, newer) = if a.updated <= b.updated { (a, b) } else { (b, a) };
+    let mut releases = newer.releases;
+    for release in older.releases {
+        if !releases.iter().any(|r| r.kind == release.kind && r.version == release.version) {
+            releases.push(release);
+        }
+    }
+    releases.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    AemCatalog {
+        updated: newer.updated,
+        releases,
+    }
+}
+
+/// Release line, version and service pack of a product version ("6.5.21.0",
+/// "2024.11.18598.20241113T125352Z-241000") or a version found in a JAR name ("6.5")
+pub(crate) fn parse_product_version(version: &str) -> Option<(ReleaseKind, String, Option<u32>)> {
+    let parts: Vec<u32> = version
+        .trim()
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+    match parts.as_slice() {
+        [year, month, build, ..] if *year >= 2000 => {
+            Some((ReleaseKind::Sdk, format!("{}.{}.{}", year, month, build), None))
+        }
+        [major, minor, service_pack, ..] => Some((
+            ReleaseKind::OnPremise,
+            format!("{}.{}.{}", major, minor, service_pack),
+            Some(*service_pack),
+        )),
+        [major, minor] => Some((ReleaseKind::OnPremise, format!("{}.{}", major, minor), None)),
+        _ => None,
+    }
+}
+
+/// Version in a quickstart JAR name (aem-sdk-quickstart-2024.11.18598...jar, AEM_6.5_Quickstart.jar)
+fn version_from_jar_name(name: &str) -> Option<String> {
+    let pattern = regex::Regex::new(r"(\d{4}\.\d+\.\d+|\d\.\d+(?:\.\d+)?)").ok()?;
+    pattern.find(name).map(|m| m.as_str().to_string())
+}
+
+/// Newest release of the same line (same major.minor for 6.x)
+fn latest_release<'a>(catalog: &'a AemCatalog, kind: ReleaseKind, version: &str) -> Option<&'a AemRelease> {
+    let line = |v: &str| v.split('.').take(2).collect::<Vec<_>>().join(".");
+    catalog
+        .releases
+        .iter()
+        .filter(|r| r.kind == kind && (kind == ReleaseKind::Sdk || line(&r.version) == line(version)))
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+}
+
+/// Product version of a running instance, else the one recorded last, else the version in its
+/// quickstart JAR name
+async fn detect_version(instance: &AemInstance) -> Option<(String, &'static str)> {
+    if check_port_open(&instance.host, instance.port, 500) {
+        if let Ok((username, password, _)) = get_instance_credentials(&instance.id) {
+            let client = instance
+                .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(10)))
+                .build()
+                .ok()?;
+            if let Some(info) = fetch_version_info(&client, &instance.base_url(), &username, &password).await {
+                return Some((info.product_version, "product_info"));
+            }
+        }
+    }
+
+    if let Some(ref version) = instance.aem_version {
+        return Some((version.clone(), "recorded"));
+    }
+    let jar = find_quickstart_jar(&PathBuf::from(&instance.path)).ok()?;
+    let name = jar.file_name()?.to_string_lossy().to_string();
+    version_from_jar_name(&name).map(|v| (v, "jar_name"))
+}
+
+fn release_info(instance: &AemInstance, detected: Option<(String, &str)>, catalog: &AemCatalog) -> InstanceReleaseInfo {
+    let mut info = InstanceReleaseInfo {
+        instance_id: instance.id.clone(),
+        instance_name: instance.name.clone(),
+        detected_version: None,
+        version_source: None,
+        kind: None,
+        service_pack: None,
+        release: None,
+        latest: None,
+        update_available: None,
+    };
+    let Some((detected_version, source)) = detected else {
+        return info;
+    };
+    info.version_source = Some(source.to_string());
+
+    if let Some((kind, mut version, mut service_pack)) = parse_product_version(&detected_version) {
+        // A 6.x JAR name tells the base release; service packs are installed later as packages
+        if kind == ReleaseKind::OnPremise && source == "jar_name" {
+            version = version.split('.').take(2).collect::<Vec<_>>().join(".");
+            service_pack = None;
+        }
+        info.kind = Some(kind);
+        info.service_pack = service_pack;
+        info.release = catalog
+            .releases
+            .iter()
+            .find(|r| r.kind == kind && r.version == version)
+            .cloned();
+        info.latest = latest_release(catalog, kind, &version).cloned();
+        // Without a service pack only the 6.x line is known, not whether it is current
+        if kind == ReleaseKind::Sdk || service_pack.is_some() {
+            info.update_available = info
+                .latest
+                .as_ref()
+                .map(|latest| compare_versions(&latest.version, &version) == Ordering::Greater);
+        }
+    }
+    info.detected_version = Some(detected_version);
+    info
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Known AEM releases, newest first
+#[command]
+pub async fn get_aem_catalog() -> Result<AemCatalog, String> {
+    Ok(load_catalog())
+}
+
+/// Download a catalog in the bundled format (`{"updated": .., "releases": [..]}`) and keep it
+/// next to the bundled one
+#[command]
+pub async fn refresh_aem_catalog(url: String) -> Result<AemCatalog, String> {
+    let entry = download_fresh(&url, None, None)
+        .await
+        .map_err(|e| format!("Failed to download AEM catalog: {}", e))?;
+    let content = std::fs::read_to_string(&entry.path).map_err(|e| format!("Failed to read AEM catalog: {}", e))?;
+    serde_json::from_str::<AemCatalog>(&content).map_err(|e| format!("Invalid AEM catalog: {}", e))?;
+
+    let file_path = get_catalog_file();
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    std::fs::write(&file_path, content).map_err(|e| format!("Failed to write AEM catalog: {}", e))?;
+    Ok(load_catalog())
+}
+
+/// Download the SDK or service pack of a catalog release through the shared download cache,
+/// emitting transfer progress under `operation_id`
+#[command]
+pub async fn download_aem_release(
+    app: AppHandle,
+    version: String,
+    operation_id: Option<String>,
+) -> Result<DownloadEntry, String> {
+    let release = load_catalog()
+        .releases
+        .into_iter()
+        .find(|r| r.version == version)
+        .ok_or_else(|| format!("AEM {} is not in the catalog", version))?;
+    let url = release
+        .download_url
+        .ok_or_else(|| format!("The catalog has no download URL for AEM {}", version))?;
+
+    let label = url.rsplit('/').next().unwrap_or(&version).to_string();
+    let mut progress = ProgressReporter::new(&app, operation_id, &label);
+    download(&url, release.sha256.as_deref(), Some(&mut progress)).await
+}
+
+/// Release, service pack and available update of every instance
+#[command]
+pub async fn get_aem_installation_overview() -> Result<Vec<InstanceReleaseInfo>, String> {
+    let catalog = load_catalog();
+    let mut overview = Vec::new();
+    for instance in load_instances()? {
+        let detected = detect_version(&instance).await;
+        overview.push(release_info(&instance, detected, &catalog));
+    }
+    Ok(overview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_product_version() {
+        assert_eq!(
+            parse_product_version("2024.11.18598.20241113T125352Z-241000"),
+            Some((ReleaseKind::Sdk, "2024.11.18598".to_string(), None))
+        );
+        assert_eq!(
+            parse_product_version("6.5.21.0"),
+            Some((ReleaseKind::OnPremise, "6.5.21".to_string(), Some(21)))
+        );
+        assert_eq!(parse_product_version("6.5"), Some((ReleaseKind::OnPremise, "6.5".to_string(), None)));
+        assert_eq!(parse_product_version("unknown"), None);
+
+        assert_eq!(
+            version_from_jar_name("aem-sdk-quickstart-2024.11.18598.20241113T125352Z-241000.jar"),
+            Some("2024.11.18598".to_string())
+        );
+        assert_eq!(version_from_jar_name("AEM_6.5_Quickstart.jar"), Some("6.5".to_string()));
+        assert_eq!(version_from_jar_name("aem-author-p4502.jar"), None);
+    }
+
+    #[test]
+    fn test_bundled_catalog_latest_release() {
+        let catalog = bundled_catalog();
+        let latest = latest_release(&catalog, ReleaseKind::OnPremise, "6.5.17").unwrap();
+        assert_eq!(latest.service_pack, Some(22));
+        assert!(latest_release(&catalog, ReleaseKind::OnPremise, "6.4.8").is_none());
+        assert!(latest_release(&catalog, ReleaseKind::Sdk, "2024.1.14000").is_some());
+    }
+}
@@ -0,0 +1,218 @@
+// Adobe I/O CLI Commands
+// Bridges the GUI with Adobe's aio CLI: detection, `aio aem rde` and `aio cloudmanager`
+// operations with parsed JSON output, and per-profile config contexts (org/program/environment)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::Instant;
+use tauri::command;
+
+use crate::commands::node_globals::node_tool;
+use crate::commands::profile::get_profile;
+use crate::i18n::{Entity, Message};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AioCliInfo {
+    pub installed: bool,
+    pub version: Option<String>,
+    /// Node version the CLI runs on
+    pub node_version: Option<String>,
+    /// Installed plugins, e.g. @adobe/aio-cli-plugin-aem-rde
+    pub plugins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AioCommandResult {
+    pub args: Vec<String>,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// Parsed stdout when the command printed JSON
+    pub json: Option<serde_json::Value>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+}
+
+/// Command groups the app may run; everything else is left to the terminal
+const ALLOWED_TOPICS: [&str; 2] = ["aem:rde", "cloudmanager"];
+
+/// Config keys holding login state; never returned to the UI or stored in profiles
+const SECRET_CONFIG_KEYS: [&str; 2] = ["ims", "$ims"];
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Version and Node version from `aio --version` ("@adobe/aio-cli/10.3.1 darwin-arm64 node-v20.11.0")
+fn parse_aio_version(output: &str) -> (Option<String>, Option<String>) {
+    let mut parts = output.split_whitespace();
+    let version = parts
+        .next()
+        .and_then(|first| first.rsplit('/').next())
+        .filter(|v| v.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .map(|v| v.to_string());
+    let node_version = parts.find_map(|p| p.strip_prefix("node-")).map(|v| v.to_string());
+    (version, node_version)
+}
+
+/// The command topic of `args` in oclif's colon form (`aem rde status` -> `aem:rde:status`)
+fn topic_of(args: &[String]) -> String {
+    args.iter()
+        .take_while(|a| !a.starts_with('-'))
+        .flat_map(|a| a.split(':'))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn is_allowed(args: &[String]) -> bool {
+    let topic = topic_of(args);
+    ALLOWED_TOPICS
+        .iter()
+        .any(|allowed| topic == *allowed || topic.starts_with(&format!("{}:", allowed)))
+}
+
+fn run_aio(args: &[String]) -> Result<AioCommandResult, String> {
+    let started = Instant::now();
+    let output = node_tool("aio")
+        .args(args)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run aio: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok(AioCommandResult {
+        args: args.to_vec(),
+        success: output.status.success(),
+        exit_code: output.status.code(),
+        json: serde_json::from_str(stdout.trim()).ok(),
+        stdout,
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Write a profile's aio config context with `aio config set`
+pub(crate) fn apply_aio_config(config: &HashMap<String, String>) -> Result<(), String> {
+    for (key, value) in config {
+        if SECRET_CONFIG_KEYS.iter().any(|s| key == s || key.starts_with(&format!("{}.", s))) {
+            continue;
+        }
+        let result = run_aio(&["config".to_string(), "set".to_string(), key.clone(), value.clone()])?;
+        if !result.success {
+            return Err(format!("aio config set {} failed: {}", key, result.stderr.trim()));
+        }
+    }
+    Ok(())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Detect the aio CLI of the active Node version (or on PATH) and its plugins
+#[command]
+pub async fn detect_aio_cli() -> Result<AioCliInfo, String> {
+    let Ok(result) = run_aio(&["--version".to_string()]) else {
+        return Ok(AioCliInfo {
+            installed: false,
+            version: None,
+            node_version: None,
+            plugins: Vec::new(),
+        });
+    };
+    let (version, node_version) = parse_aio_version(&result.stdout);
+
+    let plugins = run_aio(&["plugins".to_string(), "--json".to_string()])
+        .ok()
+        .and_then(|r| r.json)
+        .and_then(|json| json.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| p.get("name").and_then(|n| n.as_str()).map(|n| n.to_string()))
+        .collect();
+
+    Ok(AioCliInfo {
+        installed: result.success,
+        version,
+        node_version,
+        plugins,
+    })
+}
+
+/// Run an `aio aem rde ...` or `aio cloudmanager ...` command.
+/// With `json` (the default) `--json` is added so the output can be parsed.
+#[command]
+pub async fn run_aio_command(args: Vec<String>, json: Option<bool>) -> Result<AioCommandResult, String> {
+    if !is_allowed(&args) {
+        return Err(format!(
+            "Only {} commands can be run from the app",
+            ALLOWED_TOPICS.map(|t| format!("aio {}", t.replace(':', " "))).join(" and ")
+        ));
+    }
+    let mut args = args;
+    if json.unwrap_or(true) && !args.iter().any(|a| a == "--json") {
+        args.push("--json".to_string());
+    }
+    run_aio(&args)
+}
+
+/// Current aio configuration, without login tokens
+#[command]
+pub async fn get_aio_config() -> Result<serde_json::Value, String> {
+    let result = run_aio(&["config".to_string(), "list".to_string(), "--json".to_string()])?;
+    if !result.success {
+        return Err(format!("aio config list failed: {}", result.stderr.trim()));
+    }
+    let mut config = result.json.ok_or("aio config list printed no JSON")?;
+    if let Some(map) = config.as_object_mut() {
+        map.retain(|key, _| !SECRET_CONFIG_KEYS.contains(&key.as_str()));
+    }
+    Ok(config)
+}
+
+/// Apply the aio config context of a profile (also done when switching to it)
+#[command]
+pub async fn apply_profile_aio_config(profile_id: String) -> Result<bool, String> {
+    let profile = get_profile(profile_id.clone())
+        .await?
+        .ok_or_else(|| Message::NotFound(Entity::Profile, &profile_id).localized())?;
+    match profile.aio_config {
+        Some(ref config) if !config.is_empty() => {
+            apply_aio_config(config)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        assert!(is_allowed(&args("aem rde status")));
+        assert!(is_allowed(&args("aem:rde:install ./all.zip")));
+        assert!(is_allowed(&args("cloudmanager:list-programs --json")));
+        assert!(is_allowed(&args("cloudmanager program list-environments 123")));
+        assert!(!is_allowed(&args("aem")));
+        assert!(!is_allowed(&args("config set ims.contexts.x y")));
+        assert!(!is_allowed(&args("plugins install evil")));
+
+        assert_eq!(
+            parse_aio_version("@adobe/aio-cli/10.3.1 darwin-arm64 node-v20.11.0\n"),
+            (Some("10.3.1".to_string()), Some("v20.11.0".to_string()))
+        );
+        assert_eq!(parse_aio_version(""), (None, None));
+    }
+}
@@ -0,0 +1,557 @@
+// Automation Job Commands
+// User-defined jobs that run a shell command, an HTTP call or a package install when an
+// instance has started, a profile was switched or on a schedule. Runs go through the
+// operation registry (listed and cancellable like scans) and are kept in a run log.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::command;
+
+use crate::commands::certificates::http_client_builder;
+use crate::commands::lock::ensure_unlocked;
+use crate::commands::operations::{coalesce, operation_key, CancelToken};
+use crate::commands::packages::{connect, post_package};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AutomationTrigger {
+    /// An instance reached Running after being started (any instance when None)
+    InstanceStarted {
+        #[serde(default)]
+        instance_id: Option<String>,
+    },
+    /// A profile was switched to (any profile when None)
+    ProfileSwitched {
+        #[serde(default)]
+        profile_id: Option<String>,
+    },
+    /// Every `interval_minutes`, counted from the last run
+    Schedule { interval_minutes: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AutomationAction {
+    /// Run through the user's shell with AEM_EVENT, AEM_INSTANCE_ID and AEM_PROFILE_ID set;
+    /// placeholders in the command expand to references to these variables
+    Shell {
+        command: String,
+        #[serde(default)]
+        working_dir: Option<String>,
+    },
+    Http {
+        #[serde(default = "default_http_method")]
+        method: String,
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        #[serde(default)]
+        body: Option<String>,
+    },
+    /// Upload and install a package zip on an instance
+    InstallPackage { instance_id: String, package_path: String },
+}
+
+fn default_http_method() -> String {
+    "POST".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationJob {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub trigger: AutomationTrigger,
+    pub action: AutomationAction,
+    /// Run time limit (default 10 minutes)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// What caused a run; its fields are available to actions as `{event}`, `{instance_id}`
+/// and `{profile_id}` placeholders (and environment variables for shell commands)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AutomationEvent {
+    InstanceStarted { instance_id: String },
+    ProfileSwitched { profile_id: String },
+    Schedule,
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationRun {
+    pub job_id: String,
+    pub job_name: String,
+    pub event: AutomationEvent,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub success: bool,
+    /// Command output, HTTP response or install log (truncated)
+    pub output: String,
+}
+
+/// Runs kept in the run log
+const MAX_RUNS: usize = 200;
+
+/// Output kept per run
+const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+const DEFAULT_JOB_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// How often the scheduler looks for due jobs
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Serializes read-modify-write of the run log between concurrently finishing runs
+static RUN_LOG_LOCK: Mutex<()> = Mutex::new(());
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_jobs_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("automation_jobs.json")
+}
+
+fn get_runs_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("automation_runs.json")
+}
+
+fn load_jobs() -> Result<Vec<AutomationJob>, String> {
+    let file_path = get_jobs_file();
+    if !file_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read automation jobs: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse automation jobs: {}", e))
+}
+
+fn save_jobs(jobs: &[AutomationJob]) -> Result<(), String> {
+    write_json(&get_jobs_file(), jobs)
+}
+
+fn load_runs() -> Vec<AutomationRun> {
+    fs::read_to_string(get_runs_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn append_run(run: AutomationRun) {
+    let _guard = RUN_LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut runs = load_runs();
+    runs.push(run);
+    if runs.len() > MAX_RUNS {
+        runs.drain(..runs.len() - MAX_RUNS);
+    }
+    if let Err(e) = write_json(&get_runs_file(), &runs) {
+        println!("[AEM] Failed to record automation run: {}", e);
+    }
+}
+
+fn write_json<T: Serialize + ?Sized>(file_path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize: {}", e))?;
+    fs::write(file_path, content).map_err(|e| format!("Failed to write {}: {}", file_path.display(), e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+impl AutomationEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            AutomationEvent::InstanceStarted { .. } => "instance_started",
+            AutomationEvent::ProfileSwitched { .. } => "profile_switched",
+            AutomationEvent::Schedule => "schedule",
+            AutomationEvent::Manual => "manual",
+        }
+    }
+
+    fn variables(&self) -> Vec<(&'static str, String)> {
+        let (instance_id, profile_id) = match self {
+            AutomationEvent::InstanceStarted { instance_id } => (instance_id.clone(), String::new()),
+            AutomationEvent::ProfileSwitched { profile_id } => (String::new(), profile_id.clone()),
+            _ => (String::new(), String::new()),
+        };
+        vec![
+            ("event", self.name().to_string()),
+            ("instance_id", instance_id),
+            ("profile_id", profile_id),
+        ]
+    }
+}
+
+/// Whether `trigger` fires for `event`
+fn trigger_matches(trigger: &AutomationTrigger, event: &AutomationEvent) -> bool {
+    match (trigger, event) {
+        (AutomationTrigger::InstanceStarted { instance_id }, AutomationEvent::InstanceStarted { instance_id: id }) => {
+            instance_id.as_ref().is_none_or(|wanted| wanted == id)
+        }
+        (AutomationTrigger::ProfileSwitched { profile_id }, AutomationEvent::ProfileSwitched { profile_id: id }) => {
+            profile_id.as_ref().is_none_or(|wanted| wanted == id)
+        }
+        _ => false,
+    }
+}
+
+/// Replace `{event}`, `{instance_id}` and `{profile_id}` in `template`
+fn expand(template: &str, event: &AutomationEvent) -> String {
+    event
+        .variables()
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// Environment variable `run_shell` sets for an event variable
+fn shell_variable(name: &str) -> String {
+    format!("AEM_{}", name.to_uppercase())
+}
+
+/// Replace the placeholders in a shell command with references to their environment
+/// variables, so the shell never parses the values themselves. cmd runs with delayed
+/// expansion, where `!VAR!` is expanded after the command line is parsed.
+fn expand_shell(template: &str, event: &AutomationEvent, windows: bool) -> String {
+    event.variables().iter().fold(template.to_string(), |text, (name, _)| {
+        let reference = if windows {
+            format!("!{}!", shell_variable(name))
+        } else {
+            format!("\"${{{}}}\"", shell_variable(name))
+        };
+        text.replace(&format!("{{{}}}", name), &reference)
+    })
+}
+
+fn truncate_output(mut output: String) -> String {
+    if output.len() > MAX_OUTPUT_BYTES {
+        let mut end = MAX_OUTPUT_BYTES;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+        output.push_str("\n[output truncated]");
+    }
+    output
+}
+
+/// A scheduled job is due `interval_minutes` after its last run (or after it was saved)
+fn schedule_due(job: &AutomationJob, last_run: Option<&str>, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let AutomationTrigger::Schedule { interval_minutes } = job.trigger else {
+        return false;
+    };
+    let since = last_run.unwrap_or(&job.updated_at);
+    chrono::DateTime::parse_from_rfc3339(since)
+        .map(|t| now.signed_duration_since(t).num_minutes() >= interval_minutes.max(1) as i64)
+        .unwrap_or(true)
+}
+
+async fn run_shell(
+    command: &str,
+    working_dir: Option<&str>,
+    event: &AutomationEvent,
+    token: &CancelToken,
+) -> Result<String, String> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.args(["/V:ON", "/C", command]);
+        cmd
+    } else {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        let mut cmd = tokio::process::Command::new(shell);
+        cmd.args(["-c", command]);
+        cmd
+    };
+    for (name, value) in event.variables() {
+        cmd.env(shell_variable(name), value);
+    }
+    if let Some(dir) = working_dir.filter(|d| !d.is_empty()) {
+        cmd.current_dir(dir);
+    }
+    let child = cmd
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run command: {}", e))?;
+
+    let output = child.wait_with_output();
+    tokio::pin!(output);
+    let output = loop {
+        tokio::select! {
+            output = &mut output => break output.map_err(|e| format!("Failed to run command: {}", e))?,
+            _ = tokio::time::sleep(Duration::from_millis(500)) => token.check()?,
+        }
+    };
+
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    if output.status.success() {
+        Ok(text)
+    } else {
+        Err(format!("Exited with {}\n{}", output.status.code().unwrap_or(-1), text))
+    }
+}
+
+async fn run_http(
+    method: &str,
+    url: &str,
+    headers: &BTreeMap<String, String>,
+    body: Option<&str>,
+    timeout: Duration,
+) -> Result<String, String> {
+    let method = reqwest::Method::from_bytes(method.trim().to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method {}", method))?;
+    let client = http_client_builder().timeout(timeout).build().map_err(|e| e.to_string())?;
+    let mut request = client.request(method, url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        request = request.body(body.to_string());
+    }
+    let response = request.send().await.map_err(|e| format!("Request to {} failed: {}", url, e))?;
+    let status = response.status();
+    let text = format!("HTTP {}\n{}", status.as_u16(), response.text().await.unwrap_or_default());
+    if status.is_success() {
+        Ok(text)
+    } else {
+        Err(text)
+    }
+}
+
+async fn perform(job: &AutomationJob, event: &AutomationEvent, token: &CancelToken) -> Result<String, String> {
+    let timeout = job.timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_JOB_TIMEOUT);
+    let action = async {
+        match &job.action {
+            AutomationAction::Shell { command, working_dir } => {
+                run_shell(&expand_shell(command, event, cfg!(target_os = "windows")), working_dir.as_deref(), event, token).await
+            }
+            AutomationAction::Http {
+                method,
+                url,
+                headers,
+                body,
+            } => {
+                let headers = headers.iter().map(|(k, v)| (k.clone(), expand(v, event))).collect();
+                let body = body.as_deref().map(|b| expand(b, event));
+                run_http(method, &expand(url, event), &headers, body.as_deref(), timeout).await
+            }
+            AutomationAction::InstallPackage {
+                instance_id,
+                package_path,
+            } => {
+                let (instance, credentials) = connect(&expand(instance_id, event))?;
+                let path = PathBuf::from(expand(package_path, event));
+                post_package(&instance, &credentials, &path, true, true, None).await
+            }
+        }
+    };
+    tokio::time::timeout(timeout, action)
+        .await
+        .unwrap_or_else(|_| Err(format!("Timed out after {}s", timeout.as_secs())))
+}
+
+/// Run `job` for `event` as an operation (one run per job at a time; a trigger arriving
+/// while the job runs shares that run's outcome) and log the run. Scheduled and triggered
+/// runs bypass the invoke guard, so the app lock is checked here.
+async fn execute(job: AutomationJob, event: AutomationEvent) -> AutomationRun {
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let began = Instant::now();
+    let key = operation_key("automation", &job.id);
+    let result = coalesce(&key, |token| {
+        let (job, event) = (job.clone(), event.clone());
+        async move {
+            token.check()?;
+            ensure_unlocked("running automation jobs")?;
+            perform(&job, &event, &token).await
+        }
+    })
+    .await;
+
+    let run = AutomationRun {
+        job_id: job.id,
+        job_name: job.name,
+        event,
+        started_at,
+        duration_ms: began.elapsed().as_millis() as u64,
+        success: result.is_ok(),
+        output: truncate_output(result.unwrap_or_else(|e| e)),
+    };
+    append_run(run.clone());
+    run
+}
+
+/// Run the enabled jobs whose trigger matches `event` in the background
+pub(crate) fn trigger_automation(event: AutomationEvent) {
+    let jobs = match load_jobs() {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            println!("[AEM] Failed to load automation jobs: {}", e);
+            return;
+        }
+    };
+    for job in jobs.into_iter().filter(|j| j.enabled && trigger_matches(&j.trigger, &event)) {
+        tauri::async_runtime::spawn(execute(job, event.clone()));
+    }
+}
+
+/// Background task that starts scheduled jobs when they are due
+pub async fn automation_scheduler() {
+    loop {
+        tokio::time::sleep(SCHEDULER_INTERVAL).await;
+
+        let Ok(jobs) = load_jobs() else {
+            continue;
+        };
+        let runs = load_runs();
+        let now = chrono::Utc::now();
+        for job in jobs.into_iter().filter(|j| j.enabled) {
+            let last_run = runs.iter().rev().find(|r| r.job_id == job.id).map(|r| r.started_at.as_str());
+            if schedule_due(&job, last_run, now) {
+                tauri::async_runtime::spawn(execute(job, AutomationEvent::Schedule));
+            }
+        }
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Configured automation jobs
+#[command]
+pub async fn list_automation_jobs() -> Result<Vec<AutomationJob>, String> {
+    load_jobs()
+}
+
+/// Create a job (empty id) or replace the job with the same id
+#[command]
+pub async fn save_automation_job(job: AutomationJob) -> Result<AutomationJob, String> {
+    let mut job = job;
+    job.name = job.name.trim().to_string();
+    if job.name.is_empty() {
+        return Err("Job name is required".to_string());
+    }
+    if let AutomationTrigger::Schedule { interval_minutes: 0 } = job.trigger {
+        return Err("Schedule interval must be at least one minute".to_string());
+    }
+
+    let mut jobs = load_jobs()?;
+    let now = chrono::Utc::now().to_rfc3339();
+    job.updated_at = now.clone();
+    match jobs.iter_mut().find(|j| !job.id.is_empty() && j.id == job.id) {
+        Some(existing) => {
+            job.created_at = existing.created_at.clone();
+            *existing = job.clone();
+        }
+        None => {
+            if job.id.is_empty() {
+                job.id = uuid::Uuid::new_v4().to_string();
+            }
+            job.created_at = now;
+            jobs.push(job.clone());
+        }
+    }
+    save_jobs(&jobs)?;
+    Ok(job)
+}
+
+/// Delete a job; its past runs stay in the run log
+#[command]
+pub async fn delete_automation_job(id: String) -> Result<bool, String> {
+    let mut jobs = load_jobs()?;
+    let before = jobs.len();
+    jobs.retain(|j| j.id != id);
+    if jobs.len() == before {
+        return Ok(false);
+    }
+    save_jobs(&jobs)?;
+    Ok(true)
+}
+
+/// Run a job now, regardless of its trigger and whether it is enabled
+#[command]
+pub async fn run_automation_job(id: String) -> Result<AutomationRun, String> {
+    let job = load_jobs()?
+        .into_iter()
+        .find(|j| j.id == id)
+        .ok_or_else(|| format!("Automation job {} not found", id))?;
+    Ok(execute(job, AutomationEvent::Manual).await)
+}
+
+/// Logged runs, newest first, optionally of one job only
+#[command]
+pub async fn list_automation_runs(job_id: Option<String>, limit: Option<usize>) -> Result<Vec<AutomationRun>, String> {
+    let mut runs: Vec<AutomationRun> = load_runs()
+        .into_iter()
+        .rev()
+        .filter(|r| job_id.as_ref().is_none_or(|id| &r.job_id == id))
+        .collect();
+    runs.truncate(limit.unwrap_or(MAX_RUNS));
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_matches_and_expand() {
+        let started = AutomationEvent::InstanceStarted {
+            instance_id: "author".to_string(),
+        };
+        let any_instance = AutomationTrigger::InstanceStarted { instance_id: None };
+        let publish_only = AutomationTrigger::InstanceStarted {
+            instance_id: Some("publish".to_string()),
+        };
+        assert!(trigger_matches(&any_instance, &started));
+        assert!(!trigger_matches(&publish_only, &started));
+        assert!(!trigger_matches(&AutomationTrigger::ProfileSwitched { profile_id: None }, &started));
+        assert_eq!(
+            expand("http://localhost:9000/ready?instance={instance_id}&on={event}", &started),
+            "http://localhost:9000/ready?instance=author&on=instance_started"
+        );
+        assert_eq!(
+            expand_shell("./warm.sh {instance_id}; echo {event}", &started, false),
+            "./warm.sh \"${AEM_INSTANCE_ID}\"; echo \"${AEM_EVENT}\""
+        );
+        assert_eq!(expand_shell("warm.bat {instance_id}", &started, true), "warm.bat !AEM_INSTANCE_ID!");
+
+        let job: AutomationJob = serde_json::from_value(serde_json::json!({
+            "name": "Warm up",
+            "trigger": { "kind": "schedule", "interval_minutes": 30 },
+            "action": { "kind": "http", "url": "http://localhost:4502/content.html" },
+            "updated_at": "2024-06-01T10:00:00Z"
+        }))
+        .unwrap();
+        assert!(job.enabled);
+        let now = chrono::DateTime::parse_from_rfc3339("2024-06-01T10:20:00Z").unwrap().to_utc();
+        assert!(!schedule_due(&job, None, now));
+        assert!(schedule_due(&job, Some("2024-06-01T09:45:00Z"), now));
+    }
+}
@@ -0,0 +1,294 @@
+// JVM Benchmark Commands
+// Starts a stopped instance once per java_opts variant, measures startup time and heap after
+// startup, and compares the variants so -Xmx/GC tuning is backed by numbers
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::addons::expected_startup;
+use crate::commands::instance::{
+    check_port_open, start_instance_with_java_opts, stop_instance, wait_for_shutdown, MemoryStatus,
+};
+use crate::commands::jmx::jmx_memory_status;
+use crate::commands::packages::connect;
+use crate::commands::profile::get_startup_config;
+use crate::commands::readiness::wait_until_ready;
+use crate::i18n::MessageCode;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmOptionsVariant {
+    pub label: String,
+    /// JVM options for this run; empty uses the instance defaults
+    pub java_opts: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmBenchmarkRun {
+    pub label: String,
+    pub java_opts: String,
+    pub ready: bool,
+    /// Seconds from start until the instance was ready (or the wait gave up)
+    pub startup_secs: u64,
+    /// Memory read over JMX after the settle period
+    pub memory: Option<MemoryStatus>,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmBenchmarkReport {
+    pub instance_id: String,
+    pub started_at: String,
+    pub finished_at: String,
+    /// Runs in the order of the variants; the first is the baseline
+    pub runs: Vec<JvmBenchmarkRun>,
+    /// Label of the ready variant with the shortest startup
+    pub fastest: Option<String>,
+    /// Label of the ready variant with the least heap used after startup
+    pub lowest_heap: Option<String>,
+    /// One line per variant compared with the baseline
+    pub comparison: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkPhase {
+    Starting,
+    /// Ready; waiting for the settle period before reading the heap
+    Settling,
+    Stopping,
+    Finished,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmBenchmarkProgress {
+    pub instance_id: String,
+    pub label: String,
+    pub phase: BenchmarkPhase,
+}
+
+/// Event emitted with a `JvmBenchmarkProgress` whenever a run enters a new phase
+pub const JVM_BENCHMARK_EVENT: &str = "jvm-benchmark-progress";
+
+/// Seconds to let startup work settle before the heap is read
+const DEFAULT_SETTLE_SECS: u64 = 60;
+
+// ============================================
+// Helpers
+// ============================================
+
+fn percent_change(value: f64, baseline: f64) -> String {
+    if baseline == 0.0 {
+        return "n/a".to_string();
+    }
+    format!("{:+.0}%", (value - baseline) / baseline * 100.0)
+}
+
+fn heap_used(run: &JvmBenchmarkRun) -> Option<u64> {
+    run.memory.as_ref().map(|m| m.heap_used)
+}
+
+/// Compare each run with the first one
+fn compare_runs(runs: &[JvmBenchmarkRun]) -> Vec<String> {
+    let Some(baseline) = runs.first() else {
+        return Vec::new();
+    };
+    let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+    runs.iter()
+        .map(|run| {
+            if !run.ready {
+                return format!("{}: not ready ({})", run.label, run.error.as_deref().unwrap_or("unknown error"));
+            }
+            let mut line = format!("{}: startup {}s", run.label, run.startup_secs);
+            if !std::ptr::eq(run, baseline) && baseline.ready {
+                line.push_str(&format!(
+                    " ({} vs {})",
+                    percent_change(run.startup_secs as f64, baseline.startup_secs as f64),
+                    baseline.label
+                ));
+            }
+            if let Some(heap) = heap_used(run) {
+                line.push_str(&format!(", heap {:.0} MiB", mib(heap)));
+                if let Some(base_heap) = heap_used(baseline).filter(|_| !std::ptr::eq(run, baseline)) {
+                    line.push_str(&format!(" ({})", percent_change(heap as f64, base_heap as f64)));
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// Start the instance with one variant, measure it and stop it again. Errors of the run itself
+/// end up in the result; an Err means the instance could not be brought back to stopped.
+async fn run_variant(
+    app: &AppHandle,
+    instance_id: &str,
+    variant: &JvmOptionsVariant,
+    credentials: &(String, String),
+    settle: Duration,
+    timeout: Option<Duration>,
+) -> Result<JvmBenchmarkRun, String> {
+    let emit = |phase: BenchmarkPhase| {
+        let _ = app.emit(
+            JVM_BENCHMARK_EVENT,
+            JvmBenchmarkProgress {
+                instance_id: instance_id.to_string(),
+                label: variant.label.clone(),
+                phase,
+            },
+        );
+    };
+    let mut run = JvmBenchmarkRun {
+        label: variant.label.clone(),
+        java_opts: variant.java_opts.clone(),
+        ready: false,
+        startup_secs: 0,
+        memory: None,
+        error: None,
+        message_code: None,
+    };
+
+    let (instance, _) = connect(instance_id)?;
+
+    emit(BenchmarkPhase::Starting);
+    let began = Instant::now();
+    if let Err(e) = start_instance_with_java_opts(instance_id.to_string(), &variant.java_opts).await {
+        run.message_code = Some(MessageCode::new("benchmark_start_failed").with("detail", &e));
+        run.error = Some(e);
+        return Ok(run);
+    }
+    let timeout = timeout.unwrap_or_else(|| expected_startup(&instance));
+    match wait_until_ready(app, &instance, timeout).await {
+        Ok(wait) if wait.ready => {
+            run.ready = true;
+            run.startup_secs = began.elapsed().as_secs();
+
+            emit(BenchmarkPhase::Settling);
+            tokio::time::sleep(settle).await;
+            let client = instance
+                .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(10)).no_proxy())
+                .build()
+                .map_err(|e| e.to_string())?;
+            run.memory = jmx_memory_status(&client, &instance.base_url(), credentials).await;
+            if run.memory.is_none() {
+                run.error = Some("JMX console not reachable, heap not measured".to_string());
+                run.message_code = Some(MessageCode::new("benchmark_heap_not_measured"));
+            }
+        }
+        Ok(wait) => {
+            run.startup_secs = wait.elapsed_secs;
+            run.error = Some(format!("Not ready after {}s", wait.elapsed_secs));
+            run.message_code = Some(MessageCode::new("benchmark_not_ready").with("seconds", wait.elapsed_secs));
+        }
+        Err(e) => {
+            run.message_code = Some(MessageCode::new("benchmark_wait_failed").with("detail", &e));
+            run.error = Some(e);
+        }
+    }
+
+    // Every variant needs a cold start
+    emit(BenchmarkPhase::Stopping);
+    stop_instance(instance_id.to_string()).await?;
+    let stop_timeout = Duration::from_secs(get_startup_config().stop_timeout_secs);
+    if !wait_for_shutdown(&instance, stop_timeout).await {
+        return Err(format!("{} did not shut down within {}s", instance.name, stop_timeout.as_secs()));
+    }
+    Ok(run)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Start a stopped instance once per variant (in order), measure startup time and heap after
+/// `settle_secs` (default 60), and compare each variant with the first. The variants are passed
+/// at launch, so the instance's own java_opts are never changed. `timeout_secs` defaults to the
+/// expected startup time.
+#[command]
+pub async fn benchmark_jvm_options(
+    app: AppHandle,
+    instance_id: String,
+    variants: Vec<JvmOptionsVariant>,
+    settle_secs: Option<u64>,
+    timeout_secs: Option<u64>,
+) -> Result<JvmBenchmarkReport, String> {
+    if variants.len() < 2 {
+        return Err("Provide at least two java_opts variants to compare".to_string());
+    }
+    let (instance, credentials) = connect(&instance_id)?;
+    if check_port_open(&instance.host, instance.port, 500) {
+        return Err(format!("Stop {} first; each variant is measured from a cold start", instance.name));
+    }
+
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let settle = Duration::from_secs(settle_secs.unwrap_or(DEFAULT_SETTLE_SECS));
+    let timeout = timeout_secs.map(Duration::from_secs);
+    let mut runs = Vec::new();
+    for variant in &variants {
+        runs.push(run_variant(&app, &instance_id, variant, &credentials, settle, timeout).await?);
+    }
+
+    let ready = || runs.iter().filter(|r| r.ready);
+    let fastest = ready().min_by_key(|r| r.startup_secs).map(|r| r.label.clone());
+    let lowest_heap = ready()
+        .filter_map(|r| heap_used(r).map(|heap| (heap, r)))
+        .min_by_key(|(heap, _)| *heap)
+        .map(|(_, r)| r.label.clone());
+    let _ = app.emit(
+        JVM_BENCHMARK_EVENT,
+        JvmBenchmarkProgress {
+            instance_id: instance_id.clone(),
+            label: String::new(),
+            phase: BenchmarkPhase::Finished,
+        },
+    );
+
+    Ok(JvmBenchmarkReport {
+        instance_id,
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        comparison: compare_runs(&runs),
+        runs,
+        fastest,
+        lowest_heap,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_runs() {
+        let run = |label: &str, ready: bool, startup_secs: u64, heap_mib: u64| JvmBenchmarkRun {
+            label: label.to_string(),
+            java_opts: String::new(),
+            ready,
+            startup_secs,
+            memory: Some(MemoryStatus {
+                heap_used: heap_mib * 1024 * 1024,
+                heap_max: 0,
+                heap_percentage: 0.0,
+                non_heap_used: None,
+                metaspace_used: None,
+                gc_count: None,
+                gc_time_ms: None,
+            }),
+            error: (!ready).then(|| "Not ready after 600s".to_string()),
+            message_code: None,
+        };
+        let runs = [run("G1", true, 200, 1000), run("Parallel", true, 150, 1200), run("Tiny", false, 600, 0)];
+
+        let lines = compare_runs(&runs);
+        assert_eq!(lines[0], "G1: startup 200s, heap 1000 MiB");
+        assert_eq!(lines[1], "Parallel: startup 150s (-25% vs G1), heap 1200 MiB (+20%)");
+        assert_eq!(lines[2], "Tiny: not ready (Not ready after 600s)");
+    }
+}
@@ -0,0 +1,269 @@
+// OSGi Bundle Commands
+// Bundle list, start/stop/refresh and jar installation through the Felix web console
+// (/system/console/bundles)
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+use tauri::command;
+
+use crate::commands::instance::AemInstance;
+use crate::commands::packages::{connect, multipart_frame};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleInfo {
+    pub id: u64,
+    pub name: String,
+    pub symbolic_name: String,
+    pub version: String,
+    /// Felix state name (Active, Resolved, Installed, Fragment, ...)
+    pub state: String,
+    pub fragment: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleAction {
+    Start,
+    Stop,
+    /// Refresh the package wiring of the bundle
+    Refresh,
+}
+
+impl BundleAction {
+    fn as_param(self) -> &'static str {
+        match self {
+            BundleAction::Start => "start",
+            BundleAction::Stop => "stop",
+            BundleAction::Refresh => "refresh",
+        }
+    }
+}
+
+/// How long an installed bundle may take to show up in the bundle list
+const INSTALL_SETTLE_TIMEOUT: Duration = Duration::from_secs(15);
+
+// ============================================
+// Helpers
+// ============================================
+
+fn console_client(instance: &AemInstance, timeout: Duration) -> Result<reqwest::Client, String> {
+    instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(timeout))
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Bundles of a /system/console/bundles.json (or bundles/<name>.json) response
+fn parse_bundles(json: &serde_json::Value) -> Vec<BundleInfo> {
+    let text = |b: &serde_json::Value, key: &str| b.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    json.get("data")
+        .and_then(|d| d.as_array())
+        .map(|bundles| {
+            bundles
+                .iter()
+                .filter_map(|b| {
+                    Some(BundleInfo {
+                        id: b.get("id")?.as_u64()?,
+                        name: text(b, "name"),
+                        symbolic_name: text(b, "symbolicName"),
+                        version: text(b, "version"),
+                        state: text(b, "state"),
+                        fragment: b.get("fragment").and_then(|f| f.as_bool()).unwrap_or(false),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn fetch_bundles(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    url_path: &str,
+) -> Result<Vec<BundleInfo>, String> {
+    let url = format!("{}{}", instance.base_url(), url_path);
+    let response = console_client(instance, Duration::from_secs(30))?
+        .get(&url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status().as_u16()));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse bundle list: {}", e))?;
+    Ok(parse_bundles(&json))
+}
+
+async fn find_bundle(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    symbolic_name: &str,
+) -> Result<Option<BundleInfo>, String> {
+    let url_path = format!("/system/console/bundles/{}.json", symbolic_name);
+    let bundles = fetch_bundles(instance, credentials, &url_path).await;
+    // Felix answers 404 for unknown bundles
+    Ok(bundles.ok().and_then(|b| b.into_iter().find(|b| b.symbolic_name == symbolic_name)))
+}
+
+/// Bundle-SymbolicName from a jar manifest, without directives such as `;singleton:=true`
+fn manifest_symbolic_name(manifest: &str) -> Option<String> {
+    // Manifest lines are wrapped at 72 bytes with continuation lines starting with a space
+    let unwrapped = manifest.replace("\r\n", "\n").replace("\n ", "");
+    unwrapped
+        .lines()
+        .find_map(|line| line.strip_prefix("Bundle-SymbolicName:"))
+        .and_then(|value| value.split(';').next())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+fn jar_symbolic_name(path: &Path) -> Result<String, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("{} is not a jar: {}", path.display(), e))?;
+    let mut manifest = String::new();
+    archive
+        .by_name("META-INF/MANIFEST.MF")
+        .map_err(|_| format!("{} has no manifest", path.display()))?
+        .read_to_string(&mut manifest)
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    manifest_symbolic_name(&manifest).ok_or_else(|| format!("{} is not an OSGi bundle", path.display()))
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Bundles of a running instance, sorted by bundle id
+#[command]
+pub async fn list_bundles(instance_id: String) -> Result<Vec<BundleInfo>, String> {
+    let (instance, credentials) = connect(&instance_id)?;
+    let mut bundles = fetch_bundles(&instance, &credentials, "/system/console/bundles.json").await?;
+    bundles.sort_by_key(|b| b.id);
+    Ok(bundles)
+}
+
+/// Start, stop or refresh a bundle by symbolic name; returns its state afterwards
+#[command]
+pub async fn control_bundle(
+    instance_id: String,
+    symbolic_name: String,
+    action: BundleAction,
+) -> Result<BundleInfo, String> {
+    let (instance, credentials) = connect(&instance_id)?;
+    let symbolic_name = symbolic_name.trim().to_string();
+    if find_bundle(&instance, &credentials, &symbolic_name).await?.is_none() {
+        return Err(format!("Bundle {} not found", symbolic_name));
+    }
+
+    let url = format!("{}/system/console/bundles/{}", instance.base_url(), symbolic_name);
+    let response = console_client(&instance, Duration::from_secs(60))?
+        .post(&url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(&[("action", action.as_param())])
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status().as_u16()));
+    }
+
+    find_bundle(&instance, &credentials, &symbolic_name)
+        .await?
+        .ok_or_else(|| format!("Bundle {} not found", symbolic_name))
+}
+
+/// Upload and install a bundle jar (or update the bundle with the same symbolic name).
+/// `start` defaults to true, `start_level` to the console default of 20.
+#[command]
+pub async fn install_bundle(
+    instance_id: String,
+    jar_path: String,
+    start: Option<bool>,
+    start_level: Option<u32>,
+) -> Result<BundleInfo, String> {
+    let path = Path::new(&jar_path);
+    let symbolic_name = jar_symbolic_name(path)?;
+    let (instance, credentials) = connect(&instance_id)?;
+
+    let jar = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let start_level = start_level.unwrap_or(20).to_string();
+    let mut fields = vec![("action", "install"), ("bundlestartlevel", start_level.as_str())];
+    if start.unwrap_or(true) {
+        fields.push(("bundlestart", "start"));
+    }
+    fields.push(("refreshPackages", "refresh"));
+
+    let boundary = format!("----aem-env-manager-{}", uuid::Uuid::new_v4().simple());
+    let (mut body, suffix) = multipart_frame(
+        &boundary,
+        ("bundlefile", "application/java-archive"),
+        &file_name,
+        &fields,
+    );
+    body.extend_from_slice(&jar);
+    body.extend_from_slice(&suffix);
+
+    let url = format!("{}/system/console/bundles", instance.base_url());
+    let response = console_client(&instance, Duration::from_secs(300))?
+        .post(&url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .header("Content-Type", format!("multipart/form-data; boundary={}", boundary))
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status().as_u16()));
+    }
+
+    // The console installs asynchronously; wait for the bundle to be listed
+    let deadline = tokio::time::Instant::now() + INSTALL_SETTLE_TIMEOUT;
+    loop {
+        if let Some(bundle) = find_bundle(&instance, &credentials, &symbolic_name).await? {
+            return Ok(bundle);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(format!("{} was uploaded but bundle {} is not listed yet", file_name, symbolic_name));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bundles_and_manifest() {
+        let json = serde_json::json!({
+            "s": [2, 1, 0, 1, 0],
+            "data": [
+                { "id": 0, "name": "System Bundle", "symbolicName": "org.apache.felix.framework",
+                  "version": "6.0.5", "state": "Active", "fragment": false },
+                { "id": 42, "name": "My Core", "symbolicName": "com.example.core",
+                  "version": "1.0.0.SNAPSHOT", "state": "Resolved" }
+            ]
+        });
+        let bundles = parse_bundles(&json);
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[1].symbolic_name, "com.example.core");
+        assert_eq!(bundles[1].state, "Resolved");
+        assert!(!bundles[1].fragment);
+
+        let manifest = "Manifest-Version: 1.0\r\nBundle-SymbolicName: com.example.very.long.bundle.na\r\n me;singleton\
+                        :=true\r\nBundle-Version: 1.0.0\r\n";
+        assert_eq!(manifest_symbolic_name(manifest).as_deref(), Some("com.example.very.long.bundle.name"));
+        assert_eq!(manifest_symbolic_name("Manifest-Version: 1.0\n"), None);
+    }
+}
@@ -0,0 +1,256 @@
+// Certificate Commands
+// Corporate root CAs for TLS-intercepting proxies: import into a JDK's cacerts (with a
+// backup) so Maven and AEM stop failing with PKIX errors, and into the app's own trust store
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::command;
+
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JdkCaImportResult {
+    pub alias: String,
+    pub cacerts_path: String,
+    /// Copy of cacerts taken before the import
+    pub backup_path: String,
+    /// keytool output
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustedCertificate {
+    pub alias: String,
+    pub path: String,
+    pub added_at: Option<String>,
+}
+
+/// Default password of a JDK's cacerts
+const DEFAULT_STOREPASS: &str = "changeit";
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_trusted_certs_dir() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("trusted-certs")
+}
+
+fn load_trusted_certificates() -> Vec<(PathBuf, reqwest::Certificate)> {
+    let Ok(entries) = fs::read_dir(get_trusted_certs_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let bytes = fs::read(&path).ok()?;
+            let certificate = parse_certificate(&bytes).ok()?;
+            Some((path, certificate))
+        })
+        .collect()
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn validate_alias(alias: &str) -> Result<(), String> {
+    let valid = !alias.is_empty()
+        && alias.len() <= 64
+        && alias.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !alias.starts_with('.');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid alias '{}': use letters, digits, '-', '_' and '.'", alias))
+    }
+}
+
+fn is_pem(bytes: &[u8]) -> bool {
+    String::from_utf8_lossy(bytes).contains("-----BEGIN CERTIFICATE-----")
+}
+
+fn parse_certificate(bytes: &[u8]) -> Result<reqwest::Certificate, String> {
+    let parsed = if is_pem(bytes) {
+        reqwest::Certificate::from_pem(bytes)
+    } else {
+        reqwest::Certificate::from_der(bytes)
+    };
+    parsed.map_err(|e| format!("Not a PEM or DER certificate: {}", e))
+}
+
+/// cacerts of a JDK (JDK 9+ layout, then the JDK 8 `jre/` layout)
+fn find_cacerts(java_home: &Path) -> Option<PathBuf> {
+    [
+        java_home.join("lib").join("security").join("cacerts"),
+        java_home.join("jre").join("lib").join("security").join("cacerts"),
+    ]
+    .into_iter()
+    .find(|p| p.is_file())
+}
+
+fn keytool_path(java_home: &Path) -> PathBuf {
+    let name = if cfg!(target_os = "windows") { "keytool.exe" } else { "keytool" };
+    java_home.join("bin").join(name)
+}
+
+/// HTTP client builder trusting the CAs added with `add_app_trusted_ca` besides the system roots.
+/// Used for requests leaving the machine (downloads, release notes), which a proxy may intercept.
+pub(crate) fn http_client_builder() -> reqwest::ClientBuilder {
+    load_trusted_certificates()
+        .into_iter()
+        .fold(reqwest::Client::builder(), |builder, (_, certificate)| {
+            builder.add_root_certificate(certificate)
+        })
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Import a root CA into the cacerts of the JDK at `java_home`, backing cacerts up first.
+/// `storepass` defaults to the JDK default ("changeit").
+#[command]
+pub async fn import_ca_into_jdk(
+    java_home: String,
+    cert_path: String,
+    alias: String,
+    storepass: Option<String>,
+) -> Result<JdkCaImportResult, String> {
+    validate_alias(&alias)?;
+    let java_home = PathBuf::from(&java_home);
+    let cacerts = find_cacerts(&java_home).ok_or_else(|| format!("No cacerts found in {}", java_home.display()))?;
+    let keytool = keytool_path(&java_home);
+    if !keytool.exists() {
+        return Err(format!("keytool not found: {}", keytool.display()));
+    }
+    parse_certificate(&fs::read(&cert_path).map_err(|e| format!("Failed to read {}: {}", cert_path, e))?)?;
+    let storepass = storepass.filter(|p| !p.is_empty()).unwrap_or_else(|| DEFAULT_STOREPASS.to_string());
+
+    let existing = Command::new(&keytool)
+        .args(["-list", "-alias", &alias, "-storepass", &storepass, "-keystore"])
+        .arg(&cacerts)
+        .output()
+        .map_err(|e| format!("Failed to run keytool: {}", e))?;
+    if existing.status.success() {
+        return Err(format!("cacerts already contains an entry named {}", alias));
+    }
+
+    let backup = cacerts.with_file_name(format!(
+        "cacerts.bak-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::copy(&cacerts, &backup).map_err(|e| {
+        format!(
+            "Failed to back up {} (the JDK may need administrator rights to change): {}",
+            cacerts.display(),
+            e
+        )
+    })?;
+
+    let output = Command::new(&keytool)
+        .args(["-importcert", "-noprompt", "-trustcacerts", "-alias", &alias, "-file", &cert_path])
+        .args(["-storepass", &storepass, "-keystore"])
+        .arg(&cacerts)
+        .output()
+        .map_err(|e| format!("Failed to run keytool: {}", e))?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if !output.status.success() {
+        return Err(format!("keytool failed: {}", text.trim()));
+    }
+
+    Ok(JdkCaImportResult {
+        alias,
+        cacerts_path: cacerts.to_string_lossy().to_string(),
+        backup_path: backup.to_string_lossy().to_string(),
+        output: text.trim().to_string(),
+    })
+}
+
+/// Trust a root CA (PEM or DER) for the app's own HTTPS requests
+#[command]
+pub async fn add_app_trusted_ca(cert_path: String, alias: String) -> Result<TrustedCertificate, String> {
+    validate_alias(&alias)?;
+    let bytes = fs::read(&cert_path).map_err(|e| format!("Failed to read {}: {}", cert_path, e))?;
+    parse_certificate(&bytes)?;
+
+    let dir = get_trusted_certs_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create certificate directory: {}", e))?;
+    let extension = if is_pem(&bytes) { "pem" } else { "der" };
+    let target = dir.join(format!("{}.{}", alias, extension));
+    if load_trusted_certificates().iter().any(|(p, _)| p.file_stem() == target.file_stem()) {
+        return Err(format!("A certificate named {} is already trusted", alias));
+    }
+    fs::write(&target, &bytes).map_err(|e| format!("Failed to store certificate: {}", e))?;
+
+    Ok(TrustedCertificate {
+        alias,
+        path: target.to_string_lossy().to_string(),
+        added_at: Some(chrono::Utc::now().to_rfc3339()),
+    })
+}
+
+/// CAs the app trusts in addition to the system roots
+#[command]
+pub async fn list_app_trusted_cas() -> Result<Vec<TrustedCertificate>, String> {
+    let mut certificates: Vec<TrustedCertificate> = load_trusted_certificates()
+        .into_iter()
+        .map(|(path, _)| TrustedCertificate {
+            alias: path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+            added_at: fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect();
+    certificates.sort_by(|a, b| a.alias.cmp(&b.alias));
+    Ok(certificates)
+}
+
+/// Stop trusting a CA added with `add_app_trusted_ca`
+#[command]
+pub async fn remove_app_trusted_ca(alias: String) -> Result<bool, String> {
+    validate_alias(&alias)?;
+    let mut removed = false;
+    for extension in ["pem", "der"] {
+        let path = get_trusted_certs_dir().join(format!("{}.{}", alias, extension));
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove certificate: {}", e))?;
+            removed = true;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_alias_and_find_cacerts() {
+        assert!(validate_alias("corp-root-ca_2024.1").is_ok());
+        assert!(validate_alias("").is_err());
+        assert!(validate_alias("../cacerts").is_err());
+        assert!(validate_alias("corp ca").is_err());
+
+        let dir = std::env::temp_dir().join(format!("aem-env-manager-jdk-{}", uuid::Uuid::new_v4()));
+        assert_eq!(find_cacerts(&dir), None);
+        fs::create_dir_all(dir.join("jre/lib/security")).unwrap();
+        fs::write(dir.join("jre/lib/security/cacerts"), b"").unwrap();
+        assert_eq!(find_cacerts(&dir), Some(dir.join("jre/lib/security/cacerts")));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,515 @@
+// Content Package Commands
+// Exports a content subtree as an anonymized FileVault package (emails, tokens and user
+// names scrubbed) so reproduction content can be shared outside the team
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{command, AppHandle};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::commands::instance::{get_instance_credentials, load_instances};
+use crate::commands::progress::ProgressReporter;
+use crate::i18n::{Entity, Message};
+
+// ============================================
+// Data Types
+// ============================================
+
+/// What to scrub from exported properties
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrubOptions {
+    /// Replace e-mail addresses in values
+    pub emails: bool,
+    /// Blank password/token/secret/key properties and JWT-like or long random values
+    pub tokens: bool,
+    /// Replace jcr:createdBy, cq:lastModifiedBy, ... with "anonymous"
+    pub users: bool,
+    /// Additional property names to blank (`*` wildcard)
+    pub properties: Vec<String>,
+}
+
+impl Default for ScrubOptions {
+    fn default() -> Self {
+        Self {
+            emails: true,
+            tokens: true,
+            users: true,
+            properties: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentPackageResult {
+    pub output_path: String,
+    pub nodes: usize,
+    pub properties: usize,
+    /// Properties whose value was replaced
+    pub scrubbed: usize,
+    /// Nodes and properties left out (binaries, ACLs, unknown namespaces)
+    pub skipped: Vec<String>,
+}
+
+/// Namespaces declared in generated .content.xml files
+const NAMESPACES: [(&str, &str); 14] = [
+    ("jcr", "http://www.jcp.org/jcr/1.0"),
+    ("nt", "http://www.jcp.org/jcr/nt/1.0"),
+    ("mix", "http://www.jcp.org/jcr/mix/1.0"),
+    ("sling", "http://sling.apache.org/jcr/sling/1.0"),
+    ("cq", "http://www.day.com/jcr/cq/1.0"),
+    ("dam", "http://www.day.com/dam/1.0"),
+    ("oak", "http://jackrabbit.apache.org/oak/ns/1.0"),
+    ("granite", "http://www.adobe.com/jcr/granite/1.0"),
+    ("social", "http://www.adobe.com/social/1.0"),
+    ("dc", "http://purl.org/dc/elements/1.1/"),
+    ("xmp", "http://ns.adobe.com/xap/1.0/"),
+    ("xmpMM", "http://ns.adobe.com/xap/1.0/mm/"),
+    ("tiff", "http://ns.adobe.com/tiff/1.0/"),
+    ("exif", "http://ns.adobe.com/exif/1.0/"),
+];
+
+const USER_PROPERTIES: [&str; 6] = [
+    "jcr:createdBy",
+    "jcr:lastModifiedBy",
+    "cq:lastModifiedBy",
+    "cq:lastReplicatedBy",
+    "cq:lastRolledoutBy",
+    "dam:lastUploadedBy",
+];
+
+const SECRET_NAME_MARKERS: [&str; 7] = ["password", "passwd", "secret", "token", "apikey", "api_key", "privatekey"];
+
+const REDACTED: &str = "REDACTED";
+const REDACTED_EMAIL: &str = "redacted@example.com";
+
+// ============================================
+// Scrubbing
+// ============================================
+
+struct Scrubber {
+    options: ScrubOptions,
+    email: Regex,
+    secret_value: Regex,
+    scrubbed: usize,
+}
+
+impl Scrubber {
+    fn new(options: ScrubOptions) -> Self {
+        Self {
+            options,
+            email: Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap(),
+            // JWTs and long hex/base64 strings (no slashes, so paths are kept)
+            secret_value: Regex::new(r"^(eyJ[\w-]+\.[\w-]+\.[\w-]+|[A-Fa-f0-9]{32,}|[A-Za-z0-9+]{40,}={0,2})$")
+                .unwrap(),
+            scrubbed: 0,
+        }
+    }
+
+    fn name_matches(&self, name: &str) -> bool {
+        self.options.properties.iter().any(|pattern| {
+            let regex = format!("^{}$", regex::escape(pattern).replace("\\*", ".*"));
+            Regex::new(&regex).map(|re| re.is_match(name)).unwrap_or(false)
+        })
+    }
+
+    /// Scrub a string property value
+    fn scrub_string(&mut self, name: &str, value: &str) -> String {
+        let lower = name.to_lowercase();
+        let replacement = if self.name_matches(name)
+            || (self.options.tokens
+                && (SECRET_NAME_MARKERS.iter().any(|m| lower.contains(m)) || self.secret_value.is_match(value)))
+        {
+            Some(REDACTED.to_string())
+        } else if self.options.users && USER_PROPERTIES.contains(&name) {
+            Some("anonymous".to_string())
+        } else if self.options.emails && self.email.is_match(value) {
+            Some(self.email.replace_all(value, REDACTED_EMAIL).to_string())
+        } else {
+            None
+        };
+
+        match replacement {
+            Some(new) if new != value => {
+                self.scrubbed += 1;
+                new
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    /// Scrub a property in place; strings (also inside arrays) are checked
+    fn scrub(&mut self, name: &str, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => *s = self.scrub_string(name, s),
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    if let serde_json::Value::String(s) = item {
+                        *s = self.scrub_string(name, s);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ============================================
+// DocView Serialization
+// ============================================
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#xa;")
+        .replace('\t', "&#x9;")
+}
+
+/// ISO 9075 encoding of a JCR name for use as an XML element name
+fn encode_element_name(name: &str) -> String {
+    let mut encoded = String::new();
+    for (i, c) in name.chars().enumerate() {
+        let valid = c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':');
+        if !valid || (i == 0 && (c.is_ascii_digit() || c == '-' || c == '.')) {
+            encoded.push_str(&format!("_x{:04X}_", c as u32));
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
+
+/// File name of a path segment in jcr_root (jcr:content → _jcr_content)
+fn platform_name(segment: &str) -> String {
+    match segment.split_once(':') {
+        Some((prefix, local)) => format!("_{}_{}", prefix, local),
+        None => segment.to_string(),
+    }
+}
+
+/// A single value in DocView syntax, escaping the characters that start a type or array
+fn docview_scalar(value: &serde_json::Value, in_array: bool) -> Option<(Option<&'static str>, String)> {
+    match value {
+        serde_json::Value::String(s) => {
+            let mut escaped = s.replace('\\', "\\\\");
+            if in_array {
+                escaped = escaped.replace(',', "\\,");
+            }
+            if escaped.starts_with('{') || escaped.starts_with('[') {
+                escaped.insert(0, '\\');
+            }
+            Some((None, escaped))
+        }
+        serde_json::Value::Bool(b) => Some((Some("Boolean"), b.to_string())),
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => Some((Some("Long"), n.to_string())),
+        serde_json::Value::Number(n) => Some((Some("Double"), n.to_string())),
+        _ => None,
+    }
+}
+
+fn docview_value(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Array(items) => {
+            let values: Vec<(Option<&str>, String)> =
+                items.iter().filter_map(|v| docview_scalar(v, true)).collect();
+            let type_hint = values.first().and_then(|(t, _)| *t);
+            let joined: Vec<String> = values.into_iter().map(|(_, v)| v).collect();
+            Some(match type_hint {
+                Some(t) => format!("{{{}}}[{}]", t, joined.join(",")),
+                None => format!("[{}]", joined.join(",")),
+            })
+        }
+        _ => docview_scalar(value, false).map(|(t, v)| match t {
+            Some(t) => format!("{{{}}}{}", t, v),
+            None => v,
+        }),
+    }
+}
+
+struct DocViewWriter<'a> {
+    scrubber: &'a mut Scrubber,
+    prefixes: BTreeSet<&'static str>,
+    nodes: usize,
+    properties: usize,
+    skipped: Vec<String>,
+}
+
+impl DocViewWriter<'_> {
+    /// Namespace prefix of a name, or Err when the prefix is not known
+    fn check_prefix(&mut self, name: &str) -> Result<(), ()> {
+        let Some((prefix, _)) = name.split_once(':') else {
+            return Ok(());
+        };
+        match NAMESPACES.iter().find(|(p, _)| *p == prefix) {
+            Some((p, _)) => {
+                self.prefixes.insert(p);
+                Ok(())
+            }
+            None => Err(()),
+        }
+    }
+
+    /// Serialize a node (its properties as attributes, child nodes as elements)
+    fn write_node(&mut self, element: &str, path: &str, node: &serde_json::Map<String, serde_json::Value>) -> String {
+        self.nodes += 1;
+        let mut attributes = Vec::new();
+        let mut children = Vec::new();
+
+        for (name, value) in node {
+            let child_path = format!("{}/{}", path.trim_end_matches('/'), name);
+            // Binary lengths (":jcr:data") and access control are not exported
+            if name.starts_with(':') || name.starts_with("rep:") {
+                self.skipped.push(child_path);
+                continue;
+            }
+            if self.check_prefix(name).is_err() {
+                self.skipped.push(format!("{} (unknown namespace)", child_path));
+                continue;
+            }
+
+            match value {
+                serde_json::Value::Object(child) => children.push((name.clone(), child_path, child)),
+                serde_json::Value::Null => {}
+                _ => {
+                    let mut value = value.clone();
+                    self.scrubber.scrub(name, &mut value);
+                    if let Some(text) = docview_value(&value) {
+                        self.properties += 1;
+                        attributes.push(format!("{}=\"{}\"", name, xml_escape(&text)));
+                    }
+                }
+            }
+        }
+
+        let body: Vec<String> = children
+            .into_iter()
+            .map(|(name, child_path, child)| self.write_node(&encode_element_name(&name), &child_path, child))
+            .collect();
+
+        let open = if attributes.is_empty() {
+            format!("<{}", element)
+        } else {
+            format!("<{}\n    {}", element, attributes.join("\n    "))
+        };
+        if body.is_empty() {
+            format!("{}/>", open)
+        } else {
+            format!("{}>\n{}\n</{}>", open, body.join("\n"), element)
+        }
+    }
+}
+
+/// .content.xml for the exported root node
+fn render_content_xml(path: &str, root: &serde_json::Map<String, serde_json::Value>, writer: &mut DocViewWriter) -> String {
+    let body = writer.write_node("jcr:root", path, root);
+    writer.prefixes.insert("jcr");
+    let declarations: Vec<String> = NAMESPACES
+        .iter()
+        .filter(|(p, _)| writer.prefixes.contains(p))
+        .map(|(p, uri)| format!("xmlns:{}=\"{}\"", p, uri))
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
+        body.replacen("<jcr:root", &format!("<jcr:root {}", declarations.join(" ")), 1)
+    )
+}
+
+fn package_properties(name: &str, path: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8" standalone="no"?>
+<!DOCTYPE properties SYSTEM "http://java.sun.com/dtd/properties.dtd">
+<properties>
+<entry key="name">{}</entry>
+<entry key="group">aem-env-manager</entry>
+<entry key="version">1.0</entry>
+<entry key="description">Anonymized export of {} ({})</entry>
+</properties>
+"#,
+        xml_escape(name),
+        xml_escape(path),
+        chrono::Utc::now().to_rfc3339()
+    )
+}
+
+/// Build the package zip from an infinity.json export of `path`
+fn write_package(
+    output: &PathBuf,
+    path: &str,
+    content: &serde_json::Value,
+    options: ScrubOptions,
+) -> Result<ContentPackageResult, String> {
+    let root = content
+        .as_object()
+        .ok_or_else(|| format!("Unexpected content export for {}", path))?;
+
+    let mut scrubber = Scrubber::new(options);
+    let mut writer = DocViewWriter {
+        scrubber: &mut scrubber,
+        prefixes: BTreeSet::new(),
+        nodes: 0,
+        properties: 0,
+        skipped: Vec::new(),
+    };
+    let content_xml = render_content_xml(path, root, &mut writer);
+    let (nodes, properties, skipped) = (writer.nodes, writer.properties, writer.skipped);
+
+    let name = format!(
+        "anonymized-{}",
+        path.rsplit('/').next().filter(|s| !s.is_empty()).unwrap_or("content")
+    );
+    let fs_path: Vec<String> = path.split('/').filter(|s| !s.is_empty()).map(platform_name).collect();
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+    let file = fs::File::create(output).map_err(|e| format!("Failed to create package: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let entries = [
+        (
+            "META-INF/vault/filter.xml".to_string(),
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<workspaceFilter version=\"1.0\">\n    <filter root=\"{}\"/>\n</workspaceFilter>\n",
+                xml_escape(path)
+            ),
+        ),
+        ("META-INF/vault/properties.xml".to_string(), package_properties(&name, path)),
+        (format!("jcr_root/{}/.content.xml", fs_path.join("/")), content_xml),
+    ];
+    for (entry, text) in entries {
+        zip.start_file(&entry, options)
+            .map_err(|e| format!("Failed to add {} to package: {}", entry, e))?;
+        zip.write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write {} to package: {}", entry, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to finalize package: {}", e))?;
+
+    Ok(ContentPackageResult {
+        output_path: output.to_string_lossy().to_string(),
+        nodes,
+        properties,
+        scrubbed: scrubber.scrubbed,
+        skipped,
+    })
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Export the subtree at `path` as a content package with scrubbed properties.
+/// Binaries and access control entries are left out; dates are exported as strings.
+/// Progress of the content download is emitted under `operation_id`.
+#[command]
+pub async fn export_anonymized_package(
+    app: AppHandle,
+    id: String,
+    path: String,
+    output_path: String,
+    options: Option<ScrubOptions>,
+    operation_id: Option<String>,
+) -> Result<ContentPackageResult, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+    let path = format!("/{}", path.trim_matches('/'));
+    if path == "/" {
+        return Err("Exporting the repository root is not supported; choose a content path".to_string());
+    }
+
+    let (username, password, _) = get_instance_credentials(&instance.id)?;
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(60)))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut response = client
+        .get(format!("{}{}.infinity.json", instance.base_url(), path))
+        .basic_auth(&username, Some(&password))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to export {}: {}", path, e))?;
+
+    match response.status().as_u16() {
+        200 => {}
+        // Sling answers 300 with a list of depth-limited URLs when the tree is too large
+        300 => return Err(format!("{} is too large to export at once; choose a smaller subtree", path)),
+        404 => return Err(format!("{} does not exist", path)),
+        status => return Err(format!("Exporting {} returned HTTP {}", path, status)),
+    }
+
+    let mut progress = ProgressReporter::new(&app, operation_id, &path);
+    progress.start(response.content_length(), 0);
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to export {}: {}", path, e))?
+    {
+        body.extend_from_slice(&chunk);
+        progress.advance(chunk.len() as u64);
+    }
+    progress.finish();
+
+    let content: serde_json::Value =
+        serde_json::from_slice(&body).map_err(|e| format!("Failed to parse content of {}: {}", path, e))?;
+
+    write_package(&PathBuf::from(output_path), &path, &content, options.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_content_xml() {
+        let content = serde_json::json!({
+            "jcr:primaryType": "cq:Page",
+            "jcr:content": {
+                "jcr:primaryType": "cq:PageContent",
+                "jcr:title": "{team} contact",
+                "cq:lastModifiedBy": "jane.doe",
+                "hideInNav": true,
+                "tags": ["a,b", "c"],
+                "apiToken": "abc",
+                "text": "Mail jane.doe@corp.example or call",
+                "rep:policy": {"jcr:primaryType": "rep:ACL"},
+                "custom:flag": "x"
+            },
+            "1-child": {"jcr:primaryType": "nt:unstructured", ":jcr:data": 42}
+        });
+
+        let mut scrubber = Scrubber::new(ScrubOptions::default());
+        let mut writer = DocViewWriter {
+            scrubber: &mut scrubber,
+            prefixes: BTreeSet::new(),
+            nodes: 0,
+            properties: 0,
+            skipped: Vec::new(),
+        };
+        let xml = render_content_xml("/content/site/en", content.as_object().unwrap(), &mut writer);
+
+        assert!(xml.contains(r#"xmlns:cq="http://www.day.com/jcr/cq/1.0""#));
+        assert!(xml.contains(r#"jcr:title="\{team} contact""#));
+        assert!(xml.contains(r#"cq:lastModifiedBy="anonymous""#));
+        assert!(xml.contains(r#"hideInNav="{Boolean}true""#));
+        assert!(xml.contains(r#"tags="[a\,b,c]""#));
+        assert!(xml.contains(r#"apiToken="REDACTED""#));
+        assert!(xml.contains("Mail redacted@example.com or call"));
+        assert!(xml.contains("<_x0031_-child"));
+        assert!(!xml.contains("rep:policy") && !xml.contains("custom:flag"));
+        assert_eq!(writer.skipped.len(), 3);
+        assert_eq!(scrubber.scrubbed, 3);
+    }
+}
@@ -0,0 +1,324 @@
+// Content Seed Commands
+// Named sets of content packages and Sling POST fixtures applied to an instance in one go,
+// so a fresh instance gets the same standard test content every time
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{command, AppHandle};
+
+use crate::commands::instance::{check_aem_http_ready, AemInstance};
+use crate::commands::packages::{connect, post_package};
+use crate::commands::progress::ProgressReporter;
+use crate::i18n::MessageCode;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SeedStep {
+    /// Upload and install a local content package zip
+    Package { path: String },
+    /// Create or update the node at `path` through the Sling POST servlet. Arrays become
+    /// multi-value properties, numbers and booleans keep their type, null removes a property.
+    SlingPost {
+        path: String,
+        #[serde(default)]
+        primary_type: Option<String>,
+        #[serde(default)]
+        properties: BTreeMap<String, serde_json::Value>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSeed {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Applied in order
+    pub steps: Vec<SeedStep>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedStepResult {
+    pub step: SeedStep,
+    pub success: bool,
+    pub message: String,
+    #[serde(flatten)]
+    pub message_code: MessageCode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSeedResult {
+    pub seed_id: String,
+    pub instance_id: String,
+    /// False when a step failed; steps after it were not applied
+    pub success: bool,
+    pub steps: Vec<SeedStepResult>,
+}
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_seeds_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("content_seeds.json")
+}
+
+fn load_seeds() -> Result<Vec<ContentSeed>, String> {
+    let file_path = get_seeds_file();
+    if !file_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read content seeds: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse content seeds: {}", e))
+}
+
+fn save_seeds(seeds: &[ContentSeed]) -> Result<(), String> {
+    let file_path = get_seeds_file();
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(seeds).map_err(|e| format!("Failed to serialize content seeds: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write content seeds: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn scalar_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// `@TypeHint` for a JSON value so Sling stores numbers and booleans with their type
+fn type_hint(value: &serde_json::Value) -> Option<&'static str> {
+    match value {
+        serde_json::Value::Bool(_) => Some("Boolean"),
+        serde_json::Value::Number(n) if n.is_f64() => Some("Double"),
+        serde_json::Value::Number(_) => Some("Long"),
+        _ => None,
+    }
+}
+
+/// Sling POST servlet form for a fixture node
+fn sling_post_form(
+    primary_type: Option<&str>,
+    properties: &BTreeMap<String, serde_json::Value>,
+) -> Vec<(String, String)> {
+    let mut form = Vec::new();
+    if let Some(primary_type) = primary_type {
+        form.push(("jcr:primaryType".to_string(), primary_type.to_string()));
+    }
+    for (name, value) in properties {
+        match value {
+            serde_json::Value::Null => form.push((format!("{}@Delete", name), String::new())),
+            serde_json::Value::Array(items) => {
+                let hint = items.first().and_then(type_hint).unwrap_or("String");
+                form.push((format!("{}@TypeHint", name), format!("{}[]", hint)));
+                form.extend(items.iter().map(|item| (name.clone(), scalar_text(item))));
+            }
+            scalar => {
+                if let Some(hint) = type_hint(scalar) {
+                    form.push((format!("{}@TypeHint", name), hint.to_string()));
+                }
+                form.push((name.clone(), scalar_text(scalar)));
+            }
+        }
+    }
+    form
+}
+
+async fn apply_step(
+    app: &AppHandle,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    step: &SeedStep,
+    operation_id: Option<String>,
+) -> Result<(String, MessageCode), String> {
+    match step {
+        SeedStep::Package { path } => {
+            let path = Path::new(path);
+            let label = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let progress = ProgressReporter::new(app, operation_id, &label);
+            post_package(instance, credentials, path, true, true, Some(progress)).await?;
+            Ok((
+                format!("Installed {}", label),
+                MessageCode::new("seed_package_installed").with("package", &label),
+            ))
+        }
+        SeedStep::SlingPost {
+            path,
+            primary_type,
+            properties,
+        } => {
+            let path = format!("/{}", path.trim().trim_matches('/'));
+            let client = instance
+                .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(30)))
+                .build()
+                .map_err(|e| e.to_string())?;
+            let response = client
+                .post(format!("{}{}", instance.base_url(), path))
+                .basic_auth(&credentials.0, Some(&credentials.1))
+                .header("Accept", "application/json")
+                .form(&sling_post_form(primary_type.as_deref(), properties))
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?;
+            let status = response.status();
+            if status.is_success() {
+                Ok((format!("Posted {}", path), MessageCode::new("seed_node_posted").with("path", &path)))
+            } else {
+                Err(format!("Posting {} returned HTTP {}", path, status.as_u16()))
+            }
+        }
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Configured content seeds
+#[command]
+pub async fn list_content_seeds() -> Result<Vec<ContentSeed>, String> {
+    load_seeds()
+}
+
+/// Create a seed (empty id) or replace the seed with the same id
+#[command]
+pub async fn save_content_seed(seed: ContentSeed) -> Result<ContentSeed, String> {
+    let mut seed = seed;
+    seed.name = seed.name.trim().to_string();
+    if seed.name.is_empty() {
+        return Err("Seed name is required".to_string());
+    }
+    if seed.steps.is_empty() {
+        return Err("A content seed needs at least one package or fixture".to_string());
+    }
+
+    let mut seeds = load_seeds()?;
+    let now = chrono::Utc::now().to_rfc3339();
+    seed.updated_at = now.clone();
+    match seeds.iter_mut().find(|s| !seed.id.is_empty() && s.id == seed.id) {
+        Some(existing) => {
+            seed.created_at = existing.created_at.clone();
+            *existing = seed.clone();
+        }
+        None => {
+            if seed.id.is_empty() {
+                seed.id = uuid::Uuid::new_v4().to_string();
+            }
+            seed.created_at = now;
+            seeds.push(seed.clone());
+        }
+    }
+    save_seeds(&seeds)?;
+    Ok(seed)
+}
+
+#[command]
+pub async fn delete_content_seed(id: String) -> Result<bool, String> {
+    let mut seeds = load_seeds()?;
+    let before = seeds.len();
+    seeds.retain(|s| s.id != id);
+    if seeds.len() == before {
+        return Ok(false);
+    }
+    save_seeds(&seeds)?;
+    Ok(true)
+}
+
+/// Apply a seed's steps in order to a running instance, stopping at the first failure.
+/// Package uploads are reported as `TRANSFER_PROGRESS_EVENT` under `operation_id`.
+#[command]
+pub async fn apply_content_seed(
+    app: AppHandle,
+    instance_id: String,
+    seed_id: String,
+    operation_id: Option<String>,
+) -> Result<ContentSeedResult, String> {
+    let seed = load_seeds()?
+        .into_iter()
+        .find(|s| s.id == seed_id)
+        .ok_or_else(|| format!("Content seed {} not found", seed_id))?;
+    let (instance, credentials) = connect(&instance_id)?;
+    if !check_aem_http_ready(&instance, 3000).await {
+        return Err(format!("{} is not running", instance.name));
+    }
+
+    let mut result = ContentSeedResult {
+        seed_id: seed.id.clone(),
+        instance_id: instance.id.clone(),
+        success: true,
+        steps: Vec::new(),
+    };
+    for step in seed.steps {
+        let (success, message, message_code) =
+            match apply_step(&app, &instance, &credentials, &step, operation_id.clone()).await {
+                Ok((message, message_code)) => (true, message, message_code),
+                Err(e) => {
+                    let message_code = MessageCode::new("seed_step_failed").with("detail", &e);
+                    (false, e, message_code)
+                }
+            };
+        result.steps.push(SeedStepResult {
+            step,
+            success,
+            message,
+            message_code,
+        });
+        if !success {
+            result.success = false;
+            break;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sling_post_form() {
+        let step: SeedStep = serde_json::from_value(serde_json::json!({
+            "kind": "sling_post",
+            "path": "/content/test/page",
+            "primary_type": "nt:unstructured",
+            "properties": { "title": "Test", "count": 3, "tags": ["a", "b"], "hidden": false, "old": null }
+        }))
+        .unwrap();
+        let SeedStep::SlingPost {
+            primary_type,
+            properties,
+            ..
+        } = step
+        else {
+            panic!("expected a Sling POST step");
+        };
+        let form = sling_post_form(primary_type.as_deref(), &properties);
+        let has = |k: &str, v: &str| form.iter().any(|(fk, fv)| fk == k && fv == v);
+        assert!(has("jcr:primaryType", "nt:unstructured"));
+        assert!(has("count@TypeHint", "Long") && has("count", "3"));
+        assert!(has("hidden@TypeHint", "Boolean") && has("hidden", "false"));
+        assert!(has("tags@TypeHint", "String[]") && has("tags", "a") && has("tags", "b"));
+        assert!(has("old@Delete", "") && has("title", "Test"));
+    }
+}
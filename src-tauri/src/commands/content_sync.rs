@@ -0,0 +1,262 @@
+// Content Sync Commands
+// Copies content between two instances: a temporary package with the chosen filter paths is
+// built on the source, downloaded and installed on the target, then removed from the source
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::instance::AemInstance;
+use crate::commands::packages::{build_package_at, connect, download_package_to, packmgr_json_result, post_package};
+use crate::commands::progress::ProgressReporter;
+use crate::i18n::MessageCode;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentSyncPhase {
+    /// Creating the temporary package and setting its filters on the source
+    Creating,
+    Building,
+    Downloading,
+    Installing,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSyncProgress {
+    pub source_instance_id: String,
+    pub target_instance_id: String,
+    pub phase: ContentSyncPhase,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+    pub elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSyncResult {
+    pub source_instance_id: String,
+    pub target_instance_id: String,
+    pub paths: Vec<String>,
+    pub package_size: u64,
+    pub elapsed_secs: u64,
+    /// Package Manager install log of the target
+    pub log: String,
+}
+
+/// Event emitted with a `ContentSyncProgress` whenever a sync enters a new phase
+pub const CONTENT_SYNC_PROGRESS_EVENT: &str = "content-sync-progress";
+
+/// Package group of the temporary sync packages
+const SYNC_PACKAGE_GROUP: &str = "aem-env-manager";
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Filter roots with a leading slash and without duplicates or nested paths
+fn normalize_filter_paths(paths: &[String]) -> Result<Vec<String>, String> {
+    let mut roots: Vec<String> = paths
+        .iter()
+        .map(|p| format!("/{}", p.trim().trim_matches('/')))
+        .filter(|p| p != "/")
+        .collect();
+    if roots.is_empty() {
+        return Err("At least one content path is required; the repository root cannot be synced".to_string());
+    }
+    roots.sort();
+    roots.dedup();
+    let nested: Vec<String> = roots
+        .iter()
+        .filter(|p| roots.iter().any(|root| p.starts_with(&format!("{}/", root))))
+        .cloned()
+        .collect();
+    roots.retain(|p| !nested.contains(p));
+    Ok(roots)
+}
+
+/// Filter definition for /crx/packmgr/update.jsp
+fn filter_json(roots: &[String]) -> String {
+    let filters: Vec<serde_json::Value> = roots
+        .iter()
+        .map(|root| serde_json::json!({ "root": root, "rules": [] }))
+        .collect();
+    serde_json::Value::Array(filters).to_string()
+}
+
+async fn packmgr_post(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    url_path: &str,
+    form: &[(&str, &str)],
+) -> Result<serde_json::Value, String> {
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(60)))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("{}{}", instance.base_url(), url_path);
+    let response = client
+        .post(&url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status().as_u16()));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse Package Manager response: {}", e))
+}
+
+/// Create the temporary package on the source and set its filters; returns the package path
+async fn create_sync_package(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    name: &str,
+    roots: &[String],
+) -> Result<String, String> {
+    let created = packmgr_post(
+        instance,
+        credentials,
+        "/crx/packmgr/service/exec.json?cmd=create",
+        &[("packageName", name), ("groupName", SYNC_PACKAGE_GROUP)],
+    )
+    .await?;
+    packmgr_json_result(&created)?;
+    let path = created
+        .get("path")
+        .and_then(|p| p.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("/etc/packages/{}/{}.zip", SYNC_PACKAGE_GROUP, name));
+
+    let filter = filter_json(roots);
+    let updated = packmgr_post(
+        instance,
+        credentials,
+        "/crx/packmgr/update.jsp",
+        &[
+            ("path", &path),
+            ("packageName", name),
+            ("groupName", SYNC_PACKAGE_GROUP),
+            ("filter", &filter),
+            ("_charset_", "UTF-8"),
+        ],
+    )
+    .await?;
+    packmgr_json_result(&updated)?;
+    Ok(path)
+}
+
+async fn delete_package_at(instance: &AemInstance, credentials: &(String, String), path: &str) -> Result<(), String> {
+    let url_path = format!("/crx/packmgr/service/.json{}?cmd=delete", path);
+    let result = packmgr_post(instance, credentials, &url_path, &[]).await?;
+    packmgr_json_result(&result).map(|_| ())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Copy content paths (e.g. /content/mysite) from one instance to another through a temporary
+/// package. Phases are emitted as `CONTENT_SYNC_PROGRESS_EVENT`, the package download and
+/// upload as `TRANSFER_PROGRESS_EVENT` under `operation_id`.
+#[command]
+pub async fn sync_content(
+    app: AppHandle,
+    source_instance_id: String,
+    target_instance_id: String,
+    paths: Vec<String>,
+    operation_id: Option<String>,
+) -> Result<ContentSyncResult, String> {
+    let began = Instant::now();
+    let emit = |phase: ContentSyncPhase, message: Option<(String, MessageCode)>| {
+        let (message, message_code) = message.unzip();
+        let _ = app.emit(
+            CONTENT_SYNC_PROGRESS_EVENT,
+            ContentSyncProgress {
+                source_instance_id: source_instance_id.clone(),
+                target_instance_id: target_instance_id.clone(),
+                phase,
+                message,
+                message_code,
+                elapsed_secs: began.elapsed().as_secs(),
+            },
+        );
+    };
+    let fail = |error: String| {
+        let code = MessageCode::new("content_sync_failed").with("detail", &error);
+        emit(ContentSyncPhase::Failed, Some((error.clone(), code)));
+        error
+    };
+
+    if source_instance_id == target_instance_id {
+        return Err("Source and target must be different instances".to_string());
+    }
+    let roots = normalize_filter_paths(&paths)?;
+    let (source, source_credentials) = connect(&source_instance_id)?;
+    let (target, target_credentials) = connect(&target_instance_id)?;
+
+    let root_list = roots.join(", ");
+    let code = MessageCode::new("content_sync_creating").with("paths", &root_list);
+    emit(ContentSyncPhase::Creating, Some((root_list, code)));
+    let name = format!("content-sync-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+    let package_path = create_sync_package(&source, &source_credentials, &name, &roots)
+        .await
+        .map_err(fail)?;
+
+    // The temporary package is removed from the source whatever happens after it was created
+    let work_dir = std::env::temp_dir().join("aem-env-manager-sync").join(&name);
+    let transferred = async {
+        emit(ContentSyncPhase::Building, None);
+        build_package_at(&source, &source_credentials, &package_path).await?;
+
+        emit(ContentSyncPhase::Downloading, None);
+        let progress = ProgressReporter::new(&app, operation_id.clone(), &format!("{} ({})", name, source.name));
+        let zip = download_package_to(&source, &source_credentials, &package_path, &work_dir, Some(progress)).await?;
+        let size = std::fs::metadata(&zip).map(|m| m.len()).unwrap_or(0);
+
+        let code = MessageCode::new("content_sync_installing").with("instance", &target.name);
+        emit(ContentSyncPhase::Installing, Some((target.name.clone(), code)));
+        let progress = ProgressReporter::new(&app, operation_id.clone(), &format!("{} ({})", name, target.name));
+        let log = post_package(&target, &target_credentials, &zip, true, true, Some(progress)).await?;
+        Ok::<_, String>((size, log))
+    }
+    .await;
+    let _ = delete_package_at(&source, &source_credentials, &package_path).await;
+    let _ = std::fs::remove_dir_all(&work_dir);
+    let (package_size, log) = transferred.map_err(fail)?;
+
+    emit(ContentSyncPhase::Done, None);
+    Ok(ContentSyncResult {
+        source_instance_id: source_instance_id.clone(),
+        target_instance_id: target_instance_id.clone(),
+        paths: roots,
+        package_size,
+        elapsed_secs: began.elapsed().as_secs(),
+        log,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_filter_paths() {
+        let paths = vec![
+            "/content/mysite/".to_string(),
+            "content/dam/mysite".to_string(),
+            "/content/mysite/en".to_string(),
+            "/content/mysite".to_string(),
+        ];
+        let roots = normalize_filter_paths(&paths).unwrap();
+        assert_eq!(roots, vec!["/content/dam/mysite", "/content/mysite"]);
+        assert!(normalize_filter_paths(&["/".to_string()]).is_err());
+        assert_eq!(filter_json(&roots[1..]), r#"[{"root":"/content/mysite","rules":[]}]"#);
+    }
+}
@@ -0,0 +1,754 @@
+// Instance Credential Storage
+// Instances can have several named credentials (admin, replication user, read-only user); the
+// one named "default" is used unless a command selects another. They live in the OS keychain
+// (Keychain on macOS, Credential Manager on Windows, Secret Service on Linux). Without a usable
+// keychain, or with `use_os_keychain` turned off, they go to an AES-256-GCM encrypted file whose
+// key is derived from a machine-specific secret and a rotatable salt. The backend in use is
+// recorded, and entries are moved over when it changes. The plaintext `.credentials` file of
+// earlier versions is migrated on first use.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::command;
+
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialBackend {
+    Keychain,
+    /// Encrypted file in the data directory, used when no keychain is available
+    EncryptedFile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialStorageStatus {
+    pub backend: CredentialBackend,
+    /// Why the keychain could not be used
+    pub keychain_error: Option<String>,
+    /// Entries moved out of the plaintext file at startup
+    pub migrated: usize,
+    /// Entries moved from the previously used backend at startup
+    #[serde(default)]
+    pub backend_migrated: usize,
+    /// A plaintext file is left because its migration failed
+    pub plaintext_file_present: bool,
+    /// Current key of the encrypted file, None until the file is first written
+    pub key_id: Option<u32>,
+    pub key_created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptedCredentials {
+    /// Key the file was encrypted with; 0 is the random key of earlier versions
+    #[serde(default)]
+    key_id: u32,
+    /// Hex-encoded 96-bit nonce and AES-256-GCM ciphertext of the credentials JSON
+    nonce: String,
+    data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CredentialKey {
+    key_id: u32,
+    /// Hex-encoded random salt that is combined with the machine secret. Key 0 was written by
+    /// earlier versions and holds the key itself.
+    salt: String,
+    #[serde(default)]
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CredentialKeyFile {
+    #[serde(flatten)]
+    current: CredentialKey,
+    /// Key before the last rotation, kept so a rotation interrupted before the credentials were
+    /// re-encrypted does not lose them
+    #[serde(default)]
+    previous: Option<CredentialKey>,
+}
+
+/// (username, password) by credential name
+pub(crate) type NamedCredentials = BTreeMap<String, (String, String)>;
+
+/// Credentials of one instance, as stored under its keychain entry or file key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredCredentials {
+    Named(NamedCredentials),
+    /// Single pair of earlier versions, read as the default credential
+    Single(String, String),
+}
+
+impl StoredCredentials {
+    fn into_named(self) -> NamedCredentials {
+        match self {
+            StoredCredentials::Named(named) => named,
+            StoredCredentials::Single(username, password) => {
+                NamedCredentials::from([(DEFAULT_CREDENTIAL.to_string(), (username, password))])
+            }
+        }
+    }
+}
+
+type CredentialMap = HashMap<String, StoredCredentials>;
+
+/// Credential used when a command does not name one
+pub(crate) const DEFAULT_CREDENTIAL: &str = "default";
+
+/// Entry name of secrets that are not instance credentials (see `write_secret`)
+const SECRET_ENTRY: &str = "secret";
+
+const KEYRING_SERVICE: &str = "aem-env-manager";
+
+/// Account looked up once to find out whether the keychain works
+const PROBE_ACCOUNT: &str = "__aem_env_manager_probe__";
+
+/// Mixed into every derived key so it cannot be reused for anything else
+const KEY_CONTEXT: &[u8] = b"aem-env-manager credentials v1";
+
+static BACKEND: OnceLock<(CredentialBackend, Option<String>)> = OnceLock::new();
+static MIGRATED: OnceLock<usize> = OnceLock::new();
+static BACKEND_MIGRATED: OnceLock<usize> = OnceLock::new();
+static MACHINE_SECRET: OnceLock<String> = OnceLock::new();
+
+/// Serializes read-modify-write of the encrypted file and the migration
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn data_file(name: &str) -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join(name)
+}
+
+/// Plaintext file of earlier versions
+fn get_plaintext_file() -> PathBuf {
+    data_file(".credentials")
+}
+
+fn get_encrypted_file() -> PathBuf {
+    data_file(".credentials.enc")
+}
+
+fn get_key_file() -> PathBuf {
+    data_file(".credentials.key")
+}
+
+/// Backend the credentials were last stored in
+fn get_backend_file() -> PathBuf {
+    data_file(".credentials.backend")
+}
+
+fn write_private(path: &PathBuf, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// Key file of the encrypted store; the bare hex key of earlier versions is read as key 0
+fn load_key_file() -> Result<Option<CredentialKeyFile>, String> {
+    let path = get_key_file();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    if let Ok(key_file) = serde_json::from_str(&content) {
+        return Ok(Some(key_file));
+    }
+    match from_hex(content.trim()) {
+        Some(key) if key.len() == 32 => Ok(Some(CredentialKeyFile {
+            current: CredentialKey {
+                key_id: 0,
+                salt: content.trim().to_string(),
+                created_at: None,
+            },
+            previous: None,
+        })),
+        _ => Err(format!("Credentials key {} is corrupt", path.display())),
+    }
+}
+
+fn save_key_file(key_file: &CredentialKeyFile) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(key_file).map_err(|e| e.to_string())?;
+    write_private(&get_key_file(), &content)
+}
+
+/// Write a new key file with a fresh salt; the current key is kept as the previous one
+fn create_next_key(current: Option<CredentialKeyFile>) -> Result<CredentialKeyFile, String> {
+    let salt: [u8; 32] = rand::random();
+    let key_file = CredentialKeyFile {
+        current: CredentialKey {
+            key_id: current.as_ref().map_or(1, |k| k.current.key_id + 1),
+            salt: to_hex(&salt),
+            created_at: Some(chrono::Utc::now().to_rfc3339()),
+        },
+        previous: current.map(|k| k.current),
+    };
+    save_key_file(&key_file)?;
+    Ok(key_file)
+}
+
+fn load_encrypted_file() -> Result<CredentialMap, String> {
+    let path = get_encrypted_file();
+    if !path.exists() {
+        return Ok(CredentialMap::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read credentials: {}", e))?;
+    let file: EncryptedCredentials =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse credentials: {}", e))?;
+    let key_file = load_key_file()?.ok_or("The credentials key file is missing")?;
+    let key = key_for(&key_file, file.key_id, machine_secret()).ok_or_else(|| {
+        format!("Credentials were encrypted with key {}, which is no longer available", file.key_id)
+    })?;
+    let plaintext = decrypt(&key, &file)?;
+    let credentials = serde_json::from_slice(&plaintext).map_err(|e| format!("Failed to parse credentials: {}", e))?;
+
+    // Finish an interrupted rotation and move files of earlier versions to a derived key
+    if key_file.current.key_id == 0 {
+        write_encrypted_file(&create_next_key(Some(key_file))?, &credentials)?;
+    } else if file.key_id != key_file.current.key_id {
+        write_encrypted_file(&key_file, &credentials)?;
+    }
+    Ok(credentials)
+}
+
+fn write_encrypted_file(key_file: &CredentialKeyFile, credentials: &CredentialMap) -> Result<(), String> {
+    let key = key_for(key_file, key_file.current.key_id, machine_secret()).ok_or("Invalid credentials key")?;
+    let plaintext = serde_json::to_vec(credentials).map_err(|e| e.to_string())?;
+    let file = encrypt(key_file.current.key_id, &key, &plaintext)?;
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    write_private(&get_encrypted_file(), &content)
+}
+
+fn save_encrypted_file(credentials: &CredentialMap) -> Result<(), String> {
+    let key_file = match load_key_file()? {
+        Some(key_file) if key_file.current.key_id != 0 => key_file,
+        current => create_next_key(current)?,
+    };
+    write_encrypted_file(&key_file, credentials)
+}
+
+fn load_backend_file() -> Option<CredentialBackend> {
+    let content = fs::read_to_string(get_backend_file()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_backend_file(backend: CredentialBackend) -> Result<(), String> {
+    let content = serde_json::to_string(&backend).map_err(|e| e.to_string())?;
+    write_private(&get_backend_file(), &content)
+}
+
+fn load_plaintext_file() -> Result<Option<CredentialMap>, String> {
+    let path = get_plaintext_file();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse credentials file {}: {}", path.display(), e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Machine-specific secret the file key is derived from, so the encrypted file and its key file
+/// copied to another machine cannot be decrypted there
+fn machine_secret() -> &'static str {
+    MACHINE_SECRET.get_or_init(|| {
+        read_machine_id().unwrap_or_else(|| {
+            println!("[AEM] No machine id found; the credentials key is derived from the user and host name");
+            let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default();
+            let host = std::env::var("HOSTNAME")
+                .or_else(|_| std::env::var("COMPUTERNAME"))
+                .unwrap_or_default();
+            format!("{}@{}", user, host)
+        })
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_machine_id() -> Option<String> {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn read_machine_id() -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("IOPlatformUUID"))
+        .and_then(|line| line.split('"').nth(3))
+        .map(|uuid| uuid.to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn read_machine_id() -> Option<String> {
+    let output = std::process::Command::new("reg")
+        .args(["query", r"HKLM\SOFTWARE\Microsoft\Cryptography", "/v", "MachineGuid"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("MachineGuid"))
+        .and_then(|line| line.split_whitespace().last())
+        .map(|guid| guid.to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn read_machine_id() -> Option<String> {
+    None
+}
+
+fn derive_key(machine_secret: &str, salt: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_CONTEXT);
+    hasher.update(machine_secret.as_bytes());
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+/// AES key for `key_id`, from the current or the previous key of the key file
+fn key_for(key_file: &CredentialKeyFile, key_id: u32, machine_secret: &str) -> Option<[u8; 32]> {
+    let key = std::iter::once(&key_file.current)
+        .chain(key_file.previous.as_ref())
+        .find(|k| k.key_id == key_id)?;
+    let salt = from_hex(&key.salt)?;
+    if key.key_id == 0 {
+        return salt.try_into().ok();
+    }
+    Some(derive_key(machine_secret, &salt))
+}
+
+fn encrypt(key_id: u32, key: &[u8; 32], plaintext: &[u8]) -> Result<EncryptedCredentials, String> {
+    let cipher = Aes256Gcm::new(key.into());
+    let nonce: [u8; 12] = rand::random();
+    let data = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Failed to encrypt credentials".to_string())?;
+    Ok(EncryptedCredentials {
+        key_id,
+        nonce: to_hex(&nonce),
+        data: to_hex(&data),
+    })
+}
+
+fn decrypt(key: &[u8; 32], file: &EncryptedCredentials) -> Result<Vec<u8>, String> {
+    let nonce = from_hex(&file.nonce).filter(|n| n.len() == 12).ok_or("Invalid credentials nonce")?;
+    let data = from_hex(&file.data).ok_or("Invalid credentials data")?;
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(&nonce), data.as_slice())
+        .map_err(|_| "Failed to decrypt credentials; the key file does not match".to_string())
+}
+
+fn backend_state() -> &'static (CredentialBackend, Option<String>) {
+    BACKEND.get_or_init(|| {
+        if !crate::commands::profile::get_startup_config().use_os_keychain {
+            return (CredentialBackend::EncryptedFile, None);
+        }
+        match keyring::Entry::new(KEYRING_SERVICE, PROBE_ACCOUNT).and_then(|entry| entry.get_password()) {
+            Ok(_) | Err(keyring::Error::NoEntry) => (CredentialBackend::Keychain, None),
+            Err(e) => {
+                println!("[AEM] OS keychain unavailable, using the encrypted credentials file: {}", e);
+                (CredentialBackend::EncryptedFile, Some(e.to_string()))
+            }
+        }
+    })
+}
+
+fn backend() -> CredentialBackend {
+    backend_state().0
+}
+
+fn keychain_entry(instance_id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, instance_id).map_err(|e| format!("Keychain error: {}", e))
+}
+
+fn backend_read(instance_id: &str) -> Result<NamedCredentials, String> {
+    read_from(backend(), instance_id)
+}
+
+fn read_from(backend: CredentialBackend, instance_id: &str) -> Result<NamedCredentials, String> {
+    let stored = match backend {
+        CredentialBackend::Keychain => match keychain_entry(instance_id)?.get_password() {
+            Ok(secret) => serde_json::from_str::<StoredCredentials>(&secret)
+                .map(Some)
+                .map_err(|e| format!("Failed to parse keychain entry: {}", e))?,
+            Err(keyring::Error::NoEntry) => None,
+            Err(e) => return Err(format!("Failed to read from the keychain: {}", e)),
+        },
+        CredentialBackend::EncryptedFile => load_encrypted_file()?.remove(instance_id),
+    };
+    Ok(stored.map(StoredCredentials::into_named).unwrap_or_default())
+}
+
+/// Replace all credentials of an instance; an empty set removes its entry
+fn backend_write(instance_id: &str, credentials: NamedCredentials) -> Result<(), String> {
+    write_to(backend(), instance_id, credentials)
+}
+
+fn write_to(backend: CredentialBackend, instance_id: &str, credentials: NamedCredentials) -> Result<(), String> {
+    if credentials.is_empty() {
+        return remove_from(backend, instance_id);
+    }
+    let credentials = StoredCredentials::Named(credentials);
+    match backend {
+        CredentialBackend::Keychain => {
+            let secret = serde_json::to_string(&credentials).map_err(|e| e.to_string())?;
+            keychain_entry(instance_id)?
+                .set_password(&secret)
+                .map_err(|e| format!("Failed to write to the keychain: {}", e))
+        }
+        CredentialBackend::EncryptedFile => {
+            let mut stored = load_encrypted_file()?;
+            stored.insert(instance_id.to_string(), credentials);
+            save_encrypted_file(&stored)
+        }
+    }
+}
+
+fn backend_remove(instance_id: &str) -> Result<(), String> {
+    remove_from(backend(), instance_id)
+}
+
+fn remove_from(backend: CredentialBackend, instance_id: &str) -> Result<(), String> {
+    match backend {
+        CredentialBackend::Keychain => match keychain_entry(instance_id)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete from the keychain: {}", e)),
+        },
+        CredentialBackend::EncryptedFile => {
+            let mut stored = load_encrypted_file()?;
+            if stored.remove(instance_id).is_none() {
+                return Ok(());
+            }
+            save_encrypted_file(&stored)
+        }
+    }
+}
+
+/// Entries that may exist in `backend`; keychain entries cannot be listed, so the registered
+/// instances, the registry credential accounts of the profiles and the default account password
+/// are used for it
+fn stored_instance_ids(backend: CredentialBackend) -> Result<Vec<String>, String> {
+    match backend {
+        CredentialBackend::Keychain => Ok(crate::commands::instance::load_instances()?
+            .into_iter()
+            .map(|i| i.id)
+            .chain(crate::commands::registry::registry_accounts())
+            .chain([crate::commands::profile::DEFAULT_PASSWORD_ACCOUNT.to_string()])
+            .collect()),
+        CredentialBackend::EncryptedFile => Ok(load_encrypted_file()?.into_keys().collect()),
+    }
+}
+
+/// Move one instance's entry from `from` into the current backend, keeping credentials the
+/// current backend already has; returns whether there was anything to move
+fn move_entry(from: CredentialBackend, instance_id: &str) -> Result<bool, String> {
+    let previous = read_from(from, instance_id)?;
+    if previous.is_empty() {
+        return Ok(false);
+    }
+    let mut credentials = backend_read(instance_id)?;
+    for (name, credential) in previous {
+        credentials.entry(name).or_insert(credential);
+    }
+    backend_write(instance_id, credentials)?;
+    remove_from(from, instance_id)?;
+    Ok(true)
+}
+
+/// Move the entries of the backend used last into the current one once per run, e.g. after the
+/// keychain became unavailable or `use_os_keychain` was changed. The new backend is only
+/// recorded when every entry was moved, so a failed move is retried on the next run.
+fn migrate_backend() -> usize {
+    *BACKEND_MIGRATED.get_or_init(|| {
+        let current = backend();
+        // Versions that did not record the backend used the encrypted file only without a keychain
+        let previous = load_backend_file().unwrap_or(if get_encrypted_file().exists() {
+            CredentialBackend::EncryptedFile
+        } else {
+            current
+        });
+        let mut migrated = 0;
+        if previous != current {
+            let instance_ids = match stored_instance_ids(previous) {
+                Ok(ids) => ids,
+                Err(e) => {
+                    println!("[AEM] Credentials not moved to the {:?} backend: {}", current, e);
+                    return 0;
+                }
+            };
+            for instance_id in instance_ids {
+                match move_entry(previous, &instance_id) {
+                    Ok(moved) => migrated += usize::from(moved),
+                    Err(e) => {
+                        println!("[AEM] Failed to move credentials of {}: {}", instance_id, e);
+                        return migrated;
+                    }
+                }
+            }
+            println!("[AEM] Moved {} credential entries to the {:?} backend", migrated, current);
+        }
+        if let Err(e) = save_backend_file(current) {
+            println!("[AEM] Failed to record the credentials backend: {}", e);
+        }
+        migrated
+    })
+}
+
+/// Move the plaintext file into the backend once per run; the file is only deleted when every
+/// entry was moved, otherwise it is still read as a fallback
+fn migrate_plaintext() -> usize {
+    migrate_backend();
+    *MIGRATED.get_or_init(|| {
+        let plaintext = match load_plaintext_file() {
+            Ok(Some(plaintext)) => plaintext,
+            Ok(None) => return 0,
+            Err(e) => {
+                println!("[AEM] Credentials not migrated: {}", e);
+                return 0;
+            }
+        };
+        let mut migrated = 0;
+        for (instance_id, credentials) in plaintext {
+            match backend_write(&instance_id, credentials.into_named()) {
+                Ok(()) => migrated += 1,
+                Err(e) => {
+                    println!("[AEM] Failed to migrate credentials of {}: {}", instance_id, e);
+                    return migrated;
+                }
+            }
+        }
+        if let Err(e) = fs::remove_file(get_plaintext_file()) {
+            println!("[AEM] Failed to remove the plaintext credentials file: {}", e);
+        }
+        migrated
+    })
+}
+
+/// All named credentials of an instance
+pub(crate) fn list_stored_credentials(instance_id: &str) -> Result<NamedCredentials, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    migrate_plaintext();
+    let credentials = backend_read(instance_id)?;
+    if !credentials.is_empty() {
+        return Ok(credentials);
+    }
+    Ok(load_plaintext_file()
+        .ok()
+        .flatten()
+        .and_then(|mut p| p.remove(instance_id))
+        .map(StoredCredentials::into_named)
+        .unwrap_or_default())
+}
+
+pub(crate) fn read_stored_credentials(instance_id: &str, name: &str) -> Result<Option<(String, String)>, String> {
+    Ok(list_stored_credentials(instance_id)?.remove(name))
+}
+
+pub(crate) fn write_stored_credentials(
+    instance_id: &str,
+    name: &str,
+    username: &str,
+    password: &str,
+) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    migrate_plaintext();
+    let mut credentials = backend_read(instance_id)?;
+    credentials.insert(name.to_string(), (username.to_string(), password.to_string()));
+    backend_write(instance_id, credentials)
+}
+
+/// Drop a leftover plaintext copy, so a failed migration cannot bring removed credentials back
+fn remove_plaintext_copy(instance_id: &str) -> Result<(), String> {
+    if let Ok(Some(mut plaintext)) = load_plaintext_file() {
+        if plaintext.remove(instance_id).is_some() {
+            let content = serde_json::to_string_pretty(&plaintext).map_err(|e| e.to_string())?;
+            write_private(&get_plaintext_file(), &content)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove one named credential; returns whether it existed
+pub(crate) fn remove_named_credential(instance_id: &str, name: &str) -> Result<bool, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    migrate_plaintext();
+    let mut credentials = backend_read(instance_id)?;
+    if credentials.remove(name).is_none() {
+        return Ok(false);
+    }
+    let now_empty = credentials.is_empty();
+    backend_write(instance_id, credentials)?;
+    if now_empty {
+        remove_plaintext_copy(instance_id)?;
+    }
+    Ok(true)
+}
+
+pub(crate) fn remove_stored_credentials(instance_id: &str) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    migrate_plaintext();
+    backend_remove(instance_id)?;
+    remove_plaintext_copy(instance_id)
+}
+
+/// Secret stored under `account` in the same backend as instance credentials, for other
+/// secrets such as registry tokens
+pub(crate) fn read_secret(account: &str) -> Result<Option<String>, String> {
+    Ok(read_stored_credentials(account, SECRET_ENTRY)?.map(|(_, secret)| secret))
+}
+
+pub(crate) fn write_secret(account: &str, secret: &str) -> Result<(), String> {
+    write_stored_credentials(account, SECRET_ENTRY, account, secret)
+}
+
+pub(crate) fn remove_secret(account: &str) -> Result<(), String> {
+    remove_stored_credentials(account)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Where instance credentials are stored and whether the plaintext file was migrated
+#[command]
+pub async fn get_credential_storage_status() -> Result<CredentialStorageStatus, String> {
+    let (migrated, backend_migrated) = {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        (migrate_plaintext(), migrate_backend())
+    };
+    let (backend, keychain_error) = backend_state().clone();
+    let key = load_key_file().ok().flatten().map(|k| k.current);
+    Ok(CredentialStorageStatus {
+        backend,
+        keychain_error,
+        migrated,
+        backend_migrated,
+        plaintext_file_present: get_plaintext_file().exists(),
+        key_id: key.as_ref().map(|k| k.key_id),
+        key_created_at: key.and_then(|k| k.created_at),
+    })
+}
+
+/// Re-encrypt the credentials file with a new key. The new key is written first and the old
+/// one kept until the file is re-encrypted, so an interrupted rotation is finished on next read.
+#[command]
+pub async fn rotate_credentials_key() -> Result<CredentialStorageStatus, String> {
+    if backend() == CredentialBackend::Keychain {
+        return Err("Credentials are stored in the OS keychain; there is no file key to rotate".to_string());
+    }
+    {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        migrate_plaintext();
+        let credentials = load_encrypted_file()?;
+        let key_file = create_next_key(load_key_file()?)?;
+        write_encrypted_file(&key_file, &credentials)?;
+        println!(
+            "[AEM] Rotated the credentials key to key {} ({} entries re-encrypted)",
+            key_file.current.key_id,
+            credentials.len()
+        );
+    }
+    get_credential_storage_status().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let key = [7u8; 32];
+        let file = encrypt(1, &key, br#"{"author":["admin","secret"]}"#).unwrap();
+        assert!(!file.data.contains("secret"));
+        assert_eq!(decrypt(&key, &file).unwrap(), br#"{"author":["admin","secret"]}"#);
+        assert!(decrypt(&[8u8; 32], &file).is_err());
+
+        assert_eq!(from_hex(&to_hex(&[0, 15, 255])), Some(vec![0, 15, 255]));
+        assert_eq!(from_hex("abc"), None);
+    }
+
+    #[test]
+    fn test_stored_credentials_formats() {
+        let single: StoredCredentials = serde_json::from_str(r#"["admin","secret"]"#).unwrap();
+        assert_eq!(
+            single.into_named(),
+            NamedCredentials::from([("default".to_string(), ("admin".to_string(), "secret".to_string()))])
+        );
+
+        let named: StoredCredentials =
+            serde_json::from_str(r#"{"default":["admin","secret"],"replication":["repl","pw"]}"#).unwrap();
+        let named = named.into_named();
+        assert_eq!(named.len(), 2);
+        assert_eq!(named["replication"], ("repl".to_string(), "pw".to_string()));
+    }
+
+    #[test]
+    fn test_key_for_rotation() {
+        let key = |key_id: u32, salt: &str| CredentialKey {
+            key_id,
+            salt: salt.to_string(),
+            created_at: None,
+        };
+        let legacy = to_hex(&[9u8; 32]);
+        let key_file = CredentialKeyFile {
+            current: key(2, "0102"),
+            previous: Some(key(1, "0304")),
+        };
+
+        let current = key_for(&key_file, 2, "machine-a").unwrap();
+        assert_eq!(current, derive_key("machine-a", &[1, 2]));
+        assert_ne!(current, key_for(&key_file, 2, "machine-b").unwrap());
+        assert_eq!(key_for(&key_file, 1, "machine-a"), Some(derive_key("machine-a", &[3, 4])));
+        assert_eq!(key_for(&key_file, 3, "machine-a"), None);
+
+        let legacy_file = CredentialKeyFile {
+            current: key(0, &legacy),
+            previous: None,
+        };
+        assert_eq!(key_for(&legacy_file, 0, "machine-a"), Some([9u8; 32]));
+    }
+}
@@ -0,0 +1,189 @@
+// Dashboard Commands
+// Everything the dashboard shows on load, aggregated into a single call
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::commands::instance::{cached_instance_statuses, load_instances, AemInstanceStatus, AemInstanceType};
+use crate::commands::license::{calculate_license_status, list_aem_licenses, AemLicense, LicenseStatus};
+use crate::commands::maintenance::next_maintenance_at;
+use crate::commands::profile::{get_active_profile, EnvironmentProfile};
+use crate::commands::settings::next_auto_export_at;
+use crate::commands::startup_times::pending_starts;
+use crate::commands::version::{
+    get_current_java_version, get_current_maven_config, get_current_node_version, MavenConfig,
+};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardInstance {
+    pub id: String,
+    pub name: String,
+    pub instance_type: AemInstanceType,
+    pub host: String,
+    pub port: u16,
+    pub status: AemInstanceStatus,
+    /// When the status was detected; None when it is the persisted status
+    pub checked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseWarning {
+    pub license_id: String,
+    pub name: String,
+    pub status: LicenseStatus,
+    pub days_until_expiry: Option<i64>,
+    pub associated_instance_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingJobKind {
+    /// An instance was started and is not running yet
+    InstanceStart,
+    ScheduledMaintenance,
+    ScheduledExport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingJob {
+    pub kind: PendingJobKind,
+    pub label: String,
+    pub instance_id: Option<String>,
+    /// Start time for running jobs, planned time for scheduled ones (RFC 3339)
+    pub at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardState {
+    pub active_profile: Option<EnvironmentProfile>,
+    pub java_version: Option<String>,
+    pub node_version: Option<String>,
+    pub maven_config: Option<MavenConfig>,
+    pub instances: Vec<DashboardInstance>,
+    /// Expired, expiring or invalid licenses
+    pub license_warnings: Vec<LicenseWarning>,
+    pub pending_jobs: Vec<PendingJob>,
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn license_warning(license: &AemLicense) -> Option<LicenseWarning> {
+    let (status, days_until_expiry) = calculate_license_status(license);
+    if matches!(status, LicenseStatus::Valid | LicenseStatus::Unknown) {
+        return None;
+    }
+    Some(LicenseWarning {
+        license_id: license.id.clone(),
+        name: license.name.clone(),
+        status,
+        days_until_expiry,
+        associated_instance_id: license.associated_instance_id.clone(),
+    })
+}
+
+fn pending_jobs(instances: &[DashboardInstance]) -> Vec<PendingJob> {
+    let mut jobs: Vec<PendingJob> = pending_starts()
+        .into_iter()
+        .filter_map(|(instance_id, started_at)| {
+            let instance = instances.iter().find(|i| i.id == instance_id)?;
+            Some(PendingJob {
+                kind: PendingJobKind::InstanceStart,
+                label: format!("Starting {}", instance.name),
+                instance_id: Some(instance_id),
+                at: started_at,
+            })
+        })
+        .collect();
+
+    if let Some(at) = next_maintenance_at() {
+        jobs.push(PendingJob {
+            kind: PendingJobKind::ScheduledMaintenance,
+            label: "Scheduled maintenance".to_string(),
+            instance_id: None,
+            at: at.to_rfc3339(),
+        });
+    }
+    if let Some(at) = next_auto_export_at().and_then(|t| t.and_local_timezone(chrono::Local).earliest()) {
+        jobs.push(PendingJob {
+            kind: PendingJobKind::ScheduledExport,
+            label: "Configuration export".to_string(),
+            instance_id: None,
+            at: at.to_rfc3339(),
+        });
+    }
+
+    jobs.sort_by(|a, b| a.at.cmp(&b.at));
+    jobs
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Active profile, current tool versions, instance statuses (last detection, no probing),
+/// license warnings and pending jobs in one call
+#[command]
+pub async fn get_dashboard_state() -> Result<DashboardState, String> {
+    let (active_profile, java_version, node_version, maven_config, licenses) = tokio::join!(
+        get_active_profile(),
+        get_current_java_version(),
+        get_current_node_version(),
+        get_current_maven_config(),
+        list_aem_licenses(),
+    );
+
+    let statuses = cached_instance_statuses();
+    let instances: Vec<DashboardInstance> = load_instances()?
+        .into_iter()
+        .map(|instance| {
+            let cached = statuses.get(&instance.id);
+            DashboardInstance {
+                status: cached.map(|c| c.status.clone()).unwrap_or(instance.status),
+                checked_at: cached.map(|c| c.checked_at.clone()),
+                id: instance.id,
+                name: instance.name,
+                instance_type: instance.instance_type,
+                host: instance.host,
+                port: instance.port,
+            }
+        })
+        .collect();
+
+    Ok(DashboardState {
+        active_profile: active_profile?,
+        java_version: java_version.ok().flatten(),
+        node_version: node_version.ok().flatten(),
+        maven_config: maven_config.ok().flatten(),
+        license_warnings: licenses.unwrap_or_default().iter().filter_map(license_warning).collect(),
+        pending_jobs: pending_jobs(&instances),
+        instances,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_warning() {
+        let mut license: AemLicense = serde_json::from_value(serde_json::json!({
+            "id": "l1", "name": "Dev", "license_key": null, "license_file_path": null,
+            "product_name": "AEM", "product_version": null, "customer_name": null,
+            "expiry_date": "2000-01-01", "status": "unknown", "associated_instance_id": "author",
+            "notes": null, "created_at": "", "updated_at": ""
+        }))
+        .unwrap();
+        let warning = license_warning(&license).unwrap();
+        assert_eq!(warning.status, LicenseStatus::Expired);
+        assert_eq!(warning.associated_instance_id.as_deref(), Some("author"));
+
+        license.expiry_date = Some("2999-01-01".to_string());
+        assert!(license_warning(&license).is_none());
+    }
+}
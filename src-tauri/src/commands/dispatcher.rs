@@ -0,0 +1,988 @@
+// Dispatcher Configuration Commands
+// Parses dispatcher ANY files (farms, virtual hosts, filters, cache rules) and checks URLs against them,
+// computes cache hit/miss statistics from dispatcher and httpd access logs and flushes the cache
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::Duration;
+use tauri::command;
+use walkdir::WalkDir;
+
+use crate::commands::instance::{load_instances, save_instances, AemInstance, AemInstanceType};
+use crate::commands::project::load_projects;
+use crate::i18n::{Entity, Message, MessageCode};
+
+// ============================================
+// Data Types
+// ============================================
+
+/// A filter or cache rule, e.g. `/0001 { /type "allow" /path "/content/*" }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DispatcherRule {
+    /// Rule name without the leading slash (e.g. "0001")
+    pub id: String,
+    /// "allow" or "deny"
+    pub rule_type: String,
+    /// Other properties (url, path, method, extension, glob, ...); regex values keep their single quotes
+    pub properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatcherFarm {
+    pub name: String,
+    pub virtualhosts: Vec<String>,
+    pub docroot: Option<String>,
+    pub filters: Vec<DispatcherRule>,
+    pub cache_rules: Vec<DispatcherRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatcherConfig {
+    /// The dispatcher.any that was parsed
+    pub config_file: String,
+    pub farms: Vec<DispatcherFarm>,
+    /// Includes that could not be resolved, parse problems
+    pub warnings: Vec<String>,
+}
+
+/// Whether a URL passes a farm's filters and would be cached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatcherPathCheck {
+    pub farm: String,
+    pub url: String,
+    pub allowed: bool,
+    /// Last filter that matched (it decides)
+    pub matched_filter: Option<String>,
+    pub cacheable: bool,
+    pub matched_cache_rule: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathCount {
+    pub path: String,
+    pub count: usize,
+}
+
+/// Cache statistics from a dispatcher.log or access log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatcherCacheStats {
+    pub log_file: String,
+    /// Only requests from the last N minutes were counted (None: whole log)
+    pub window_minutes: Option<u32>,
+    /// Requests with a cache status
+    pub requests: usize,
+    pub hits: usize,
+    pub misses: usize,
+    /// Passed through without cache lookup (none, passed, ...)
+    pub uncached: usize,
+    /// hits / (hits + misses), 0 when there were no lookups
+    pub hit_ratio: f64,
+    /// Most frequent MISS paths, most misses first
+    pub top_misses: Vec<PathCount>,
+    /// Earliest and latest counted request (RFC 3339)
+    pub first_request: Option<String>,
+    pub last_request: Option<String>,
+}
+
+/// Where to send cache invalidation requests for an instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DispatcherEndpoint {
+    /// Dispatcher base URL, e.g. http://localhost:8080
+    pub url: String,
+    /// Flush agent path (`DEFAULT_INVALIDATE_PATH` when unset)
+    #[serde(default)]
+    pub invalidate_path: Option<String>,
+    /// Host header for dispatchers that pick the farm by virtual host
+    #[serde(default)]
+    pub host_header: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatcherFlushResult {
+    pub path: String,
+    pub success: bool,
+    pub status: Option<u16>,
+    pub message: String,
+    #[serde(flatten)]
+    pub message_code: MessageCode,
+}
+
+/// Path the dispatcher module handles invalidation requests on
+const DEFAULT_INVALIDATE_PATH: &str = "/dispatcher/invalidate.cache";
+
+// ============================================
+// ANY File Parsing
+// ============================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    /// `/name` (without the slash)
+    Name(String),
+    /// Quoted or bare value; single quotes mark a regular expression
+    Value { value: String, regex: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Value { value: String, regex: bool },
+    Block(Vec<(Option<String>, Node)>),
+}
+
+/// Nested includes deeper than this are assumed to be cycles
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Glob match supporting `*` and `?`
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let regex = regex::escape(pattern).replace("\\*", ".*").replace("\\?", ".");
+    Regex::new(&format!("^{}$", regex))
+        .map(|re| re.is_match(text))
+        .unwrap_or(false)
+}
+
+/// Files matched by an `$include` pattern (wildcards in the file name only)
+fn resolve_include(pattern: &str, base_dirs: &[&Path]) -> Vec<PathBuf> {
+    for base in base_dirs {
+        let full = base.join(pattern);
+        let (Some(dir), Some(file_pattern)) = (full.parent(), full.file_name().and_then(|f| f.to_str())) else {
+            continue;
+        };
+
+        if !file_pattern.contains(['*', '?']) {
+            if full.is_file() {
+                return vec![full];
+            }
+            continue;
+        }
+
+        let mut matches: Vec<PathBuf> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.is_file()
+                    && p.file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| glob_matches(file_pattern, n))
+                        .unwrap_or(false)
+            })
+            .collect();
+        if !matches.is_empty() {
+            matches.sort();
+            return matches;
+        }
+    }
+    Vec::new()
+}
+
+/// Tokenize ANY text, splicing in `$include`d files
+fn tokenize(
+    content: &str,
+    file_dir: &Path,
+    root_dir: &Path,
+    depth: usize,
+    tokens: &mut Vec<Token>,
+    warnings: &mut Vec<String>,
+) {
+    let chars: Vec<char> = content.chars().collect();
+    let mut i = 0;
+    let mut pending_include = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '{' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            '}' => {
+                tokens.push(Token::Close);
+                i += 1;
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..].iter().position(|&ch| ch == c).map(|p| i + 1 + p);
+                let end = end.unwrap_or(chars.len());
+                let value: String = chars[i + 1..end].iter().collect();
+                i = end + 1;
+
+                if pending_include {
+                    pending_include = false;
+                    if depth >= MAX_INCLUDE_DEPTH {
+                        warnings.push(format!("Include nesting too deep at {}", value));
+                        continue;
+                    }
+                    let files = resolve_include(&value, &[file_dir, root_dir]);
+                    if files.is_empty() {
+                        warnings.push(format!("Unresolved include: {}", value));
+                    }
+                    for file in files {
+                        match fs::read_to_string(&file) {
+                            Ok(included) => {
+                                let dir = file.parent().unwrap_or(file_dir).to_path_buf();
+                                tokenize(&included, &dir, root_dir, depth + 1, tokens, warnings);
+                            }
+                            Err(e) => warnings.push(format!("Failed to read {}: {}", file.display(), e)),
+                        }
+                    }
+                } else {
+                    tokens.push(Token::Value { value, regex: c == '\'' });
+                }
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && !chars[i].is_whitespace() && !matches!(chars[i], '{' | '}' | '"' | '\'') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if word == "$include" {
+                    pending_include = true;
+                } else if let Some(name) = word.strip_prefix('/') {
+                    tokens.push(Token::Name(name.to_string()));
+                } else {
+                    tokens.push(Token::Value { value: word, regex: false });
+                }
+            }
+        }
+    }
+}
+
+/// Parse items up to the closing brace of the current block
+fn parse_items(tokens: &[Token], pos: &mut usize) -> Vec<(Option<String>, Node)> {
+    let mut items = Vec::new();
+
+    while *pos < tokens.len() {
+        let token = tokens[*pos].clone();
+        *pos += 1;
+        match token {
+            Token::Close => break,
+            Token::Open => items.push((None, Node::Block(parse_items(tokens, pos)))),
+            Token::Value { value, regex } => items.push((None, Node::Value { value, regex })),
+            Token::Name(name) => {
+                let node = match tokens.get(*pos) {
+                    Some(Token::Open) => {
+                        *pos += 1;
+                        Node::Block(parse_items(tokens, pos))
+                    }
+                    Some(Token::Value { value, regex }) => {
+                        *pos += 1;
+                        Node::Value { value: value.clone(), regex: *regex }
+                    }
+                    _ => Node::Value { value: String::new(), regex: false },
+                };
+                items.push((Some(name), node));
+            }
+        }
+    }
+
+    items
+}
+
+fn child<'a>(items: &'a [(Option<String>, Node)], name: &str) -> Option<&'a Node> {
+    items
+        .iter()
+        .find(|(n, _)| n.as_deref().map(|n| n.eq_ignore_ascii_case(name)).unwrap_or(false))
+        .map(|(_, node)| node)
+}
+
+fn block<'a>(items: &'a [(Option<String>, Node)], name: &str) -> &'a [(Option<String>, Node)] {
+    match child(items, name) {
+        Some(Node::Block(children)) => children,
+        _ => &[],
+    }
+}
+
+fn value_text(value: &str, regex: bool) -> String {
+    if regex {
+        format!("'{}'", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_rules(items: &[(Option<String>, Node)]) -> Vec<DispatcherRule> {
+    items
+        .iter()
+        .filter_map(|(name, node)| {
+            let Node::Block(props) = node else {
+                return None;
+            };
+            let mut properties = BTreeMap::new();
+            let mut rule_type = String::new();
+            for (key, value) in props {
+                if let (Some(key), Node::Value { value, regex }) = (key, value) {
+                    if key == "type" {
+                        rule_type = value.to_lowercase();
+                    } else {
+                        properties.insert(key.clone(), value_text(value, *regex));
+                    }
+                }
+            }
+            Some(DispatcherRule {
+                id: name.clone().unwrap_or_default(),
+                rule_type,
+                properties,
+            })
+        })
+        .collect()
+}
+
+fn parse_farms(items: &[(Option<String>, Node)]) -> Vec<DispatcherFarm> {
+    block(items, "farms")
+        .iter()
+        .filter_map(|(name, node)| {
+            let Node::Block(farm) = node else {
+                return None;
+            };
+            let cache = block(farm, "cache");
+            Some(DispatcherFarm {
+                name: name.clone().unwrap_or_default(),
+                virtualhosts: block(farm, "virtualhosts")
+                    .iter()
+                    .filter_map(|(_, n)| match n {
+                        Node::Value { value, .. } => Some(value.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+                docroot: match child(cache, "docroot") {
+                    Some(Node::Value { value, .. }) => Some(value.clone()),
+                    _ => None,
+                },
+                filters: parse_rules(block(farm, "filter")),
+                cache_rules: parse_rules(block(cache, "rules")),
+            })
+        })
+        .collect()
+}
+
+/// Parse a dispatcher.any file including everything it pulls in
+fn parse_dispatcher_any(file: &Path) -> Result<DispatcherConfig, String> {
+    let content = fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let dir = file.parent().unwrap_or(Path::new("."));
+
+    let mut tokens = Vec::new();
+    let mut warnings = Vec::new();
+    tokenize(&content, dir, dir, 0, &mut tokens, &mut warnings);
+
+    let mut pos = 0;
+    let items = parse_items(&tokens, &mut pos);
+
+    Ok(DispatcherConfig {
+        config_file: file.to_string_lossy().to_string(),
+        farms: parse_farms(&items),
+        warnings,
+    })
+}
+
+// ============================================
+// URL Checks
+// ============================================
+
+/// Sling URL decomposition: (path, selectors, extension, suffix)
+fn decompose_url(url: &str) -> (String, String, String, String) {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').collect();
+
+    let Some(index) = segments.iter().position(|s| s.contains('.')) else {
+        return (path.to_string(), String::new(), String::new(), String::new());
+    };
+
+    let (resource, rest) = segments[index].split_once('.').unwrap_or((segments[index], ""));
+    let mut resource_path = segments[..index].join("/");
+    resource_path.push('/');
+    resource_path.push_str(resource);
+
+    let parts: Vec<&str> = rest.split('.').collect();
+    let (extension, selectors) = parts.split_last().map(|(e, s)| (e.to_string(), s.join("."))).unwrap_or_default();
+    let suffix = if index + 1 < segments.len() {
+        format!("/{}", segments[index + 1..].join("/"))
+    } else {
+        String::new()
+    };
+
+    (resource_path, selectors, extension, suffix)
+}
+
+/// Match a property value: 'regex' (single quotes) or "glob"
+fn property_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_prefix('\'').and_then(|p| p.strip_suffix('\'')) {
+        Some(regex) => Regex::new(&format!("^(?:{})$", regex))
+            .map(|re| re.is_match(value))
+            .unwrap_or(false),
+        None => glob_matches(pattern, value),
+    }
+}
+
+fn filter_matches(rule: &DispatcherRule, method: &str, url: &str) -> bool {
+    let (path, selectors, extension, suffix) = decompose_url(url);
+    let request_path = url.split(['?', '#']).next().unwrap_or_default();
+    let query = url.split_once('?').map(|(_, q)| q).unwrap_or_default();
+
+    rule.properties.iter().all(|(key, pattern)| match key.as_str() {
+        "method" => property_matches(pattern, method),
+        "url" => property_matches(pattern, request_path),
+        "path" => property_matches(pattern, &path),
+        "selectors" => property_matches(pattern, &selectors),
+        "extension" => property_matches(pattern, &extension),
+        "suffix" => property_matches(pattern, &suffix),
+        "query" => property_matches(pattern, query),
+        "glob" => property_matches(pattern, &format!("{} {} HTTP/1.1", method, url)),
+        // Other properties (protocol, ...) are not evaluated
+        _ => true,
+    })
+}
+
+/// Evaluate filters and cache rules of a farm for a GET request to `url`
+fn check_farm(farm: &DispatcherFarm, url: &str) -> DispatcherPathCheck {
+    // The last matching filter decides; nothing matching means denied
+    let matched_filter = farm
+        .filters
+        .iter()
+        .rev()
+        .find(|rule| filter_matches(rule, "GET", url));
+    let allowed = matched_filter.map(|r| r.rule_type == "allow").unwrap_or(false);
+
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let matched_cache_rule = farm
+        .cache_rules
+        .iter()
+        .rev()
+        .find(|rule| rule.properties.get("glob").map(|g| property_matches(g, path)).unwrap_or(false));
+    let has_query = url.contains('?');
+    let cacheable = allowed && !has_query && matched_cache_rule.map(|r| r.rule_type == "allow").unwrap_or(false);
+
+    let reason = if !allowed {
+        match matched_filter {
+            Some(rule) => format!("Denied by filter /{}", rule.id),
+            None => "No filter allows this request".to_string(),
+        }
+    } else if has_query {
+        "Requests with a query string are not cached".to_string()
+    } else {
+        match matched_cache_rule {
+            Some(rule) if rule.rule_type == "allow" => format!("Cached by rule /{}", rule.id),
+            Some(rule) => format!("Not cached: rule /{} denies", rule.id),
+            None => "Not cached: no cache rule matches".to_string(),
+        }
+    };
+
+    DispatcherPathCheck {
+        farm: farm.name.clone(),
+        url: url.to_string(),
+        allowed,
+        matched_filter: matched_filter.map(|r| r.id.clone()),
+        cacheable,
+        matched_cache_rule: matched_cache_rule.map(|r| r.id.clone()),
+        reason,
+    }
+}
+
+// ============================================
+// Log Analysis
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CacheResult {
+    Hit,
+    Miss,
+    Uncached,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct LogEntry {
+    time: Option<chrono::DateTime<chrono::FixedOffset>>,
+    path: String,
+    result: CacheResult,
+}
+
+/// Default number of MISS paths returned
+const DEFAULT_TOP_MISSES: usize = 20;
+
+/// Timestamp in brackets: httpd `[16/Oct/2025:10:15:30 +0000]` or
+/// dispatcher 4.x `[Thu Oct 16 10:15:30 2025]` (local time)
+fn parse_log_time(line: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let start = line.find('[')?;
+    let end = start + line[start..].find(']')?;
+    let text = &line[start + 1..end];
+
+    chrono::DateTime::parse_from_str(text, "%d/%b/%Y:%H:%M:%S %z")
+        .ok()
+        .or_else(|| {
+            let naive = chrono::NaiveDateTime::parse_from_str(text, "%a %b %e %H:%M:%S %Y").ok()?;
+            naive
+                .and_local_timezone(chrono::Local)
+                .single()
+                .map(|t| t.fixed_offset())
+        })
+}
+
+/// Quoted request line of a log entry
+static LOG_REQUEST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#""(?:GET|HEAD|POST|PUT|DELETE|OPTIONS|PATCH) ([^" ]+)[^"]*""#).expect("valid request regex")
+});
+
+/// Cache status after the request line
+static LOG_CACHE_STATUS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:\[action\s*|\b)(hit|miss|none|passed|expired|stale)(?:\]|\b)").expect("valid status regex")
+});
+
+/// Request path and cache status of a log line. Understands the dispatcher log
+/// (`"GET /a.html" 200 miss [farm/0] 12ms` and `... [actionhit]`) and access logs
+/// whose LogFormat includes a HIT/MISS field (e.g. `%{X-Cache}o`).
+fn parse_log_line(line: &str) -> Option<LogEntry> {
+    let captures = LOG_REQUEST.captures(line)?;
+    let path = captures.get(1)?.as_str();
+    let rest = &line[captures.get(0)?.end()..];
+
+    let result = match LOG_CACHE_STATUS.captures(rest)?.get(1)?.as_str().to_lowercase().as_str() {
+        "hit" => CacheResult::Hit,
+        "miss" | "expired" | "stale" => CacheResult::Miss,
+        _ => CacheResult::Uncached,
+    };
+
+    Some(LogEntry {
+        time: parse_log_time(line),
+        path: path.split('?').next().unwrap_or(path).to_string(),
+        result,
+    })
+}
+
+/// Hit ratio and top MISS paths of entries at or after `since`
+fn cache_stats(
+    entries: impl Iterator<Item = LogEntry>,
+    since: Option<chrono::DateTime<chrono::FixedOffset>>,
+    top: usize,
+) -> DispatcherCacheStats {
+    let mut stats = DispatcherCacheStats {
+        log_file: String::new(),
+        window_minutes: None,
+        requests: 0,
+        hits: 0,
+        misses: 0,
+        uncached: 0,
+        hit_ratio: 0.0,
+        top_misses: Vec::new(),
+        first_request: None,
+        last_request: None,
+    };
+    let mut misses: HashMap<String, usize> = HashMap::new();
+    let mut first = None;
+    let mut last = None;
+
+    for entry in entries {
+        if let (Some(since), Some(time)) = (since, entry.time) {
+            if time < since {
+                continue;
+            }
+        }
+        // Without a timestamp a line cannot be placed in the window
+        if since.is_some() && entry.time.is_none() {
+            continue;
+        }
+
+        stats.requests += 1;
+        match entry.result {
+            CacheResult::Hit => stats.hits += 1,
+            CacheResult::Miss => {
+                stats.misses += 1;
+                *misses.entry(entry.path).or_default() += 1;
+            }
+            CacheResult::Uncached => stats.uncached += 1,
+        }
+        if let Some(time) = entry.time {
+            first = Some(first.map_or(time, |f: chrono::DateTime<chrono::FixedOffset>| f.min(time)));
+            last = Some(last.map_or(time, |l: chrono::DateTime<chrono::FixedOffset>| l.max(time)));
+        }
+    }
+
+    let lookups = stats.hits + stats.misses;
+    if lookups > 0 {
+        stats.hit_ratio = stats.hits as f64 / lookups as f64;
+    }
+
+    let mut top_misses: Vec<PathCount> = misses
+        .into_iter()
+        .map(|(path, count)| PathCount { path, count })
+        .collect();
+    top_misses.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.path.cmp(&b.path)));
+    top_misses.truncate(top);
+    stats.top_misses = top_misses;
+    stats.first_request = first.map(|t| t.to_rfc3339());
+    stats.last_request = last.map(|t| t.to_rfc3339());
+    stats
+}
+
+/// dispatcher.log (or the newest access log) of a dispatcher instance
+fn locate_log(id: &str) -> Result<PathBuf, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id && i.instance_type == AemInstanceType::Dispatcher)
+        .ok_or_else(|| format!("No dispatcher instance with ID {}", id))?;
+    let root = instance.host_path();
+
+    let mut logs: Vec<PathBuf> = WalkDir::new(&root)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            (name.starts_with("dispatcher") && name.ends_with(".log")) || name.starts_with("access")
+        })
+        .collect();
+    // Prefer dispatcher.log over access logs, then the most recently written
+    logs.sort_by_key(|p| {
+        let is_access = p.file_name().unwrap_or_default().to_string_lossy().starts_with("access");
+        let modified = fs::metadata(p).and_then(|m| m.modified()).ok();
+        (is_access, std::cmp::Reverse(modified))
+    });
+
+    logs.into_iter()
+        .next()
+        .ok_or_else(|| format!("No dispatcher or access log found below {}", root.display()))
+}
+
+// ============================================
+// Config Location
+// ============================================
+
+/// dispatcher.any below a directory (or the file itself)
+fn find_dispatcher_any(root: &Path) -> Option<PathBuf> {
+    if root.is_file() {
+        return Some(root.to_path_buf());
+    }
+    WalkDir::new(root)
+        .max_depth(6)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .find(|e| e.file_type().is_file() && e.file_name() == "dispatcher.any")
+        .map(|e| e.into_path())
+}
+
+/// Dispatcher config of a registered project (its dispatcher module) or dispatcher instance
+fn locate_config(id: &str) -> Result<PathBuf, String> {
+    let root = if let Some(project) = load_projects()?.into_iter().find(|p| p.id == id) {
+        let module = Path::new(&project.path).join("dispatcher");
+        if module.is_dir() {
+            module
+        } else {
+            PathBuf::from(project.path)
+        }
+    } else if let Some(instance) = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id && i.instance_type == AemInstanceType::Dispatcher)
+    {
+        instance.host_path()
+    } else {
+        return Err(format!("No project or dispatcher instance with ID {}", id));
+    };
+
+    find_dispatcher_any(&root).ok_or_else(|| format!("No dispatcher.any found below {}", root.display()))
+}
+
+// ============================================
+// Cache Flush
+// ============================================
+
+/// Endpoint configured on the instance; dispatcher instances default to their own URL
+fn flush_endpoint(instance: &AemInstance) -> Option<DispatcherEndpoint> {
+    instance.dispatcher.clone().or_else(|| {
+        (instance.instance_type == AemInstanceType::Dispatcher).then(|| DispatcherEndpoint {
+            url: instance.base_url(),
+            invalidate_path: None,
+            host_header: None,
+        })
+    })
+}
+
+fn invalidate_url(endpoint: &DispatcherEndpoint) -> String {
+    let path = endpoint
+        .invalidate_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or(DEFAULT_INVALIDATE_PATH);
+    format!("{}/{}", endpoint.url.trim_end_matches('/'), path.trim_start_matches('/'))
+}
+
+/// Send one invalidation request (what a flush replication agent sends on activation)
+async fn flush_path(client: &reqwest::Client, endpoint: &DispatcherEndpoint, path: &str) -> DispatcherFlushResult {
+    let mut request = client
+        .post(invalidate_url(endpoint))
+        .header("CQ-Action", "Activate")
+        .header("CQ-Handle", path)
+        .header("CQ-Path", path)
+        .header("Content-Type", "application/octet-stream")
+        .header("Content-Length", "0");
+    if let Some(host) = endpoint.host_header.as_deref().filter(|h| !h.is_empty()) {
+        request = request.header("Host", host);
+    }
+
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            DispatcherFlushResult {
+                path: path.to_string(),
+                success: status.is_success(),
+                status: Some(status.as_u16()),
+                message: if status.is_success() {
+                    "Invalidated".to_string()
+                } else {
+                    format!("HTTP {}: {}", status.as_u16(), body.trim())
+                },
+                message_code: if status.is_success() {
+                    MessageCode::new("dispatcher_invalidated").with("path", path)
+                } else {
+                    MessageCode::new("dispatcher_flush_rejected")
+                        .with("path", path)
+                        .with("status", status.as_u16())
+                        .with("detail", body.trim())
+                },
+            }
+        }
+        Err(e) => DispatcherFlushResult {
+            path: path.to_string(),
+            success: false,
+            status: None,
+            message: format!("Request failed: {}", e),
+            message_code: MessageCode::new("dispatcher_flush_failed")
+                .with("path", path)
+                .with("detail", e),
+        },
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Farms, virtual hosts, filters and cache rules of a project's or dispatcher instance's config
+#[command]
+pub async fn inspect_dispatcher_config(id: String) -> Result<DispatcherConfig, String> {
+    parse_dispatcher_any(&locate_config(&id)?)
+}
+
+/// Check whether a URL is allowed by the filters and cacheable, per farm
+/// (only farms whose virtual hosts match `host`, when given)
+#[command]
+pub async fn check_dispatcher_path(
+    id: String,
+    url: String,
+    host: Option<String>,
+) -> Result<Vec<DispatcherPathCheck>, String> {
+    let config = parse_dispatcher_any(&locate_config(&id)?)?;
+    let url = if url.starts_with('/') { url } else { format!("/{}", url) };
+
+    Ok(config
+        .farms
+        .iter()
+        .filter(|farm| match &host {
+            Some(host) => farm.virtualhosts.iter().any(|v| glob_matches(v, host)),
+            None => true,
+        })
+        .map(|farm| check_farm(farm, &url))
+        .collect())
+}
+
+/// Cache hit ratio and top MISS paths from a dispatcher instance's log or an explicit log file,
+/// over the last `window_minutes` (whole log when omitted)
+#[command]
+pub async fn analyze_dispatcher_cache(
+    id: Option<String>,
+    log_path: Option<String>,
+    window_minutes: Option<u32>,
+    top: Option<usize>,
+) -> Result<DispatcherCacheStats, String> {
+    let log_file = match (log_path, id) {
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(id)) => locate_log(&id)?,
+        (None, None) => return Err("Either a dispatcher instance or a log file is required".to_string()),
+    };
+
+    let file = fs::File::open(&log_file).map_err(|e| format!("Failed to open {}: {}", log_file.display(), e))?;
+    let since = window_minutes
+        .map(|m| (chrono::Local::now() - chrono::Duration::minutes(m as i64)).fixed_offset());
+    // Logs may contain non-UTF-8 bytes (e.g. in request paths); decode each line lossily
+    // instead of stopping at the first such line
+    let entries = BufReader::new(file)
+        .split(b'\n')
+        .map_while(Result::ok)
+        .filter_map(|line| parse_log_line(&String::from_utf8_lossy(&line)));
+
+    let mut stats = cache_stats(entries, since, top.unwrap_or(DEFAULT_TOP_MISSES));
+    stats.log_file = log_file.to_string_lossy().to_string();
+    stats.window_minutes = window_minutes;
+    Ok(stats)
+}
+
+/// Set (or with None remove) the dispatcher an instance's cache flushes go to
+#[command]
+pub async fn set_dispatcher_endpoint(
+    instance_id: String,
+    endpoint: Option<DispatcherEndpoint>,
+) -> Result<Option<DispatcherEndpoint>, String> {
+    if let Some(ref endpoint) = endpoint {
+        reqwest::Url::parse(&endpoint.url).map_err(|e| format!("Invalid dispatcher URL {}: {}", endpoint.url, e))?;
+    }
+    let mut instances = load_instances()?;
+    let instance = instances
+        .iter_mut()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &instance_id).localized())?;
+    instance.dispatcher = endpoint.clone();
+    instance.updated_at = chrono::Utc::now().to_rfc3339();
+    save_instances(&instances)?;
+    Ok(endpoint)
+}
+
+/// Invalidate the dispatcher cache for content paths (e.g. /content/mysite/en) through the
+/// instance's dispatcher endpoint; one result per path
+#[command]
+pub async fn flush_dispatcher(instance_id: String, paths: Vec<String>) -> Result<Vec<DispatcherFlushResult>, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &instance_id).localized())?;
+    let endpoint = flush_endpoint(&instance)
+        .ok_or_else(|| format!("{} has no dispatcher endpoint configured", instance.name))?;
+    let paths: Vec<String> = paths
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .map(|p| if p.starts_with('/') { p.to_string() } else { format!("/{}", p) })
+        .collect();
+    if paths.is_empty() {
+        return Err("At least one path to flush is required".to_string());
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut results = Vec::new();
+    for path in &paths {
+        results.push(flush_path(&client, &endpoint, path).await);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check() {
+        let dir = std::env::temp_dir().join(format!("aem-env-dispatcher-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("filters")).unwrap();
+        fs::write(
+            dir.join("filters/filters.any"),
+            r#"/0001 { /type "deny" /url "*" }
+/0010 { /type "allow" /method "GET" /path "/content/*" /extension '(html|json)' }
+/0020 { /type "deny" /selectors '(infinity|tidy)' }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("dispatcher.any"),
+            r#"# Sample config
+/farms {
+  /publishfarm {
+    /virtualhosts { "www.example.com" "*.example.org" }
+    /filter { $include "filters/*.any" }
+    /cache {
+      /docroot "/var/www/html"
+      /rules {
+        /0000 { /glob "*" /type "allow" }
+        /0001 { /glob "/content/private/*" /type "deny" }
+      }
+    }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let config = parse_dispatcher_any(&dir.join("dispatcher.any")).unwrap();
+        assert!(config.warnings.is_empty());
+        let farm = &config.farms[0];
+        assert_eq!(farm.name, "publishfarm");
+        assert_eq!(farm.virtualhosts, vec!["www.example.com", "*.example.org"]);
+        assert_eq!(farm.docroot.as_deref(), Some("/var/www/html"));
+        assert_eq!(farm.filters.len(), 3);
+        assert_eq!(farm.filters[1].properties["extension"], "'(html|json)'");
+
+        let page = check_farm(farm, "/content/site/en.html");
+        assert!(page.allowed && page.cacheable);
+        assert_eq!(page.matched_filter.as_deref(), Some("0010"));
+
+        assert!(!check_farm(farm, "/content/site/en.infinity.json").allowed);
+        assert!(!check_farm(farm, "/bin/querybuilder.json").allowed);
+        assert!(!check_farm(farm, "/content/private/a.html").cacheable);
+        assert!(!check_farm(farm, "/content/site/en.html?q=1").cacheable);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        let log = r#"[16/Oct/2025:10:15:30 +0000] [I] [pid 12] "GET /content/site/en.html" 200 hit [publishfarm/0] 1ms "localhost"
+[16/Oct/2025:10:15:31 +0000] [I] [pid 12] "GET /content/site/en.html?x=1" 200 miss [publishfarm/0] 80ms "localhost"
+[16/Oct/2025:10:16:00 +0000] [I] [pid 12] "GET /content/site/de.html" 200 miss [publishfarm/0] 75ms "localhost"
+[Thu Oct 16 10:17:00 2025] [I] [pid 12] "GET /content/site/en.html" - 200 3ms [publishfarm/-] [actionmiss] localhost
+[16/Oct/2025:10:18:00 +0000] [I] [pid 12] "POST /bin/form" 200 none [publishfarm/0] 20ms "localhost"
+[16/Oct/2025:10:18:01 +0000] [I] [pid 12] Dispatcher initialized"#;
+
+        let entries = log.lines().filter_map(parse_log_line);
+        let stats = cache_stats(entries, None, 1);
+        assert_eq!(stats.requests, 5);
+        assert_eq!((stats.hits, stats.misses, stats.uncached), (1, 3, 1));
+        assert_eq!(stats.hit_ratio, 0.25);
+        assert_eq!(stats.top_misses.len(), 1);
+        assert_eq!(stats.top_misses[0].path, "/content/site/en.html");
+        assert_eq!(stats.top_misses[0].count, 2);
+
+        // The hit at 10:15:30 falls outside the window
+        let since = chrono::DateTime::parse_from_rfc3339("2025-10-16T10:15:31+00:00").ok();
+        let stats = cache_stats(log.lines().filter_map(parse_log_line), since, 10);
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.first_request.as_deref(), Some("2025-10-16T10:15:31+00:00"));
+    }
+
+    #[test]
+    fn test_decompose_url() {
+        assert_eq!(
+            decompose_url("/content/site/en.model.json/suffix/a"),
+            (
+                "/content/site/en".to_string(),
+                "model".to_string(),
+                "json".to_string(),
+                "/suffix/a".to_string()
+            )
+        );
+        assert_eq!(decompose_url("/content/site").0, "/content/site");
+    }
+
+    #[test]
+    fn test_invalidate_url() {
+        let mut endpoint = DispatcherEndpoint {
+            url: "http://localhost:8080/".to_string(),
+            invalidate_path: None,
+            host_header: None,
+        };
+        assert_eq!(invalidate_url(&endpoint), "http://localhost:8080/dispatcher/invalidate.cache");
+        endpoint.invalidate_path = Some("flush".to_string());
+        assert_eq!(invalidate_url(&endpoint), "http://localhost:8080/flush");
+    }
+}
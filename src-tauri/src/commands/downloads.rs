@@ -0,0 +1,493 @@
+// Download Manager
+// Shared resumable downloads with SHA-256 verification and a size-limited cache. A partial
+// file is only resumed while the server still has the same file (If-Range on its validator).
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{command, AppHandle};
+
+use crate::commands::certificates::http_client_builder;
+use crate::commands::progress::ProgressReporter;
+use crate::platform::PlatformOps;
+
+/// Serializes read-modify-write cycles of the cache index
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// One lock per URL, so two callers never write the same partial file
+static URL_LOCKS: Mutex<BTreeMap<String, Arc<tokio::sync::Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+// ============================================
+// Data Types
+// ============================================
+
+/// A file kept in the download cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadEntry {
+    pub url: String,
+    pub file_name: String,
+    /// Absolute path of the cached file
+    pub path: String,
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the file
+    pub sha256: String,
+    pub downloaded_at: String,
+    /// Used for least-recently-used eviction
+    pub last_used_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadCacheInfo {
+    pub cache_dir: String,
+    pub total_bytes: u64,
+    pub limit_bytes: u64,
+    pub entries: Vec<DownloadEntry>,
+}
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_cache_dir() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("downloads")
+}
+
+fn get_index_file() -> PathBuf {
+    get_cache_dir().join("index.json")
+}
+
+fn load_index() -> Vec<DownloadEntry> {
+    fs::read_to_string(get_index_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(entries: &[DownloadEntry]) -> Result<(), String> {
+    fs::create_dir_all(get_cache_dir())
+        .map_err(|e| format!("Failed to create download cache: {}", e))?;
+
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize download cache index: {}", e))?;
+
+    fs::write(get_index_file(), content)
+        .map_err(|e| format!("Failed to write download cache index: {}", e))
+}
+
+fn cache_limit_bytes() -> u64 {
+    crate::commands::profile::get_startup_config().download_cache_limit_mb * 1024 * 1024
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Hex-encoded SHA-256 of a file
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Cache file name for a URL: a short URL hash keeps same-named files apart
+fn cache_file_name(url: &str) -> String {
+    let name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|u| u.rsplit('/').next())
+        .filter(|n| !n.is_empty())
+        .unwrap_or("download");
+
+    let digest = Sha256::digest(url.as_bytes());
+    let prefix: String = digest.iter().take(4).map(|b| format!("{:02x}", b)).collect();
+
+    format!("{}-{}", prefix, name)
+}
+
+/// Entries to evict (least recently used first) so the cache fits within `limit`
+fn entries_to_evict(entries: &[DownloadEntry], limit: u64, keep_url: &str) -> Vec<String> {
+    let mut total: u64 = entries.iter().map(|e| e.size).sum();
+    let mut candidates: Vec<&DownloadEntry> = entries.iter().filter(|e| e.url != keep_url).collect();
+    candidates.sort_by(|a, b| a.last_used_at.cmp(&b.last_used_at));
+
+    let mut evicted = Vec::new();
+    for entry in candidates {
+        if total <= limit {
+            break;
+        }
+        total = total.saturating_sub(entry.size);
+        evicted.push(entry.url.clone());
+    }
+    evicted
+}
+
+/// Record a finished download and evict old entries beyond the size limit
+fn register_entry(entry: DownloadEntry) -> Result<DownloadEntry, String> {
+    let _guard = INDEX_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut entries = load_index();
+    entries.retain(|e| e.url != entry.url);
+    entries.push(entry.clone());
+
+    let evicted = entries_to_evict(&entries, cache_limit_bytes(), &entry.url);
+    entries.retain(|e| {
+        if evicted.contains(&e.url) {
+            let _ = fs::remove_file(&e.path);
+            false
+        } else {
+            true
+        }
+    });
+
+    save_index(&entries)?;
+    Ok(entry)
+}
+
+/// Drop the cached copy of `url`; returns the bytes freed
+fn remove_cached(url: &str) -> Result<u64, String> {
+    let _guard = INDEX_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut entries = load_index();
+    let mut freed = 0;
+    entries.retain(|e| {
+        if e.url == url {
+            freed += e.size;
+            let _ = fs::remove_file(&e.path);
+            false
+        } else {
+            true
+        }
+    });
+
+    save_index(&entries)?;
+    Ok(freed)
+}
+
+/// Return the cached entry for `url` if its file is still present and intact
+fn cached_entry(url: &str, expected_sha256: Option<&str>) -> Option<DownloadEntry> {
+    let _guard = INDEX_LOCK.lock().ok()?;
+
+    let mut entries = load_index();
+    let entry = entries.iter_mut().find(|e| e.url == url)?;
+
+    let path = PathBuf::from(&entry.path);
+    let size_ok = fs::metadata(&path).map(|m| m.len() == entry.size).unwrap_or(false);
+    let hash_ok = expected_sha256
+        .map(|expected| entry.sha256.eq_ignore_ascii_case(expected))
+        .unwrap_or(true);
+    if !size_ok || !hash_ok {
+        return None;
+    }
+
+    entry.last_used_at = chrono::Utc::now().to_rfc3339();
+    let entry = entry.clone();
+    let _ = save_index(&entries);
+    Some(entry)
+}
+
+// ============================================
+// Download Manager
+// ============================================
+
+fn url_lock(url: &str) -> Result<Arc<tokio::sync::Mutex<()>>, String> {
+    let mut locks = URL_LOCKS.lock().map_err(|e| e.to_string())?;
+    Ok(locks.entry(url.to_string()).or_default().clone())
+}
+
+/// Validator to send as If-Range: a strong ETag, else Last-Modified
+fn response_validator(response: &reqwest::Response) -> Option<String> {
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    header(reqwest::header::ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(reqwest::header::LAST_MODIFIED))
+}
+
+fn discard_partial(partial: &Path, validator_file: &Path) {
+    let _ = fs::remove_file(partial);
+    let _ = fs::remove_file(validator_file);
+}
+
+/// Offset and validator to resume a partial file from. A partial file without a validator
+/// cannot be checked against the server's current file and is discarded.
+fn resume_point(partial: &Path, validator_file: &Path) -> Option<(u64, String)> {
+    let size = fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+    let validator = fs::read_to_string(validator_file).ok().filter(|v| !v.trim().is_empty());
+    match validator {
+        Some(validator) if size > 0 => Some((size, validator.trim().to_string())),
+        _ => {
+            discard_partial(partial, validator_file);
+            None
+        }
+    }
+}
+
+async fn send_request(
+    client: &reqwest::Client,
+    url: &str,
+    resume: Option<&(u64, String)>,
+) -> Result<reqwest::Response, String> {
+    let mut request = client.get(url);
+    if let Some((offset, validator)) = resume {
+        request = request
+            .header(reqwest::header::RANGE, format!("bytes={}-", offset))
+            .header(reqwest::header::IF_RANGE, validator);
+    }
+    request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))
+}
+
+/// Download `url` into the cache (or reuse the cached copy) and return its entry.
+///
+/// Interrupted downloads continue from the partial file via an HTTP Range request, as long as
+/// the file on the server is unchanged; otherwise it starts over. Concurrent calls for the same
+/// URL wait for each other. When `expected_sha256` is given, a mismatching file is discarded
+/// and reported.
+pub(crate) async fn download(
+    url: &str,
+    expected_sha256: Option<&str>,
+    mut progress: Option<&mut ProgressReporter>,
+) -> Result<DownloadEntry, String> {
+    let lock = url_lock(url)?;
+    let _guard = lock.lock().await;
+
+    if let Some(entry) = cached_entry(url, expected_sha256) {
+        if let Some(progress) = progress {
+            progress.start(Some(entry.size), entry.size);
+            progress.finish();
+        }
+        return Ok(entry);
+    }
+
+    let result = fetch(url, expected_sha256, progress.as_deref_mut()).await;
+    if let (Err(e), Some(progress)) = (&result, progress) {
+        progress.fail(e);
+    }
+    result
+}
+
+/// Download `url` into the cache, resuming a partial file when possible
+async fn fetch(
+    url: &str,
+    expected_sha256: Option<&str>,
+    mut progress: Option<&mut ProgressReporter>,
+) -> Result<DownloadEntry, String> {
+    let cache_dir = get_cache_dir();
+    fs::create_dir_all(&cache_dir).map_err(|e| format!("Failed to create download cache: {}", e))?;
+
+    let file_name = cache_file_name(url);
+    let target = cache_dir.join(&file_name);
+    let partial = cache_dir.join(format!("{}.part", file_name));
+    let validator_file = cache_dir.join(format!("{}.part.validator", file_name));
+
+    let client = http_client_builder()
+        .connect_timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut resume = resume_point(&partial, &validator_file);
+    let mut response = send_request(&client, url, resume.as_ref()).await?;
+
+    // The partial file is longer than the server's file: it is stale, start over
+    if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && resume.is_some() {
+        discard_partial(&partial, &validator_file);
+        resume = None;
+        response = send_request(&client, url, None).await?;
+    }
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+
+    // The server sends the whole file again when it ignores the Range header or the file
+    // changed since the partial download (If-Range did not match)
+    let resume_from = match resume {
+        Some((offset, _)) if response.status() == reqwest::StatusCode::PARTIAL_CONTENT => offset,
+        _ => {
+            match response_validator(&response) {
+                Some(validator) => fs::write(&validator_file, validator)
+                    .map_err(|e| format!("Failed to write {}: {}", validator_file.display(), e))?,
+                None => {
+                    let _ = fs::remove_file(&validator_file);
+                }
+            }
+            0
+        }
+    };
+    let resumed = resume_from > 0;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&partial)
+        .map_err(|e| format!("Failed to open {}: {}", partial.display(), e))?;
+
+    if let Some(ref mut progress) = progress {
+        progress.start(response.content_length().map(|len| len + resume_from), resume_from);
+    }
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download of {} interrupted: {}", url, e))?
+    {
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+        if let Some(ref mut progress) = progress {
+            progress.advance(chunk.len() as u64);
+        }
+    }
+    drop(file);
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+
+    let sha256 = sha256_file(&partial)?;
+    if let Some(expected) = expected_sha256 {
+        if !sha256.eq_ignore_ascii_case(expected) {
+            discard_partial(&partial, &validator_file);
+            return Err(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                url, expected, sha256
+            ));
+        }
+    }
+
+    fs::rename(&partial, &target).map_err(|e| format!("Failed to finalize download: {}", e))?;
+    let _ = fs::remove_file(&validator_file);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    register_entry(DownloadEntry {
+        url: url.to_string(),
+        file_name,
+        path: target.to_string_lossy().to_string(),
+        size: fs::metadata(&target).map(|m| m.len()).unwrap_or(0),
+        sha256,
+        downloaded_at: now.clone(),
+        last_used_at: now,
+    })
+}
+
+/// Download `url` again even when a cached copy exists, e.g. for files that change upstream
+pub(crate) async fn download_fresh(
+    url: &str,
+    expected_sha256: Option<&str>,
+    progress: Option<&mut ProgressReporter>,
+) -> Result<DownloadEntry, String> {
+    remove_cached(url)?;
+    download(url, expected_sha256, progress).await
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Download a file through the shared cache, emitting transfer progress under `operation_id`
+#[command]
+pub async fn download_to_cache(
+    app: AppHandle,
+    url: String,
+    sha256: Option<String>,
+    operation_id: Option<String>,
+) -> Result<DownloadEntry, String> {
+    let mut progress = ProgressReporter::new(&app, operation_id, &cache_file_name(&url));
+    download(&url, sha256.as_deref().filter(|s| !s.is_empty()), Some(&mut progress)).await
+}
+
+/// List cached downloads
+#[command]
+pub async fn list_download_cache() -> Result<DownloadCacheInfo, String> {
+    let entries = load_index();
+
+    Ok(DownloadCacheInfo {
+        cache_dir: get_cache_dir().to_string_lossy().to_string(),
+        total_bytes: entries.iter().map(|e| e.size).sum(),
+        limit_bytes: cache_limit_bytes(),
+        entries,
+    })
+}
+
+/// Remove one cached download (by URL), or the whole cache including partial files.
+/// Returns the number of bytes freed.
+#[command]
+pub async fn clear_download_cache(url: Option<String>) -> Result<u64, String> {
+    if let Some(url) = url {
+        return remove_cached(&url);
+    }
+
+    let _guard = INDEX_LOCK.lock().map_err(|e| e.to_string())?;
+    let cache_dir = get_cache_dir();
+    let freed = crate::platform::common::dir_size(&cache_dir);
+    if cache_dir.exists() {
+        fs::remove_dir_all(&cache_dir).map_err(|e| format!("Failed to clear download cache: {}", e))?;
+    }
+
+    save_index(&[])?;
+    Ok(freed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, size: u64, last_used_at: &str) -> DownloadEntry {
+        DownloadEntry {
+            url: url.to_string(),
+            file_name: cache_file_name(url),
+            path: String::new(),
+            size,
+            sha256: String::new(),
+            downloaded_at: last_used_at.to_string(),
+            last_used_at: last_used_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cache_file_name() {
+        let name = cache_file_name("https://example.com/jdk/OpenJDK17.tar.gz?token=abc");
+        assert!(name.ends_with("-OpenJDK17.tar.gz"));
+        assert_ne!(name, cache_file_name("https://mirror.example.com/OpenJDK17.tar.gz"));
+        assert!(cache_file_name("https://example.com/").ends_with("-download"));
+    }
+
+    #[test]
+    fn test_entries_to_evict() {
+        let entries = vec![
+            entry("https://a", 400, "2024-01-01T00:00:00Z"),
+            entry("https://b", 400, "2024-03-01T00:00:00Z"),
+            entry("https://c", 400, "2024-02-01T00:00:00Z"),
+        ];
+        assert_eq!(entries_to_evict(&entries, 800, "https://a"), vec!["https://c"]);
+        assert_eq!(entries_to_evict(&entries, 1200, "https://a"), Vec::<String>::new());
+    }
+}
@@ -2,14 +2,17 @@
 // Handles symlink-based version switching and shell configuration
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::command;
 
 #[cfg(unix)]
 use std::os::unix::fs::symlink;
 
+use crate::i18n::MessageCode;
 use crate::platform::common::ensure_dir_exists;
+use crate::platform::PlatformOps;
 
 // ============================================
 // Data Types
@@ -32,6 +35,8 @@ pub struct EnvironmentStatus {
 pub struct InitResult {
     pub success: bool,
     pub message: String,
+    #[serde(flatten)]
+    pub message_code: MessageCode,
     pub env_dir: String,
     pub shell_config_updated: bool,
 }
@@ -43,6 +48,92 @@ pub struct SymlinkResult {
     pub previous_target: Option<String>,
     pub current_target: String,
     pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+/// Shells that alias/completion snippets can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl ShellKind {
+    /// Shell of the current user, from $SHELL (PowerShell on Windows)
+    pub fn detect() -> Self {
+        if cfg!(target_os = "windows") {
+            return ShellKind::Powershell;
+        }
+        let shell = std::env::var("SHELL").unwrap_or_default();
+        if shell.contains("fish") {
+            ShellKind::Fish
+        } else if shell.contains("bash") {
+            ShellKind::Bash
+        } else {
+            ShellKind::Zsh
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "bash" => Some(ShellKind::Bash),
+            "zsh" => Some(ShellKind::Zsh),
+            "fish" => Some(ShellKind::Fish),
+            "powershell" | "pwsh" => Some(ShellKind::Powershell),
+            _ => None,
+        }
+    }
+}
+
+/// Generated shell aliases and whether they were added to the managed block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellAliasResult {
+    pub shell: ShellKind,
+    pub snippet: String,
+    pub installed: bool,
+    pub config_path: Option<String>,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+/// Named copy of the environment state we manage, restorable independent of profiles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvSnapshot {
+    pub name: String,
+    pub created_at: String,
+    pub java_symlink: Option<String>,
+    pub node_symlink: Option<String>,
+    /// JAVA_HOME, MAVEN_OPTS, NODE_OPTIONS as seen by the app (None when unset)
+    pub variables: BTreeMap<String, Option<String>>,
+    /// PATH entries below the environment directory
+    pub managed_path_entries: Vec<String>,
+    /// Managed block of the shell config, including its markers
+    pub shell_block: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvRestoreResult {
+    pub name: String,
+    pub restored: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Variables captured in environment snapshots
+const SNAPSHOT_VARIABLES: [&str; 3] = ["JAVA_HOME", "MAVEN_OPTS", "NODE_OPTIONS"];
+
+/// Result of clearing the Gatekeeper quarantine flag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineResult {
+    pub path: String,
+    /// The quarantine attribute was present and has been removed
+    pub quarantine_removed: bool,
+    /// Gatekeeper notarization verdict for bundles (None when not applicable)
+    pub notarized: Option<bool>,
 }
 
 // ============================================
@@ -57,12 +148,12 @@ fn get_env_dir() -> Result<PathBuf, String> {
 }
 
 /// Get the Java symlink path
-fn get_java_symlink_path() -> Result<PathBuf, String> {
+pub(crate) fn get_java_symlink_path() -> Result<PathBuf, String> {
     Ok(get_env_dir()?.join("java").join("current"))
 }
 
 /// Get the Node symlink path
-fn get_node_symlink_path() -> Result<PathBuf, String> {
+pub(crate) fn get_node_symlink_path() -> Result<PathBuf, String> {
     Ok(get_env_dir()?.join("node").join("current"))
 }
 
@@ -104,7 +195,7 @@ pub async fn check_environment_status() -> Result<EnvironmentStatus, String> {
 }
 
 /// Check if shell configuration contains our setup
-fn check_shell_configured() -> Result<bool, String> {
+pub(crate) fn check_shell_configured() -> Result<bool, String> {
     let shell_config = get_shell_config_path()?;
 
     if !shell_config.exists() {
@@ -118,7 +209,7 @@ fn check_shell_configured() -> Result<bool, String> {
 }
 
 /// Get the shell configuration file path
-fn get_shell_config_path() -> Result<PathBuf, String> {
+pub(crate) fn get_shell_config_path() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
     let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
 
@@ -140,6 +231,7 @@ fn get_shell_config_path() -> Result<PathBuf, String> {
 #[command]
 pub async fn initialize_environment() -> Result<InitResult, String> {
     let env_dir = get_env_dir()?;
+    snapshot_before("initialize-environment");
 
     // Track if we created the directory (for rollback)
     let dir_existed = env_dir.exists();
@@ -174,6 +266,7 @@ pub async fn initialize_environment() -> Result<InitResult, String> {
             Ok(InitResult {
                 success: true,
                 message: "Environment initialized successfully".to_string(),
+                message_code: MessageCode::new("environment_initialized"),
                 env_dir: env_dir.to_string_lossy().to_string(),
                 shell_config_updated: shell_updated,
             })
@@ -229,6 +322,142 @@ fi
     Ok(true)
 }
 
+// ============================================
+// Shell Aliases and Completion
+// ============================================
+
+const ALIASES_START: &str = "# AEM Environment Manager - Aliases";
+const ALIASES_END: &str = "# End AEM Environment Manager Aliases";
+const MANAGED_BLOCK_END: &str = "# End AEM Environment Manager Block";
+
+/// Functions (aemstart, aemstop, aemswitch, aemjava) and completions calling the CLI at `binary`
+pub(crate) fn shell_alias_snippet(shell: ShellKind, binary: &str) -> String {
+    match shell {
+        ShellKind::Bash | ShellKind::Zsh => {
+            let completion = if shell == ShellKind::Bash {
+                r#"_aem_env_complete() {
+  local kind=instances
+  [ "${COMP_WORDS[0]}" = "aemswitch" ] && kind=profiles
+  local IFS=$'
+'
+  COMPREPLY=($(compgen -W "$(aem_env list "$kind" 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+}
+complete -F _aem_env_complete aemstart aemstop aemswitch"#
+            } else {
+                r#"if (( $+functions[compdef] )); then
+  _aem_env_instances() { local -a items; items=("${(@f)$(aem_env list instances 2>/dev/null)}"); compadd -a items }
+  _aem_env_profiles() { local -a items; items=("${(@f)$(aem_env list profiles 2>/dev/null)}"); compadd -a items }
+  compdef _aem_env_instances aemstart aemstop
+  compdef _aem_env_profiles aemswitch
+fi"#
+            };
+            format!(
+                r#"{start}
+aem_env() {{ "{binary}" "$@"; }}
+aemstart() {{ aem_env start "$@"; }}
+aemstop() {{ aem_env stop "$@"; }}
+aemswitch() {{ aem_env switch "$@"; }}
+aemjava() {{ aem_env java-home; }}
+{completion}
+{end}"#,
+                start = ALIASES_START,
+                end = ALIASES_END,
+                binary = binary,
+                completion = completion,
+            )
+        }
+        ShellKind::Fish => format!(
+            r#"{start}
+function aem_env; "{binary}" $argv; end
+function aemstart; aem_env start $argv; end
+function aemstop; aem_env stop $argv; end
+function aemswitch; aem_env switch $argv; end
+function aemjava; aem_env java-home; end
+complete -c aemstart -f -a "(aem_env list instances 2>/dev/null)"
+complete -c aemstop -f -a "(aem_env list instances 2>/dev/null)"
+complete -c aemswitch -f -a "(aem_env list profiles 2>/dev/null)"
+{end}"#,
+            start = ALIASES_START,
+            end = ALIASES_END,
+            binary = binary,
+        ),
+        ShellKind::Powershell => format!(
+            r#"{start}
+function aem_env {{ & "{binary}" @args }}
+function aemstart {{ aem_env start @args }}
+function aemstop {{ aem_env stop @args }}
+function aemswitch {{ aem_env switch @args }}
+function aemjava {{ aem_env java-home }}
+Register-ArgumentCompleter -CommandName aemstart, aemstop -ParameterName args -ScriptBlock {{
+  param($cmd, $param, $word) aem_env list instances | Where-Object {{ $_ -like "$word*" }}
+}}
+Register-ArgumentCompleter -CommandName aemswitch -ParameterName args -ScriptBlock {{
+  param($cmd, $param, $word) aem_env list profiles | Where-Object {{ $_ -like "$word*" }}
+}}
+{end}"#,
+            start = ALIASES_START,
+            end = ALIASES_END,
+            binary = binary,
+        ),
+    }
+}
+
+/// Put `snippet` inside the managed block, replacing previously installed aliases
+fn insert_aliases(content: &str, snippet: &str) -> Option<String> {
+    let content = match (content.find(ALIASES_START), content.find(ALIASES_END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}", &content[..start], content[end + ALIASES_END.len()..].trim_start_matches('\n'))
+        }
+        _ => content.to_string(),
+    };
+
+    let block_end = content.find(MANAGED_BLOCK_END)?;
+    Some(format!("{}{}\n{}", &content[..block_end], snippet, &content[block_end..]))
+}
+
+/// Generate shell aliases (`aemstart author`, `aemswitch cloud`, ...) with completion,
+/// optionally adding them to the managed block of the shell configuration
+#[command]
+pub async fn generate_shell_aliases(shell: Option<ShellKind>, install: bool) -> Result<ShellAliasResult, String> {
+    let shell = shell.unwrap_or_else(ShellKind::detect);
+    let binary = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate executable: {}", e))?
+        .to_string_lossy()
+        .to_string();
+    let snippet = shell_alias_snippet(shell, &binary);
+
+    let mut result = ShellAliasResult {
+        shell,
+        snippet,
+        installed: false,
+        config_path: None,
+        message: None,
+        message_code: None,
+    };
+    if !install {
+        return Ok(result);
+    }
+
+    // The managed block lives in a POSIX shell config
+    if !matches!(shell, ShellKind::Bash | ShellKind::Zsh) {
+        result.message = Some("Add the snippet to your shell configuration manually".to_string());
+        result.message_code = Some(MessageCode::new("shell_aliases_manual"));
+        return Ok(result);
+    }
+
+    let shell_config = get_shell_config_path()?;
+    let content = fs::read_to_string(&shell_config).unwrap_or_default();
+    let updated = insert_aliases(&content, &result.snippet)
+        .ok_or("Shell is not configured yet - initialize the environment first")?;
+
+    fs::write(&shell_config, updated)
+        .map_err(|e| format!("Failed to write shell config: {}", e))?;
+
+    result.installed = true;
+    result.config_path = Some(shell_config.to_string_lossy().to_string());
+    Ok(result)
+}
+
 /// Remove shell configuration (for cleanup)
 #[command]
 pub async fn remove_shell_config() -> Result<bool, String> {
@@ -241,6 +470,8 @@ pub async fn remove_shell_config() -> Result<bool, String> {
     let content = fs::read_to_string(&shell_config)
         .map_err(|e| format!("Failed to read shell config: {}", e))?;
 
+    snapshot_before("remove-shell-config");
+
     // Remove our managed block
     let start_marker = "# AEM Environment Manager - Managed Block";
     let end_marker = "# End AEM Environment Manager Block";
@@ -290,8 +521,12 @@ pub async fn set_java_symlink(java_home: String) -> Result<SymlinkResult, String
         return Err(format!("Invalid Java installation (no java binary): {}", java_home));
     }
 
+    // Downloaded JDKs keep the quarantine flag; clear it so `java` isn't blocked
+    let quarantine = prepare_downloaded_path(&target);
+
     // Get previous target
     let previous_target = read_symlink_target(&symlink_path);
+    snapshot_before("java-symlink");
 
     // Ensure parent directory exists
     if let Some(parent) = symlink_path.parent() {
@@ -313,11 +548,21 @@ pub async fn set_java_symlink(java_home: String) -> Result<SymlinkResult, String
     std::os::windows::fs::symlink_dir(&target, &symlink_path)
         .map_err(|e| format!("Failed to create symlink: {}", e))?;
 
+    let (message, code) = if quarantine.notarized == Some(false) {
+        (
+            "Java symlink updated successfully (warning: JDK is not notarized, Gatekeeper may block it)",
+            "java_symlink_updated_not_notarized",
+        )
+    } else {
+        ("Java symlink updated successfully", "java_symlink_updated")
+    };
+
     Ok(SymlinkResult {
         success: true,
         previous_target,
+        message_code: Some(MessageCode::new(code).with("path", &java_home)),
         current_target: java_home,
-        message: Some("Java symlink updated successfully".to_string()),
+        message: Some(message.to_string()),
     })
 }
 
@@ -354,6 +599,7 @@ pub async fn set_node_symlink(node_path: String) -> Result<SymlinkResult, String
 
     // Get previous target
     let previous_target = read_symlink_target(&symlink_path);
+    snapshot_before("node-symlink");
 
     // Ensure parent directory exists
     if let Some(parent) = symlink_path.parent() {
@@ -378,6 +624,7 @@ pub async fn set_node_symlink(node_path: String) -> Result<SymlinkResult, String
     Ok(SymlinkResult {
         success: true,
         previous_target,
+        message_code: Some(MessageCode::new("node_symlink_updated").with("path", &node_path)),
         current_target: node_path,
         message: Some("Node symlink updated successfully".to_string()),
     })
@@ -389,6 +636,7 @@ pub async fn remove_java_symlink() -> Result<bool, String> {
     let symlink_path = get_java_symlink_path()?;
 
     if symlink_path.exists() || symlink_path.is_symlink() {
+        snapshot_before("remove-java-symlink");
         fs::remove_file(&symlink_path)
             .map_err(|e| format!("Failed to remove Java symlink: {}", e))?;
         Ok(true)
@@ -403,6 +651,7 @@ pub async fn remove_node_symlink() -> Result<bool, String> {
     let symlink_path = get_node_symlink_path()?;
 
     if symlink_path.exists() || symlink_path.is_symlink() {
+        snapshot_before("remove-node-symlink");
         fs::remove_file(&symlink_path)
             .map_err(|e| format!("Failed to remove Node symlink: {}", e))?;
         Ok(true)
@@ -411,6 +660,284 @@ pub async fn remove_node_symlink() -> Result<bool, String> {
     }
 }
 
+// ============================================
+// Gatekeeper / Quarantine
+// ============================================
+
+/// Bundle that owns a path (e.g. temurin-17.jdk for .../temurin-17.jdk/Contents/Home)
+fn bundle_root(path: &Path) -> &Path {
+    path.ancestors()
+        .find(|p| {
+            p.extension()
+                .map(|ext| ext == "jdk" || ext == "app")
+                .unwrap_or(false)
+        })
+        .unwrap_or(path)
+}
+
+/// Remove the quarantine flag from a downloaded JDK/JAR and check notarization
+/// Best effort: failures are reported as "not removed"
+pub(crate) fn prepare_downloaded_path(path: &Path) -> QuarantineResult {
+    let platform = crate::platform::current_platform();
+    let root = bundle_root(path);
+
+    QuarantineResult {
+        path: root.to_string_lossy().to_string(),
+        quarantine_removed: platform.remove_quarantine(root).unwrap_or(false),
+        notarized: platform.is_notarized(root),
+    }
+}
+
+/// Clear the Gatekeeper quarantine flag of a downloaded SDK, JDK or JAR
+#[command]
+pub async fn clear_quarantine(path: String) -> Result<QuarantineResult, String> {
+    let target = PathBuf::from(&path);
+    if !target.exists() {
+        return Err(format!("Path not found: {}", path));
+    }
+
+    let platform = crate::platform::current_platform();
+    let root = bundle_root(&target);
+
+    Ok(QuarantineResult {
+        path: root.to_string_lossy().to_string(),
+        quarantine_removed: platform.remove_quarantine(root)?,
+        notarized: platform.is_notarized(root),
+    })
+}
+
+// ============================================
+// Environment Snapshots
+// ============================================
+
+const MANAGED_BLOCK_START: &str = "# AEM Environment Manager - Managed Block";
+const VARIABLES_BLOCK_START: &str = "# AEM Environment Manager - Restored Variables";
+const VARIABLES_BLOCK_END: &str = "# End AEM Environment Manager Restored Variables";
+
+fn get_snapshots_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("env_snapshots.json")
+}
+
+fn load_snapshots() -> Result<Vec<EnvSnapshot>, String> {
+    let file_path = get_snapshots_file();
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read snapshots: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse snapshots: {}", e))
+}
+
+fn save_snapshots(snapshots: &[EnvSnapshot]) -> Result<(), String> {
+    let file_path = get_snapshots_file();
+    if let Some(parent) = file_path.parent() {
+        ensure_dir_exists(&parent.to_path_buf())?;
+    }
+    let content =
+        serde_json::to_string_pretty(snapshots).map_err(|e| format!("Failed to serialize snapshots: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write snapshots: {}", e))
+}
+
+/// Byte range of the block between `start_marker` and `end_marker` (markers included)
+fn marked_block_range(content: &str, start_marker: &str, end_marker: &str) -> Option<std::ops::Range<usize>> {
+    let start = content.find(start_marker)?;
+    let end = start + content[start..].find(end_marker)? + end_marker.len();
+    Some(start..end)
+}
+
+/// Byte range of the managed block (markers included)
+fn managed_block_range(content: &str) -> Option<std::ops::Range<usize>> {
+    marked_block_range(content, MANAGED_BLOCK_START, MANAGED_BLOCK_END)
+}
+
+/// Shell config with the block at `range` replaced by `block` (removed when None, appended when missing)
+fn replace_block(content: &str, range: Option<std::ops::Range<usize>>, block: Option<&str>) -> String {
+    match (range, block) {
+        (Some(range), Some(block)) => format!("{}{}{}", &content[..range.start], block, &content[range.end..]),
+        (Some(range), None) => format!("{}{}", content[..range.start].trim_end(), &content[range.end..]),
+        (None, Some(block)) => format!("{}\n\n{}\n", content.trim_end(), block),
+        (None, None) => content.to_string(),
+    }
+}
+
+/// Shell config with its managed block replaced by `block` (removed when None, appended when missing)
+fn replace_managed_block(content: &str, block: Option<&str>) -> String {
+    replace_block(content, managed_block_range(content), block)
+}
+
+/// Shell config with the restored variables block replaced by exports of `variables`
+/// (`unset` for variables that were not set)
+fn replace_variables_block(content: &str, variables: &[(&str, Option<&str>)]) -> String {
+    let mut block = format!("{}\n", VARIABLES_BLOCK_START);
+    for (key, value) in variables {
+        match value {
+            Some(value) => block.push_str(&format!("export {}='{}'\n", key, value.replace('\'', "'\\''"))),
+            None => block.push_str(&format!("unset {}\n", key)),
+        }
+    }
+    block.push_str(VARIABLES_BLOCK_END);
+    let range = marked_block_range(content, VARIABLES_BLOCK_START, VARIABLES_BLOCK_END);
+    replace_block(content, range, Some(&block))
+}
+
+/// Point a managed symlink at `target`, or remove it when None
+fn restore_symlink(path: &Path, target: Option<&str>) -> Result<(), String> {
+    if path.exists() || path.is_symlink() {
+        fs::remove_file(path).map_err(|e| format!("Failed to remove symlink: {}", e))?;
+    }
+    let Some(target) = target else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        ensure_dir_exists(&parent.to_path_buf())?;
+    }
+
+    #[cfg(unix)]
+    symlink(target, path).map_err(|e| format!("Failed to create symlink: {}", e))?;
+
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(target, path).map_err(|e| format!("Failed to create symlink: {}", e))?;
+
+    Ok(())
+}
+
+/// Snapshot the environment as `before-<operation>` before an operation changes it; a failed
+/// snapshot does not stop the operation
+pub(crate) fn snapshot_before(operation: &str) {
+    if let Err(e) = snapshot_environment(&format!("before-{}", operation)) {
+        println!("[AEM] Failed to snapshot environment: {}", e);
+    }
+}
+
+/// Capture the current environment state and store it under `name` (replacing a
+/// snapshot of the same name). Also used before risky operations.
+pub(crate) fn snapshot_environment(name: &str) -> Result<EnvSnapshot, String> {
+    let env_dir = get_env_dir()?;
+    let shell_block = get_shell_config_path()
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| managed_block_range(&c).map(|r| c[r].to_string()));
+
+    let snapshot = EnvSnapshot {
+        name: name.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        java_symlink: read_symlink_target(&get_java_symlink_path()?),
+        node_symlink: read_symlink_target(&get_node_symlink_path()?),
+        variables: SNAPSHOT_VARIABLES
+            .iter()
+            .map(|v| (v.to_string(), std::env::var(v).ok()))
+            .collect(),
+        managed_path_entries: std::env::var_os("PATH")
+            .map(|path| {
+                std::env::split_paths(&path)
+                    .filter(|p| p.starts_with(&env_dir))
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        shell_block,
+    };
+
+    let mut snapshots = load_snapshots()?;
+    snapshots.retain(|s| s.name != name);
+    snapshots.push(snapshot.clone());
+    save_snapshots(&snapshots)?;
+
+    Ok(snapshot)
+}
+
+/// Snapshot JAVA_HOME, managed PATH entries, MAVEN_OPTS, NODE_OPTIONS, the version
+/// symlinks and the managed shell block under a name
+#[command]
+pub async fn create_env_snapshot(name: String) -> Result<EnvSnapshot, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Snapshot name must not be empty".to_string());
+    }
+    snapshot_environment(name)
+}
+
+#[command]
+pub async fn list_env_snapshots() -> Result<Vec<EnvSnapshot>, String> {
+    let mut snapshots = load_snapshots()?;
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(snapshots)
+}
+
+#[command]
+pub async fn delete_env_snapshot(name: String) -> Result<bool, String> {
+    let mut snapshots = load_snapshots()?;
+    let before = snapshots.len();
+    snapshots.retain(|s| s.name != name);
+    if snapshots.len() == before {
+        return Ok(false);
+    }
+    save_snapshots(&snapshots)?;
+    Ok(true)
+}
+
+/// Restore the version symlinks and the managed shell block from a snapshot. Variables that
+/// differ from the snapshot are exported from a block of the shell config. New shells pick up
+/// the restored state.
+#[command]
+pub async fn restore_env_snapshot(name: String) -> Result<EnvRestoreResult, String> {
+    let snapshot = load_snapshots()?
+        .into_iter()
+        .find(|s| s.name == name)
+        .ok_or_else(|| format!("Environment snapshot not found: {}", name))?;
+
+    let mut result = EnvRestoreResult {
+        name,
+        restored: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for (label, path, target) in [
+        ("Java symlink", get_java_symlink_path()?, &snapshot.java_symlink),
+        ("Node symlink", get_node_symlink_path()?, &snapshot.node_symlink),
+    ] {
+        if read_symlink_target(&path) == *target {
+            continue;
+        }
+        match restore_symlink(&path, target.as_deref()) {
+            Ok(()) => result.restored.push(label.to_string()),
+            Err(e) => result.errors.push(format!("{}: {}", label, e)),
+        }
+    }
+
+    let shell_config = get_shell_config_path()?;
+    let content = fs::read_to_string(&shell_config).unwrap_or_default();
+    let mut updated = content.clone();
+    let mut changed = Vec::new();
+    let current_block = managed_block_range(&content).map(|r| &content[r]);
+    if current_block != snapshot.shell_block.as_deref() {
+        updated = replace_managed_block(&updated, snapshot.shell_block.as_deref());
+        changed.push("Shell configuration".to_string());
+    }
+
+    // JAVA_HOME comes from the managed block while the Java symlink is set
+    let variables: Vec<(&str, Option<&str>)> = snapshot
+        .variables
+        .iter()
+        .filter(|(key, _)| !(key.as_str() == "JAVA_HOME" && snapshot.java_symlink.is_some()))
+        .filter(|(key, value)| std::env::var(key).ok() != **value)
+        .map(|(key, value)| (key.as_str(), value.as_deref()))
+        .collect();
+    if !variables.is_empty() {
+        updated = replace_variables_block(&updated, &variables);
+        changed.extend(variables.iter().map(|(key, _)| key.to_string()));
+    }
+
+    if updated != content {
+        match fs::write(&shell_config, updated) {
+            Ok(()) => result.restored.extend(changed),
+            Err(e) => result.errors.push(format!("Failed to write shell config: {}", e)),
+        }
+    }
+
+    Ok(result)
+}
+
 // ============================================
 // Utility Commands
 // ============================================
@@ -488,4 +1015,53 @@ mod tests {
         let result = get_shell_config_path();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_insert_aliases() {
+        let config = "export A=1\n# AEM Environment Manager - Managed Block\nexport JAVA_HOME=x\n# End AEM Environment Manager Block\n";
+        let snippet = shell_alias_snippet(ShellKind::Zsh, "/opt/aem-env-manager");
+
+        let installed = insert_aliases(config, &snippet).unwrap();
+        assert!(installed.contains("aemstart() { aem_env start \"$@\"; }"));
+        assert!(installed.find(ALIASES_END) < installed.find(MANAGED_BLOCK_END));
+
+        // Re-installing replaces the previous aliases
+        let reinstalled = insert_aliases(&installed, &snippet).unwrap();
+        assert_eq!(reinstalled, installed);
+
+        assert!(insert_aliases("export A=1\n", &snippet).is_none());
+    }
+
+    #[test]
+    fn test_replace_managed_block() {
+        let block = "# AEM Environment Manager - Managed Block\nexport JAVA_HOME=old\n# End AEM Environment Manager Block";
+        let config = format!("export A=1\n{}\nexport B=2\n", block);
+        assert_eq!(managed_block_range(&config).map(|r| &config[r]), Some(block));
+
+        let newer = block.replace("old", "new");
+        let replaced = replace_managed_block(&config, Some(&newer));
+        assert_eq!(replaced, format!("export A=1\n{}\nexport B=2\n", newer));
+        assert_eq!(replace_managed_block(&replaced, None), "export A=1\nexport B=2\n");
+        assert_eq!(replace_managed_block("export A=1\n", Some(block)), format!("export A=1\n\n{}\n", block));
+
+        let restored = replace_variables_block("export A=1\n", &[("MAVEN_OPTS", Some("-Xmx1g -Da='b'")), ("NODE_OPTIONS", None)]);
+        assert_eq!(
+            restored,
+            "export A=1\n\n# AEM Environment Manager - Restored Variables\nexport MAVEN_OPTS='-Xmx1g -Da='\\''b'\\'''\nunset NODE_OPTIONS\n# End AEM Environment Manager Restored Variables\n"
+        );
+        let again = replace_variables_block(&restored, &[("MAVEN_OPTS", None)]);
+        assert_eq!(again.matches(VARIABLES_BLOCK_START).count(), 1);
+        assert!(again.contains("unset MAVEN_OPTS\n") && !again.contains("NODE_OPTIONS"));
+    }
+
+    #[test]
+    fn test_bundle_root() {
+        let home = Path::new("/Library/Java/JavaVirtualMachines/temurin-17.jdk/Contents/Home");
+        assert_eq!(
+            bundle_root(home),
+            Path::new("/Library/Java/JavaVirtualMachines/temurin-17.jdk")
+        );
+        let jar = Path::new("/opt/aem/aem-author-p4502.jar");
+        assert_eq!(bundle_root(jar), jar);
+    }
 }
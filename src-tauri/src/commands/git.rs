@@ -0,0 +1,347 @@
+// Git Status Commands
+// Branch, working tree state and last commit of registered projects (via libgit2),
+// and profile suggestions for the checked-out branch
+
+use git2::{BranchType, ErrorCode, Repository, StatusOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::profile::{get_active_profile, get_startup_config, switch_profile};
+use crate::commands::project::{load_projects, AemProject, BranchProfile};
+use crate::i18n::{Entity, Message};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitInfo {
+    pub id: String,
+    pub short_id: String,
+    pub summary: String,
+    pub author: String,
+    /// Commit time (RFC 3339)
+    pub time: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitStatus {
+    pub project_id: String,
+    /// Checked-out branch (None when detached or not a repository)
+    pub branch: Option<String>,
+    pub detached: bool,
+    pub head: Option<CommitInfo>,
+    /// Files with staged changes
+    pub staged: usize,
+    /// Tracked files with unstaged changes
+    pub modified: usize,
+    pub untracked: usize,
+    pub upstream: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Shown before a build-and-install, e.g. uncommitted changes
+    pub warning: Option<String>,
+}
+
+/// Profile mapped to a project's checked-out branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchProfileSuggestion {
+    pub project_id: String,
+    pub project_name: String,
+    pub branch: Option<String>,
+    /// Mapped profile (None when no pattern matches)
+    pub profile_id: Option<String>,
+    pub matched_pattern: Option<String>,
+    pub active_profile_id: Option<String>,
+    /// The mapped profile differs from the active one
+    pub switch_needed: bool,
+    /// The profile was switched automatically (`auto_switch_profile` setting)
+    pub switched: bool,
+}
+
+/// Event emitted when the branch of a project with branch mappings changes
+pub const BRANCH_CHANGED_EVENT: &str = "project-branch-changed";
+
+/// How often the branch watcher checks registered projects
+const BRANCH_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+impl GitStatus {
+    pub fn is_dirty(&self) -> bool {
+        self.staged + self.modified > 0
+    }
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn commit_info(commit: &git2::Commit) -> CommitInfo {
+    let id = commit.id().to_string();
+    CommitInfo {
+        short_id: id.chars().take(7).collect(),
+        id,
+        summary: commit.summary().unwrap_or_default().to_string(),
+        author: commit.author().name().unwrap_or_default().to_string(),
+        time: chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default(),
+    }
+}
+
+/// Deployment warning for a status (None when the tree is clean)
+fn deploy_warning(status: &GitStatus) -> Option<String> {
+    let location = match (&status.branch, &status.head) {
+        (Some(branch), _) => format!("branch {}", branch),
+        (None, Some(head)) => format!("detached HEAD {}", head.short_id),
+        (None, None) => "a repository without commits".to_string(),
+    };
+
+    if status.is_dirty() {
+        Some(format!(
+            "Deploying from {} with {} uncommitted change(s)",
+            location,
+            status.staged + status.modified
+        ))
+    } else if status.behind > 0 {
+        Some(format!(
+            "Deploying from {} which is {} commit(s) behind {}",
+            location,
+            status.behind,
+            status.upstream.as_deref().unwrap_or("upstream")
+        ))
+    } else {
+        None
+    }
+}
+
+/// Branch name match where `*` stands for any characters
+fn branch_matches(pattern: &str, branch: &str) -> bool {
+    let regex = format!("^{}$", regex::escape(pattern.trim()).replace("\\*", ".*"));
+    regex::Regex::new(&regex).map(|re| re.is_match(branch)).unwrap_or(false)
+}
+
+/// First branch mapping of the project matching `branch`
+fn profile_for_branch<'a>(project: &'a AemProject, branch: &str) -> Option<&'a BranchProfile> {
+    project.branch_profiles.iter().find(|m| branch_matches(&m.pattern, branch))
+}
+
+/// Checked-out branch of the repository containing `path`
+fn current_branch(path: &Path) -> Option<String> {
+    let repo = Repository::discover(path).ok()?;
+    if repo.head_detached().unwrap_or(false) {
+        return None;
+    }
+    let branch = match repo.head() {
+        Ok(head) => head.shorthand().map(str::to_string),
+        // Unborn branch: read the name HEAD points at
+        Err(_) => repo
+            .find_reference("HEAD")
+            .ok()?
+            .symbolic_target()
+            .map(|t| t.trim_start_matches("refs/heads/").to_string()),
+    };
+    branch
+}
+
+async fn branch_suggestion(project: &AemProject, branch: Option<String>) -> BranchProfileSuggestion {
+    let mapping = branch.as_deref().and_then(|b| profile_for_branch(project, b));
+    let active_profile_id = get_active_profile().await.ok().flatten().map(|p| p.id);
+    let profile_id = mapping.map(|m| m.profile_id.clone());
+
+    BranchProfileSuggestion {
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        switch_needed: profile_id.is_some() && profile_id != active_profile_id,
+        matched_pattern: mapping.map(|m| m.pattern.clone()),
+        branch,
+        profile_id,
+        active_profile_id,
+        switched: false,
+    }
+}
+
+/// Read the git status of the repository containing `path`
+pub(crate) fn repository_status(project_id: &str, path: &Path) -> Result<GitStatus, String> {
+    let repo = Repository::discover(path).map_err(|e| format!("Not a git repository: {}", e.message()))?;
+
+    let mut status = GitStatus {
+        project_id: project_id.to_string(),
+        branch: None,
+        detached: repo.head_detached().unwrap_or(false),
+        head: None,
+        staged: 0,
+        modified: 0,
+        untracked: 0,
+        upstream: None,
+        ahead: 0,
+        behind: 0,
+        warning: None,
+    };
+
+    match repo.head() {
+        Ok(head) => {
+            if !status.detached {
+                status.branch = head.shorthand().map(str::to_string);
+            }
+            status.head = head.peel_to_commit().ok().map(|c| commit_info(&c));
+        }
+        // Fresh repository: HEAD points at a branch without commits
+        Err(e) if e.code() == ErrorCode::UnbornBranch => {}
+        Err(e) => return Err(format!("Failed to read HEAD: {}", e.message())),
+    }
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| format!("Failed to read status: {}", e.message()))?;
+    for entry in statuses.iter() {
+        let s = entry.status();
+        if s.is_wt_new() {
+            status.untracked += 1;
+        }
+        if s.is_index_new() || s.is_index_modified() || s.is_index_deleted() || s.is_index_renamed() || s.is_index_typechange() {
+            status.staged += 1;
+        }
+        if s.is_wt_modified() || s.is_wt_deleted() || s.is_wt_renamed() || s.is_wt_typechange() {
+            status.modified += 1;
+        }
+    }
+
+    if let Some(branch_name) = &status.branch {
+        let upstream = repo
+            .find_branch(branch_name, BranchType::Local)
+            .and_then(|b| b.upstream());
+        if let Ok(upstream) = upstream {
+            status.upstream = upstream.name().ok().flatten().map(str::to_string);
+            let local = repo.head().ok().and_then(|h| h.target());
+            if let (Some(local), Some(remote)) = (local, upstream.get().target()) {
+                if let Ok((ahead, behind)) = repo.graph_ahead_behind(local, remote) {
+                    status.ahead = ahead;
+                    status.behind = behind;
+                }
+            }
+        }
+    }
+
+    status.warning = deploy_warning(&status);
+    Ok(status)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Git status of a registered project
+#[command]
+pub async fn get_project_git_status(id: String) -> Result<GitStatus, String> {
+    let project = load_projects()?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Project, &id).localized())?;
+
+    repository_status(&project.id, Path::new(&project.path))
+}
+
+/// Git status of all registered projects that are git repositories
+#[command]
+pub async fn get_all_projects_git_status() -> Result<Vec<GitStatus>, String> {
+    Ok(load_projects()?
+        .iter()
+        .filter_map(|p| repository_status(&p.id, Path::new(&p.path)).ok())
+        .collect())
+}
+
+/// Profile mapped to the project's checked-out branch
+#[command]
+pub async fn suggest_branch_profile(id: String) -> Result<BranchProfileSuggestion, String> {
+    let project = load_projects()?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Project, &id).localized())?;
+
+    let branch = current_branch(Path::new(&project.path));
+    Ok(branch_suggestion(&project, branch).await)
+}
+
+/// Background task that watches the branches of projects with branch mappings.
+/// On a change it switches the profile when `auto_switch_profile` is enabled and
+/// emits `project-branch-changed` so the UI can suggest (or report) the switch.
+pub async fn branch_watcher(app: AppHandle) {
+    let mut last_branches: HashMap<String, Option<String>> = HashMap::new();
+
+    loop {
+        let projects = load_projects().unwrap_or_default();
+        for project in projects.iter().filter(|p| !p.branch_profiles.is_empty()) {
+            let branch = current_branch(Path::new(&project.path));
+            let previous = last_branches.insert(project.id.clone(), branch.clone());
+
+            // The first observation only records the branch; nothing changed yet
+            if previous.is_none() || previous == Some(branch.clone()) {
+                continue;
+            }
+
+            let mut suggestion = branch_suggestion(project, branch).await;
+            if suggestion.switch_needed && get_startup_config().auto_switch_profile {
+                if let Some(profile_id) = suggestion.profile_id.clone() {
+                    match switch_profile(profile_id).await {
+                        Ok(result) if result.success => suggestion.switched = true,
+                        Ok(result) => println!("[AEM] Branch profile switch failed: {:?}", result.error),
+                        Err(e) => println!("[AEM] Branch profile switch failed: {}", e),
+                    }
+                }
+            }
+
+            let _ = app.emit(BRANCH_CHANGED_EVENT, &suggestion);
+        }
+
+        tokio::time::sleep(BRANCH_POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_status() {
+        let dir = std::env::temp_dir().join(format!("aem-env-git-{}", uuid::Uuid::new_v4()));
+        let repo = Repository::init(&dir).unwrap();
+
+        // Unborn branch, one untracked file
+        std::fs::write(dir.join("pom.xml"), "<project/>").unwrap();
+        let status = repository_status("p1", &dir).unwrap();
+        assert!(status.head.is_none());
+        assert_eq!(status.untracked, 1);
+        assert!(!status.is_dirty());
+
+        // Commit, then modify the tracked file
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("pom.xml")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial commit", &tree, &[])
+            .unwrap();
+        std::fs::write(dir.join("pom.xml"), "<project></project>").unwrap();
+
+        let status = repository_status("p1", &dir).unwrap();
+        assert_eq!(status.head.as_ref().map(|h| h.summary.as_str()), Some("Initial commit"));
+        assert_eq!(status.modified, 1);
+        assert!(status.warning.unwrap().contains("1 uncommitted change"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_branch_matches() {
+        assert!(branch_matches("main", "main"));
+        assert!(branch_matches("release/6.5*", "release/6.5.21"));
+        assert!(branch_matches("feature/*", "feature/cloud-migration"));
+        assert!(!branch_matches("release/6.5*", "release/cloud"));
+        assert!(!branch_matches("main", "main-old"));
+    }
+}
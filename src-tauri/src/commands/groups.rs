@@ -0,0 +1,302 @@
+// Instance Group Commands
+// Named sets of instances (e.g. "local-dev", "project-x") stored next to the instances,
+// with bulk start, stop and health check over the members of a group
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+use crate::commands::instance::{
+    check_instance_health, load_instances, start_instance, stop_instance, HealthCheckResult,
+};
+use crate::i18n::MessageCode;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceGroup {
+    pub id: String,
+    pub name: String,
+    /// Member instances, in the order bulk operations run
+    #[serde(default)]
+    pub instance_ids: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Outcome of a bulk operation on one member of a group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupMemberResult {
+    pub instance_id: String,
+    pub name: String,
+    pub success: bool,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+    /// Set by group health checks
+    #[serde(default)]
+    pub health: Option<HealthCheckResult>,
+}
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_groups_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("instance_groups.json")
+}
+
+pub(crate) fn load_groups() -> Result<Vec<InstanceGroup>, String> {
+    let file_path = get_groups_file();
+    if !file_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read instance groups: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse instance groups: {}", e))
+}
+
+fn save_groups(groups: &[InstanceGroup]) -> Result<(), String> {
+    let file_path = get_groups_file();
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(groups).map_err(|e| format!("Failed to serialize instance groups: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write instance groups: {}", e))
+}
+
+/// Drop a deleted instance from every group
+pub(crate) fn remove_instance_from_groups(instance_id: &str) -> Result<(), String> {
+    let mut groups = load_groups()?;
+    let mut changed = false;
+    for group in groups.iter_mut() {
+        let before = group.instance_ids.len();
+        group.instance_ids.retain(|id| id != instance_id);
+        changed |= group.instance_ids.len() != before;
+    }
+    if changed {
+        save_groups(&groups)?;
+    }
+    Ok(())
+}
+
+/// Member instance IDs of a group
+pub(crate) fn group_instance_ids(group_id: &str) -> Result<Vec<String>, String> {
+    load_groups()?
+        .into_iter()
+        .find(|g| g.id == group_id)
+        .map(|g| g.instance_ids)
+        .ok_or_else(|| format!("Instance group {} not found", group_id))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn validate_name(groups: &[InstanceGroup], name: &str, own_id: Option<&str>) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Group name is required".to_string());
+    }
+    if groups
+        .iter()
+        .any(|g| Some(g.id.as_str()) != own_id && g.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("A group named {} already exists", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Add `instance_ids` to the group's members, skipping ones it already has
+fn add_members(group: &mut InstanceGroup, instance_ids: Vec<String>) {
+    for id in instance_ids {
+        if !group.instance_ids.contains(&id) {
+            group.instance_ids.push(id);
+        }
+    }
+}
+
+/// Fails on IDs that are not registered instances
+fn check_instances_exist(instance_ids: &[String]) -> Result<(), String> {
+    let instances = load_instances()?;
+    match instance_ids.iter().find(|id| !instances.iter().any(|i| &i.id == *id)) {
+        Some(id) => Err(format!("Instance {} not found", id)),
+        None => Ok(()),
+    }
+}
+
+/// Members of a group with their names; members whose instance was deleted are skipped
+fn group_members(group_id: &str) -> Result<Vec<(String, String)>, String> {
+    let instances = load_instances()?;
+    Ok(group_instance_ids(group_id)?
+        .into_iter()
+        .filter_map(|id| instances.iter().find(|i| i.id == id).map(|i| (id, i.name.clone())))
+        .collect())
+}
+
+fn member_result(instance_id: String, name: String, outcome: Result<(), String>) -> GroupMemberResult {
+    let message_code = match outcome {
+        Ok(()) => MessageCode::new("group_member_succeeded").with("name", &name),
+        Err(ref e) => MessageCode::new("group_member_failed").with("name", &name).with("detail", e),
+    };
+    GroupMemberResult {
+        instance_id,
+        name,
+        success: outcome.is_ok(),
+        error: outcome.err(),
+        message_code: Some(message_code),
+        health: None,
+    }
+}
+
+fn update_group(id: &str, change: impl FnOnce(&mut InstanceGroup)) -> Result<InstanceGroup, String> {
+    let mut groups = load_groups()?;
+    let group = groups
+        .iter_mut()
+        .find(|g| g.id == id)
+        .ok_or_else(|| format!("Instance group {} not found", id))?;
+    change(group);
+    group.updated_at = chrono::Utc::now().to_rfc3339();
+    let group = group.clone();
+    save_groups(&groups)?;
+    Ok(group)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+#[command]
+pub async fn list_instance_groups() -> Result<Vec<InstanceGroup>, String> {
+    let mut groups = load_groups()?;
+    groups.sort_by_key(|g| g.name.to_lowercase());
+    Ok(groups)
+}
+
+#[command]
+pub async fn create_instance_group(name: String, instance_ids: Option<Vec<String>>) -> Result<InstanceGroup, String> {
+    let mut groups = load_groups()?;
+    let name = validate_name(&groups, &name, None)?;
+    let instance_ids = instance_ids.unwrap_or_default();
+    check_instances_exist(&instance_ids)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut group = InstanceGroup {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        instance_ids: Vec::new(),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    add_members(&mut group, instance_ids);
+    groups.push(group.clone());
+    save_groups(&groups)?;
+    Ok(group)
+}
+
+#[command]
+pub async fn rename_instance_group(id: String, name: String) -> Result<InstanceGroup, String> {
+    let name = validate_name(&load_groups()?, &name, Some(&id))?;
+    update_group(&id, |group| group.name = name)
+}
+
+/// Delete a group; its instances are not touched
+#[command]
+pub async fn delete_instance_group(id: String) -> Result<bool, String> {
+    let mut groups = load_groups()?;
+    let before = groups.len();
+    groups.retain(|g| g.id != id);
+    if groups.len() == before {
+        return Ok(false);
+    }
+    save_groups(&groups)?;
+    Ok(true)
+}
+
+/// Add instances to a group (an instance can be in several groups)
+#[command]
+pub async fn assign_instances_to_group(group_id: String, instance_ids: Vec<String>) -> Result<InstanceGroup, String> {
+    check_instances_exist(&instance_ids)?;
+    update_group(&group_id, |group| add_members(group, instance_ids))
+}
+
+#[command]
+pub async fn remove_instances_from_group(group_id: String, instance_ids: Vec<String>) -> Result<InstanceGroup, String> {
+    update_group(&group_id, |group| {
+        group.instance_ids.retain(|id| !instance_ids.contains(id))
+    })
+}
+
+/// Start every instance of a group, in member order; a failure does not stop the others
+#[command]
+pub async fn start_instance_group(id: String) -> Result<Vec<GroupMemberResult>, String> {
+    let mut results = Vec::new();
+    for (instance_id, name) in group_members(&id)? {
+        let outcome = start_instance(instance_id.clone()).await.map(|_| ());
+        results.push(member_result(instance_id, name, outcome));
+    }
+    Ok(results)
+}
+
+/// Stop every instance of a group, in reverse member order
+#[command]
+pub async fn stop_instance_group(id: String) -> Result<Vec<GroupMemberResult>, String> {
+    let mut results = Vec::new();
+    for (instance_id, name) in group_members(&id)?.into_iter().rev() {
+        let outcome = stop_instance(instance_id.clone()).await.map(|_| ());
+        results.push(member_result(instance_id, name, outcome));
+    }
+    Ok(results)
+}
+
+/// Health check of every instance of a group, run concurrently
+#[command]
+pub async fn check_instance_group_health(id: String) -> Result<Vec<GroupMemberResult>, String> {
+    let checks = group_members(&id)?.into_iter().map(|(instance_id, name)| async move {
+        match check_instance_health(instance_id.clone(), None).await {
+            Ok(health) => GroupMemberResult {
+                health: Some(health),
+                ..member_result(instance_id, name, Ok(()))
+            },
+            Err(e) => member_result(instance_id, name, Err(e)),
+        }
+    });
+    Ok(futures_util::future::join_all(checks).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(id: &str, name: &str) -> InstanceGroup {
+        InstanceGroup {
+            id: id.to_string(),
+            name: name.to_string(),
+            instance_ids: Vec::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate_name() {
+        let groups = vec![group("a", "local-dev")];
+        assert_eq!(validate_name(&groups, "  project-x ", None).unwrap(), "project-x");
+        assert!(validate_name(&groups, "Local-Dev", None).is_err());
+        assert!(validate_name(&groups, "LOCAL-DEV", Some("a")).is_ok());
+        assert!(validate_name(&groups, " ", None).is_err());
+    }
+
+    #[test]
+    fn test_add_members() {
+        let mut g = group("a", "local-dev");
+        add_members(&mut g, vec!["author".to_string(), "publish".to_string()]);
+        add_members(&mut g, vec!["publish".to_string(), "dispatcher".to_string()]);
+        assert_eq!(g.instance_ids, vec!["author", "publish", "dispatcher"]);
+    }
+}
@@ -0,0 +1,335 @@
+// IDE Integration Commands
+// Active environment queries and per-project VS Code / IntelliJ configuration
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+use crate::commands::instance::load_instances;
+use crate::commands::profile::{get_active_profile, resolve_profile_java};
+use crate::i18n::Locale;
+
+// ============================================
+// Data Types
+// ============================================
+
+/// What an IDE plugin or task needs to know about the active profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdeEnvironment {
+    pub profile_id: Option<String>,
+    pub profile_name: Option<String>,
+    pub java_home: Option<String>,
+    pub java_version: Option<String>,
+    pub node_path: Option<String>,
+    /// Path of the active Maven settings.xml
+    pub maven_settings: Option<String>,
+    pub instances: Vec<IdeInstance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdeInstance {
+    pub id: String,
+    pub name: String,
+    pub instance_type: String,
+    pub url: String,
+    /// Linked to the active profile as its author or publish instance
+    pub in_profile: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdeTarget {
+    Vscode,
+    Intellij,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IdeConfigResult {
+    pub files_written: Vec<String>,
+    /// `<jdk>` element for IntelliJ's global jdk.table.xml (IDE-level, not written by us)
+    pub jdk_table_entry: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// JDK name used in IDE configuration, e.g. "aem-env-17"
+fn jdk_name(java_version: &str) -> String {
+    format!("aem-env-{}", java_version)
+}
+
+/// VS Code runtime name for a Java major version ("1.8" and "8" both map to JavaSE-1.8)
+fn vscode_runtime_name(java_version: &str) -> String {
+    match java_version.trim_start_matches("1.") {
+        "8" => "JavaSE-1.8".to_string(),
+        major => format!("JavaSE-{}", major),
+    }
+}
+
+/// Merge Java settings from the active profile into an existing .vscode/settings.json value
+fn merge_vscode_settings(mut settings: serde_json::Value, env: &IdeEnvironment) -> serde_json::Value {
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+    let (Some(java_home), Some(version)) = (&env.java_home, &env.java_version) else {
+        return settings;
+    };
+
+    let runtime = vscode_runtime_name(version);
+    let object = settings.as_object_mut().expect("settings is an object");
+
+    let mut runtimes: Vec<serde_json::Value> = object
+        .get("java.configuration.runtimes")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.get("name").and_then(|n| n.as_str()) != Some(runtime.as_str()))
+        .map(|mut r| {
+            if let Some(r) = r.as_object_mut() {
+                r.remove("default");
+            }
+            r
+        })
+        .collect();
+    runtimes.push(serde_json::json!({ "name": runtime, "path": java_home, "default": true }));
+    object.insert("java.configuration.runtimes".to_string(), runtimes.into());
+
+    object.insert(
+        "maven.terminal.customEnv".to_string(),
+        serde_json::json!([{ "environmentVariable": "JAVA_HOME", "value": java_home }]),
+    );
+    if let Some(maven_settings) = &env.maven_settings {
+        object.insert("java.configuration.maven.userSettings".to_string(), maven_settings.clone().into());
+    }
+    for os in ["osx", "linux", "windows"] {
+        let key = format!("terminal.integrated.env.{}", os);
+        let mut vars = object.get(&key).cloned().unwrap_or_else(|| serde_json::json!({}));
+        if let Some(vars) = vars.as_object_mut() {
+            vars.insert("JAVA_HOME".to_string(), java_home.clone().into());
+        }
+        object.insert(key, vars);
+    }
+
+    settings
+}
+
+/// `<jdk>` entry for IntelliJ's jdk.table.xml
+fn intellij_jdk_entry(name: &str, version: &str, java_home: &str) -> String {
+    format!(
+        r#"<jdk version="2">
+  <name value="{name}" />
+  <type value="JavaSDK" />
+  <version value="java version &quot;{version}&quot;" />
+  <homePath value="{home}" />
+</jdk>"#,
+        name = name,
+        version = version,
+        home = java_home.replace('\\', "/"),
+    )
+}
+
+/// Point an existing .idea/misc.xml at `jdk_name`, or create a minimal one
+fn update_intellij_misc_xml(existing: Option<&str>, jdk_name: &str, version: &str) -> String {
+    let language_level = match version.trim_start_matches("1.") {
+        major @ ("5" | "6" | "7" | "8") => format!("JDK_1_{}", major),
+        major => format!("JDK_{}", major),
+    };
+
+    if let Some(xml) = existing.filter(|x| x.contains("ProjectRootManager")) {
+        let name_re = regex::Regex::new(r#"project-jdk-name="[^"]*""#).expect("valid regex");
+        let level_re = regex::Regex::new(r#"languageLevel="[^"]*""#).expect("valid regex");
+        let xml = name_re.replace(xml, format!(r#"project-jdk-name="{}""#, jdk_name));
+        return level_re
+            .replace(&xml, format!(r#"languageLevel="{}""#, language_level))
+            .to_string();
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<project version="4">
+  <component name="ProjectRootManager" version="2" languageLevel="{}" project-jdk-name="{}" project-jdk-type="JavaSDK" />
+</project>
+"#,
+        language_level, jdk_name
+    )
+}
+
+/// Active profile's environment (works without an active profile; fields are then empty)
+pub(crate) async fn active_environment() -> Result<IdeEnvironment, String> {
+    let locale = Locale::current();
+    let profile = get_active_profile().await?;
+
+    let java = match &profile {
+        Some(profile) => resolve_profile_java(profile).await,
+        None => None,
+    };
+    let maven_settings = crate::commands::version::get_current_maven_config()
+        .await
+        .ok()
+        .flatten()
+        .map(|m| m.path);
+
+    let linked: Vec<String> = profile
+        .iter()
+        .flat_map(|p| [p.author_instance_id.clone(), p.publish_instance_id.clone()])
+        .flatten()
+        .collect();
+    let instances = load_instances()?
+        .into_iter()
+        .map(|i| IdeInstance {
+            in_profile: linked.contains(&i.id),
+            name: i.display_label(locale),
+            instance_type: serde_json::to_value(&i.instance_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            url: format!("http://{}:{}", i.host, i.port),
+            id: i.id,
+        })
+        .collect();
+
+    Ok(IdeEnvironment {
+        profile_id: profile.as_ref().map(|p| p.id.clone()),
+        profile_name: profile.as_ref().map(|p| p.name.clone()),
+        java_home: java.as_ref().map(|j| j.path.clone()),
+        java_version: java.map(|j| j.version),
+        node_path: profile.and_then(|p| p.node_path).filter(|p| !p.is_empty()),
+        maven_settings,
+        instances,
+    })
+}
+
+/// Write IDE configuration for `project_dir` from the active environment
+pub(crate) fn write_ide_config(
+    project_dir: &Path,
+    targets: &[IdeTarget],
+    env: &IdeEnvironment,
+) -> Result<IdeConfigResult, String> {
+    if !project_dir.is_dir() {
+        return Err(format!("Project directory not found: {}", project_dir.display()));
+    }
+
+    let mut result = IdeConfigResult {
+        files_written: Vec::new(),
+        jdk_table_entry: None,
+        warnings: Vec::new(),
+    };
+
+    let (Some(java_home), Some(version)) = (&env.java_home, &env.java_version) else {
+        result.warnings.push("The active profile has no resolvable JDK".to_string());
+        return Ok(result);
+    };
+
+    for target in targets {
+        match target {
+            IdeTarget::Vscode => {
+                let path = project_dir.join(".vscode").join("settings.json");
+                let existing = match fs::read_to_string(&path) {
+                    // VS Code accepts comments in settings.json; never overwrite what we cannot parse
+                    Ok(content) => match serde_json::from_str(&content) {
+                        Ok(value) => value,
+                        Err(_) => {
+                            result
+                                .warnings
+                                .push(format!("Skipped {}: not plain JSON (comments?)", path.display()));
+                            continue;
+                        }
+                    },
+                    Err(_) => serde_json::json!({}),
+                };
+
+                let content = serde_json::to_string_pretty(&merge_vscode_settings(existing, env))
+                    .map_err(|e| format!("Failed to serialize VS Code settings: {}", e))?;
+                write_file(&path, &content)?;
+                result.files_written.push(path.to_string_lossy().to_string());
+            }
+            IdeTarget::Intellij => {
+                let name = jdk_name(version);
+                let path = project_dir.join(".idea").join("misc.xml");
+                let existing = fs::read_to_string(&path).ok();
+                write_file(&path, &update_intellij_misc_xml(existing.as_deref(), &name, version))?;
+                result.files_written.push(path.to_string_lossy().to_string());
+                result.jdk_table_entry = Some(intellij_jdk_entry(&name, version, java_home));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Get the active profile's JAVA_HOME, tools and instances for IDE integrations
+#[command]
+pub async fn get_ide_environment() -> Result<IdeEnvironment, String> {
+    active_environment().await
+}
+
+/// Generate .vscode/settings.json and/or .idea/misc.xml for a project from the active profile
+#[command]
+pub async fn generate_ide_config(project_dir: String, targets: Vec<IdeTarget>) -> Result<IdeConfigResult, String> {
+    let env = active_environment().await?;
+    write_ide_config(&PathBuf::from(project_dir), &targets, &env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env() -> IdeEnvironment {
+        IdeEnvironment {
+            profile_id: Some("p1".to_string()),
+            profile_name: Some("AEM 6.5".to_string()),
+            java_home: Some("/opt/jdk-11".to_string()),
+            java_version: Some("11".to_string()),
+            node_path: None,
+            maven_settings: None,
+            instances: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_vscode_settings() {
+        let existing = serde_json::json!({
+            "editor.tabSize": 4,
+            "java.configuration.runtimes": [
+                { "name": "JavaSE-1.8", "path": "/opt/jdk-8", "default": true },
+                { "name": "JavaSE-11", "path": "/old/jdk-11" }
+            ]
+        });
+        let merged = merge_vscode_settings(existing, &env());
+
+        assert_eq!(merged["editor.tabSize"], 4);
+        let runtimes = merged["java.configuration.runtimes"].as_array().unwrap();
+        assert_eq!(runtimes.len(), 2);
+        assert!(runtimes[0].get("default").is_none());
+        assert_eq!(runtimes[1]["path"], "/opt/jdk-11");
+        assert_eq!(merged["terminal.integrated.env.linux"]["JAVA_HOME"], "/opt/jdk-11");
+    }
+
+    #[test]
+    fn test_update_intellij_misc_xml() {
+        let existing = r#"<project version="4">
+  <component name="ProjectRootManager" version="2" languageLevel="JDK_1_8" project-jdk-name="1.8" project-jdk-type="JavaSDK" />
+</project>"#;
+        let updated = update_intellij_misc_xml(Some(existing), "aem-env-11", "11");
+        assert!(updated.contains(r#"project-jdk-name="aem-env-11""#));
+        assert!(updated.contains(r#"languageLevel="JDK_11""#));
+
+        let created = update_intellij_misc_xml(None, "aem-env-1.8", "1.8");
+        assert!(created.contains(r#"languageLevel="JDK_1_8""#));
+    }
+}
@@ -0,0 +1,466 @@
+// Data Integrity Commands
+// Startup self-check of the JSON stores: every file must be readable, valid JSON, match the
+// format of its store and not carry a newer schema version than this build, and references between profiles, instances, projects and licenses
+// must resolve. A corrupt file is moved aside and restored from the last good copy kept by
+// this check, or from the latest automatic export; the report is emitted to the UI.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::instance::AemInstance;
+use crate::commands::license::AemLicense;
+use crate::commands::profile::{get_startup_config, AppConfig, EnvironmentProfile};
+use crate::commands::project::AemProject;
+use crate::commands::settings::read_auto_export_entry;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssueKind {
+    Unreadable,
+    InvalidJson,
+    /// Valid JSON that does not match the format of the store
+    SchemaMismatch,
+    /// Written by a newer version of the app; left untouched
+    UnsupportedSchemaVersion,
+    /// Points at a profile or instance that does not exist
+    DanglingReference,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    /// Store name, e.g. instances.json, profiles/<id>.json or config/config.json
+    pub store: String,
+    pub kind: IntegrityIssueKind,
+    pub detail: String,
+    pub repaired: bool,
+    /// Backup the store was restored from
+    pub restored_from: Option<String>,
+    /// Where the corrupt file was moved
+    pub quarantined_to: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub timestamp: String,
+    /// Number of store files checked
+    pub checked: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+/// Event emitted with the report when the startup check found issues
+pub const INTEGRITY_REPORT_EVENT: &str = "integrity-report";
+
+/// Report of the check done at startup
+static STARTUP_INTEGRITY: Mutex<Option<IntegrityReport>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StoreSchema {
+    Instances,
+    Projects,
+    Licenses,
+    Profile,
+    AppConfig,
+    /// Only has to be valid JSON
+    Json,
+}
+
+/// Newest `schema_version` a store may carry; stores without the field are version 1
+const SCHEMA_VERSION: u64 = 1;
+
+/// JSON stores in the data directory, by path relative to it; profiles are found in profiles/
+const DATA_STORES: [(&str, StoreSchema); 23] = [
+    ("instances.json", StoreSchema::Instances),
+    ("instance_groups.json", StoreSchema::Json),
+    ("projects.json", StoreSchema::Projects),
+    ("aem_licenses.json", StoreSchema::Licenses),
+    ("license_reminders.json", StoreSchema::Json),
+    ("node_globals.json", StoreSchema::Json),
+    ("password_rotations.json", StoreSchema::Json),
+    ("automation_jobs.json", StoreSchema::Json),
+    ("automation_runs.json", StoreSchema::Json),
+    ("app_lock.json", StoreSchema::Json),
+    ("env_snapshots.json", StoreSchema::Json),
+    ("startup_times.json", StoreSchema::Json),
+    ("port_allocations.json", StoreSchema::Json),
+    ("aem_catalog.json", StoreSchema::Json),
+    ("maintenance_state.json", StoreSchema::Json),
+    ("activity.json", StoreSchema::Json),
+    ("content_seeds.json", StoreSchema::Json),
+    ("shared_users.json", StoreSchema::Json),
+    (".credentials.backend", StoreSchema::Json),
+    ("trash/index.json", StoreSchema::Json),
+    ("snapshots/index.json", StoreSchema::Json),
+    ("diagnostics/heap-dumps/index.json", StoreSchema::Json),
+    ("downloads/index.json", StoreSchema::Json),
+];
+
+/// JSON stores in the config directory, named config/<file> as in export archives
+const CONFIG_STORES: [(&str, StoreSchema); 2] =
+    [("config.json", StoreSchema::AppConfig), ("scan_paths.json", StoreSchema::Json)];
+
+struct Store {
+    name: String,
+    path: PathBuf,
+    schema: StoreSchema,
+}
+
+/// Directories the check works on
+struct IntegrityDirs {
+    data: PathBuf,
+    config: PathBuf,
+    /// Last good copy of every store, refreshed by each check
+    last_good: PathBuf,
+    auto_export: Option<PathBuf>,
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn integrity_dirs() -> IntegrityDirs {
+    let platform = crate::platform::current_platform();
+    let data = platform.get_data_dir();
+    IntegrityDirs {
+        last_good: data.join("backups").join("integrity"),
+        config: platform.get_config_dir(),
+        auto_export: get_startup_config().auto_export_dir.filter(|d| !d.is_empty()).map(PathBuf::from),
+        data,
+    }
+}
+
+fn list_stores(dirs: &IntegrityDirs) -> Vec<Store> {
+    let mut stores: Vec<Store> = DATA_STORES
+        .iter()
+        .map(|(file, schema)| Store {
+            name: file.to_string(),
+            path: dirs.data.join(file),
+            schema: *schema,
+        })
+        .chain(CONFIG_STORES.iter().map(|(file, schema)| Store {
+            name: format!("config/{}", file),
+            path: dirs.config.join(file),
+            schema: *schema,
+        }))
+        .filter(|store| store.path.is_file())
+        .collect();
+
+    let mut profiles: Vec<PathBuf> = fs::read_dir(dirs.data.join("profiles"))
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default();
+    profiles.retain(|p| p.is_file() && p.extension().is_some_and(|e| e == "json"));
+    profiles.sort();
+    stores.extend(profiles.into_iter().map(|path| Store {
+        name: format!("profiles/{}", path.file_name().unwrap_or_default().to_string_lossy()),
+        path,
+        schema: StoreSchema::Profile,
+    }));
+    stores
+}
+
+/// Check store content against its format
+fn validate(content: &[u8], schema: StoreSchema) -> Result<(), (IntegrityIssueKind, String)> {
+    let value: serde_json::Value =
+        serde_json::from_slice(content).map_err(|e| (IntegrityIssueKind::InvalidJson, e.to_string()))?;
+    match value.get("schema_version").map(|v| v.as_u64()) {
+        Some(Some(version)) if version > SCHEMA_VERSION => {
+            return Err((
+                IntegrityIssueKind::UnsupportedSchemaVersion,
+                format!("Schema version {} is newer than the supported version {}", version, SCHEMA_VERSION),
+            ))
+        }
+        Some(None) => {
+            return Err((IntegrityIssueKind::SchemaMismatch, "schema_version is not a number".to_string()))
+        }
+        _ => {}
+    }
+    let typed = match schema {
+        StoreSchema::Instances => serde_json::from_value::<Vec<AemInstance>>(value).map(drop),
+        StoreSchema::Projects => serde_json::from_value::<Vec<AemProject>>(value).map(drop),
+        StoreSchema::Licenses => serde_json::from_value::<Vec<AemLicense>>(value).map(drop),
+        StoreSchema::Profile => serde_json::from_value::<EnvironmentProfile>(value).map(drop),
+        StoreSchema::AppConfig => serde_json::from_value::<AppConfig>(value).map(drop),
+        StoreSchema::Json => Ok(()),
+    };
+    typed.map_err(|e| (IntegrityIssueKind::SchemaMismatch, e.to_string()))
+}
+
+/// Valid backup of a store: the last good copy first, then the newest automatic export
+fn find_backup(store: &Store, dirs: &IntegrityDirs) -> Option<(PathBuf, Vec<u8>)> {
+    let last_good = dirs.last_good.join(&store.name);
+    fs::read(&last_good)
+        .ok()
+        .map(|content| (last_good, content))
+        .into_iter()
+        .chain(
+            dirs.auto_export
+                .as_deref()
+                .and_then(|dir| read_auto_export_entry(dir, &store.name)),
+        )
+        .find(|(_, content)| validate(content, store.schema).is_ok())
+}
+
+/// Move the corrupt file aside and write the backup in its place. Without a valid backup the
+/// file is left untouched.
+fn repair(store: &Store, dirs: &IntegrityDirs, issue: &mut IntegrityIssue) {
+    let Some((source, content)) = find_backup(store, dirs) else {
+        return;
+    };
+    let mut quarantine = store.path.clone().into_os_string();
+    quarantine.push(format!(".corrupt-{}", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    let quarantine = PathBuf::from(quarantine);
+
+    if let Err(e) = fs::rename(&store.path, &quarantine) {
+        issue.detail = format!("{} (not repaired: {})", issue.detail, e);
+        return;
+    }
+    issue.quarantined_to = Some(quarantine.to_string_lossy().to_string());
+    match fs::write(&store.path, content) {
+        Ok(()) => {
+            issue.repaired = true;
+            issue.restored_from = Some(source.to_string_lossy().to_string());
+        }
+        Err(e) => issue.detail = format!("{} (restore failed: {})", issue.detail, e),
+    }
+}
+
+fn save_last_good(store: &Store, dirs: &IntegrityDirs) {
+    let target = dirs.last_good.join(&store.name);
+    if let Some(parent) = target.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::copy(&store.path, target);
+}
+
+fn read_store<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn dangling(store: &str, detail: String) -> IntegrityIssue {
+    IntegrityIssue {
+        store: store.to_string(),
+        kind: IntegrityIssueKind::DanglingReference,
+        detail,
+        repaired: false,
+        restored_from: None,
+        quarantined_to: None,
+    }
+}
+
+/// The referenced id when it is not among `ids`
+fn missing<'a>(ids: &HashSet<&str>, id: &'a Option<String>) -> Option<&'a str> {
+    id.as_deref().filter(|id| !ids.contains(id))
+}
+
+/// References to profiles and instances that do not exist
+fn dangling_references(
+    config: Option<&AppConfig>,
+    profiles: &[EnvironmentProfile],
+    instances: &[AemInstance],
+    projects: &[AemProject],
+    licenses: &[AemLicense],
+) -> Vec<IntegrityIssue> {
+    let profile_ids: HashSet<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+    let instance_ids: HashSet<&str> = instances.iter().map(|i| i.id.as_str()).collect();
+    let mut issues = Vec::new();
+
+    if let Some(id) = config.and_then(|c| missing(&profile_ids, &c.active_profile_id)) {
+        issues.push(dangling("config/config.json", format!("Active profile {} does not exist", id)));
+    }
+    for profile in profiles {
+        for reference in [&profile.author_instance_id, &profile.publish_instance_id] {
+            let Some(id) = missing(&instance_ids, reference) else {
+                continue;
+            };
+            issues.push(dangling(
+                &format!("profiles/{}.json", profile.id),
+                format!("Profile {} refers to missing instance {}", profile.name, id),
+            ));
+        }
+    }
+    for instance in instances {
+        if let Some(id) = missing(&profile_ids, &instance.profile_id) {
+            issues.push(dangling(
+                "instances.json",
+                format!("Instance {} refers to missing profile {}", instance.name, id),
+            ));
+        }
+    }
+    for project in projects {
+        if let Some(id) = missing(&profile_ids, &project.profile_id) {
+            issues.push(dangling(
+                "projects.json",
+                format!("Project {} refers to missing profile {}", project.name, id),
+            ));
+        }
+        for reference in [&project.author_instance_id, &project.publish_instance_id] {
+            let Some(id) = missing(&instance_ids, reference) else {
+                continue;
+            };
+            issues.push(dangling(
+                "projects.json",
+                format!("Project {} refers to missing instance {}", project.name, id),
+            ));
+        }
+    }
+    for license in licenses {
+        if let Some(id) = missing(&instance_ids, &license.associated_instance_id) {
+            issues.push(dangling(
+                "aem_licenses.json",
+                format!("License {} refers to missing instance {}", license.name, id),
+            ));
+        }
+    }
+    issues
+}
+
+fn check_stores(dirs: &IntegrityDirs) -> IntegrityReport {
+    let stores = list_stores(dirs);
+    let mut issues = Vec::new();
+
+    for store in &stores {
+        let checked = fs::read(&store.path)
+            .map_err(|e| (IntegrityIssueKind::Unreadable, e.to_string()))
+            .and_then(|content| validate(&content, store.schema));
+        match checked {
+            Ok(()) => save_last_good(store, dirs),
+            Err((kind, detail)) => {
+                let mut issue = IntegrityIssue {
+                    store: store.name.clone(),
+                    kind,
+                    detail,
+                    repaired: false,
+                    restored_from: None,
+                    quarantined_to: None,
+                };
+                // A newer app version's data is not replaced by an older copy
+                if kind != IntegrityIssueKind::UnsupportedSchemaVersion {
+                    repair(store, dirs, &mut issue);
+                }
+                issues.push(issue);
+            }
+        }
+    }
+
+    let profiles: Vec<EnvironmentProfile> = stores
+        .iter()
+        .filter(|s| s.schema == StoreSchema::Profile)
+        .filter_map(|s| read_store(&s.path))
+        .collect();
+    issues.extend(dangling_references(
+        read_store::<AppConfig>(&dirs.config.join("config.json")).as_ref(),
+        &profiles,
+        &read_store::<Vec<AemInstance>>(&dirs.data.join("instances.json")).unwrap_or_default(),
+        &read_store::<Vec<AemProject>>(&dirs.data.join("projects.json")).unwrap_or_default(),
+        &read_store::<Vec<AemLicense>>(&dirs.data.join("aem_licenses.json")).unwrap_or_default(),
+    ));
+
+    IntegrityReport {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        checked: stores.len(),
+        issues,
+    }
+}
+
+/// Startup task: check and repair the stores before anything else loads them, and tell the
+/// UI what was found
+pub fn check_integrity_on_startup(app: &AppHandle) {
+    let report = check_stores(&integrity_dirs());
+    for issue in &report.issues {
+        let outcome = if issue.repaired { "repaired" } else { "not repaired" };
+        println!("[AEM] Integrity issue in {} ({}): {}", issue.store, outcome, issue.detail);
+    }
+    if !report.issues.is_empty() {
+        let _ = app.emit(INTEGRITY_REPORT_EVENT, &report);
+    }
+    if let Ok(mut last) = STARTUP_INTEGRITY.lock() {
+        *last = Some(report);
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// What the startup integrity check found (None before it ran)
+#[command]
+pub async fn get_integrity_report() -> Result<Option<IntegrityReport>, String> {
+    Ok(STARTUP_INTEGRITY.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Check (and repair) the JSON stores on demand
+#[command]
+pub async fn run_integrity_check() -> Result<IntegrityReport, String> {
+    tokio::task::spawn_blocking(|| check_stores(&integrity_dirs()))
+        .await
+        .map_err(|e| format!("Integrity check failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(b"[]", StoreSchema::Instances).is_ok());
+        assert_eq!(validate(b"[{", StoreSchema::Json).unwrap_err().0, IntegrityIssueKind::InvalidJson);
+        assert_eq!(
+            validate(br#"{"name": 1}"#, StoreSchema::Instances).unwrap_err().0,
+            IntegrityIssueKind::SchemaMismatch
+        );
+        assert!(validate(br#"{"any": "thing"}"#, StoreSchema::Json).is_ok());
+        assert!(validate(br#"{"schema_version": 1}"#, StoreSchema::Json).is_ok());
+        assert_eq!(
+            validate(br#"{"schema_version": 2}"#, StoreSchema::Json).unwrap_err().0,
+            IntegrityIssueKind::UnsupportedSchemaVersion
+        );
+        assert_eq!(
+            validate(br#"{"schema_version": "1"}"#, StoreSchema::Json).unwrap_err().0,
+            IntegrityIssueKind::SchemaMismatch
+        );
+    }
+
+    #[test]
+    fn test_check_stores_restores_last_good_copy() {
+        let root = std::env::temp_dir().join(format!("aem-env-integrity-{}", uuid::Uuid::new_v4()));
+        let dirs = IntegrityDirs {
+            data: root.join("data"),
+            config: root.join("config"),
+            last_good: root.join("data/backups/integrity"),
+            auto_export: None,
+        };
+        fs::create_dir_all(&dirs.data).unwrap();
+        fs::create_dir_all(&dirs.config).unwrap();
+        let instance = r#"[{"id": "a1", "name": "Author", "instance_type": "author", "host": "localhost",
+            "port": 4502, "profile_id": "missing"}]"#;
+        fs::write(dirs.data.join("instances.json"), instance).unwrap();
+        fs::write(dirs.data.join("startup_times.json"), "{").unwrap();
+
+        let first = check_stores(&dirs);
+        let issue = |report: &IntegrityReport, store: &str, kind| {
+            report.issues.iter().find(|i| i.store == store && i.kind == kind).cloned()
+        };
+        assert_eq!(first.checked, 2);
+        assert_eq!(first.issues.len(), 2);
+        assert!(!issue(&first, "startup_times.json", IntegrityIssueKind::InvalidJson).unwrap().repaired);
+        assert!(issue(&first, "instances.json", IntegrityIssueKind::DanglingReference).is_some());
+
+        // The last good copy of instances.json replaces a truncated file
+        fs::write(dirs.data.join("instances.json"), &instance[..20]).unwrap();
+        let second = check_stores(&dirs);
+        let restored = issue(&second, "instances.json", IntegrityIssueKind::InvalidJson).unwrap();
+        assert!(restored.repaired);
+        assert!(Path::new(restored.quarantined_to.as_deref().unwrap()).exists());
+        assert_eq!(fs::read_to_string(dirs.data.join("instances.json")).unwrap(), instance);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
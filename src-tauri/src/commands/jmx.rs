@@ -0,0 +1,291 @@
+// JMX Metrics Commands
+// Reads JVM, Oak and Sling job queue MBeans as JSON through the Felix JMX console
+// (/system/console/jmx); also the source of the memory figures in health checks
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::command;
+
+use crate::commands::instance::MemoryStatus;
+use crate::commands::packages::connect;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcMetrics {
+    pub name: String,
+    pub count: u64,
+    pub time_ms: u64,
+}
+
+/// Job statistics of a Sling job queue (`AllQueues` combines every queue)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobQueueMetrics {
+    pub name: String,
+    pub queued: u64,
+    pub active: u64,
+    pub failed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JmxMetrics {
+    pub instance_id: String,
+    pub collected_at: String,
+    /// None when the JMX console is not reachable
+    pub memory: Option<MemoryStatus>,
+    /// Collectors with an MBean on this JVM
+    pub garbage_collectors: Vec<GcMetrics>,
+    pub thread_count: Option<u64>,
+    /// Open JCR sessions (Oak repository statistics)
+    pub oak_session_count: Option<u64>,
+    pub job_queues: Vec<JobQueueMetrics>,
+}
+
+/// Garbage collector MBean names used by the common HotSpot collectors
+const GC_MBEAN_NAMES: [&str; 8] = [
+    "G1 Young Generation",
+    "G1 Old Generation",
+    "PS Scavenge",
+    "PS MarkSweep",
+    "ParNew",
+    "ConcurrentMarkSweep",
+    "Copy",
+    "MarkSweepCompact",
+];
+
+/// Sling job queues reported by `get_jmx_metrics`
+const JOB_QUEUE_NAMES: [&str; 3] = ["AllQueues", "Granite Workflow Queue", "Granite Transient Workflow Queue"];
+
+const OAK_REPOSITORY_STATS: &str = "org.apache.jackrabbit.oak:name=Oak Repository Statistics,type=RepositoryStats";
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Fetch a single MBean from the Felix JMX console as JSON
+async fn fetch_jmx_mbean(
+    client: &reqwest::Client,
+    base_url: &str,
+    credentials: &(String, String),
+    object_name: &str,
+) -> Option<serde_json::Value> {
+    let encoded = object_name
+        .replace(':', "%3A")
+        .replace('=', "%3D")
+        .replace(',', "%2C")
+        .replace(' ', "%20");
+    let url = format!("{}/system/console/jmx/{}.json", base_url, encoded);
+
+    let response = client
+        .get(&url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .send()
+        .await
+        .ok()?;
+
+    if !response.status().is_success() {
+        return None;
+    }
+
+    response.json().await.ok()
+}
+
+/// Look up an MBean attribute in the JMX console JSON
+/// Accepts attributes at the top level, as an object under "attributes",
+/// or as a list of `{name, value}` entries
+fn jmx_attribute<'a>(mbean: &'a serde_json::Value, name: &str) -> Option<&'a serde_json::Value> {
+    if let Some(value) = mbean.get(name) {
+        return Some(value);
+    }
+
+    match mbean.get("attributes")? {
+        serde_json::Value::Object(map) => map.get(name),
+        serde_json::Value::Array(list) => list
+            .iter()
+            .find(|a| a.get("name").and_then(|n| n.as_str()) == Some(name))
+            .and_then(|a| a.get("value")),
+        _ => None,
+    }
+}
+
+/// Read a numeric JMX value that may be encoded as a number or a string
+fn jmx_number(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64().or_else(|| n.as_i64().filter(|v| *v >= 0).map(|v| v as u64)),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Latest value of an Oak time series (a composite with per-second, per-minute, ... arrays),
+/// or the value itself when it is a plain number
+fn jmx_latest(value: &serde_json::Value) -> Option<u64> {
+    jmx_number(value).or_else(|| {
+        ["per second", "per minute"]
+            .iter()
+            .find_map(|key| value.get(*key)?.as_array()?.last().and_then(jmx_number))
+    })
+}
+
+/// Parse a `MemoryUsage` composite into (used, max); an undefined max (-1) becomes 0
+fn parse_jmx_memory_usage(value: &serde_json::Value) -> Option<(u64, u64)> {
+    let used = jmx_number(value.get("used")?)?;
+    let max = value.get("max").and_then(jmx_number).unwrap_or(0);
+    Some((used, max))
+}
+
+fn heap_percentage(used: u64, max: u64) -> f32 {
+    if max > 0 {
+        (used as f32 / max as f32) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Collectors of the JVM; the candidate MBeans are probed concurrently, missing ones are skipped
+async fn garbage_collectors(
+    client: &reqwest::Client,
+    base_url: &str,
+    credentials: &(String, String),
+) -> Vec<GcMetrics> {
+    let probes = GC_MBEAN_NAMES.iter().map(|gc_name| async move {
+        let object_name = format!("java.lang:type=GarbageCollector,name={}", gc_name);
+        let gc = fetch_jmx_mbean(client, base_url, credentials, &object_name).await?;
+        Some(GcMetrics {
+            name: gc_name.to_string(),
+            count: jmx_attribute(&gc, "CollectionCount").and_then(jmx_number).unwrap_or(0),
+            time_ms: jmx_attribute(&gc, "CollectionTime").and_then(jmx_number).unwrap_or(0),
+        })
+    });
+    futures_util::future::join_all(probes).await.into_iter().flatten().collect()
+}
+
+/// Heap, non-heap, metaspace and GC totals from the JVM MBeans
+pub(crate) async fn jmx_memory_status(
+    client: &reqwest::Client,
+    base_url: &str,
+    credentials: &(String, String),
+) -> Option<MemoryStatus> {
+    memory_and_collectors(client, base_url, credentials).await.map(|(memory, _)| memory)
+}
+
+async fn memory_and_collectors(
+    client: &reqwest::Client,
+    base_url: &str,
+    credentials: &(String, String),
+) -> Option<(MemoryStatus, Vec<GcMetrics>)> {
+    let memory = fetch_jmx_mbean(client, base_url, credentials, "java.lang:type=Memory").await?;
+    let (heap_used, heap_max) = jmx_attribute(&memory, "HeapMemoryUsage").and_then(parse_jmx_memory_usage)?;
+    let non_heap_used = jmx_attribute(&memory, "NonHeapMemoryUsage")
+        .and_then(parse_jmx_memory_usage)
+        .map(|(used, _)| used);
+
+    let metaspace_used = fetch_jmx_mbean(client, base_url, credentials, "java.lang:type=MemoryPool,name=Metaspace")
+        .await
+        .and_then(|m| jmx_attribute(&m, "Usage").and_then(parse_jmx_memory_usage))
+        .map(|(used, _)| used);
+
+    let collectors = garbage_collectors(client, base_url, credentials).await;
+    let (gc_count, gc_time_ms) = if collectors.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(collectors.iter().map(|gc| gc.count).sum()),
+            Some(collectors.iter().map(|gc| gc.time_ms).sum()),
+        )
+    };
+
+    let status = MemoryStatus {
+        heap_used,
+        heap_max,
+        heap_percentage: heap_percentage(heap_used, heap_max),
+        non_heap_used,
+        metaspace_used,
+        gc_count,
+        gc_time_ms,
+    };
+    Some((status, collectors))
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Key MBeans of a running instance: memory, garbage collectors, threads, Oak sessions and
+/// Sling job queues. Metrics whose MBean is missing are left empty.
+#[command]
+pub async fn get_jmx_metrics(instance_id: String) -> Result<JmxMetrics, String> {
+    let (instance, credentials) = connect(&instance_id)?;
+    let base_url = instance.base_url();
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(10)).no_proxy())
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let (memory, garbage_collectors) = memory_and_collectors(&client, &base_url, &credentials)
+        .await
+        .map_or((None, Vec::new()), |(memory, collectors)| (Some(memory), collectors));
+    let thread_count = fetch_jmx_mbean(&client, &base_url, &credentials, "java.lang:type=Threading")
+        .await
+        .and_then(|t| jmx_attribute(&t, "ThreadCount").and_then(jmx_number));
+    if memory.is_none() && thread_count.is_none() {
+        return Err(format!("JMX console of {} is not reachable", instance.name));
+    }
+
+    let oak_session_count = fetch_jmx_mbean(&client, &base_url, &credentials, OAK_REPOSITORY_STATS)
+        .await
+        .and_then(|stats| jmx_attribute(&stats, "SessionCount").and_then(jmx_latest));
+
+    let mut job_queues = Vec::new();
+    for name in JOB_QUEUE_NAMES {
+        let object_name = format!("org.apache.sling:type=queues,name={}", name);
+        if let Some(queue) = fetch_jmx_mbean(&client, &base_url, &credentials, &object_name).await {
+            let stat = |attribute: &str| jmx_attribute(&queue, attribute).and_then(jmx_number).unwrap_or(0);
+            job_queues.push(JobQueueMetrics {
+                name: name.to_string(),
+                queued: stat("NumberOfQueuedJobs"),
+                active: stat("NumberOfActiveJobs"),
+                failed: stat("NumberOfFailedJobs"),
+            });
+        }
+    }
+
+    Ok(JmxMetrics {
+        instance_id,
+        collected_at: chrono::Utc::now().to_rfc3339(),
+        memory,
+        garbage_collectors,
+        thread_count,
+        oak_session_count,
+        job_queues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jmx_memory_usage() {
+        let mbean = serde_json::json!({
+            "attributes": [
+                {"name": "HeapMemoryUsage", "value": {"used": 536870912, "max": 1073741824, "committed": 0, "init": 0}},
+                {"name": "NonHeapMemoryUsage", "value": {"used": "1024", "max": -1}}
+            ]
+        });
+        let heap = jmx_attribute(&mbean, "HeapMemoryUsage").and_then(parse_jmx_memory_usage);
+        assert_eq!(heap, Some((536870912, 1073741824)));
+        let non_heap = jmx_attribute(&mbean, "NonHeapMemoryUsage").and_then(parse_jmx_memory_usage);
+        assert_eq!(non_heap, Some((1024, 0)));
+    }
+
+    #[test]
+    fn test_jmx_latest() {
+        let series = serde_json::json!({ "per second": [3, 4, 5], "per minute": [2] });
+        assert_eq!(jmx_latest(&series), Some(5));
+        assert_eq!(jmx_latest(&serde_json::json!("12")), Some(12));
+        assert_eq!(jmx_latest(&serde_json::json!({})), None);
+    }
+}
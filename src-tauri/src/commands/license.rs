@@ -2,9 +2,12 @@
 // Handles AEM license file management and validation
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::command;
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter};
 
+use crate::i18n::{Entity, LocalizedText, Message, MessageCode};
 use crate::platform::PlatformOps;
 
 // ============================================
@@ -16,6 +19,10 @@ use crate::platform::PlatformOps;
 pub struct AemLicense {
     pub id: String,
     pub name: String,
+    #[serde(default)]
+    pub display_name: Option<LocalizedText>,
+    #[serde(default)]
+    pub localized_description: Option<LocalizedText>,
     pub license_key: Option<String>,
     pub license_file_path: Option<String>,
     pub product_name: String,
@@ -45,6 +52,8 @@ pub struct LicenseValidationResult {
     pub status: LicenseStatus,
     pub days_until_expiry: Option<i64>,
     pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
 }
 
 // ============================================
@@ -152,7 +161,7 @@ pub async fn update_aem_license(id: String, mut license: AemLicense) -> Result<A
     let index = licenses
         .iter()
         .position(|l| l.id == id)
-        .ok_or_else(|| format!("License {} not found", id))?;
+        .ok_or_else(|| Message::NotFound(Entity::License, &id).localized())?;
 
     // Preserve original ID and created_at
     license.id = id;
@@ -177,7 +186,7 @@ pub async fn delete_aem_license(id: String) -> Result<bool, String> {
     licenses.retain(|l| l.id != id);
 
     if licenses.len() == initial_len {
-        return Err(format!("License {} not found", id));
+        return Err(Message::NotFound(Entity::License, &id).localized());
     }
 
     save_licenses(&licenses)?;
@@ -195,23 +204,30 @@ pub async fn validate_aem_license(id: String) -> Result<LicenseValidationResult,
     let license = licenses
         .iter()
         .find(|l| l.id == id)
-        .ok_or_else(|| format!("License {} not found", id))?;
+        .ok_or_else(|| Message::NotFound(Entity::License, &id).localized())?;
 
     let (status, days_until_expiry) = calculate_license_status(license);
 
-    let message = match status {
-        LicenseStatus::Valid => Some("License is valid".to_string()),
-        LicenseStatus::Expired => Some("License has expired".to_string()),
-        LicenseStatus::Expiring => Some(format!("License will expire in {} days", days_until_expiry.unwrap_or(0))),
-        LicenseStatus::Invalid => Some("License is invalid".to_string()),
-        LicenseStatus::Unknown => Some("License status unknown".to_string()),
+    let (message, message_code) = match status {
+        LicenseStatus::Valid => ("License is valid".to_string(), MessageCode::new("license_valid")),
+        LicenseStatus::Expired => ("License has expired".to_string(), MessageCode::new("license_expired")),
+        LicenseStatus::Expiring => {
+            let days = days_until_expiry.unwrap_or(0);
+            (
+                format!("License will expire in {} days", days),
+                MessageCode::new("license_expiring").with("days", days),
+            )
+        }
+        LicenseStatus::Invalid => ("License is invalid".to_string(), MessageCode::new("license_invalid")),
+        LicenseStatus::Unknown => ("License status unknown".to_string(), MessageCode::new("license_status_unknown")),
     };
 
     Ok(LicenseValidationResult {
         is_valid: status == LicenseStatus::Valid || status == LicenseStatus::Expiring,
         status,
         days_until_expiry,
-        message,
+        message: Some(message),
+        message_code: Some(message_code),
     })
 }
 
@@ -543,12 +559,12 @@ pub async fn associate_license_with_instance(
     let license = licenses
         .iter_mut()
         .find(|l| l.id == license_id)
-        .ok_or_else(|| format!("License {} not found", license_id))?;
+        .ok_or_else(|| Message::NotFound(Entity::License, &license_id).localized())?;
 
     // Verify instance exists
     let instance = crate::commands::instance::get_instance(instance_id.clone()).await?;
     if instance.is_none() {
-        return Err(format!("Instance {} not found", instance_id));
+        return Err(Message::NotFound(Entity::Instance, &instance_id).localized());
     }
 
     license.associated_instance_id = Some(instance_id);
@@ -585,6 +601,8 @@ pub async fn import_license_from_file(
     let license = AemLicense {
         id: uuid::Uuid::new_v4().to_string(),
         name: format!("{} License", instance_name),
+        display_name: None,
+        localized_description: None,
         license_key: parsed.license_key,
         license_file_path: Some(file_path),
         product_name: parsed.product_name.unwrap_or_else(|| "AEM".to_string()),
@@ -611,7 +629,7 @@ fn update_license_status(license: &mut AemLicense) {
     license.status = status;
 }
 
-fn calculate_license_status(license: &AemLicense) -> (LicenseStatus, Option<i64>) {
+pub(crate) fn calculate_license_status(license: &AemLicense) -> (LicenseStatus, Option<i64>) {
     // If no expiry date, check if license file exists
     if license.expiry_date.is_none() {
         if let Some(ref path) = license.license_file_path {
@@ -694,6 +712,129 @@ pub async fn get_license_statistics() -> Result<LicenseStatistics, String> {
     Ok(stats)
 }
 
+// ============================================
+// License Expiry Reminders
+// ============================================
+
+/// Last reminder sent for a license
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LicenseReminder {
+    /// Threshold (days before expiry) the reminder was for; 0 once expired
+    threshold_days: u32,
+    /// Local date of the reminder (YYYY-MM-DD)
+    notified_on: String,
+}
+
+/// Payload of LICENSE_REMINDER_EVENT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseExpiryReminder {
+    pub license_id: String,
+    pub name: String,
+    /// Negative once the license has expired
+    pub days_until_expiry: i64,
+}
+
+pub const LICENSE_REMINDER_EVENT: &str = "license-expiry-reminder";
+
+const LICENSE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+fn get_reminders_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("license_reminders.json")
+}
+
+fn load_reminders() -> HashMap<String, LicenseReminder> {
+    std::fs::read_to_string(get_reminders_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_reminders(reminders: &HashMap<String, LicenseReminder>) -> Result<(), String> {
+    let file_path = get_reminders_file();
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(reminders)
+        .map_err(|e| format!("Failed to serialize license reminders: {}", e))?;
+    std::fs::write(&file_path, content).map_err(|e| format!("Failed to write license reminders: {}", e))
+}
+
+/// Smallest configured threshold the license is within, 0 once it has expired
+fn reminder_threshold(days_until_expiry: i64, thresholds: &[u32]) -> Option<u32> {
+    if days_until_expiry < 0 {
+        return Some(0);
+    }
+    thresholds.iter().copied().filter(|t| days_until_expiry <= *t as i64).min()
+}
+
+/// Licenses that reached a threshold they were not reminded of yet, at most one reminder per
+/// license and day. Updates `reminders` and forgets licenses that are no longer near expiry.
+fn due_reminders(
+    licenses: &[AemLicense],
+    thresholds: &[u32],
+    reminders: &mut HashMap<String, LicenseReminder>,
+    today: &str,
+) -> Vec<LicenseExpiryReminder> {
+    reminders.retain(|id, _| licenses.iter().any(|l| l.id == *id));
+
+    let mut due = Vec::new();
+    for license in licenses {
+        let (_, days) = calculate_license_status(license);
+        let Some((days, threshold)) = days.and_then(|d| reminder_threshold(d, thresholds).map(|t| (d, t))) else {
+            reminders.remove(&license.id);
+            continue;
+        };
+        let reminded = reminders
+            .get(&license.id)
+            .is_some_and(|r| r.threshold_days <= threshold || r.notified_on == today);
+        if reminded {
+            continue;
+        }
+
+        reminders.insert(
+            license.id.clone(),
+            LicenseReminder {
+                threshold_days: threshold,
+                notified_on: today.to_string(),
+            },
+        );
+        due.push(LicenseExpiryReminder {
+            license_id: license.id.clone(),
+            name: license.name.clone(),
+            days_until_expiry: days,
+        });
+    }
+    due
+}
+
+fn check_license_expiry(app: &AppHandle, thresholds: &[u32]) -> Result<(), String> {
+    let licenses = load_licenses()?;
+    let mut reminders = load_reminders();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+    for reminder in due_reminders(&licenses, thresholds, &mut reminders, &today) {
+        crate::notifications::license_expiry(app, &reminder.name, reminder.days_until_expiry);
+        let _ = app.emit(LICENSE_REMINDER_EVENT, &reminder);
+    }
+    save_reminders(&reminders)
+}
+
+/// Background task that checks licenses daily and reminds at the `license_reminder_days`
+/// thresholds (disabled when the list is empty)
+pub async fn license_reminder_scheduler(app: AppHandle) {
+    loop {
+        let thresholds = crate::commands::profile::get_startup_config().license_reminder_days;
+        if !thresholds.is_empty() {
+            if let Err(e) = check_license_expiry(&app, &thresholds) {
+                println!("[AEM] License expiry check failed: {}", e);
+            }
+        }
+
+        tokio::time::sleep(LICENSE_CHECK_INTERVAL).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -703,6 +844,8 @@ mod tests {
         let mut license = AemLicense {
             id: "test".to_string(),
             name: "Test License".to_string(),
+            display_name: None,
+            localized_description: None,
             license_key: None,
             license_file_path: None,
             product_name: "AEM".to_string(),
@@ -726,4 +869,32 @@ mod tests {
         assert_eq!(status, LicenseStatus::Expired);
         assert!(days.unwrap() < 0);
     }
+
+    #[test]
+    fn test_due_reminders() {
+        let thresholds = [30, 14, 7, 1];
+        assert_eq!(reminder_threshold(20, &thresholds), Some(30));
+        assert_eq!(reminder_threshold(7, &thresholds), Some(7));
+        assert_eq!(reminder_threshold(45, &thresholds), None);
+        assert_eq!(reminder_threshold(-1, &thresholds), Some(0));
+
+        let license: AemLicense = serde_json::from_value(serde_json::json!({
+            "id": "l1", "name": "Dev", "license_key": null, "license_file_path": null,
+            "product_name": "AEM", "product_version": null, "customer_name": null,
+            "expiry_date": (chrono::Utc::now() + chrono::Duration::days(10)).to_rfc3339(),
+            "status": "unknown", "associated_instance_id": null, "notes": null,
+            "created_at": "", "updated_at": ""
+        }))
+        .unwrap();
+        let mut reminders = HashMap::new();
+
+        let due = due_reminders(std::slice::from_ref(&license), &thresholds, &mut reminders, "2025-01-01");
+        assert_eq!(due.len(), 1);
+        assert_eq!(reminders["l1"].threshold_days, 14);
+        // Same threshold on a later day is not repeated
+        assert!(due_reminders(&[license], &thresholds, &mut reminders, "2025-01-02").is_empty());
+        // Deleted licenses are forgotten
+        assert!(due_reminders(&[], &thresholds, &mut reminders, "2025-01-02").is_empty());
+        assert!(reminders.is_empty());
+    }
 }
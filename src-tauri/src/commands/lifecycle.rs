@@ -0,0 +1,320 @@
+// App Lifecycle Commands
+// Quit-time safety check: instances this app starts that are still running are reported
+// to the UI, which decides whether to stop them, leave them running or cancel the quit.
+// On startup, persisted instance states left behind by a crashed session are reconciled.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, Manager};
+
+use crate::commands::instance::{
+    detect_all_instances_status, detect_instance_status, load_instances, save_instances, stop_instance,
+    AemInstance, AemInstanceStatus, InstanceStatusResult,
+};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningInstance {
+    pub id: String,
+    pub name: String,
+    pub port: u16,
+    pub status: AemInstanceStatus,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuitDecision {
+    /// Stop the running instances, then quit
+    StopInstances,
+    /// Quit and leave the instances running
+    LeaveRunning,
+    /// Keep the app open
+    Cancel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Returned only when the app stays open (cancelled, or an instance failed to stop)
+pub struct QuitResult {
+    pub stopped: Vec<String>,
+    /// Instances that failed to stop, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+/// Event emitted (with the running instances) when Quit is chosen while instances run
+pub const QUIT_REQUESTED_EVENT: &str = "quit-requested";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciledInstance {
+    pub instance_id: String,
+    pub name: String,
+    pub previous_status: AemInstanceStatus,
+    pub status: AemInstanceStatus,
+    pub previous_process_id: Option<u32>,
+    pub process_id: Option<u32>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryReport {
+    pub timestamp: String,
+    /// Number of instances probed
+    pub checked: usize,
+    /// Instances whose persisted state did not match reality
+    pub reconciled: Vec<ReconciledInstance>,
+}
+
+/// Event emitted with the report when startup recovery changed anything
+pub const STATE_RECOVERED_EVENT: &str = "state-recovered";
+
+/// Report of the reconciliation done at startup
+static STARTUP_RECOVERY: Mutex<Option<RecoveryReport>> = Mutex::new(None);
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Instances the app starts itself: local host and a configured installation path
+fn is_supervised(instance: &AemInstance) -> bool {
+    !instance.path.is_empty() && matches!(instance.host.as_str(), "localhost" | "127.0.0.1" | "::1")
+}
+
+async fn running_instances() -> Vec<RunningInstance> {
+    let instances = load_instances().unwrap_or_default();
+    let statuses = detect_all_instances_status().await.unwrap_or_default();
+
+    instances
+        .into_iter()
+        .filter(is_supervised)
+        .filter_map(|instance| {
+            let status = statuses.iter().find(|s| s.instance_id == instance.id)?.status.clone();
+            matches!(status, AemInstanceStatus::Running | AemInstanceStatus::Starting).then(|| RunningInstance {
+                id: instance.id,
+                name: instance.name,
+                port: instance.port,
+                status,
+            })
+        })
+        .collect()
+}
+
+/// Why the persisted state of an instance is wrong, or None when it still holds
+fn stale_reason(instance: &AemInstance, detected: &InstanceStatusResult) -> Option<String> {
+    if let Some(recorded) = instance.process_id {
+        match detected.process_id {
+            None => return Some(format!("Recorded process {} is no longer running", recorded)),
+            Some(pid) if pid != recorded => {
+                return Some(format!(
+                    "Port {} is now served by process {} instead of {}",
+                    instance.port, pid, recorded
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    if instance.status == detected.status {
+        return None;
+    }
+    match instance.status {
+        AemInstanceStatus::Starting | AemInstanceStatus::Stopping => Some(format!(
+            "Stale {:?} state left by a previous session",
+            instance.status
+        )),
+        AemInstanceStatus::Running => Some("Marked as running but no longer reachable".to_string()),
+        // Unknown is what the app records after handing an instance to a terminal
+        AemInstanceStatus::Unknown => None,
+        _ => Some(format!("Persisted as {:?}, detected {:?}", instance.status, detected.status)),
+    }
+}
+
+/// Probe every instance and correct persisted statuses and process ids. Probing takes a while,
+/// so the instances are loaded again before saving and a correction is only applied to an
+/// instance whose status did not change meanwhile (e.g. started from the UI).
+async fn reconcile() -> Result<RecoveryReport, String> {
+    let instances = load_instances()?;
+    let mut reconciled = Vec::new();
+    let mut corrections = Vec::new();
+
+    for instance in &instances {
+        let Ok(detected) = detect_instance_status(instance.id.clone()).await else {
+            continue;
+        };
+        // Only processes of local instances belong to the instance
+        let process_id = if is_supervised(instance) && detected.status != AemInstanceStatus::PortConflict {
+            detected.process_id
+        } else {
+            None
+        };
+
+        if let Some(reason) = stale_reason(instance, &detected) {
+            reconciled.push(ReconciledInstance {
+                instance_id: instance.id.clone(),
+                name: instance.name.clone(),
+                previous_status: instance.status.clone(),
+                status: detected.status.clone(),
+                previous_process_id: instance.process_id,
+                process_id,
+                reason,
+            });
+        }
+
+        if instance.status != detected.status || instance.process_id != process_id {
+            corrections.push((instance.id.clone(), instance.status.clone(), detected.status, process_id));
+        }
+    }
+
+    if !corrections.is_empty() {
+        let mut current = load_instances()?;
+        for (id, seen, status, process_id) in corrections {
+            if let Some(instance) = current.iter_mut().find(|i| i.id == id && i.status == seen) {
+                instance.status = status;
+                instance.process_id = process_id;
+            }
+        }
+        save_instances(&current)?;
+    }
+
+    Ok(RecoveryReport {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        checked: instances.len(),
+        reconciled,
+    })
+}
+
+/// Startup task: reconcile state left by the previous session and tell the UI what changed
+pub async fn recover_state_on_startup(app: AppHandle) {
+    match reconcile().await {
+        Ok(report) => {
+            for entry in &report.reconciled {
+                println!("[AEM] Recovered state of {}: {}", entry.name, entry.reason);
+            }
+            if !report.reconciled.is_empty() {
+                let _ = app.emit(STATE_RECOVERED_EVENT, &report);
+            }
+            if let Ok(mut last) = STARTUP_RECOVERY.lock() {
+                *last = Some(report);
+            }
+        }
+        Err(e) => println!("[AEM] Startup state recovery failed: {}", e),
+    }
+}
+
+fn exit_app() -> ! {
+    // std::process::exit bypasses the ExitRequested handler that keeps the app in the tray
+    std::process::exit(0)
+}
+
+/// Tray "Quit": exit right away when nothing runs, otherwise show the window and ask
+pub async fn request_quit(app: AppHandle) {
+    let running = running_instances().await;
+    if running.is_empty() {
+        exit_app();
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit(QUIT_REQUESTED_EVENT, &running);
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Supervised instances that are still running (what a quit would leave behind)
+#[command]
+pub async fn get_running_instances_for_quit() -> Result<Vec<RunningInstance>, String> {
+    Ok(running_instances().await)
+}
+
+/// Apply the user's answer to the quit prompt
+#[command]
+pub async fn resolve_quit(decision: QuitDecision) -> Result<QuitResult, String> {
+    let mut result = QuitResult {
+        stopped: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    match decision {
+        QuitDecision::Cancel => return Ok(result),
+        QuitDecision::LeaveRunning => exit_app(),
+        QuitDecision::StopInstances => {
+            for instance in running_instances().await {
+                match stop_instance(instance.id.clone()).await {
+                    Ok(_) => result.stopped.push(instance.id),
+                    Err(e) => result.failed.push((instance.id, e)),
+                }
+            }
+        }
+    }
+
+    // Stay open when an instance could not be stopped so the user can decide again
+    if result.failed.is_empty() {
+        exit_app();
+    }
+    Ok(result)
+}
+
+/// What startup recovery reconciled (None while it is still running)
+#[command]
+pub async fn get_startup_recovery_report() -> Result<Option<RecoveryReport>, String> {
+    Ok(STARTUP_RECOVERY.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Reconcile persisted instance states with reality on demand
+#[command]
+pub async fn reconcile_instance_states() -> Result<RecoveryReport, String> {
+    reconcile().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quit_decision_serde() {
+        let decision: QuitDecision = serde_json::from_str("\"stop_instances\"").unwrap();
+        assert_eq!(decision, QuitDecision::StopInstances);
+        assert_eq!(serde_json::to_string(&QuitDecision::LeaveRunning).unwrap(), "\"leave_running\"");
+    }
+
+    #[test]
+    fn test_stale_reason() {
+        let mut instance: AemInstance = serde_json::from_value(serde_json::json!({
+            "name": "Author", "instance_type": "author", "host": "localhost", "port": 4502,
+            "status": "starting", "process_id": 4242
+        }))
+        .unwrap();
+        let mut detected = InstanceStatusResult {
+            instance_id: String::new(),
+            status: AemInstanceStatus::Stopped,
+            checked_at: String::new(),
+            duration_ms: 0,
+            process_id: None,
+            process_name: None,
+            error: None,
+        };
+        assert!(stale_reason(&instance, &detected).unwrap().contains("4242"));
+
+        instance.process_id = None;
+        assert!(stale_reason(&instance, &detected).unwrap().starts_with("Stale Starting"));
+
+        instance.status = AemInstanceStatus::Unknown;
+        assert_eq!(stale_reason(&instance, &detected), None);
+
+        instance.status = AemInstanceStatus::Running;
+        instance.process_id = Some(4242);
+        detected.status = AemInstanceStatus::Running;
+        detected.process_id = Some(4242);
+        assert_eq!(stale_reason(&instance, &detected), None);
+    }
+}
@@ -0,0 +1,263 @@
+// App Lock Commands
+// PIN-protected read-only mode for shared demo machines: while locked, commands flagged as
+// destructive in the action catalog (delete, reset, stop, credential reveal, ...) are rejected
+// before they run, everything else such as status viewing keeps working
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::ipc::Invoke;
+use tauri::{command, AppHandle, Emitter, Runtime};
+
+use crate::commands::actions::load_actions;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppLockStatus {
+    pub pin_set: bool,
+    pub locked: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AppLockFile {
+    /// Hex-encoded salt and SHA-256 of salt + PIN
+    #[serde(default)]
+    salt: Option<String>,
+    #[serde(default)]
+    pin_hash: Option<String>,
+    #[serde(default)]
+    locked: bool,
+}
+
+/// Emitted with the new `AppLockStatus` whenever the app is locked or unlocked
+pub const APP_LOCK_EVENT: &str = "app-lock-changed";
+
+const MIN_PIN_LENGTH: usize = 4;
+
+/// Delay after a wrong PIN, to slow down guessing
+const WRONG_PIN_DELAY: Duration = Duration::from_secs(1);
+
+/// Loaded lock state; read on every command invocation
+static LOCK_STATE: Mutex<Option<AppLockFile>> = Mutex::new(None);
+
+/// Commands flagged as destructive in the action catalog
+static DESTRUCTIVE_COMMANDS: OnceLock<HashSet<String>> = OnceLock::new();
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_lock_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("app_lock.json")
+}
+
+fn load_lock_file() -> AppLockFile {
+    fs::read_to_string(get_lock_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_lock_file(lock: &AppLockFile) -> Result<(), String> {
+    let file_path = get_lock_file();
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(lock).map_err(|e| format!("Failed to serialize app lock: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write app lock: {}", e))
+}
+
+fn with_lock_state<R>(f: impl FnOnce(&mut AppLockFile) -> R) -> R {
+    let mut guard = LOCK_STATE.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(load_lock_file))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn hash_pin(salt: &str, pin: &str) -> String {
+    Sha256::digest(format!("{}{}", salt, pin).as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn verify_pin(lock: &AppLockFile, pin: &str) -> bool {
+    match (&lock.salt, &lock.pin_hash) {
+        (Some(salt), Some(hash)) => hash_pin(salt, pin) == *hash,
+        _ => false,
+    }
+}
+
+fn validate_pin(pin: &str) -> Result<(), String> {
+    if pin.chars().count() < MIN_PIN_LENGTH {
+        return Err(format!("The PIN must have at least {} characters", MIN_PIN_LENGTH));
+    }
+    Ok(())
+}
+
+fn status(lock: &AppLockFile) -> AppLockStatus {
+    AppLockStatus {
+        pin_set: lock.pin_hash.is_some(),
+        locked: lock.locked,
+    }
+}
+
+fn is_destructive(command: &str) -> bool {
+    DESTRUCTIVE_COMMANDS
+        .get_or_init(|| {
+            load_actions()
+                .into_iter()
+                .filter(|a| a.destructive)
+                .map(|a| a.id)
+                .collect()
+        })
+        .contains(command)
+}
+
+/// Error while the app is locked, for destructive operations outside the command handler
+pub(crate) fn ensure_unlocked(operation: &str) -> Result<(), String> {
+    if with_lock_state(|lock| lock.locked) {
+        return Err(format!("The app is locked; unlock it to allow {}", operation));
+    }
+    Ok(())
+}
+
+/// Error for a destructive command while the app is locked
+pub(crate) fn check_command_allowed(command: &str) -> Result<(), String> {
+    if is_destructive(command) {
+        ensure_unlocked(&command.replace('_', " "))?;
+    }
+    Ok(())
+}
+
+/// Wrap the generated invoke handler so locked commands are rejected before they run
+pub fn guard_invoke_handler<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if let Err(e) = check_command_allowed(invoke.message.command()) {
+            invoke.resolver.reject(e);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+fn set_locked(app: &AppHandle, locked: bool) -> Result<AppLockStatus, String> {
+    let status = with_lock_state(|lock| {
+        let mut updated = lock.clone();
+        updated.locked = locked;
+        save_lock_file(&updated)?;
+        *lock = updated;
+        Ok::<_, String>(status(lock))
+    })?;
+    let _ = app.emit(APP_LOCK_EVENT, &status);
+    Ok(status)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+#[command]
+pub async fn get_app_lock_status() -> Result<AppLockStatus, String> {
+    Ok(with_lock_state(|lock| status(lock)))
+}
+
+/// Set, change or (with no `new_pin`) remove the lock PIN. Changing an existing PIN needs the
+/// current one; removing it also unlocks the app. A forgotten PIN can be reset by deleting
+/// app_lock.json from the data directory.
+#[command]
+pub async fn set_app_lock_pin(current_pin: Option<String>, new_pin: Option<String>) -> Result<AppLockStatus, String> {
+    if let Some(pin) = new_pin.as_deref() {
+        validate_pin(pin)?;
+    }
+    let current_ok = with_lock_state(|lock| {
+        lock.pin_hash.is_none() || current_pin.as_deref().is_some_and(|pin| verify_pin(lock, pin))
+    });
+    if !current_ok {
+        tokio::time::sleep(WRONG_PIN_DELAY).await;
+        return Err("The current PIN is wrong".to_string());
+    }
+
+    with_lock_state(|lock| {
+        let updated = match new_pin {
+            Some(pin) => {
+                let salt: String = (0..16).map(|_| format!("{:02x}", rand::thread_rng().gen::<u8>())).collect();
+                AppLockFile {
+                    pin_hash: Some(hash_pin(&salt, &pin)),
+                    salt: Some(salt),
+                    locked: lock.locked,
+                }
+            }
+            None => AppLockFile::default(),
+        };
+        save_lock_file(&updated)?;
+        *lock = updated;
+        Ok(status(lock))
+    })
+}
+
+/// Switch to read-only mode; requires a PIN to be set
+#[command]
+pub async fn lock_app(app: AppHandle) -> Result<AppLockStatus, String> {
+    if with_lock_state(|lock| lock.pin_hash.is_none()) {
+        return Err("Set a PIN before locking the app".to_string());
+    }
+    set_locked(&app, true)
+}
+
+#[command]
+pub async fn unlock_app(app: AppHandle, pin: String) -> Result<AppLockStatus, String> {
+    if !with_lock_state(|lock| verify_pin(lock, &pin)) {
+        tokio::time::sleep(WRONG_PIN_DELAY).await;
+        return Err("Wrong PIN".to_string());
+    }
+    set_locked(&app, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_pin_and_destructive_commands() {
+        let salt = "0a1b2c".to_string();
+        let lock = AppLockFile {
+            pin_hash: Some(hash_pin(&salt, "4711")),
+            salt: Some(salt),
+            locked: true,
+        };
+        assert!(verify_pin(&lock, "4711"));
+        assert!(!verify_pin(&lock, "4712"));
+        assert!(!verify_pin(&AppLockFile::default(), ""));
+        assert!(validate_pin("123").is_err());
+
+        for command in [
+            "delete_instance",
+            "stop_instance",
+            "reset_instance_repository",
+            "get_credentials",
+            "run_automation_job",
+            "install_package",
+            "save_app_config",
+        ] {
+            assert!(is_destructive(command), "{} should be destructive", command);
+        }
+        for command in ["detect_all_instances_status", "check_instance_health", "unlock_app", "get_app_lock_status"] {
+            assert!(!is_destructive(command), "{} should stay allowed", command);
+        }
+    }
+}
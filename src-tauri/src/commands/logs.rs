@@ -0,0 +1,518 @@
+// Log Tailing Commands
+// Follows instance logs (crx-quickstart/logs/error.log, ..., or the console log of a managed
+// instance) and streams new lines to the frontend as events. Fast-growing logs are not read
+// in full: when a tail falls too far behind it skips ahead and reports what it dropped.
+// The logs of an instance can also be zipped (optionally for a date range) for support.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{command, AppHandle};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::commands::instance::{get_console_log_file, load_instances};
+use crate::commands::window::emit_to_windows;
+use crate::i18n::{Entity, Message};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogFile {
+    pub name: String,
+    pub size: u64,
+    pub modified_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogTail {
+    pub tail_id: String,
+    pub instance_id: String,
+    pub log: String,
+    pub path: String,
+    pub started_at: String,
+}
+
+/// Payload of `LOG_LINES_EVENT`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLines {
+    pub tail_id: String,
+    pub instance_id: String,
+    pub log: String,
+    pub lines: Vec<String>,
+    /// Lines skipped since the previous event because the log grew faster than it is streamed
+    pub dropped_lines: u64,
+    /// Bytes skipped without being read, for the same reason
+    pub dropped_bytes: u64,
+    /// The log was rotated or truncated and is followed from its start again
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogExportResult {
+    pub file_path: String,
+    /// Paths relative to crx-quickstart/logs
+    pub included: Vec<String>,
+    /// Files left out because they lie outside the date range
+    pub skipped: Vec<String>,
+    /// Uncompressed size of the included logs
+    pub total_bytes: u64,
+}
+
+struct ActiveTail {
+    info: LogTail,
+    stop: Arc<AtomicBool>,
+}
+
+/// Event emitted with `LogLines` whenever a tailed log has new lines
+pub const LOG_LINES_EVENT: &str = "log-lines";
+
+/// Name of the console log of a managed instance (stdout/stderr of the process)
+pub const CONSOLE_LOG: &str = "console";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Most bytes read per poll; a tail further behind catches up over several polls
+const MAX_READ_BYTES: u64 = 256 * 1024;
+
+/// When more than this is unread, older data is skipped
+const MAX_BACKLOG_BYTES: u64 = 1024 * 1024;
+
+/// Most lines sent in one event; older lines of a larger batch are dropped
+const MAX_LINES_PER_EVENT: usize = 1000;
+
+/// Lines longer than this are emitted in pieces
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// How far back `initial_lines` looks for the last lines of the log
+const INITIAL_WINDOW_BYTES: u64 = 128 * 1024;
+
+static TAILS: Mutex<Option<HashMap<String, ActiveTail>>> = Mutex::new(None);
+
+// ============================================
+// Helpers
+// ============================================
+
+fn with_tails<R>(f: impl FnOnce(&mut HashMap<String, ActiveTail>) -> R) -> R {
+    let mut guard = TAILS.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(HashMap::new))
+}
+
+fn logs_dir(instance_id: &str) -> Result<PathBuf, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, instance_id).localized())?;
+    Ok(instance.crx_quickstart_dir().join("logs"))
+}
+
+/// Path of a log of an instance; only plain file names inside crx-quickstart/logs are accepted
+fn resolve_log(instance_id: &str, log: &str) -> Result<PathBuf, String> {
+    let logs_dir = logs_dir(instance_id)?;
+    if log == CONSOLE_LOG {
+        return Ok(get_console_log_file(instance_id));
+    }
+    if log.is_empty() || log.contains(['/', '\\']) || log.starts_with('.') {
+        return Err(format!("Invalid log name: {}", log));
+    }
+    Ok(logs_dir.join(log))
+}
+
+/// Append `bytes` to the unfinished line in `partial` and return the completed lines
+fn take_lines(partial: &mut Vec<u8>, bytes: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for &byte in bytes {
+        if byte == b'\n' || partial.len() >= MAX_LINE_BYTES {
+            let line = String::from_utf8_lossy(partial);
+            lines.push(line.trim_end_matches('\r').to_string());
+            partial.clear();
+            if byte == b'\n' {
+                continue;
+            }
+        }
+        partial.push(byte);
+    }
+    lines
+}
+
+/// Read position and unfinished line of a followed log
+struct TailCursor {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+    /// Discard everything up to the next line break (after starting mid-file)
+    resync: bool,
+    /// Only keep this many lines of the first batch
+    keep_last: Option<usize>,
+}
+
+impl TailCursor {
+    fn new(path: PathBuf, initial_lines: usize) -> Self {
+        let len = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let offset = if initial_lines > 0 {
+            len.saturating_sub(INITIAL_WINDOW_BYTES)
+        } else {
+            len
+        };
+        Self {
+            path,
+            offset,
+            partial: Vec::new(),
+            resync: offset > 0 && initial_lines > 0,
+            keep_last: Some(initial_lines),
+        }
+    }
+
+    /// Lines written since the last call, how many lines/bytes were dropped and whether the log rotated
+    fn poll(&mut self) -> (Vec<String>, u64, u64, bool) {
+        let Ok(len) = fs::metadata(&self.path).map(|m| m.len()) else {
+            return (Vec::new(), 0, 0, false);
+        };
+
+        let mut rotated = false;
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+            self.resync = false;
+            rotated = true;
+        }
+
+        let mut dropped_bytes = 0;
+        if len - self.offset > MAX_BACKLOG_BYTES {
+            dropped_bytes = len - MAX_BACKLOG_BYTES - self.offset;
+            self.offset = len - MAX_BACKLOG_BYTES;
+            self.partial.clear();
+            self.resync = true;
+        }
+
+        let mut bytes = Vec::new();
+        if let Ok(mut file) = fs::File::open(&self.path) {
+            if file.seek(SeekFrom::Start(self.offset)).is_ok() {
+                let _ = file.take(MAX_READ_BYTES.min(len - self.offset)).read_to_end(&mut bytes);
+            }
+        }
+        self.offset += bytes.len() as u64;
+
+        let mut slice = &bytes[..];
+        if self.resync {
+            match slice.iter().position(|&b| b == b'\n') {
+                Some(pos) => {
+                    slice = &slice[pos + 1..];
+                    self.resync = false;
+                }
+                None => slice = &[],
+            }
+        }
+
+        let mut lines = take_lines(&mut self.partial, slice);
+        // The first batch is cut to the requested history without counting it as dropped
+        if let Some(keep) = self.keep_last.take().filter(|n| *n > 0) {
+            let start = lines.len().saturating_sub(keep.min(MAX_LINES_PER_EVENT));
+            lines.drain(..start);
+            return (lines, 0, dropped_bytes, rotated);
+        }
+        let dropped_lines = lines.len().saturating_sub(MAX_LINES_PER_EVENT);
+        lines.drain(..dropped_lines);
+        (lines, dropped_lines as u64, dropped_bytes, rotated)
+    }
+}
+
+async fn run_tail(app: AppHandle, info: LogTail, stop: Arc<AtomicBool>, initial_lines: usize) {
+    let mut cursor = TailCursor::new(PathBuf::from(&info.path), initial_lines);
+    while !stop.load(Ordering::Relaxed) {
+        let (lines, dropped_lines, dropped_bytes, rotated) = cursor.poll();
+        if !lines.is_empty() || dropped_lines > 0 || dropped_bytes > 0 || rotated {
+            let _ = emit_to_windows(
+                &app,
+                LOG_LINES_EVENT,
+                LogLines {
+                    tail_id: info.tail_id.clone(),
+                    instance_id: info.instance_id.clone(),
+                    log: info.log.clone(),
+                    lines,
+                    dropped_lines,
+                    dropped_bytes,
+                    rotated,
+                },
+                Some(&info.instance_id),
+                None,
+            );
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    with_tails(|tails| tails.remove(&info.tail_id));
+}
+
+fn get_log_exports_dir() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("log-exports")
+}
+
+fn parse_date(value: Option<&str>) -> Result<Option<chrono::NaiveDate>, String> {
+    value
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").map_err(|_| format!("Invalid date '{}': use YYYY-MM-DD", v))
+        })
+        .transpose()
+}
+
+/// Whether a log may hold entries between `from` and `to`. A log ends at its modification
+/// date; rotated logs (error.log.2024-05-01) only hold the day in their name.
+fn log_in_range(
+    name: &str,
+    modified: chrono::NaiveDate,
+    from: Option<chrono::NaiveDate>,
+    to: Option<chrono::NaiveDate>,
+) -> bool {
+    let rotated_on = regex::Regex::new(r"\d{4}-\d{2}-\d{2}")
+        .ok()
+        .and_then(|pattern| pattern.find(name))
+        .and_then(|m| chrono::NaiveDate::parse_from_str(m.as_str(), "%Y-%m-%d").ok());
+    let last = rotated_on.unwrap_or(modified);
+    let first = rotated_on.unwrap_or(chrono::NaiveDate::MIN);
+    from.is_none_or(|from| last >= from) && to.is_none_or(|to| first <= to)
+}
+
+fn file_modified_date(path: &Path) -> Option<chrono::NaiveDate> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(chrono::DateTime::<chrono::Local>::from(modified).date_naive())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Log files of an instance (crx-quickstart/logs, plus the console log of managed instances)
+#[command]
+pub async fn list_instance_logs(instance_id: String) -> Result<Vec<LogFile>, String> {
+    let logs_dir = logs_dir(&instance_id)?;
+    let console = get_console_log_file(&instance_id);
+
+    let mut files: Vec<(String, PathBuf)> = fs::read_dir(&logs_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_file())
+                .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    if console.exists() {
+        files.insert(0, (CONSOLE_LOG.to_string(), console));
+    }
+
+    Ok(files
+        .into_iter()
+        .map(|(name, path)| {
+            let metadata = fs::metadata(&path).ok();
+            LogFile {
+                name,
+                size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+                modified_at: metadata
+                    .and_then(|m| m.modified().ok())
+                    .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+            }
+        })
+        .collect())
+}
+
+/// Follow a log of an instance (error.log by default) and emit `LOG_LINES_EVENT`s.
+/// `initial_lines` sends the last lines of the log first. Tailing the same log again
+/// returns the running tail.
+#[command]
+pub async fn start_log_tail(
+    app: AppHandle,
+    instance_id: String,
+    log: Option<String>,
+    initial_lines: Option<usize>,
+) -> Result<LogTail, String> {
+    let log = log.filter(|l| !l.is_empty()).unwrap_or_else(|| "error.log".to_string());
+    let path = resolve_log(&instance_id, &log)?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let info = with_tails(|tails| {
+        if let Some(existing) = tails
+            .values()
+            .find(|t| t.info.instance_id == instance_id && t.info.log == log)
+        {
+            return Err(existing.info.clone());
+        }
+        let info = LogTail {
+            tail_id: uuid::Uuid::new_v4().to_string(),
+            instance_id: instance_id.clone(),
+            log: log.clone(),
+            path: path.to_string_lossy().to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        tails.insert(
+            info.tail_id.clone(),
+            ActiveTail {
+                info: info.clone(),
+                stop: stop.clone(),
+            },
+        );
+        Ok(info)
+    });
+
+    match info {
+        Ok(info) => {
+            tauri::async_runtime::spawn(run_tail(app, info.clone(), stop, initial_lines.unwrap_or(0)));
+            Ok(info)
+        }
+        Err(existing) => Ok(existing),
+    }
+}
+
+/// Stop following a log
+#[command]
+pub async fn stop_log_tail(tail_id: String) -> Result<bool, String> {
+    Ok(with_tails(|tails| match tails.remove(&tail_id) {
+        Some(tail) => {
+            tail.stop.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }))
+}
+
+/// Logs currently followed
+#[command]
+pub async fn list_log_tails() -> Result<Vec<LogTail>, String> {
+    let mut tails: Vec<LogTail> = with_tails(|tails| tails.values().map(|t| t.info.clone()).collect());
+    tails.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(tails)
+}
+
+/// Zip all files under crx-quickstart/logs (optionally only those with entries between `from`
+/// and `to`, as YYYY-MM-DD) into the app's log-exports directory, e.g. for Adobe support
+#[command]
+pub async fn export_instance_logs(
+    instance_id: String,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<LogExportResult, String> {
+    let from = parse_date(from.as_deref())?;
+    let to = parse_date(to.as_deref())?;
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &instance_id).localized())?;
+    let logs_dir = instance.crx_quickstart_dir().join("logs");
+    if !logs_dir.is_dir() {
+        return Err(format!("No logs found in {}", logs_dir.display()));
+    }
+
+    let dir = get_log_exports_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create log export directory: {}", e))?;
+    let archive_path = dir.join(format!(
+        "{}-logs-{}.zip",
+        instance.name.replace(['/', '\\', ' '], "_"),
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let file = fs::File::create(&archive_path).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true)
+        .unix_permissions(0o644);
+
+    let mut result = LogExportResult {
+        file_path: archive_path.to_string_lossy().to_string(),
+        included: Vec::new(),
+        skipped: Vec::new(),
+        total_bytes: 0,
+    };
+    for entry in WalkDir::new(&logs_dir).sort_by_file_name().into_iter().flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let name = path
+            .strip_prefix(&logs_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let modified = file_modified_date(path).unwrap_or_else(|| chrono::Local::now().date_naive());
+        if !log_in_range(&name, modified, from, to) {
+            result.skipped.push(name);
+            continue;
+        }
+
+        let mut source = fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        zip.start_file(&name, options)
+            .map_err(|e| format!("Failed to add {} to zip: {}", name, e))?;
+        result.total_bytes +=
+            std::io::copy(&mut source, &mut zip).map_err(|e| format!("Failed to write {} to zip: {}", name, e))?;
+        result.included.push(name);
+    }
+    zip.finish().map_err(|e| format!("Failed to finalize zip: {}", e))?;
+
+    if result.included.is_empty() {
+        let _ = fs::remove_file(&archive_path);
+        return Err("No logs in the selected date range".to_string());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_in_range() {
+        let date = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let (from, to) = (Some(date("2024-05-01")), Some(date("2024-05-03")));
+        assert!(log_in_range("error.log", date("2024-05-10"), from, to));
+        assert!(!log_in_range("error.log", date("2024-04-30"), from, to));
+        assert!(log_in_range("error.log.2024-05-02", date("2024-05-03"), from, to));
+        assert!(!log_in_range("error.log.2024-05-04", date("2024-05-05"), from, to));
+        assert!(log_in_range("request.log", date("2020-01-01"), None, None));
+        assert!(parse_date(Some("05/01/2024")).is_err());
+    }
+
+    #[test]
+    fn test_take_lines() {
+        let mut partial = Vec::new();
+        assert_eq!(take_lines(&mut partial, b"first\r\nsec"), vec!["first"]);
+        assert_eq!(take_lines(&mut partial, b"ond\n\nthird"), vec!["second", ""]);
+        assert_eq!(partial, b"third");
+    }
+
+    #[test]
+    fn test_tail_cursor() {
+        let path = std::env::temp_dir().join(format!("aem-env-manager-tail-{}.log", uuid::Uuid::new_v4()));
+        fs::write(&path, "old 1\nold 2\nold 3\n").unwrap();
+
+        let mut cursor = TailCursor::new(path.clone(), 2);
+        assert_eq!(cursor.poll().0, vec!["old 2", "old 3"]);
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"new 1\n").unwrap();
+        assert_eq!(cursor.poll(), (vec!["new 1".to_string()], 0, 0, false));
+
+        // Rotation: the file starts over
+        fs::write(&path, "rotated\n").unwrap();
+        assert_eq!(cursor.poll(), (vec!["rotated".to_string()], 0, 0, true));
+
+        // A burst larger than the backlog is skipped ahead
+        let burst = "x".repeat(99) + "\n";
+        fs::write(&path, burst.repeat(30_000)).unwrap();
+        cursor.offset = 0;
+        let (lines, dropped_lines, dropped_bytes, _) = cursor.poll();
+        assert_eq!(dropped_bytes, 3_000_000 - MAX_BACKLOG_BYTES);
+        assert_eq!(lines.len(), MAX_LINES_PER_EVENT);
+        assert!(dropped_lines > 0);
+        let _ = fs::remove_file(&path);
+    }
+}
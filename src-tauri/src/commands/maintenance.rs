@@ -0,0 +1,506 @@
+// Instance Maintenance Commands
+// Archives old crx-quickstart logs and clears temp files of stopped instances
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::command;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::commands::instance::{check_port_open, load_instances, AemInstance};
+use crate::i18n::{Entity, Message, MessageCode};
+use crate::platform::common::dir_size;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub instance_id: String,
+    /// Rotated log files moved into the archive
+    pub archived_logs: usize,
+    /// Archive created for this run (if any logs were old enough)
+    pub archive_path: Option<String>,
+    pub tmp_cleared: bool,
+    pub bytes_reclaimed: u64,
+    /// Why temp cleanup was skipped (e.g. instance running)
+    pub skipped_reason: Option<String>,
+    /// Translation of `skipped_reason`
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MaintenanceState {
+    last_run_at: Option<String>,
+    /// Last measured segmentstore size per instance (for growth detection)
+    #[serde(default)]
+    segmentstore_sizes: std::collections::HashMap<String, u64>,
+}
+
+/// Disk usage breakdown of an instance directory (bytes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceDiskUsage {
+    pub instance_id: String,
+    pub total: u64,
+    pub segmentstore: u64,
+    pub datastore: u64,
+    pub index: u64,
+    pub logs: u64,
+    /// Everything else below crx-quickstart
+    pub other: u64,
+    /// Segmentstore size at the previous measurement
+    pub previous_segmentstore: Option<u64>,
+    /// Segmentstore grew enough that revision cleanup is likely overdue
+    pub revision_cleanup_suggested: bool,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+/// Segmentstore growth ratio between measurements that suggests revision cleanup
+const SEGMENTSTORE_GROWTH_RATIO: f64 = 1.5;
+
+/// Ignore growth below this size; small repositories grow fast without issues
+const SEGMENTSTORE_MIN_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// TarMK state of an instance repository, read from the segmentstore directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryStatus {
+    pub tar_file_count: usize,
+    /// Last modification of journal.log (last persisted head revision)
+    pub journal_modified: Option<String>,
+    /// Last compaction recorded in gc.log, if any
+    pub last_compaction: Option<String>,
+    pub needs_maintenance: bool,
+    pub hint: Option<String>,
+}
+
+/// Offline compaction is considered overdue after this many days
+const COMPACTION_MAX_AGE_DAYS: u64 = 30;
+
+/// More tar files than this usually means old generations are piling up
+const MAX_TAR_FILES: usize = 100;
+
+/// Temp directories below crx-quickstart that are safe to clear while stopped
+const TMP_DIRS: [&str; 2] = ["tmp", "launchpad/tmp"];
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_state_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("maintenance_state.json")
+}
+
+fn load_state() -> MaintenanceState {
+    fs::read_to_string(get_state_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &MaintenanceState) -> Result<(), String> {
+    let file_path = get_state_file();
+    if let Some(parent) = file_path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+        }
+    }
+
+    let content =
+        serde_json::to_string_pretty(state).map_err(|e| format!("Failed to serialize maintenance state: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write maintenance state: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Active logs end in ".log"; anything else in the logs dir (error.log.2024-01-01,
+/// request.log.1, ...) has been rotated by Sling and can be archived
+fn is_rotated_log(name: &str) -> bool {
+    !name.ends_with(".log") && name.contains(".log")
+}
+
+fn is_older_than(path: &Path, max_age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|age| age > max_age)
+        .unwrap_or(false)
+}
+
+/// Move rotated logs older than `max_age` into a zip under logs/archive
+fn archive_old_logs(
+    logs_dir: &Path,
+    max_age: Duration,
+    report: &mut MaintenanceReport,
+) -> Result<(), String> {
+    let old_logs: Vec<PathBuf> = fs::read_dir(logs_dir)
+        .map_err(|e| format!("Failed to read logs directory: {}", e))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| is_rotated_log(&p.file_name().unwrap_or_default().to_string_lossy()))
+        .filter(|p| is_older_than(p, max_age))
+        .collect();
+
+    if old_logs.is_empty() {
+        return Ok(());
+    }
+
+    let archive_dir = logs_dir.join("archive");
+    fs::create_dir_all(&archive_dir).map_err(|e| format!("Failed to create archive directory: {}", e))?;
+    let archive_path = archive_dir.join(format!("logs-{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+
+    let file = fs::File::create(&archive_path).map_err(|e| format!("Failed to create log archive: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+
+    let mut original_bytes = 0u64;
+    for log in &old_logs {
+        let name = log.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let content = fs::read(log).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        original_bytes += content.len() as u64;
+
+        zip.start_file(&name, options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", name, e))?;
+        zip.write_all(&content)
+            .map_err(|e| format!("Failed to write {} to archive: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to finalize log archive: {}", e))?;
+
+    // Only remove originals once the archive is complete
+    for log in &old_logs {
+        match fs::remove_file(log) {
+            Ok(_) => report.archived_logs += 1,
+            Err(e) => report.errors.push(format!("Failed to remove {}: {}", log.display(), e)),
+        }
+    }
+
+    let archive_size = fs::metadata(&archive_path).map(|m| m.len()).unwrap_or(0);
+    report.bytes_reclaimed += original_bytes.saturating_sub(archive_size);
+    report.archive_path = Some(archive_path.to_string_lossy().to_string());
+
+    Ok(())
+}
+
+/// Whether segmentstore growth between two measurements suggests revision cleanup
+fn needs_revision_cleanup(previous: Option<u64>, current: u64) -> bool {
+    match previous {
+        Some(prev) if prev > 0 => {
+            current >= SEGMENTSTORE_MIN_BYTES && current as f64 / prev as f64 >= SEGMENTSTORE_GROWTH_RATIO
+        }
+        _ => false,
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn to_rfc3339(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339()
+}
+
+/// Decide whether the segmentstore needs compaction. Without a recorded compaction only the
+/// tar file count is used; a repository not written since its last compaction needs none.
+fn compaction_hint(
+    tar_file_count: usize,
+    compaction_age: Option<Duration>,
+    journal_age: Option<Duration>,
+) -> Option<String> {
+    let max_age = Duration::from_secs(COMPACTION_MAX_AGE_DAYS * 24 * 3600);
+
+    if tar_file_count > MAX_TAR_FILES {
+        return Some(format!(
+            "{} tar files in segmentstore; run offline compaction to reclaim old revisions",
+            tar_file_count
+        ));
+    }
+
+    match (compaction_age, journal_age) {
+        (Some(age), Some(journal)) if age > max_age && journal < age => Some(format!(
+            "No compaction for {} days; consider running offline compaction",
+            age.as_secs() / 86400
+        )),
+        _ => None,
+    }
+}
+
+/// Read tar file count, journal age and last compaction from the segmentstore
+/// Returns None when the instance has no TarMK repository on this machine
+pub(crate) fn repository_status(instance: &AemInstance) -> Option<RepositoryStatus> {
+    let segmentstore = instance.crx_quickstart_dir().join("repository").join("segmentstore");
+    if !segmentstore.is_dir() {
+        return None;
+    }
+
+    let tar_file_count = fs::read_dir(&segmentstore)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().extension().map(|ext| ext == "tar").unwrap_or(false))
+        .count();
+
+    // gc.log gets a line per compaction; journal.log is rewritten with every persisted head
+    let last_compaction = modified_at(&segmentstore.join("gc.log"));
+    let journal_modified = modified_at(&segmentstore.join("journal.log"));
+    let age = |time: Option<SystemTime>| time.and_then(|t| SystemTime::now().duration_since(t).ok());
+
+    let hint = compaction_hint(tar_file_count, age(last_compaction), age(journal_modified));
+
+    Some(RepositoryStatus {
+        tar_file_count,
+        journal_modified: journal_modified.map(to_rfc3339),
+        last_compaction: last_compaction.map(to_rfc3339),
+        needs_maintenance: hint.is_some(),
+        hint,
+    })
+}
+
+/// Remove the contents of the instance temp directories
+fn clear_tmp_dirs(crx_dir: &Path, report: &mut MaintenanceReport) {
+    for tmp in TMP_DIRS {
+        let tmp_dir = crx_dir.join(tmp);
+        if !tmp_dir.is_dir() {
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(&tmp_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let size = dir_size(&path);
+            let result = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            match result {
+                Ok(_) => report.bytes_reclaimed += size,
+                Err(e) => report.errors.push(format!("Failed to remove {}: {}", path.display(), e)),
+            }
+        }
+        report.tmp_cleared = true;
+    }
+}
+
+fn run_instance_maintenance(instance: &AemInstance, log_retention_days: u32) -> MaintenanceReport {
+    let mut report = MaintenanceReport {
+        instance_id: instance.id.clone(),
+        archived_logs: 0,
+        archive_path: None,
+        tmp_cleared: false,
+        bytes_reclaimed: 0,
+        skipped_reason: None,
+        message_code: None,
+        errors: Vec::new(),
+    };
+
+    let crx_dir = instance.crx_quickstart_dir();
+    if !crx_dir.is_dir() {
+        report.skipped_reason = Some(format!("crx-quickstart not found at {}", crx_dir.display()));
+        report.message_code =
+            Some(MessageCode::new("maintenance_quickstart_missing").with("path", crx_dir.display()));
+        return report;
+    }
+
+    let logs_dir = crx_dir.join("logs");
+    if logs_dir.is_dir() {
+        let max_age = Duration::from_secs(log_retention_days as u64 * 24 * 3600);
+        if let Err(e) = archive_old_logs(&logs_dir, max_age, &mut report) {
+            report.errors.push(e);
+        }
+    }
+
+    // Temp files may be in use while the instance runs
+    if check_port_open(&instance.host, instance.port, 500) {
+        report.skipped_reason = Some("Instance is running; temp files were left untouched".to_string());
+        report.message_code = Some(MessageCode::new("maintenance_instance_running"));
+    } else {
+        clear_tmp_dirs(&crx_dir, &mut report);
+    }
+
+    report
+}
+
+// ============================================
+// Maintenance Commands
+// ============================================
+
+/// Archive old logs and clear temp files for one instance
+/// `log_retention_days` defaults to the app setting
+#[command]
+pub async fn cleanup_instance_files(
+    instance_id: String,
+    log_retention_days: Option<u32>,
+) -> Result<MaintenanceReport, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &instance_id).localized())?;
+
+    let days = log_retention_days
+        .unwrap_or_else(|| crate::commands::profile::get_startup_config().log_retention_days);
+
+    Ok(run_instance_maintenance(&instance, days))
+}
+
+/// Run maintenance for all instances
+#[command]
+pub async fn run_maintenance(log_retention_days: Option<u32>) -> Result<Vec<MaintenanceReport>, String> {
+    let days = log_retention_days
+        .unwrap_or_else(|| crate::commands::profile::get_startup_config().log_retention_days);
+
+    let reports = load_instances()?
+        .iter()
+        .map(|instance| run_instance_maintenance(instance, days))
+        .collect();
+
+    let mut state = load_state();
+    state.last_run_at = Some(chrono::Utc::now().to_rfc3339());
+    save_state(&state)?;
+
+    Ok(reports)
+}
+
+/// Break down disk usage of an instance (segmentstore, datastore, index, logs)
+#[command]
+pub async fn get_instance_disk_usage(id: String) -> Result<InstanceDiskUsage, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+
+    let crx_dir = instance.crx_quickstart_dir();
+    if !crx_dir.is_dir() {
+        return Err(format!("crx-quickstart not found at {}", crx_dir.display()));
+    }
+
+    let repository_dir = crx_dir.join("repository");
+    let total = dir_size(&crx_dir);
+    let segmentstore = dir_size(&repository_dir.join("segmentstore"));
+    let datastore = dir_size(&repository_dir.join("datastore"));
+    let index = dir_size(&repository_dir.join("index"));
+    let logs = dir_size(&crx_dir.join("logs"));
+    let other = total.saturating_sub(segmentstore + datastore + index + logs);
+
+    let mut state = load_state();
+    let previous_segmentstore = state.segmentstore_sizes.get(&id).copied();
+    let revision_cleanup_suggested = needs_revision_cleanup(previous_segmentstore, segmentstore);
+    state.segmentstore_sizes.insert(id.clone(), segmentstore);
+    let _ = save_state(&state);
+
+    let (previous_mb, current_mb) = (previous_segmentstore.unwrap_or(0) / 1024 / 1024, segmentstore / 1024 / 1024);
+    let message = revision_cleanup_suggested.then(|| {
+        format!(
+            "Segmentstore grew from {} MB to {} MB since the last check; consider running revision cleanup (online or offline compaction)",
+            previous_mb, current_mb
+        )
+    });
+    let message_code = revision_cleanup_suggested.then(|| {
+        MessageCode::new("revision_cleanup_suggested")
+            .with("previous", previous_mb)
+            .with("current", current_mb)
+    });
+
+    Ok(InstanceDiskUsage {
+        instance_id: id,
+        total,
+        segmentstore,
+        datastore,
+        index,
+        logs,
+        other,
+        previous_segmentstore,
+        revision_cleanup_suggested,
+        message,
+        message_code,
+    })
+}
+
+/// When the scheduler runs maintenance next (None when no interval is configured)
+pub(crate) fn next_maintenance_at() -> Option<chrono::DateTime<chrono::Utc>> {
+    let interval_hours = crate::commands::profile::get_startup_config()
+        .maintenance_interval_hours
+        .filter(|h| *h > 0)?;
+    let next = load_state()
+        .last_run_at
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+        .map(|last| last.with_timezone(&chrono::Utc) + chrono::Duration::hours(interval_hours as i64));
+    Some(next.unwrap_or_else(chrono::Utc::now))
+}
+
+/// Background task that runs maintenance when `maintenance_interval_hours` has elapsed
+pub async fn maintenance_scheduler() {
+    loop {
+        let config = crate::commands::profile::get_startup_config();
+        if let Some(interval_hours) = config.maintenance_interval_hours.filter(|h| *h > 0) {
+            let due = load_state()
+                .last_run_at
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+                .map(|last| chrono::Utc::now().signed_duration_since(last).num_hours() >= interval_hours as i64)
+                .unwrap_or(true);
+
+            if due {
+                if let Err(e) = run_maintenance(None).await {
+                    println!("[AEM] Scheduled maintenance failed: {}", e);
+                }
+            }
+        }
+
+        // Trash retention is enforced regardless of the maintenance interval
+        if let Err(e) = crate::commands::trash::purge_expired_trash() {
+            println!("[AEM] Failed to purge trash: {}", e);
+        }
+
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_rotated_log() {
+        assert!(!is_rotated_log("error.log"));
+        assert!(is_rotated_log("error.log.2024-01-01"));
+        assert!(is_rotated_log("request.log.1"));
+        assert!(!is_rotated_log("README.txt"));
+    }
+
+    #[test]
+    fn test_needs_revision_cleanup() {
+        let gb = 1024 * 1024 * 1024;
+        assert!(!needs_revision_cleanup(None, 10 * gb));
+        assert!(!needs_revision_cleanup(Some(gb / 2), gb));
+        assert!(needs_revision_cleanup(Some(2 * gb), 4 * gb));
+        assert!(!needs_revision_cleanup(Some(4 * gb), 5 * gb));
+    }
+
+    #[test]
+    fn test_compaction_hint() {
+        let day = Duration::from_secs(24 * 3600);
+        assert!(compaction_hint(10, Some(day), Some(day)).is_none());
+        assert!(compaction_hint(10, Some(day * 45), Some(day)).is_some());
+        assert!(compaction_hint(MAX_TAR_FILES + 1, Some(day), Some(day)).is_some());
+        // Not written since the last compaction
+        assert!(compaction_hint(10, Some(day * 45), Some(day * 50)).is_none());
+        // No compaction recorded: only the tar file count counts
+        assert!(compaction_hint(10, None, Some(day)).is_none());
+        assert!(compaction_hint(MAX_TAR_FILES + 1, None, None).is_some());
+    }
+}
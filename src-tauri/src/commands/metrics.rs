@@ -0,0 +1,501 @@
+// Instance Metrics History
+// Compact per-instance ring buffer of health check samples for sparklines,
+// uptime and memory trends
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::command;
+
+use crate::i18n::{Entity, Message};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+/// A single health sample for an instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSample {
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub running: bool,
+    pub response_time_ms: u32,
+    pub heap_used_mb: u32,
+    pub heap_max_mb: u32,
+    pub bundles_active: u16,
+    pub bundles_total: u16,
+    /// Raw samples averaged into this one (1 for a raw sample), so buckets that absorb further
+    /// samples stay weighted correctly
+    pub sample_count: u32,
+}
+
+/// Uptime and memory trend of an instance over a time range
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UptimeSummary {
+    pub instance_id: String,
+    /// Unix timestamps of the first and last sample in the range
+    pub first_sample: Option<i64>,
+    pub last_sample: Option<i64>,
+    /// Seconds covered by samples; gaps without monitoring are not counted
+    pub monitored_secs: i64,
+    pub running_secs: i64,
+    pub uptime_percentage: f32,
+    /// Averages over the running samples
+    pub avg_response_time_ms: u32,
+    pub avg_heap_used_mb: u32,
+    pub peak_heap_used_mb: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsPruneResult {
+    pub instances: usize,
+    /// Samples dropped or merged into downsampled buckets
+    pub removed_samples: usize,
+    /// History files of instances that no longer exist
+    pub removed_files: usize,
+}
+
+/// Size of one encoded sample in bytes
+const RECORD_SIZE: usize = 29;
+
+/// Size of a sample in files of earlier versions, which had no sample count and no header
+const LEGACY_RECORD_SIZE: usize = 25;
+
+/// Header of history files holding `RECORD_SIZE` records
+const FILE_MAGIC: &[u8; 4] = b"AMH2";
+
+/// Samples newer than this are kept at full resolution
+const FULL_RESOLUTION_SECS: i64 = 60 * 60;
+
+/// Older samples are averaged into buckets of this size
+const DOWNSAMPLE_BUCKET_SECS: i64 = 5 * 60;
+
+/// A sample stands for the time until the next one, up to this long
+const MAX_SAMPLE_GAP_SECS: i64 = 15 * 60;
+
+impl MetricSample {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.timestamp.to_le_bytes());
+        buf.push(self.running as u8);
+        buf.extend_from_slice(&self.response_time_ms.to_le_bytes());
+        buf.extend_from_slice(&self.heap_used_mb.to_le_bytes());
+        buf.extend_from_slice(&self.heap_max_mb.to_le_bytes());
+        buf.extend_from_slice(&self.bundles_active.to_le_bytes());
+        buf.extend_from_slice(&self.bundles_total.to_le_bytes());
+        buf.extend_from_slice(&self.sample_count.to_le_bytes());
+    }
+
+    fn decode(record: &[u8]) -> Option<Self> {
+        let sample_count = match record.len() {
+            RECORD_SIZE => u32::from_le_bytes(record[25..29].try_into().ok()?).max(1),
+            LEGACY_RECORD_SIZE => 1,
+            _ => return None,
+        };
+
+        Some(Self {
+            timestamp: i64::from_le_bytes(record[0..8].try_into().ok()?),
+            running: record[8] != 0,
+            response_time_ms: u32::from_le_bytes(record[9..13].try_into().ok()?),
+            heap_used_mb: u32::from_le_bytes(record[13..17].try_into().ok()?),
+            heap_max_mb: u32::from_le_bytes(record[17..21].try_into().ok()?),
+            bundles_active: u16::from_le_bytes(record[21..23].try_into().ok()?),
+            bundles_total: u16::from_le_bytes(record[23..25].try_into().ok()?),
+            sample_count,
+        })
+    }
+
+    /// Sample of an instance that is not running
+    pub fn stopped() -> Self {
+        Self {
+            timestamp: chrono::Utc::now().timestamp(),
+            running: false,
+            response_time_ms: 0,
+            heap_used_mb: 0,
+            heap_max_mb: 0,
+            bundles_active: 0,
+            bundles_total: 0,
+            sample_count: 1,
+        }
+    }
+
+    /// Build a sample from a health check result
+    pub fn from_health(result: &crate::commands::instance::HealthCheckResult) -> Self {
+        use crate::commands::instance::AemInstanceStatus;
+
+        let (heap_used_mb, heap_max_mb) = result
+            .memory_status
+            .as_ref()
+            .map(|m| ((m.heap_used / 1024 / 1024) as u32, (m.heap_max / 1024 / 1024) as u32))
+            .unwrap_or((0, 0));
+        let (bundles_active, bundles_total) = result
+            .bundle_status
+            .as_ref()
+            .map(|b| (b.active as u16, b.total as u16))
+            .unwrap_or((0, 0));
+
+        Self {
+            timestamp: chrono::Utc::now().timestamp(),
+            running: result.status == AemInstanceStatus::Running,
+            response_time_ms: result.response_time.unwrap_or(0) as u32,
+            heap_used_mb,
+            heap_max_mb,
+            bundles_active,
+            bundles_total,
+            sample_count: 1,
+        }
+    }
+}
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_metrics_dir() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("metrics")
+}
+
+/// History file of an instance; IDs that could point outside the metrics directory are rejected
+fn get_metrics_file(instance_id: &str) -> Result<PathBuf, String> {
+    if instance_id.is_empty() || instance_id.contains(['/', '\\']) || instance_id.contains("..") {
+        return Err(format!("Invalid instance id: {}", instance_id));
+    }
+    Ok(get_metrics_dir().join(format!("{}.bin", instance_id)))
+}
+
+/// Fails unless `instance_id` is a registered instance
+fn check_known_instance(instance_id: &str) -> Result<(), String> {
+    if crate::commands::instance::load_instances()?.iter().any(|i| i.id == instance_id) {
+        Ok(())
+    } else {
+        Err(Message::NotFound(Entity::Instance, instance_id).localized())
+    }
+}
+
+fn load_samples(instance_id: &str) -> Result<Vec<MetricSample>, String> {
+    let file_path = get_metrics_file(instance_id)?;
+    if !file_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let bytes = std::fs::read(&file_path).map_err(|e| format!("Failed to read metrics: {}", e))?;
+
+    let (records, record_size) = match bytes.strip_prefix(FILE_MAGIC.as_slice()) {
+        Some(records) => (records, RECORD_SIZE),
+        None => (bytes.as_slice(), LEGACY_RECORD_SIZE),
+    };
+    Ok(records.chunks_exact(record_size).filter_map(MetricSample::decode).collect())
+}
+
+fn save_samples(instance_id: &str, samples: &[MetricSample]) -> Result<(), String> {
+    let dir = get_metrics_dir();
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create metrics directory: {}", e))?;
+    }
+
+    let mut buf = Vec::with_capacity(FILE_MAGIC.len() + samples.len() * RECORD_SIZE);
+    buf.extend_from_slice(FILE_MAGIC);
+    for sample in samples {
+        sample.encode(&mut buf);
+    }
+
+    std::fs::write(get_metrics_file(instance_id)?, buf).map_err(|e| format!("Failed to write metrics: {}", e))
+}
+
+/// Drop samples outside the retention window and average older samples into buckets
+fn compact_samples(samples: Vec<MetricSample>, now: i64, retention_hours: u32) -> Vec<MetricSample> {
+    let cutoff = now - retention_hours as i64 * 3600;
+    let full_resolution_from = now - FULL_RESOLUTION_SECS;
+
+    let mut compacted: Vec<MetricSample> = Vec::new();
+    let mut bucket: Vec<MetricSample> = Vec::new();
+
+    for sample in samples.into_iter().filter(|s| s.timestamp >= cutoff) {
+        if sample.timestamp >= full_resolution_from {
+            if !bucket.is_empty() {
+                compacted.push(average_samples(&bucket));
+                bucket.clear();
+            }
+            compacted.push(sample);
+            continue;
+        }
+
+        let same_bucket = bucket
+            .first()
+            .map(|b| b.timestamp / DOWNSAMPLE_BUCKET_SECS == sample.timestamp / DOWNSAMPLE_BUCKET_SECS)
+            .unwrap_or(true);
+        if !same_bucket {
+            compacted.push(average_samples(&bucket));
+            bucket.clear();
+        }
+        bucket.push(sample);
+    }
+
+    if !bucket.is_empty() {
+        compacted.push(average_samples(&bucket));
+    }
+
+    compacted
+}
+
+/// Average a group of samples into one (timestamp of the first sample), weighting each by the
+/// raw samples it stands for
+fn average_samples(samples: &[MetricSample]) -> MetricSample {
+    let n = samples.iter().map(|s| s.sample_count.max(1) as u64).sum::<u64>().max(1);
+    let avg = |f: &dyn Fn(&MetricSample) -> u64| {
+        samples.iter().map(|s| f(s) * s.sample_count.max(1) as u64).sum::<u64>() / n
+    };
+
+    MetricSample {
+        timestamp: samples.first().map(|s| s.timestamp).unwrap_or_default(),
+        running: samples.iter().any(|s| s.running),
+        response_time_ms: avg(&|s| s.response_time_ms as u64) as u32,
+        heap_used_mb: avg(&|s| s.heap_used_mb as u64) as u32,
+        heap_max_mb: avg(&|s| s.heap_max_mb as u64) as u32,
+        bundles_active: avg(&|s| s.bundles_active as u64) as u16,
+        bundles_total: avg(&|s| s.bundles_total as u64) as u16,
+        sample_count: n.min(u32::MAX as u64) as u32,
+    }
+}
+
+/// Append a sample to the instance history, applying retention and downsampling
+pub fn record_sample(instance_id: &str, sample: MetricSample) -> Result<(), String> {
+    let retention_hours = crate::commands::profile::get_startup_config().metrics_retention_hours;
+
+    let mut samples = load_samples(instance_id)?;
+    let now = sample.timestamp;
+    samples.push(sample);
+
+    save_samples(instance_id, &compact_samples(samples, now, retention_hours))
+}
+
+/// Remove the stored history for an instance
+pub fn delete_metrics(instance_id: &str) -> Result<(), String> {
+    let file_path = get_metrics_file(instance_id)?;
+    if file_path.exists() {
+        std::fs::remove_file(&file_path).map_err(|e| format!("Failed to delete metrics: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Time-weighted uptime of consecutive samples plus response time and heap figures
+fn summarize_uptime(instance_id: &str, samples: &[MetricSample]) -> UptimeSummary {
+    let (mut monitored_secs, mut running_secs) = (0, 0);
+    for pair in samples.windows(2) {
+        let covered = (pair[1].timestamp - pair[0].timestamp).clamp(0, MAX_SAMPLE_GAP_SECS);
+        monitored_secs += covered;
+        if pair[0].running {
+            running_secs += covered;
+        }
+    }
+
+    let running: Vec<&MetricSample> = samples.iter().filter(|s| s.running).collect();
+    let n = running.len().max(1) as u64;
+    let avg = |f: &dyn Fn(&MetricSample) -> u64| (running.iter().map(|s| f(s)).sum::<u64>() / n) as u32;
+
+    UptimeSummary {
+        instance_id: instance_id.to_string(),
+        first_sample: samples.first().map(|s| s.timestamp),
+        last_sample: samples.last().map(|s| s.timestamp),
+        monitored_secs,
+        running_secs,
+        uptime_percentage: if monitored_secs > 0 {
+            running_secs as f32 / monitored_secs as f32 * 100.0
+        } else {
+            0.0
+        },
+        avg_response_time_ms: avg(&|s| s.response_time_ms as u64),
+        avg_heap_used_mb: avg(&|s| s.heap_used_mb as u64),
+        peak_heap_used_mb: running.iter().map(|s| s.heap_used_mb).max().unwrap_or(0),
+    }
+}
+
+fn parse_time(value: &Option<String>) -> Result<Option<i64>, String> {
+    value
+        .as_ref()
+        .map(|v| {
+            chrono::DateTime::parse_from_rfc3339(v)
+                .map(|t| t.timestamp())
+                .map_err(|e| format!("Invalid timestamp '{}': {}", v, e))
+        })
+        .transpose()
+}
+
+// ============================================
+// Metrics Commands
+// ============================================
+
+/// Get stored metrics for an instance within an optional time range (RFC 3339)
+/// When max_points is set, samples are averaged down to at most that many points
+#[command]
+pub async fn get_instance_metrics(
+    instance_id: String,
+    from: Option<String>,
+    to: Option<String>,
+    max_points: Option<usize>,
+) -> Result<Vec<MetricSample>, String> {
+    check_known_instance(&instance_id)?;
+    let from = parse_time(&from)?.unwrap_or(i64::MIN);
+    let to = parse_time(&to)?.unwrap_or(i64::MAX);
+
+    let samples: Vec<MetricSample> = load_samples(&instance_id)?
+        .into_iter()
+        .filter(|s| s.timestamp >= from && s.timestamp <= to)
+        .collect();
+
+    match max_points {
+        Some(max) if max > 0 && samples.len() > max => {
+            let chunk = samples.len().div_ceil(max);
+            Ok(samples.chunks(chunk).map(average_samples).collect())
+        }
+        _ => Ok(samples),
+    }
+}
+
+/// Uptime percentage and memory trend of an instance within an optional time range (RFC 3339)
+#[command]
+pub async fn get_instance_uptime(
+    instance_id: String,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<UptimeSummary, String> {
+    let samples = get_instance_metrics(instance_id.clone(), from, to, None).await?;
+    Ok(summarize_uptime(&instance_id, &samples))
+}
+
+/// Apply retention to the history of every instance and remove the history of deleted
+/// instances. `retention_hours` defaults to the configured metrics retention.
+#[command]
+pub async fn prune_instance_metrics(retention_hours: Option<u32>) -> Result<MetricsPruneResult, String> {
+    let retention_hours =
+        retention_hours.unwrap_or_else(|| crate::commands::profile::get_startup_config().metrics_retention_hours);
+    let instance_ids: Vec<String> = crate::commands::instance::load_instances()?
+        .into_iter()
+        .map(|i| i.id)
+        .collect();
+
+    let mut result = MetricsPruneResult {
+        instances: 0,
+        removed_samples: 0,
+        removed_files: 0,
+    };
+    let Ok(entries) = std::fs::read_dir(get_metrics_dir()) else {
+        return Ok(result);
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("bin") {
+            continue;
+        }
+        let Some(instance_id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+
+        if !instance_ids.contains(&instance_id) {
+            delete_metrics(&instance_id)?;
+            result.removed_files += 1;
+            continue;
+        }
+
+        let samples = load_samples(&instance_id)?;
+        let before = samples.len();
+        let compacted = compact_samples(samples, now, retention_hours);
+        result.instances += 1;
+        result.removed_samples += before - compacted.len();
+        if compacted.len() != before {
+            save_samples(&instance_id, &compacted)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Clear stored metrics for an instance
+#[command]
+pub async fn clear_instance_metrics(instance_id: String) -> Result<bool, String> {
+    check_known_instance(&instance_id)?;
+    delete_metrics(&instance_id)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: i64, response_time_ms: u32) -> MetricSample {
+        MetricSample {
+            timestamp,
+            running: true,
+            response_time_ms,
+            heap_used_mb: 512,
+            heap_max_mb: 1024,
+            bundles_active: 600,
+            bundles_total: 610,
+            sample_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_metrics_file_rejects_paths() {
+        assert!(get_metrics_file("5f0c8a8e-author").unwrap().ends_with("5f0c8a8e-author.bin"));
+        assert!(get_metrics_file("../instances").is_err());
+        assert!(get_metrics_file("a/b").is_err());
+        assert!(get_metrics_file("a\\b").is_err());
+        assert!(get_metrics_file("").is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let original = sample(1_700_000_000, 120);
+        let mut buf = Vec::new();
+        original.encode(&mut buf);
+        assert_eq!(buf.len(), RECORD_SIZE);
+        assert_eq!(MetricSample::decode(&buf), Some(original.clone()));
+        // Records of earlier versions count as one raw sample
+        assert_eq!(MetricSample::decode(&buf[..LEGACY_RECORD_SIZE]), Some(original));
+    }
+
+    #[test]
+    fn test_compact_samples() {
+        let now = 1_700_000_000;
+        let samples = vec![
+            sample(now - 48 * 3600, 100), // outside retention
+            sample(now - 3 * 3600, 100),  // same 5-minute bucket
+            sample(now - 3 * 3600 + 30, 200),
+            sample(now - 60, 300), // full resolution
+        ];
+        let compacted = compact_samples(samples, now, 24);
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(compacted[0].response_time_ms, 150);
+        assert_eq!(compacted[0].sample_count, 2);
+        assert_eq!(compacted[1].response_time_ms, 300);
+
+        // A later sample joining the bucket counts once, not as much as the two already in it
+        let mut samples = compacted;
+        samples.insert(1, sample(now - 3 * 3600 + 60, 450));
+        let compacted = compact_samples(samples, now, 24);
+        assert_eq!(compacted[0].response_time_ms, 250);
+        assert_eq!(compacted[0].sample_count, 3);
+    }
+
+    #[test]
+    fn test_summarize_uptime() {
+        let now = 1_700_000_000;
+        let mut stopped = MetricSample::stopped();
+        stopped.timestamp = now + 60;
+        let samples = vec![
+            sample(now, 100),
+            stopped,
+            sample(now + 120, 300),
+            sample(now + 7200, 200), // after a gap, only 15 minutes are counted
+        ];
+        let summary = summarize_uptime("a", &samples);
+        assert_eq!(summary.monitored_secs, 60 + 60 + MAX_SAMPLE_GAP_SECS);
+        assert_eq!(summary.running_secs, 60 + MAX_SAMPLE_GAP_SECS);
+        assert_eq!(summary.avg_response_time_ms, 200);
+        assert_eq!(summary.peak_heap_used_mb, 512);
+        assert_eq!(summary.last_sample, Some(now + 7200));
+    }
+}
@@ -1,18 +1,112 @@
 // Tauri Commands Module
 // Exposes Rust functions to the frontend via IPC
 
+pub mod actions;
+pub mod activity;
+pub mod addons;
+pub mod aem_catalog;
+pub mod aio;
+pub mod automation;
+pub mod benchmark;
+pub mod bundles;
+pub mod certificates;
+pub mod content_package;
+pub mod content_seed;
+pub mod content_sync;
+pub mod credential_store;
+pub mod dashboard;
+pub mod diagnostics;
+pub mod dispatcher;
+pub mod downloads;
 pub mod environment;
+pub mod git;
+pub mod groups;
+pub mod ide;
 pub mod instance;
+pub mod integrity;
+pub mod jmx;
 pub mod license;
+pub mod lifecycle;
+pub mod lock;
+pub mod logs;
+pub mod maintenance;
+pub mod metrics;
+pub mod node_globals;
+pub mod operations;
+pub mod packages;
+pub mod port_plan;
 pub mod profile;
+pub mod progress;
+pub mod project;
+pub mod quick_actions;
+pub mod readiness;
+pub mod registry;
+pub mod report;
+pub mod repository;
+pub mod runtimes;
+pub mod search;
+pub mod security;
+pub mod service_pack;
 pub mod settings;
+pub mod share;
+pub mod sling_log;
+pub mod startup_times;
+pub mod tool_config;
+pub mod trash;
+pub mod updates;
 pub mod version;
 pub mod window;
 
+pub use actions::*;
+pub use activity::*;
+pub use addons::*;
+pub use aem_catalog::*;
+pub use aio::*;
+pub use automation::*;
+pub use benchmark::*;
+pub use bundles::*;
+pub use certificates::*;
+pub use content_package::*;
+pub use content_seed::*;
+pub use content_sync::*;
+pub use credential_store::*;
+pub use dashboard::*;
+pub use diagnostics::*;
+pub use dispatcher::*;
+pub use downloads::*;
 pub use environment::*;
+pub use git::*;
+pub use groups::*;
+pub use ide::*;
 pub use instance::*;
+pub use integrity::*;
+pub use jmx::*;
 pub use license::*;
+pub use lifecycle::*;
+pub use lock::*;
+pub use logs::*;
+pub use maintenance::*;
+pub use metrics::*;
+pub use node_globals::*;
+pub use operations::*;
+pub use packages::*;
+pub use port_plan::*;
 pub use profile::*;
+pub use project::*;
+pub use quick_actions::*;
+pub use readiness::*;
+pub use registry::*;
+pub use report::*;
+pub use repository::*;
+pub use search::*;
+pub use security::*;
+pub use service_pack::*;
 pub use settings::*;
+pub use share::*;
+pub use sling_log::*;
+pub use startup_times::*;
+pub use tool_config::*;
+pub use trash::*;
+pub use updates::*;
 pub use version::*;
 pub use window::*;
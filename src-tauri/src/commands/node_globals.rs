@@ -0,0 +1,287 @@
+// Global npm Packages
+// Globally installed npm packages (aio CLI, gulp, ...) and the corepack setting are recorded
+// per Node version, so they can be re-installed after switching to another Node version
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use tauri::command;
+
+use crate::commands::environment::get_node_symlink_path;
+use crate::commands::version::get_current_node_version;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GlobalPackage {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalPackageSnapshot {
+    /// Node version the packages were installed for (as reported by `node -v`)
+    pub node_version: String,
+    pub packages: Vec<GlobalPackage>,
+    /// Whether `corepack enable` had put yarn/pnpm shims next to node
+    #[serde(default)]
+    pub corepack_enabled: bool,
+    pub captured_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalPackageRestoreResult {
+    pub node_version: String,
+    pub installed: Vec<String>,
+    pub already_installed: Vec<String>,
+    /// Packages that failed to install, with the error
+    pub failed: Vec<(String, String)>,
+    pub corepack_enabled: bool,
+}
+
+/// Packages that come with Node itself
+const BUNDLED_PACKAGES: [&str; 2] = ["npm", "corepack"];
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_snapshots_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("node_globals.json")
+}
+
+fn load_snapshots() -> HashMap<String, GlobalPackageSnapshot> {
+    std::fs::read_to_string(get_snapshots_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_snapshots(snapshots: &HashMap<String, GlobalPackageSnapshot>) -> Result<(), String> {
+    let file_path = get_snapshots_file();
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(snapshots)
+        .map_err(|e| format!("Failed to serialize global packages: {}", e))?;
+    std::fs::write(&file_path, content).map_err(|e| format!("Failed to write global packages: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn version_key(node_version: &str) -> String {
+    node_version.trim().trim_start_matches('v').to_string()
+}
+
+/// Directory holding node, npm and corepack shims of the active Node version, if managed by the app
+fn current_node_bin_dir() -> Option<PathBuf> {
+    let current = get_node_symlink_path().ok()?;
+    let bin = if cfg!(target_os = "windows") { current } else { current.join("bin") };
+    bin.exists().then_some(bin)
+}
+
+/// A Node CLI (npm, corepack, aio) of the active Node version, with that version first on PATH
+pub(crate) fn node_tool(name: &str) -> Command {
+    let bin_dir = current_node_bin_dir();
+    let program = if cfg!(target_os = "windows") { format!("{}.cmd", name) } else { name.to_string() };
+    let mut cmd = Command::new(match bin_dir {
+        Some(ref dir) => dir.join(&program),
+        None => PathBuf::from(&program),
+    });
+    if let Some(dir) = bin_dir {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let paths = std::iter::once(dir).chain(std::env::split_paths(&path));
+        if let Ok(joined) = std::env::join_paths(paths) {
+            cmd.env("PATH", joined);
+        }
+    }
+    cmd
+}
+
+/// Packages from `npm ls -g --depth=0 --json`, without the ones bundled with Node
+fn parse_npm_globals(json: &str) -> Result<Vec<GlobalPackage>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse npm output: {}", e))?;
+    let mut packages: Vec<GlobalPackage> = value
+        .get("dependencies")
+        .and_then(|d| d.as_object())
+        .map(|deps| {
+            deps.iter()
+                .filter(|(name, _)| !BUNDLED_PACKAGES.contains(&name.as_str()))
+                .map(|(name, info)| GlobalPackage {
+                    name: name.clone(),
+                    version: info.get("version").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+fn list_installed_globals() -> Result<Vec<GlobalPackage>, String> {
+    let output = node_tool("npm")
+        .args(["ls", "-g", "--depth=0", "--json"])
+        .output()
+        .map_err(|e| format!("Failed to run npm: {}", e))?;
+    // npm ls exits non-zero on peer dependency problems but still prints the tree
+    parse_npm_globals(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether yarn or pnpm in the Node bin directory are corepack shims
+fn corepack_enabled() -> bool {
+    let Some(bin_dir) = current_node_bin_dir() else {
+        return false;
+    };
+    ["yarn", "pnpm", "yarn.cmd", "pnpm.cmd"].iter().any(|name| {
+        let path = bin_dir.join(name);
+        std::fs::read_link(&path)
+            .map(|target| target.to_string_lossy().contains("corepack"))
+            .unwrap_or_else(|_| {
+                std::fs::read_to_string(&path).is_ok_and(|content| content.contains("corepack"))
+            })
+    })
+}
+
+/// Packages of `snapshot` that the active Node version does not have
+pub(crate) fn missing_global_packages(snapshot: &GlobalPackageSnapshot) -> Result<Vec<String>, String> {
+    let installed = list_installed_globals()?;
+    Ok(snapshot
+        .packages
+        .iter()
+        .filter(|p| !installed.iter().any(|i| i.name == p.name))
+        .map(|p| p.name.clone())
+        .collect())
+}
+
+/// Record the global packages of the active Node version
+pub(crate) async fn capture_global_packages() -> Result<GlobalPackageSnapshot, String> {
+    let node_version = get_current_node_version()
+        .await?
+        .ok_or("No active Node version found")?;
+    let snapshot = GlobalPackageSnapshot {
+        packages: list_installed_globals()?,
+        corepack_enabled: corepack_enabled(),
+        captured_at: chrono::Utc::now().to_rfc3339(),
+        node_version: node_version.clone(),
+    };
+
+    let mut snapshots = load_snapshots();
+    snapshots.insert(version_key(&node_version), snapshot.clone());
+    save_snapshots(&snapshots)?;
+    Ok(snapshot)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Record the globally installed npm packages of the active Node version
+#[command]
+pub async fn snapshot_global_packages() -> Result<GlobalPackageSnapshot, String> {
+    capture_global_packages().await
+}
+
+/// Recorded global package sets, one per Node version
+#[command]
+pub async fn list_global_package_snapshots() -> Result<Vec<GlobalPackageSnapshot>, String> {
+    let mut snapshots: Vec<GlobalPackageSnapshot> = load_snapshots().into_values().collect();
+    snapshots.sort_by(|a, b| crate::commands::version::compare_versions(&b.node_version, &a.node_version));
+    Ok(snapshots)
+}
+
+/// Install the packages recorded for `from_node_version` into the active Node version.
+/// `packages` limits the restore to the given names; recorded versions are kept.
+#[command]
+pub async fn restore_global_packages(
+    from_node_version: String,
+    packages: Option<Vec<String>>,
+) -> Result<GlobalPackageRestoreResult, String> {
+    let snapshot = load_snapshots()
+        .remove(&version_key(&from_node_version))
+        .ok_or_else(|| format!("No global packages recorded for Node {}", from_node_version))?;
+    let node_version = get_current_node_version()
+        .await?
+        .ok_or("No active Node version found")?;
+    let missing = missing_global_packages(&snapshot)?;
+
+    let mut result = GlobalPackageRestoreResult {
+        node_version,
+        installed: Vec::new(),
+        already_installed: Vec::new(),
+        failed: Vec::new(),
+        corepack_enabled: false,
+    };
+
+    for package in snapshot.packages {
+        if packages.as_ref().is_some_and(|only| !only.contains(&package.name)) {
+            continue;
+        }
+        if !missing.contains(&package.name) {
+            result.already_installed.push(package.name);
+            continue;
+        }
+
+        let spec = if package.version.is_empty() {
+            package.name.clone()
+        } else {
+            format!("{}@{}", package.name, package.version)
+        };
+        match node_tool("npm").args(["install", "-g", &spec]).output() {
+            Ok(output) if output.status.success() => result.installed.push(package.name),
+            Ok(output) => result
+                .failed
+                .push((package.name, String::from_utf8_lossy(&output.stderr).trim().to_string())),
+            Err(e) => result.failed.push((package.name, format!("Failed to run npm: {}", e))),
+        }
+    }
+
+    if snapshot.corepack_enabled && !corepack_enabled() {
+        match node_tool("corepack").arg("enable").output() {
+            Ok(output) if output.status.success() => result.corepack_enabled = true,
+            Ok(output) => result
+                .failed
+                .push(("corepack".to_string(), String::from_utf8_lossy(&output.stderr).trim().to_string())),
+            Err(e) => result.failed.push(("corepack".to_string(), format!("Failed to run corepack: {}", e))),
+        }
+    }
+
+    // The active version now has these packages too
+    let _ = capture_global_packages().await;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_npm_globals() {
+        let json = r#"{
+            "name": "lib",
+            "dependencies": {
+                "npm": { "version": "10.2.4" },
+                "gulp": { "version": "4.0.2", "overridden": false },
+                "@adobe/aio-cli": { "version": "10.3.1", "overridden": false },
+                "corepack": { "version": "0.24.0" }
+            }
+        }"#;
+        let packages = parse_npm_globals(json).unwrap();
+        assert_eq!(
+            packages,
+            vec![
+                GlobalPackage { name: "@adobe/aio-cli".to_string(), version: "10.3.1".to_string() },
+                GlobalPackage { name: "gulp".to_string(), version: "4.0.2".to_string() },
+            ]
+        );
+        assert!(parse_npm_globals("{}").unwrap().is_empty());
+        assert_eq!(version_key("v20.11.0\n"), "20.11.0");
+    }
+}
@@ -0,0 +1,238 @@
+// Operation Coalescing and Cancellation
+// Long scans and detections run at most once per key: a second request while one is in
+// flight waits for and shares its result. Running operations can be cancelled; they check
+// their token between units of work and stop early.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::command;
+use tokio::sync::watch;
+
+// ============================================
+// Data Types
+// ============================================
+
+/// Cooperative cancellation flag handed to a running operation
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Err when the operation was cancelled, for use with `?` between units of work
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningOperation {
+    pub key: String,
+    pub started_at: String,
+    /// Requests sharing the result besides the one that started it
+    pub waiters: usize,
+    pub cancelled: bool,
+}
+
+type OperationResult = Option<Result<serde_json::Value, String>>;
+
+struct InFlight {
+    token: CancelToken,
+    started_at: String,
+    waiters: usize,
+    result: watch::Receiver<OperationResult>,
+}
+
+/// Error returned by cancelled operations
+pub const CANCELLED: &str = "Operation cancelled";
+
+static IN_FLIGHT: Mutex<Option<HashMap<String, InFlight>>> = Mutex::new(None);
+
+// ============================================
+// Helpers
+// ============================================
+
+fn with_in_flight<R>(f: impl FnOnce(&mut HashMap<String, InFlight>) -> R) -> R {
+    let mut guard = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(HashMap::new))
+}
+
+/// Removes the entry when the operation ends, and tells waiters if it was dropped midway
+struct Leader {
+    key: String,
+    sender: watch::Sender<OperationResult>,
+}
+
+impl Drop for Leader {
+    fn drop(&mut self) {
+        with_in_flight(|ops| ops.remove(&self.key));
+        self.sender.send_if_modified(|result| {
+            if result.is_some() {
+                return false;
+            }
+            *result = Some(Err("Operation aborted".to_string()));
+            true
+        });
+    }
+}
+
+enum Role {
+    Leader(Leader, CancelToken),
+    Waiter(watch::Receiver<OperationResult>),
+}
+
+fn join(key: &str) -> Role {
+    with_in_flight(|ops| {
+        if let Some(op) = ops.get_mut(key) {
+            op.waiters += 1;
+            return Role::Waiter(op.result.clone());
+        }
+
+        let token = CancelToken::default();
+        let (sender, result) = watch::channel(None);
+        ops.insert(
+            key.to_string(),
+            InFlight {
+                token: token.clone(),
+                started_at: chrono::Utc::now().to_rfc3339(),
+                waiters: 0,
+                result,
+            },
+        );
+        Role::Leader(
+            Leader {
+                key: key.to_string(),
+                sender,
+            },
+            token,
+        )
+    })
+}
+
+/// Key of an operation with arguments, so different arguments do not share a result
+pub(crate) fn operation_key(name: &str, args: &impl Serialize) -> String {
+    match serde_json::to_string(args) {
+        Ok(args) if args != "null" => format!("{}:{}", name, args),
+        _ => name.to_string(),
+    }
+}
+
+/// Run `operation` unless one with the same key is in flight, in which case its result is shared
+pub(crate) async fn coalesce<T, F, Fut>(key: &str, operation: F) -> Result<T, String>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(CancelToken) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    match join(key) {
+        Role::Leader(leader, token) => {
+            let result = operation(token).await;
+            let shared = match &result {
+                Ok(value) => serde_json::to_value(value).map_err(|e| e.to_string()),
+                Err(e) => Err(e.clone()),
+            };
+            let _ = leader.sender.send(Some(shared));
+            result
+        }
+        Role::Waiter(mut receiver) => {
+            let result = receiver
+                .wait_for(|r| r.is_some())
+                .await
+                .map_err(|_| "Operation aborted".to_string())?
+                .clone()
+                .unwrap_or_else(|| Err("Operation aborted".to_string()))?;
+            serde_json::from_value(result).map_err(|e| e.to_string())
+        }
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Scans and detections currently in flight
+#[command]
+pub async fn list_running_operations() -> Result<Vec<RunningOperation>, String> {
+    let mut operations: Vec<RunningOperation> = with_in_flight(|ops| {
+        ops.iter()
+            .map(|(key, op)| RunningOperation {
+                key: key.clone(),
+                started_at: op.started_at.clone(),
+                waiters: op.waiters,
+                cancelled: op.token.is_cancelled(),
+            })
+            .collect()
+    });
+    operations.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(operations)
+}
+
+/// Ask a running operation to stop; it and everyone waiting on it get an error.
+/// `key` may also be a bare operation name, which cancels it for any arguments.
+#[command]
+pub async fn cancel_operation(key: String) -> Result<bool, String> {
+    Ok(with_in_flight(|ops| {
+        let mut found = false;
+        for (op_key, op) in ops.iter() {
+            if op_key == &key || op_key.starts_with(&format!("{}:", key)) {
+                op.token.cancel();
+                found = true;
+            }
+        }
+        found
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalesce_and_cancel() {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let slow = |runs: Arc<std::sync::atomic::AtomicUsize>| {
+                coalesce("test-scan", move |token: CancelToken| async move {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    for _ in 0..20 {
+                        token.check()?;
+                        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    }
+                    Ok(vec![1, 2, 3])
+                })
+            };
+
+            let (a, b) = tokio::join!(slow(runs.clone()), slow(runs.clone()));
+            assert_eq!(a.unwrap(), vec![1, 2, 3]);
+            assert_eq!(b.unwrap(), vec![1, 2, 3]);
+            assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+            let cancel = async {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                cancel_operation("test-scan".to_string()).await.unwrap()
+            };
+            let (result, cancelled) = tokio::join!(slow(runs.clone()), cancel);
+            assert!(cancelled);
+            assert_eq!(result.unwrap_err(), CANCELLED);
+            assert!(with_in_flight(|ops| ops.is_empty()));
+        });
+
+        assert_eq!(operation_key("scan_aem_instances", &None::<Vec<String>>), "scan_aem_instances");
+        assert_eq!(operation_key("scan", &Some(vec!["/opt"])), "scan:[\"/opt\"]");
+    }
+}
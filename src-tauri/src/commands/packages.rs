@@ -0,0 +1,552 @@
+// CRX Package Manager Commands
+// Packages known to an instance's Package Manager (/crx/packmgr), read with the
+// instance's stored credentials; upload/install of local package zips and build/download
+// of packages on the instance, with streamed transfer progress
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{command, AppHandle};
+use tokio::io::AsyncReadExt;
+
+use crate::commands::activity::{record_activity, ActivityKind};
+use crate::commands::instance::{get_named_instance_credentials, load_instances, AemInstance};
+use crate::commands::progress::ProgressReporter;
+use crate::i18n::{Entity, Message};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackageInfo {
+    pub group: String,
+    pub name: String,
+    pub version: String,
+    /// Repository path, e.g. /etc/packages/my_packages/site-content-1.0.zip
+    pub path: String,
+    pub download_name: String,
+    pub size: u64,
+    /// When the package was last installed (None when only uploaded)
+    pub installed_at: Option<String>,
+    pub installed_by: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageUploadResult {
+    pub group: String,
+    pub name: String,
+    pub version: String,
+    pub installed: bool,
+    /// Package Manager response (includes the install log)
+    pub log: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageDownloadResult {
+    pub output_path: String,
+    pub size: u64,
+}
+
+/// Uploads and installs of large packages (service packs) take many minutes
+pub(crate) const PACKAGE_UPLOAD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Building a package of a large content tree can take as long as installing one
+const PACKAGE_BUILD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Bytes read from the package per streamed chunk
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+
+// ============================================
+// Helpers
+// ============================================
+
+fn millis_to_rfc3339(value: Option<&serde_json::Value>) -> Option<String> {
+    let millis = value?.as_i64().filter(|m| *m > 0)?;
+    chrono::DateTime::<chrono::Utc>::from_timestamp_millis(millis).map(|t| t.to_rfc3339())
+}
+
+/// Packages from a /crx/packmgr/list.jsp response
+fn parse_package_list(json: &serde_json::Value) -> Vec<PackageInfo> {
+    let text = |p: &serde_json::Value, key: &str| p.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let mut packages: Vec<PackageInfo> = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .map(|results| {
+            results
+                .iter()
+                .map(|p| PackageInfo {
+                    group: text(p, "group"),
+                    name: text(p, "name"),
+                    version: text(p, "version"),
+                    path: text(p, "path"),
+                    download_name: text(p, "downloadName"),
+                    size: p.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+                    installed_at: millis_to_rfc3339(p.get("lastUnpacked")),
+                    installed_by: p
+                        .get("lastUnpackedBy")
+                        .and_then(|v| v.as_str())
+                        .filter(|v| !v.is_empty() && *v != "null")
+                        .map(str::to_string),
+                    last_modified: millis_to_rfc3339(p.get("lastModified")),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.sort_by(|a, b| a.group.cmp(&b.group).then_with(|| a.name.cmp(&b.name)));
+    packages
+}
+
+pub(crate) fn connect(instance_id: &str) -> Result<(AemInstance, (String, String)), String> {
+    connect_as(instance_id, None)
+}
+
+/// Instance and the credentials stored under `credential`, the default ones when None
+pub(crate) fn connect_as(
+    instance_id: &str,
+    credential: Option<&str>,
+) -> Result<(AemInstance, (String, String)), String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, instance_id).localized())?;
+    let (username, password, _) = get_named_instance_credentials(&instance.id, credential)?;
+    Ok((instance, (username, password)))
+}
+
+pub(crate) async fn fetch_packages(
+    instance: &AemInstance,
+    credentials: &(String, String),
+) -> Result<Vec<PackageInfo>, String> {
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(30)))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("{}/crx/packmgr/list.jsp", instance.base_url());
+    let response = client
+        .get(&url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status().as_u16()));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse package list: {}", e))?;
+    Ok(parse_package_list(&json))
+}
+
+/// Repository path of a package below /etc/packages, normalized with a leading slash
+fn normalize_package_path(path: &str) -> Result<String, String> {
+    let path = format!("/{}", path.trim().trim_matches('/'));
+    if !path.starts_with("/etc/packages/") || !path.ends_with(".zip") || path.contains("/../") {
+        return Err(format!("{} is not a package path (/etc/packages/<group>/<name>.zip)", path));
+    }
+    Ok(path)
+}
+
+/// Outcome of a `/crx/packmgr/service/.json` command (`{"success": true, "msg": "Package built"}`)
+pub(crate) fn packmgr_json_result(json: &serde_json::Value) -> Result<String, String> {
+    let message = json.get("msg").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+    if json.get("success").and_then(|s| s.as_bool()).unwrap_or(false) {
+        Ok(message)
+    } else if message.is_empty() {
+        Err("Package Manager sent an unexpected response".to_string())
+    } else {
+        Err(format!("Package Manager failed: {}", message))
+    }
+}
+
+/// Build the package at `package_path` (its filter is re-read and the zip regenerated)
+pub(crate) async fn build_package_at(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    package_path: &str,
+) -> Result<String, String> {
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(PACKAGE_BUILD_TIMEOUT))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(format!("{}/crx/packmgr/service/.json{}", instance.base_url(), package_path))
+        .query(&[("cmd", "build")])
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to build {}: {}", package_path, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Building {} returned HTTP {}", package_path, response.status().as_u16()));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse build response: {}", e))?;
+    packmgr_json_result(&json)
+}
+
+/// Download the package zip at `package_path` into `target_dir`, keeping its file name.
+/// The zip is written next to the target as `.part` and renamed once complete.
+pub(crate) async fn download_package_to(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    package_path: &str,
+    target_dir: &Path,
+    mut progress: Option<ProgressReporter>,
+) -> Result<PathBuf, String> {
+    let file_name = package_path.rsplit('/').next().unwrap_or_default();
+    std::fs::create_dir_all(target_dir)
+        .map_err(|e| format!("Failed to create {}: {}", target_dir.display(), e))?;
+    let target = target_dir.join(file_name);
+    let partial = target_dir.join(format!("{}.part", file_name));
+
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(PACKAGE_UPLOAD_TIMEOUT))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut response = client
+        .get(format!("{}{}", instance.base_url(), package_path))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", package_path, e))?;
+    match response.status().as_u16() {
+        200 => {}
+        404 => return Err(format!("{} does not exist", package_path)),
+        status => return Err(format!("Downloading {} returned HTTP {}", package_path, status)),
+    }
+
+    let mut file =
+        std::fs::File::create(&partial).map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+    if let Some(ref mut progress) = progress {
+        progress.start(response.content_length(), 0);
+    }
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download of {} interrupted: {}", package_path, e))?
+    {
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+        if let Some(ref mut progress) = progress {
+            progress.advance(chunk.len() as u64);
+        }
+    }
+    drop(file);
+    if let Some(ref mut progress) = progress {
+        progress.finish();
+    }
+
+    std::fs::rename(&partial, &target).map_err(|e| format!("Failed to finalize download: {}", e))?;
+    Ok(target)
+}
+
+/// multipart/form-data framing around an uploaded file: the form fields and file part header
+/// before it, the closing boundary after it. `file_part` is the field name and content type.
+pub(crate) fn multipart_frame(
+    boundary: &str,
+    file_part: (&str, &str),
+    file_name: &str,
+    fields: &[(&str, &str)],
+) -> (Vec<u8>, Vec<u8>) {
+    let mut prefix = String::new();
+    for (name, value) in fields {
+        prefix.push_str(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, value
+        ));
+    }
+    prefix.push_str(&format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+         Content-Type: {}\r\n\r\n",
+        boundary,
+        file_part.0,
+        file_name.replace('"', ""),
+        file_part.1
+    ));
+    (prefix.into_bytes(), format!("\r\n--{}--\r\n", boundary).into_bytes())
+}
+
+/// Status code and message of a service.jsp response (`<status code="200">ok</status>`)
+pub(crate) fn packmgr_status(response: &str) -> Option<(u16, String)> {
+    let pattern = regex::Regex::new(r#"<status code="(\d+)">([^<]*)</status>"#).ok()?;
+    let captures = pattern.captures(response)?;
+    Some((captures[1].parse().ok()?, captures[2].trim().to_string()))
+}
+
+/// Text of the first `<tag>` inside the `<package>` element of a service.jsp response
+fn packmgr_package_field(response: &str, tag: &str) -> String {
+    let package = response
+        .split_once("<package>")
+        .and_then(|(_, rest)| rest.split_once("</package>"))
+        .map(|(package, _)| package)
+        .unwrap_or_default();
+    package
+        .split_once(&format!("<{}>", tag))
+        .and_then(|(_, rest)| rest.split_once(&format!("</{}>", tag)))
+        .map(|(value, _)| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Upload a package zip to service.jsp (and install it with `install`), streaming it from
+/// disk; `progress` reports the bytes sent. Returns the response XML.
+pub(crate) async fn post_package(
+    instance: &AemInstance,
+    credentials: &(String, String),
+    path: &Path,
+    install: bool,
+    force: bool,
+    mut progress: Option<ProgressReporter>,
+) -> Result<String, String> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let file_size = file
+        .metadata()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let boundary = format!("----aem-env-manager-{}", uuid::Uuid::new_v4().simple());
+    let (prefix, suffix) = multipart_frame(
+        &boundary,
+        ("file", "application/zip"),
+        &file_name,
+        &[("force", &force.to_string()), ("install", &install.to_string())],
+    );
+    let content_length = prefix.len() as u64 + file_size + suffix.len() as u64;
+    if let Some(ref mut progress) = progress {
+        progress.start(Some(file_size), 0);
+    }
+
+    let state = (file, Some(prefix), Some(suffix), progress);
+    let stream = futures_util::stream::try_unfold(state, |(mut file, prefix, suffix, mut progress)| async move {
+        if let Some(prefix) = prefix {
+            return Ok::<_, std::io::Error>(Some((prefix, (file, None, suffix, progress))));
+        }
+        let mut buffer = vec![0u8; UPLOAD_CHUNK_SIZE];
+        let read = file.read(&mut buffer).await?;
+        if read > 0 {
+            buffer.truncate(read);
+            if let Some(ref mut progress) = progress {
+                progress.advance(read as u64);
+            }
+            return Ok(Some((buffer, (file, None, suffix, progress))));
+        }
+        match suffix {
+            Some(suffix) => {
+                if let Some(ref mut progress) = progress {
+                    progress.finish();
+                }
+                Ok(Some((suffix, (file, None, None, progress))))
+            }
+            None => Ok(None),
+        }
+    });
+
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(PACKAGE_UPLOAD_TIMEOUT))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(format!("{}/crx/packmgr/service.jsp", instance.base_url()))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .header("Content-Type", format!("multipart/form-data; boundary={}", boundary))
+        .header("Content-Length", content_length)
+        .body(reqwest::Body::wrap_stream(stream))
+        .send()
+        .await
+        .map_err(|e| format!("Package upload failed: {}", e))?;
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("Package Manager returned HTTP {}", status.as_u16()));
+    }
+    match packmgr_status(&text) {
+        Some((200, _)) => {
+            if install {
+                record_activity(&instance.id, ActivityKind::PackageInstalled, format!("Installed {}", file_name));
+            }
+            Ok(text)
+        }
+        Some((code, message)) => Err(format!("Package Manager failed ({}): {}", code, message)),
+        None => Err("Package Manager sent an unexpected response".to_string()),
+    }
+}
+
+async fn upload(
+    app: &AppHandle,
+    instance_id: &str,
+    package_path: &str,
+    install: bool,
+    force: bool,
+    credential: Option<&str>,
+    operation_id: Option<String>,
+) -> Result<PackageUploadResult, String> {
+    let (instance, credentials) = connect_as(instance_id, credential)?;
+    let path = Path::new(package_path);
+    let label = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let progress = ProgressReporter::new(app, operation_id, &label);
+
+    let log = post_package(&instance, &credentials, path, install, force, Some(progress)).await?;
+    Ok(PackageUploadResult {
+        group: packmgr_package_field(&log, "group"),
+        name: packmgr_package_field(&log, "name"),
+        version: packmgr_package_field(&log, "version"),
+        installed: install,
+        log,
+    })
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Packages in the Package Manager of a running instance, by group and name. Package
+/// commands authenticate with the named `credential` if given, else the default one.
+#[command]
+pub async fn list_packages(instance_id: String, credential: Option<String>) -> Result<Vec<PackageInfo>, String> {
+    let (instance, credentials) = connect_as(&instance_id, credential.as_deref())?;
+    fetch_packages(&instance, &credentials).await
+}
+
+/// Upload a local package zip without installing it. `force` replaces an uploaded package
+/// of the same name; progress is emitted as `TRANSFER_PROGRESS_EVENT` under `operation_id`.
+#[command]
+pub async fn upload_package(
+    app: AppHandle,
+    instance_id: String,
+    package_path: String,
+    force: Option<bool>,
+    credential: Option<String>,
+    operation_id: Option<String>,
+) -> Result<PackageUploadResult, String> {
+    let force = force.unwrap_or(false);
+    upload(&app, &instance_id, &package_path, false, force, credential.as_deref(), operation_id).await
+}
+
+/// Upload a local package zip and install it, replacing an uploaded package of the same name
+#[command]
+pub async fn install_package(
+    app: AppHandle,
+    instance_id: String,
+    package_path: String,
+    credential: Option<String>,
+    operation_id: Option<String>,
+) -> Result<PackageUploadResult, String> {
+    upload(&app, &instance_id, &package_path, true, true, credential.as_deref(), operation_id).await
+}
+
+/// Build a package already defined on the instance, e.g. /etc/packages/my_packages/site-content-1.0.zip,
+/// and return it as listed afterwards
+#[command]
+pub async fn build_package(
+    instance_id: String,
+    package_path: String,
+    credential: Option<String>,
+) -> Result<PackageInfo, String> {
+    let package_path = normalize_package_path(&package_path)?;
+    let (instance, credentials) = connect_as(&instance_id, credential.as_deref())?;
+    build_package_at(&instance, &credentials, &package_path).await?;
+    fetch_packages(&instance, &credentials)
+        .await?
+        .into_iter()
+        .find(|p| p.path == package_path)
+        .ok_or_else(|| format!("{} is no longer listed after the build", package_path))
+}
+
+/// Save a built package zip into `target_dir`, emitting transfer progress under `operation_id`
+#[command]
+pub async fn download_package(
+    app: AppHandle,
+    instance_id: String,
+    package_path: String,
+    target_dir: String,
+    credential: Option<String>,
+    operation_id: Option<String>,
+) -> Result<PackageDownloadResult, String> {
+    let package_path = normalize_package_path(&package_path)?;
+    let (instance, credentials) = connect_as(&instance_id, credential.as_deref())?;
+    let progress = ProgressReporter::new(&app, operation_id, &package_path);
+    let target = download_package_to(&instance, &credentials, &package_path, Path::new(&target_dir), Some(progress))
+        .await?;
+    Ok(PackageDownloadResult {
+        output_path: target.to_string_lossy().to_string(),
+        size: std::fs::metadata(&target).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_package_list() {
+        let json = serde_json::json!({
+            "results": [
+                {
+                    "pid": "my_packages:site-content:1.0",
+                    "path": "/etc/packages/my_packages/site-content-1.0.zip",
+                    "name": "site-content",
+                    "downloadName": "site-content-1.0.zip",
+                    "group": "my_packages",
+                    "version": "1.0",
+                    "size": 20480,
+                    "lastModified": 1717236000000i64,
+                    "lastUnpacked": 1717239600000i64,
+                    "lastUnpackedBy": "admin"
+                },
+                {
+                    "path": "/etc/packages/adobe/aem-service-pkg-6.5.21.0.zip",
+                    "name": "aem-service-pkg",
+                    "group": "adobe/cq650/servicepack",
+                    "version": "6.5.21.0",
+                    "size": 700000000,
+                    "lastUnpackedBy": "null"
+                }
+            ],
+            "total": 2
+        });
+        let packages = parse_package_list(&json);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "aem-service-pkg");
+        assert_eq!(packages[0].installed_at, None);
+        assert_eq!(packages[0].installed_by, None);
+        assert_eq!(packages[1].installed_at.as_deref(), Some("2024-06-01T11:00:00+00:00"));
+        assert_eq!(packages[1].installed_by.as_deref(), Some("admin"));
+    }
+
+    #[test]
+    fn test_packmgr_response() {
+        let xml = r#"<crx version="1.6.0" user="admin"><response><data><package>
+<group>my_packages</group><name>site-content</name><version>1.0</version>
+<downloadName>site-content-1.0.zip</downloadName></package><log>Import done</log></data>
+<status code="200">ok</status></response></crx>"#;
+        assert_eq!(packmgr_status(xml), Some((200, "ok".to_string())));
+        assert_eq!(packmgr_package_field(xml, "name"), "site-content");
+        assert_eq!(packmgr_package_field(xml, "version"), "1.0");
+        assert_eq!(packmgr_package_field("<crx/>", "group"), "");
+
+        let (prefix, suffix) = multipart_frame("b", ("file", "application/zip"), "site.zip", &[("install", "true")]);
+        let prefix = String::from_utf8(prefix).unwrap();
+        assert!(prefix.starts_with("--b\r\nContent-Disposition: form-data; name=\"install\"\r\n\r\ntrue\r\n"));
+        assert!(prefix.ends_with("filename=\"site.zip\"\r\nContent-Type: application/zip\r\n\r\n"));
+        assert_eq!(suffix, b"\r\n--b--\r\n");
+
+        assert_eq!(
+            packmgr_json_result(&serde_json::json!({"success": true, "msg": "Package built"})),
+            Ok("Package built".to_string())
+        );
+        assert!(packmgr_json_result(&serde_json::json!({"success": false, "msg": "no such package"})).is_err());
+        assert_eq!(
+            normalize_package_path("etc/packages/my_packages/site-1.0.zip/").as_deref(),
+            Ok("/etc/packages/my_packages/site-1.0.zip")
+        );
+        assert!(normalize_package_path("/etc/packages/../../apps/x.zip").is_err());
+        assert!(normalize_package_path("/content/site").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+// Port Planning Commands
+// Assigns each workspace (project) its own slot of author/publish/dispatcher/debug ports
+// following a recorded convention, e.g. 4502/4503 for the first and 4512/4513 for the second
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+use crate::commands::instance::{check_port_open, load_instances};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+/// Ports of slot n are the base ports plus n * `step`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortConvention {
+    pub author_base: u16,
+    pub publish_base: u16,
+    pub dispatcher_base: u16,
+    /// Debug port of the author; the publish uses the next port
+    pub debug_base: u16,
+    pub step: u16,
+}
+
+impl Default for PortConvention {
+    fn default() -> Self {
+        Self {
+            author_base: 4502,
+            publish_base: 4503,
+            dispatcher_base: 8080,
+            debug_base: 5005,
+            step: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortSet {
+    pub author: u16,
+    pub publish: u16,
+    pub dispatcher: u16,
+    pub author_debug: u16,
+    pub publish_debug: u16,
+}
+
+impl PortSet {
+    fn ports(&self) -> [u16; 5] {
+        [self.author, self.publish, self.dispatcher, self.author_debug, self.publish_debug]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortAllocation {
+    /// Project id or any other name the instances are grouped by
+    pub workspace: String,
+    pub slot: u16,
+    pub ports: PortSet,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PortPlanFile {
+    #[serde(default)]
+    convention: PortConvention,
+    #[serde(default)]
+    allocations: Vec<PortAllocation>,
+}
+
+/// Slots tried before giving up
+const MAX_SLOTS: u16 = 100;
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_port_plan_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("port_allocations.json")
+}
+
+fn load_plan() -> Result<PortPlanFile, String> {
+    let file_path = get_port_plan_file();
+    if !file_path.exists() {
+        return Ok(PortPlanFile::default());
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read port allocations: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse port allocations: {}", e))
+}
+
+fn save_plan(plan: &PortPlanFile) -> Result<(), String> {
+    let file_path = get_port_plan_file();
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(plan).map_err(|e| format!("Failed to serialize port allocations: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write port allocations: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Ports of a slot, or None when they would overflow
+fn slot_ports(convention: &PortConvention, slot: u16) -> Option<PortSet> {
+    let offset = slot.checked_mul(convention.step)?;
+    Some(PortSet {
+        author: convention.author_base.checked_add(offset)?,
+        publish: convention.publish_base.checked_add(offset)?,
+        dispatcher: convention.dispatcher_base.checked_add(offset)?,
+        author_debug: convention.debug_base.checked_add(offset)?,
+        publish_debug: convention.debug_base.checked_add(offset)?.checked_add(1)?,
+    })
+}
+
+/// First slot not recorded for another workspace whose ports are all free
+fn first_free_slot(
+    convention: &PortConvention,
+    allocations: &[PortAllocation],
+    is_taken: impl Fn(u16) -> bool,
+) -> Option<(u16, PortSet)> {
+    (0..MAX_SLOTS)
+        .filter(|slot| allocations.iter().all(|a| a.slot != *slot))
+        .filter_map(|slot| slot_ports(convention, slot).map(|ports| (slot, ports)))
+        .find(|(_, ports)| ports.ports().iter().all(|port| !is_taken(*port)))
+}
+
+fn validate_convention(convention: &PortConvention) -> Result<(), String> {
+    if convention.step < 2 {
+        return Err("The port step must be at least 2".to_string());
+    }
+    let first = slot_ports(convention, 0).ok_or("Base ports are out of range")?;
+    let mut seen = HashSet::new();
+    if !first.ports().iter().all(|port| seen.insert(*port)) {
+        return Err("Base ports of a slot must all be different".to_string());
+    }
+    Ok(())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+#[command]
+pub async fn get_port_convention() -> Result<PortConvention, String> {
+    Ok(load_plan()?.convention)
+}
+
+/// Change the convention; existing allocations keep their ports
+#[command]
+pub async fn set_port_convention(convention: PortConvention) -> Result<PortConvention, String> {
+    validate_convention(&convention)?;
+    let mut plan = load_plan()?;
+    plan.convention = convention.clone();
+    save_plan(&plan)?;
+    Ok(convention)
+}
+
+/// Recorded allocations by slot
+#[command]
+pub async fn list_port_allocations() -> Result<Vec<PortAllocation>, String> {
+    let mut allocations = load_plan()?.allocations;
+    allocations.sort_by_key(|a| a.slot);
+    Ok(allocations)
+}
+
+/// Ports of a workspace: its recorded allocation, or the next slot whose ports are not used by
+/// a registered instance or another process. The new slot is recorded unless `dry_run`.
+#[command]
+pub async fn allocate_workspace_ports(workspace: String, dry_run: Option<bool>) -> Result<PortAllocation, String> {
+    let workspace = workspace.trim().to_string();
+    if workspace.is_empty() {
+        return Err("Workspace name is required".to_string());
+    }
+    let mut plan = load_plan()?;
+    if let Some(existing) = plan.allocations.iter().find(|a| a.workspace == workspace) {
+        return Ok(existing.clone());
+    }
+
+    // Ports of other slots count too, in case a convention makes slots overlap
+    let mut reserved: HashSet<u16> = load_instances()?
+        .iter()
+        .flat_map(|i| [Some(i.port), i.debug_port])
+        .flatten()
+        .collect();
+    reserved.extend(plan.allocations.iter().flat_map(|a| a.ports.ports()));
+    let is_taken = |port: u16| reserved.contains(&port) || check_port_open("127.0.0.1", port, 100);
+    let (slot, ports) = first_free_slot(&plan.convention, &plan.allocations, is_taken)
+        .ok_or_else(|| format!("No free port slot within the first {} slots", MAX_SLOTS))?;
+
+    let allocation = PortAllocation {
+        workspace,
+        slot,
+        ports,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if !dry_run.unwrap_or(false) {
+        plan.allocations.push(allocation.clone());
+        save_plan(&plan)?;
+    }
+    Ok(allocation)
+}
+
+/// Forget a workspace's allocation so its slot can be reused
+#[command]
+pub async fn release_workspace_ports(workspace: String) -> Result<bool, String> {
+    let mut plan = load_plan()?;
+    let before = plan.allocations.len();
+    plan.allocations.retain(|a| a.workspace != workspace);
+    if plan.allocations.len() == before {
+        return Ok(false);
+    }
+    save_plan(&plan)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_free_slot() {
+        let convention = PortConvention::default();
+        let taken = PortAllocation {
+            workspace: "site-a".to_string(),
+            slot: 0,
+            ports: slot_ports(&convention, 0).unwrap(),
+            created_at: String::new(),
+        };
+
+        // Slot 0 is recorded, slot 1 has its publish port in use
+        let (slot, ports) = first_free_slot(&convention, &[taken], |port| port == 4513).unwrap();
+        assert_eq!(slot, 2);
+        assert_eq!(ports.ports(), [4522, 4523, 8100, 5025, 5026]);
+
+        assert!(validate_convention(&convention).is_ok());
+        let clashing = PortConvention {
+            publish_base: 4502,
+            ..PortConvention::default()
+        };
+        assert!(validate_convention(&clashing).is_err());
+    }
+}
@@ -3,6 +3,12 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use tauri::command;
 
+use crate::commands::credential_store::{read_secret, write_secret};
+use crate::commands::instance::CredentialsPolicy;
+use crate::commands::trash::{move_to_trash, TrashKind};
+use crate::commands::updates::UpdatePolicy;
+use crate::commands::version::{java_policy_violation, JavaPolicy, JavaVersion};
+use crate::i18n::{Entity, LocalizedText, Message, MessageCode};
 use crate::platform::PlatformOps;
 
 // ============================================
@@ -15,6 +21,11 @@ pub struct EnvironmentProfile {
     pub id: String,
     pub name: String,
     pub description: Option<String>,
+    // Localized display name/description (zh/en)
+    #[serde(default)]
+    pub display_name: Option<LocalizedText>,
+    #[serde(default)]
+    pub localized_description: Option<LocalizedText>,
     // Java configuration
     pub java_version: Option<String>,
     pub java_manager_id: Option<String>,
@@ -30,6 +41,9 @@ pub struct EnvironmentProfile {
     pub publish_instance_id: Option<String>,
     // Custom environment variables
     pub env_vars: Option<HashMap<String, String>>,
+    // Adobe I/O CLI config context (e.g. cloudmanager_orgid), applied with `aio config set`
+    #[serde(default)]
+    pub aio_config: Option<HashMap<String, String>>,
     // Timestamps
     #[serde(default)]
     pub created_at: String,
@@ -45,11 +59,16 @@ pub struct ProfileSwitchResult {
     pub success: bool,
     pub profile_id: String,
     pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
     pub error: Option<String>,
     pub java_switched: bool,
     pub node_switched: bool,
     pub maven_switched: bool,
     pub env_vars_set: bool,
+    /// Non-fatal issues, e.g. the selected JDK violates the vendor policy
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +91,113 @@ pub struct AppConfig {
     pub start_minimized: bool,
     pub show_notifications: bool,
     pub log_level: String,
+    /// UI language tag (en, zh-CN, zh-TW) used for backend messages
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// What to do when an instance has no stored credentials
+    #[serde(default)]
+    pub credentials_policy: CredentialsPolicy,
+    /// Account used by the `default_account` credentials policy
+    #[serde(default = "default_account")]
+    pub default_username: String,
+    /// Password of the default account as sent by the settings page or left in config files of
+    /// earlier versions; it is moved into the credential store and never written to config.json
+    #[serde(default, skip_serializing)]
+    pub default_password: Option<String>,
+    /// Credential store account holding the default password; "admin" is used when unset
+    #[serde(default)]
+    pub default_password_account: Option<String>,
+    /// Hours of instance metrics history to keep
+    #[serde(default = "default_metrics_retention_hours")]
+    pub metrics_retention_hours: u32,
+    /// Rotated instance logs older than this are archived by maintenance
+    #[serde(default = "default_log_retention_days")]
+    pub log_retention_days: u32,
+    /// Run maintenance automatically every N hours (disabled when unset)
+    #[serde(default)]
+    pub maintenance_interval_hours: Option<u32>,
+    /// Preferred JDK vendors and minimum version
+    #[serde(default)]
+    pub java_policy: JavaPolicy,
+    /// Relocated data directory (instances, backups, downloads); default location when unset
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    /// Download cache size limit; least recently used files are evicted beyond it
+    #[serde(default = "default_download_cache_limit_mb")]
+    pub download_cache_limit_mb: u64,
+    /// Update channel, deferral and staged rollout bucket
+    #[serde(default)]
+    pub update_policy: UpdatePolicy,
+    /// Directory for the nightly configuration export (disabled when unset)
+    #[serde(default)]
+    pub auto_export_dir: Option<String>,
+    /// Local hour (0-23) after which the nightly export runs
+    #[serde(default = "default_auto_export_hour")]
+    pub auto_export_hour: u32,
+    /// Number of automatic exports to keep
+    #[serde(default = "default_auto_export_keep")]
+    pub auto_export_keep: usize,
+    /// Days deleted profiles, Maven configs and instances stay in the trash
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Seconds to wait for an instance to shut down via its stop script before killing it
+    #[serde(default = "default_stop_timeout_secs")]
+    pub stop_timeout_secs: u64,
+    /// Days before expiry at which licenses are reminded of (none when empty)
+    #[serde(default = "default_license_reminder_days")]
+    pub license_reminder_days: Vec<u32>,
+    /// Store instance credentials in the OS keychain; when off (or without a usable keychain)
+    /// they go to the encrypted credentials file. Applies after a restart; entries already in
+    /// the keychain are not moved.
+    #[serde(default = "default_true")]
+    pub use_os_keychain: bool,
+}
+
+fn default_language() -> String {
+    "en".to_string()
+}
+
+fn default_account() -> String {
+    "admin".to_string()
+}
+
+/// Credential store account of the default account password
+pub(crate) const DEFAULT_PASSWORD_ACCOUNT: &str = "app:default-password";
+
+fn default_metrics_retention_hours() -> u32 {
+    24
+}
+
+fn default_log_retention_days() -> u32 {
+    7
+}
+
+fn default_download_cache_limit_mb() -> u64 {
+    10 * 1024
+}
+
+fn default_auto_export_hour() -> u32 {
+    2
+}
+
+fn default_auto_export_keep() -> usize {
+    7
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_stop_timeout_secs() -> u64 {
+    120
+}
+
+fn default_license_reminder_days() -> Vec<u32> {
+    vec![30, 14, 7, 1]
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for AppConfig {
@@ -84,6 +210,25 @@ impl Default for AppConfig {
             start_minimized: false,
             show_notifications: true,
             log_level: "info".to_string(),
+            language: default_language(),
+            credentials_policy: CredentialsPolicy::default(),
+            default_username: default_account(),
+            default_password: None,
+            default_password_account: None,
+            metrics_retention_hours: default_metrics_retention_hours(),
+            log_retention_days: default_log_retention_days(),
+            maintenance_interval_hours: None,
+            java_policy: JavaPolicy::default(),
+            data_dir: None,
+            download_cache_limit_mb: default_download_cache_limit_mb(),
+            update_policy: UpdatePolicy::default(),
+            auto_export_dir: None,
+            auto_export_hour: default_auto_export_hour(),
+            auto_export_keep: default_auto_export_keep(),
+            trash_retention_days: default_trash_retention_days(),
+            stop_timeout_secs: default_stop_timeout_secs(),
+            license_reminder_days: default_license_reminder_days(),
+            use_os_keychain: true,
         }
     }
 }
@@ -92,6 +237,15 @@ impl Default for AppConfig {
 // Storage Helpers
 // ============================================
 
+/// Overwrite top-level fields of `stored` with those present in `patch`
+pub(crate) fn merge_json_object(stored: &mut serde_json::Value, patch: &serde_json::Value) {
+    if let (Some(stored), Some(patch)) = (stored.as_object_mut(), patch.as_object()) {
+        for (key, value) in patch {
+            stored.insert(key.clone(), value.clone());
+        }
+    }
+}
+
 fn get_profiles_dir() -> PathBuf {
     let platform = crate::platform::current_platform();
     platform.get_data_dir().join("profiles")
@@ -110,11 +264,25 @@ fn ensure_profiles_dir() -> Result<(), String> {
     Ok(())
 }
 
+/// IDs of all stored profiles, from the profile file names
+pub(crate) fn profile_ids() -> Vec<String> {
+    std::fs::read_dir(get_profiles_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|e| e == "json"))
+                .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn get_profile_file(id: &str) -> PathBuf {
     get_profiles_dir().join(format!("{}.json", id))
 }
 
-fn load_profile_from_file(id: &str) -> Result<Option<EnvironmentProfile>, String> {
+pub(crate) fn load_profile_from_file(id: &str) -> Result<Option<EnvironmentProfile>, String> {
     let file_path = get_profile_file(id);
     if !file_path.exists() {
         return Ok(None);
@@ -140,10 +308,17 @@ fn save_profile_to_file(profile: &EnvironmentProfile) -> Result<(), String> {
     std::fs::write(&file_path, content).map_err(|e| format!("Failed to write profile: {}", e))
 }
 
+/// Move the profile file to the trash so it can be restored
 fn delete_profile_file(id: &str) -> Result<(), String> {
     let file_path = get_profile_file(id);
     if file_path.exists() {
-        std::fs::remove_file(&file_path).map_err(|e| format!("Failed to delete profile: {}", e))?;
+        let label = load_profile_from_file(id)
+            .ok()
+            .flatten()
+            .map(|p| p.name)
+            .unwrap_or_else(|| id.to_string());
+        move_to_trash(TrashKind::Profile, &label, Some(&file_path), None)
+            .map_err(|e| format!("Failed to delete profile: {}", e))?;
     }
     Ok(())
 }
@@ -160,7 +335,33 @@ fn load_config() -> Result<AppConfig, String> {
     serde_json::from_str(&content).map_err(|e| format!("Failed to parse config: {}", e))
 }
 
+/// Move a plaintext default password into the credential store and reference it instead
+fn store_default_password(config: &mut AppConfig) -> Result<(), String> {
+    if let Some(password) = config.default_password.take() {
+        write_secret(DEFAULT_PASSWORD_ACCOUNT, &password)?;
+        config.default_password_account = Some(DEFAULT_PASSWORD_ACCOUNT.to_string());
+    }
+    Ok(())
+}
+
+/// Password of the default account used by the `default_account` credentials policy; a
+/// plaintext password left by an earlier version is moved into the credential store first
+pub(crate) fn default_account_password() -> Result<String, String> {
+    let mut config = load_config()?;
+    if config.default_password.is_some() {
+        store_default_password(&mut config)?;
+        save_config(&config)?;
+    }
+    match &config.default_password_account {
+        Some(account) => Ok(read_secret(account)?.unwrap_or_else(default_account)),
+        None => Ok(default_account()),
+    }
+}
+
 fn save_config(config: &AppConfig) -> Result<(), String> {
+    let mut config = config.clone();
+    store_default_password(&mut config)?;
+
     let file_path = get_config_file();
 
     // Ensure parent directory exists
@@ -171,7 +372,7 @@ fn save_config(config: &AppConfig) -> Result<(), String> {
     }
 
     let content =
-        serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+        serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
 
     std::fs::write(&file_path, content).map_err(|e| format!("Failed to write config: {}", e))
 }
@@ -265,7 +466,7 @@ pub async fn create_profile(mut profile: EnvironmentProfile) -> Result<Environme
 #[command]
 pub async fn update_profile(id: String, mut profile: EnvironmentProfile) -> Result<EnvironmentProfile, String> {
     // Verify profile exists
-    let existing = load_profile_from_file(&id)?.ok_or_else(|| format!("Profile {} not found", id))?;
+    let existing = load_profile_from_file(&id)?.ok_or_else(|| Message::NotFound(Entity::Profile, &id).localized())?;
 
     // Preserve original ID and created_at
     profile.id = id;
@@ -290,6 +491,7 @@ pub async fn delete_profile(id: String) -> Result<bool, String> {
     }
 
     delete_profile_file(&id)?;
+    crate::commands::registry::delete_registry_credentials(&id)?;
     Ok(true)
 }
 
@@ -297,25 +499,53 @@ pub async fn delete_profile(id: String) -> Result<bool, String> {
 // Profile Switching
 // ============================================
 
+/// Resolve the JDK a profile selects (by path, else by version)
+pub(crate) async fn resolve_profile_java(profile: &EnvironmentProfile) -> Option<JavaVersion> {
+    if let Some(path) = profile.java_path.as_ref().filter(|p| !p.is_empty()) {
+        return crate::commands::version::validate_java_path(path.clone()).await.ok();
+    }
+
+    let version = profile.java_version.as_ref().filter(|v| !v.is_empty())?;
+    crate::commands::version::scan_java_versions()
+        .await
+        .ok()?
+        .into_iter()
+        .find(|v| v.version == *version)
+}
+
+/// Warning when the profile's JDK violates the configured Java policy
+async fn profile_java_policy_warning(profile: &EnvironmentProfile) -> Option<String> {
+    let java = resolve_profile_java(profile).await?;
+    let policy = get_startup_config().java_policy;
+    java_policy_violation(&java, &policy).map(|note| format!("Java policy: {}", note))
+}
+
 /// Switch to a different environment profile
 /// This updates symlinks for Java and Node to enable system-wide version switching
 #[command]
 pub async fn switch_profile(profile_id: String) -> Result<ProfileSwitchResult, String> {
     // Load profile
-    let profile = load_profile_from_file(&profile_id)?.ok_or_else(|| format!("Profile {} not found", profile_id))?;
+    let profile = load_profile_from_file(&profile_id)?.ok_or_else(|| Message::NotFound(Entity::Profile, &profile_id).localized())?;
 
     let mut result = ProfileSwitchResult {
         success: true,
         profile_id: profile_id.clone(),
         message: None,
+        message_code: None,
         error: None,
         java_switched: false,
         node_switched: false,
         maven_switched: false,
         env_vars_set: false,
+        warnings: Vec::new(),
     };
 
     let mut errors = Vec::new();
+    crate::commands::environment::snapshot_before("switch-profile");
+
+    if let Some(warning) = profile_java_policy_warning(&profile).await {
+        result.warnings.push(warning);
+    }
 
     // Switch Java version using symlink (if path is specified)
     if let Some(ref java_path) = profile.java_path {
@@ -353,6 +583,15 @@ pub async fn switch_profile(profile_id: String) -> Result<ProfileSwitchResult, S
         }
     }
 
+    // Remember the global npm packages of the Node version being switched away from
+    let switches_node = profile.node_path.as_deref().is_some_and(|p| !p.is_empty())
+        || profile.node_version.as_deref().is_some_and(|v| !v.is_empty());
+    let previous_globals = if switches_node {
+        crate::commands::node_globals::capture_global_packages().await.ok()
+    } else {
+        None
+    };
+
     // Switch Node version using symlink (if path is specified)
     if let Some(ref node_path) = profile.node_path {
         if !node_path.is_empty() {
@@ -392,6 +631,21 @@ pub async fn switch_profile(profile_id: String) -> Result<ProfileSwitchResult, S
         }
     }
 
+    // Tell which CLI tools the new Node version lacks; restore_global_packages installs them
+    if let Some(previous) = previous_globals.filter(|_| result.node_switched) {
+        let current = crate::commands::version::get_current_node_version().await.ok().flatten();
+        if current.is_some_and(|v| v.trim() != previous.node_version.trim()) {
+            let missing = crate::commands::node_globals::missing_global_packages(&previous).unwrap_or_default();
+            if !missing.is_empty() {
+                result.warnings.push(format!(
+                    "Global npm packages from Node {} are not installed: {}",
+                    previous.node_version,
+                    missing.join(", ")
+                ));
+            }
+        }
+    }
+
     // Switch Maven config
     if let Some(ref maven_id) = profile.maven_config_id {
         match crate::commands::version::switch_maven_config(maven_id.clone()).await {
@@ -404,6 +658,12 @@ pub async fn switch_profile(profile_id: String) -> Result<ProfileSwitchResult, S
         }
     }
 
+    // Swap npm registry and Maven server credentials (after settings.xml was replaced above)
+    match crate::commands::registry::apply_profile_registry(&profile_id) {
+        Ok(sync) => result.warnings.extend(sync.warnings),
+        Err(e) => result.warnings.push(format!("Registry credentials: {}", e)),
+    }
+
     // Set environment variables
     if let Some(ref env_vars) = profile.env_vars {
         let platform = crate::platform::current_platform();
@@ -417,6 +677,13 @@ pub async fn switch_profile(profile_id: String) -> Result<ProfileSwitchResult, S
         }
     }
 
+    // Point the aio CLI at the profile's org/program/environment
+    if let Some(ref aio_config) = profile.aio_config {
+        if let Err(e) = crate::commands::aio::apply_aio_config(aio_config) {
+            result.warnings.push(format!("aio config: {}", e));
+        }
+    }
+
     // Update profile last_used_at
     let mut updated_profile = profile;
     updated_profile.last_used_at = Some(chrono::Utc::now().to_rfc3339());
@@ -425,16 +692,26 @@ pub async fn switch_profile(profile_id: String) -> Result<ProfileSwitchResult, S
 
     // Update config to track active profile
     let mut config = load_config()?;
-    config.active_profile_id = Some(profile_id);
+    config.active_profile_id = Some(profile_id.clone());
     save_config(&config)?;
 
+    crate::commands::automation::trigger_automation(crate::commands::automation::AutomationEvent::ProfileSwitched {
+        profile_id,
+    });
+
     // Set result status
     if errors.is_empty() {
         result.message = Some("Profile switched successfully".to_string());
+        result.message_code = Some(MessageCode::new("profile_switched").with("name", &updated_profile.name));
     } else {
         result.success = false;
         result.error = Some(errors.join("; "));
         result.message = Some("Profile switch completed with errors".to_string());
+        result.message_code = Some(
+            MessageCode::new("profile_switched_with_errors")
+                .with("name", &updated_profile.name)
+                .with("errors", errors.join("; ")),
+        );
     }
 
     Ok(result)
@@ -458,7 +735,7 @@ pub async fn get_active_profile() -> Result<Option<EnvironmentProfile>, String>
 /// Validate a profile before switching
 #[command]
 pub async fn validate_profile(profile_id: String) -> Result<ProfileValidationResult, String> {
-    let profile = load_profile_from_file(&profile_id)?.ok_or_else(|| format!("Profile {} not found", profile_id))?;
+    let profile = load_profile_from_file(&profile_id)?.ok_or_else(|| Message::NotFound(Entity::Profile, &profile_id).localized())?;
 
     let mut result = ProfileValidationResult {
         is_valid: true,
@@ -487,6 +764,10 @@ pub async fn validate_profile(profile_id: String) -> Result<ProfileValidationRes
         result.warnings.push("No Java version specified".to_string());
     }
 
+    if let Some(warning) = profile_java_policy_warning(&profile).await {
+        result.warnings.push(warning);
+    }
+
     // Check Node version
     if let Some(ref node_version) = profile.node_version {
         if !node_version.is_empty() {
@@ -554,8 +835,20 @@ pub async fn load_app_config() -> Result<AppConfig, String> {
 }
 
 /// Save application configuration
+/// Fields missing from the payload keep their stored values, so settings the
+/// frontend does not know about are not reset on every save
 #[command]
-pub async fn save_app_config(config: AppConfig) -> Result<(), String> {
+pub async fn save_app_config(config: serde_json::Value) -> Result<(), String> {
+    let mut stored = load_config()?;
+    store_default_password(&mut stored)?;
+    let mut merged =
+        serde_json::to_value(stored).map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    merge_json_object(&mut merged, &config);
+
+    let config: AppConfig =
+        serde_json::from_value(merged).map_err(|e| format!("Failed to parse config: {}", e))?;
+
     save_config(&config)
 }
 
@@ -568,7 +861,7 @@ pub fn get_startup_config() -> AppConfig {
 /// Export profile to JSON
 #[command]
 pub async fn export_profile(profile_id: String) -> Result<String, String> {
-    let profile = load_profile_from_file(&profile_id)?.ok_or_else(|| format!("Profile {} not found", profile_id))?;
+    let profile = load_profile_from_file(&profile_id)?.ok_or_else(|| Message::NotFound(Entity::Profile, &profile_id).localized())?;
 
     serde_json::to_string_pretty(&profile).map_err(|e| format!("Failed to export profile: {}", e))
 }
@@ -599,7 +892,7 @@ pub async fn import_profile(json_content: String) -> Result<EnvironmentProfile,
 /// Duplicate a profile
 #[command]
 pub async fn duplicate_profile(profile_id: String) -> Result<EnvironmentProfile, String> {
-    let source = load_profile_from_file(&profile_id)?.ok_or_else(|| format!("Profile {} not found", profile_id))?;
+    let source = load_profile_from_file(&profile_id)?.ok_or_else(|| Message::NotFound(Entity::Profile, &profile_id).localized())?;
 
     let mut new_profile = source.clone();
     new_profile.id = uuid::Uuid::new_v4().to_string();
@@ -627,5 +920,27 @@ mod tests {
         assert_eq!(config.theme, "system");
         assert_eq!(config.health_check_interval, 30);
         assert!(!config.auto_switch_profile);
+        assert_eq!(config.credentials_policy, CredentialsPolicy::DefaultAccount);
+        assert_eq!(config.default_username, "admin");
+    }
+
+    #[test]
+    fn test_default_password_not_serialized() {
+        let config: AppConfig = serde_json::from_value(serde_json::json!({
+            "active_profile_id": null,
+            "theme": "system",
+            "auto_switch_profile": false,
+            "health_check_interval": 30,
+            "start_minimized": false,
+            "show_notifications": true,
+            "log_level": "info",
+            "default_password": "secret"
+        }))
+        .unwrap();
+        assert_eq!(config.default_password.as_deref(), Some("secret"));
+
+        let written = serde_json::to_value(&config).unwrap();
+        assert!(written.get("default_password").is_none());
+        assert!(written["default_password_account"].is_null());
     }
 }
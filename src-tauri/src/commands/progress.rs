@@ -0,0 +1,142 @@
+// Transfer Progress
+// One progress event schema for long HTTP transfers (downloads, package exports), so the
+// UI can show byte counts, rate and remaining time the same way for all of them
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgress {
+    /// Chosen by the caller (or generated) to tell concurrent transfers apart
+    pub operation_id: String,
+    pub label: String,
+    /// None when the server does not send a length
+    pub total_bytes: Option<u64>,
+    pub done_bytes: u64,
+    /// Average since the transfer started
+    pub bytes_per_sec: u64,
+    pub eta_secs: Option<u64>,
+    pub finished: bool,
+    /// Set when the transfer failed; `finished` is true then as well
+    pub error: Option<String>,
+}
+
+/// Event emitted with a `TransferProgress` while a transfer runs, and once when it ends
+pub const TRANSFER_PROGRESS_EVENT: &str = "transfer-progress";
+
+/// Minimum time between two progress events of one transfer
+const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Average rate of `transferred` bytes over `elapsed`, and the seconds left for `remaining`
+fn rate_and_eta(transferred: u64, elapsed: Duration, remaining: Option<u64>) -> (u64, Option<u64>) {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 || transferred == 0 {
+        return (0, None);
+    }
+    let rate = transferred as f64 / secs;
+    (rate as u64, remaining.map(|r| (r as f64 / rate).ceil() as u64))
+}
+
+/// Emits throttled `TRANSFER_PROGRESS_EVENT`s for one transfer. A reporter dropped before
+/// `finish` or `fail` reports the transfer as failed, so the UI never waits on it forever.
+pub(crate) struct ProgressReporter {
+    app: AppHandle,
+    progress: TransferProgress,
+    started: Instant,
+    /// Bytes already present when the transfer started (resumed downloads)
+    resumed_bytes: u64,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressReporter {
+    /// Reporter for `operation_id`, or a generated id when none is given
+    pub(crate) fn new(app: &AppHandle, operation_id: Option<String>, label: &str) -> Self {
+        Self {
+            app: app.clone(),
+            progress: TransferProgress {
+                operation_id: operation_id
+                    .filter(|id| !id.is_empty())
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                label: label.to_string(),
+                total_bytes: None,
+                done_bytes: 0,
+                bytes_per_sec: 0,
+                eta_secs: None,
+                finished: false,
+                error: None,
+            },
+            started: Instant::now(),
+            resumed_bytes: 0,
+            last_emit: None,
+        }
+    }
+
+    /// Called once the size is known; `done_bytes` counts what an earlier attempt transferred
+    pub(crate) fn start(&mut self, total_bytes: Option<u64>, done_bytes: u64) {
+        self.progress.total_bytes = total_bytes;
+        self.progress.done_bytes = done_bytes;
+        self.resumed_bytes = done_bytes;
+        self.started = Instant::now();
+        self.emit();
+    }
+
+    pub(crate) fn advance(&mut self, bytes: u64) {
+        self.progress.done_bytes += bytes;
+        if self.last_emit.is_none_or(|t| t.elapsed() >= EMIT_INTERVAL) {
+            self.emit();
+        }
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.progress.finished = true;
+        self.emit();
+    }
+
+    pub(crate) fn fail(&mut self, error: &str) {
+        self.progress.finished = true;
+        self.progress.error = Some(error.to_string());
+        self.emit();
+    }
+
+    fn emit(&mut self) {
+        let progress = &mut self.progress;
+        let remaining = progress.total_bytes.map(|t| t.saturating_sub(progress.done_bytes));
+        let transferred = progress.done_bytes.saturating_sub(self.resumed_bytes);
+        (progress.bytes_per_sec, progress.eta_secs) = rate_and_eta(transferred, self.started.elapsed(), remaining);
+        if progress.finished {
+            progress.eta_secs = progress.error.is_none().then_some(0);
+        }
+        let _ = self.app.emit(TRANSFER_PROGRESS_EVENT, &*progress);
+        self.last_emit = Some(Instant::now());
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        if !self.progress.finished {
+            self.fail("Transfer did not complete");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_and_eta() {
+        assert_eq!(rate_and_eta(0, Duration::from_secs(1), Some(100)), (0, None));
+        assert_eq!(rate_and_eta(1000, Duration::from_secs(2), Some(1500)), (500, Some(3)));
+        assert_eq!(rate_and_eta(1000, Duration::from_secs(2), None), (500, None));
+        assert_eq!(rate_and_eta(300, Duration::from_secs(1), Some(100)), (300, Some(1)));
+    }
+}
@@ -0,0 +1,696 @@
+// AEM Project Registry Commands
+// Registers AEM Maven projects, reads their root pom and links them to profiles and instances,
+// pre-fetches their Maven dependencies and cleans up their build output
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::command;
+use walkdir::WalkDir;
+
+use crate::commands::instance::{load_instances, AemInstanceType};
+use crate::commands::profile::{get_profile, list_profiles, merge_json_object};
+use crate::commands::version::parse_maven_local_repository;
+use crate::platform::common::dir_size;
+use crate::i18n::{Entity, Message};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AemProject {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Directory containing the root pom.xml
+    pub path: String,
+    #[serde(default)]
+    pub pom: PomInfo,
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    #[serde(default)]
+    pub author_instance_id: Option<String>,
+    #[serde(default)]
+    pub publish_instance_id: Option<String>,
+    /// Profiles for checked-out branches, first match wins
+    #[serde(default)]
+    pub branch_profiles: Vec<BranchProfile>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+/// Profile to use while a branch matching `pattern` (e.g. "release/6.5*") is checked out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BranchProfile {
+    /// Branch name, `*` matches any characters
+    pub pattern: String,
+    pub profile_id: String,
+}
+
+/// What the root pom.xml says about the project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PomInfo {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    /// AEM Project Archetype version the project was generated from
+    pub archetype_version: Option<String>,
+    pub modules: Vec<String>,
+    pub aem_host: Option<String>,
+    pub aem_port: Option<u16>,
+    pub aem_publish_host: Option<String>,
+    pub aem_publish_port: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyPrefetchResult {
+    pub project_id: String,
+    /// settings.xml passed to Maven; None when Maven's own defaults applied
+    pub settings_path: Option<String>,
+    pub local_repository: String,
+    pub success: bool,
+    pub duration_secs: u64,
+    pub cache_size_before: u64,
+    pub cache_size_after: u64,
+    /// Bytes added to the local repository by the run
+    pub cache_size_delta: i64,
+    /// Last lines of Maven's output
+    pub output_tail: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildDirKind {
+    /// Maven `target/` next to a pom.xml
+    Target,
+    /// `node_modules/` next to a package.json
+    NodeModules,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildDirEntry {
+    pub project_id: String,
+    pub project_name: String,
+    pub path: String,
+    pub kind: BuildDirKind,
+    pub size_bytes: u64,
+    /// Last modification of the directory itself, roughly the last build
+    pub modified_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuildDirCleanupResult {
+    pub deleted: Vec<String>,
+    pub bytes_reclaimed: u64,
+    pub errors: Vec<String>,
+}
+
+/// Default limit for `mvn dependency:go-offline`
+const PREFETCH_TIMEOUT_SECS: u64 = 30 * 60;
+
+/// Lines of Maven output kept in the result
+const OUTPUT_TAIL_LINES: usize = 30;
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_projects_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("projects.json")
+}
+
+pub(crate) fn load_projects() -> Result<Vec<AemProject>, String> {
+    let file_path = get_projects_file();
+    if !file_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content =
+        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read projects: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse projects: {}", e))
+}
+
+fn save_projects(projects: &[AemProject]) -> Result<(), String> {
+    let file_path = get_projects_file();
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(projects)
+        .map_err(|e| format!("Failed to serialize projects: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write projects: {}", e))
+}
+
+// ============================================
+// POM Introspection
+// ============================================
+
+/// Remove every `<tag>...</tag>` block (non-nested tags only)
+fn strip_blocks(xml: &str, tag: &str) -> String {
+    let re = Regex::new(&format!(r"(?s)<{tag}(\s[^>]*)?>.*?</{tag}>", tag = regex::escape(tag)))
+        .expect("valid regex");
+    re.replace_all(xml, "").to_string()
+}
+
+/// Text of the first `<tag>` element
+fn element_text(xml: &str, tag: &str) -> Option<String> {
+    let re = Regex::new(&format!(r"(?s)<{tag}>\s*(.*?)\s*</{tag}>", tag = regex::escape(tag)))
+        .expect("valid regex");
+    re.captures(xml)
+        .map(|c| c[1].to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Archetype version as recorded by the AEM Project Archetype (README or pom comment)
+fn find_archetype_version(text: &str) -> Option<String> {
+    let re = Regex::new(r"(?i)aem-project-archetype\D{0,40}?(\d+(?:\.\d+)*)").expect("valid regex");
+    re.captures(text).map(|c| c[1].to_string())
+}
+
+/// Parse the root pom.xml of an AEM Maven project
+fn parse_pom(pom: &str) -> PomInfo {
+    let archetype_version = find_archetype_version(pom);
+    let pom = Regex::new(r"(?s)<!--.*?-->").expect("valid regex").replace_all(pom, "").to_string();
+
+    // Profiles can redefine modules and properties; only the defaults are interesting here
+    let pom = strip_blocks(&pom, "profiles");
+    let parent = element_text(&pom, "parent").unwrap_or_default();
+
+    let mut top_level = pom.clone();
+    for tag in ["parent", "dependencyManagement", "dependencies", "build", "reporting", "pluginRepositories", "repositories"] {
+        top_level = strip_blocks(&top_level, tag);
+    }
+
+    let properties = element_text(&top_level, "properties").unwrap_or_default();
+    let modules = element_text(&top_level, "modules")
+        .map(|block| {
+            Regex::new(r"<module>\s*(.*?)\s*</module>")
+                .expect("valid regex")
+                .captures_iter(&block)
+                .map(|c| c[1].to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PomInfo {
+        group_id: element_text(&top_level, "groupId").or_else(|| element_text(&parent, "groupId")),
+        artifact_id: element_text(&top_level, "artifactId"),
+        version: element_text(&top_level, "version").or_else(|| element_text(&parent, "version")),
+        name: element_text(&top_level, "name"),
+        archetype_version,
+        modules,
+        aem_host: element_text(&properties, "aem.host"),
+        aem_port: element_text(&properties, "aem.port").and_then(|p| p.parse().ok()),
+        aem_publish_host: element_text(&properties, "aem.publish.host"),
+        aem_publish_port: element_text(&properties, "aem.publish.port").and_then(|p| p.parse().ok()),
+    }
+}
+
+/// Read pom.xml (and README for the archetype version) from a project directory
+fn inspect_project(dir: &Path) -> Result<PomInfo, String> {
+    let pom_path = dir.join("pom.xml");
+    let pom = fs::read_to_string(&pom_path)
+        .map_err(|e| format!("Failed to read {}: {}", pom_path.display(), e))?;
+
+    let mut info = parse_pom(&pom);
+    if info.archetype_version.is_none() {
+        info.archetype_version = fs::read_to_string(dir.join("README.md"))
+            .ok()
+            .and_then(|readme| find_archetype_version(&readme));
+    }
+    Ok(info)
+}
+
+/// Link instances whose port matches aem.port / aem.publish.port, and the profile using them
+async fn auto_link(project: &mut AemProject) {
+    let Ok(instances) = load_instances() else {
+        return;
+    };
+
+    let find = |port: Option<u16>, instance_type: AemInstanceType| {
+        let port = port?;
+        instances
+            .iter()
+            .find(|i| i.port == port && i.instance_type == instance_type)
+            .map(|i| i.id.clone())
+    };
+
+    if project.author_instance_id.is_none() {
+        project.author_instance_id = find(project.pom.aem_port, AemInstanceType::Author);
+    }
+    if project.publish_instance_id.is_none() {
+        project.publish_instance_id = find(project.pom.aem_publish_port, AemInstanceType::Publish);
+    }
+
+    if project.profile_id.is_none() && project.author_instance_id.is_some() {
+        project.profile_id = list_profiles().await.ok().and_then(|profiles| {
+            profiles
+                .into_iter()
+                .find(|p| p.author_instance_id == project.author_instance_id)
+                .map(|p| p.id)
+        });
+    }
+}
+
+// ============================================
+// Project Commands
+// ============================================
+
+/// List registered projects
+#[command]
+pub async fn list_projects() -> Result<Vec<AemProject>, String> {
+    load_projects()
+}
+
+/// Register an AEM Maven project directory
+#[command]
+pub async fn register_project(path: String, name: Option<String>) -> Result<AemProject, String> {
+    let dir = PathBuf::from(path.trim());
+    let pom = inspect_project(&dir)?;
+
+    let mut projects = load_projects()?;
+    let path = dir.to_string_lossy().to_string();
+    if projects.iter().any(|p| p.path == path) {
+        return Err(format!("Project already registered: {}", path));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut project = AemProject {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name
+            .filter(|n| !n.trim().is_empty())
+            .or_else(|| pom.name.clone())
+            .or_else(|| pom.artifact_id.clone())
+            .unwrap_or_else(|| path.clone()),
+        path,
+        pom,
+        profile_id: None,
+        author_instance_id: None,
+        publish_instance_id: None,
+        branch_profiles: Vec::new(),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    auto_link(&mut project).await;
+
+    projects.push(project.clone());
+    save_projects(&projects)?;
+
+    Ok(project)
+}
+
+/// Re-read a project's pom.xml
+#[command]
+pub async fn refresh_project(id: String) -> Result<AemProject, String> {
+    let mut projects = load_projects()?;
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Project, &id).localized())?;
+
+    project.pom = inspect_project(Path::new(&project.path))?;
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+    auto_link(project).await;
+
+    let project = project.clone();
+    save_projects(&projects)?;
+    Ok(project)
+}
+
+/// Update a project (e.g. its name or profile/instance links); only given fields change
+#[command]
+pub async fn update_project(id: String, project: serde_json::Value) -> Result<AemProject, String> {
+    let mut projects = load_projects()?;
+
+    let index = projects
+        .iter()
+        .position(|p| p.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Project, &id).localized())?;
+
+    let mut merged = serde_json::to_value(&projects[index])
+        .map_err(|e| format!("Failed to serialize project: {}", e))?;
+    merge_json_object(&mut merged, &project);
+
+    let mut project: AemProject =
+        serde_json::from_value(merged).map_err(|e| format!("Failed to parse project: {}", e))?;
+
+    // Preserve identity; the pom info only changes through refresh
+    project.id = id;
+    project.path = projects[index].path.clone();
+    project.pom = projects[index].pom.clone();
+    project.created_at = projects[index].created_at.clone();
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+
+    projects[index] = project.clone();
+    save_projects(&projects)?;
+
+    Ok(project)
+}
+
+/// Remove a project from the registry (files are not touched)
+#[command]
+pub async fn delete_project(id: String) -> Result<bool, String> {
+    let mut projects = load_projects()?;
+    let initial_len = projects.len();
+
+    projects.retain(|p| p.id != id);
+
+    if projects.len() == initial_len {
+        return Err(Message::NotFound(Entity::Project, &id).localized());
+    }
+
+    save_projects(&projects)?;
+    Ok(true)
+}
+
+/// Registered project containing `path` (e.g. a module directory or a terminal's cwd)
+#[command]
+pub async fn find_project_for_path(path: String) -> Result<Option<AemProject>, String> {
+    let path = PathBuf::from(path);
+    Ok(load_projects()?
+        .into_iter()
+        .filter(|p| path.starts_with(&p.path))
+        .max_by_key(|p| p.path.len()))
+}
+
+// ============================================
+// Dependency Pre-fetch
+// ============================================
+
+/// Maven wrapper of the project if it has one, otherwise mvn from PATH
+fn maven_executable(project_dir: &Path) -> PathBuf {
+    let (wrapper, mvn) = if cfg!(target_os = "windows") { ("mvnw.cmd", "mvn.cmd") } else { ("mvnw", "mvn") };
+    let wrapper = project_dir.join(wrapper);
+    if wrapper.is_file() {
+        wrapper
+    } else {
+        PathBuf::from(mvn)
+    }
+}
+
+/// settings.xml for a pre-fetch: the given Maven config, else the one of the project's profile,
+/// else ~/.m2/settings.xml when present
+async fn prefetch_settings(project: &AemProject, maven_config_id: Option<String>) -> Result<Option<PathBuf>, String> {
+    let config_id = match maven_config_id {
+        Some(id) => Some(id),
+        None => match &project.profile_id {
+            Some(profile_id) => get_profile(profile_id.clone()).await?.and_then(|p| p.maven_config_id),
+            None => None,
+        },
+    };
+    match config_id {
+        Some(id) => {
+            let platform = crate::platform::current_platform();
+            let path = platform.get_data_dir().join("maven-configs").join(format!("{}.xml", id));
+            if !path.exists() {
+                return Err(format!("Maven config '{}' not found", id));
+            }
+            Ok(Some(path))
+        }
+        None => Ok(dirs::home_dir()
+            .map(|h| h.join(".m2").join("settings.xml"))
+            .filter(|p| p.exists())),
+    }
+}
+
+/// Last `max_lines` non-empty lines of Maven's stdout and stderr
+fn output_tail(stdout: &[u8], stderr: &[u8], max_lines: usize) -> Vec<String> {
+    let text = format!("{}\n{}", String::from_utf8_lossy(stdout), String::from_utf8_lossy(stderr));
+    let lines: Vec<String> = text
+        .lines()
+        .map(|l| l.trim_end().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(max_lines)..].to_vec()
+}
+
+async fn local_repository_size(path: PathBuf) -> u64 {
+    tokio::task::spawn_blocking(move || dir_size(&path)).await.unwrap_or(0)
+}
+
+/// Download all dependencies and plugins of a project into the local Maven repository
+/// (`mvn dependency:go-offline`), so a first build on a new profile or network does not stall.
+/// Uses the given Maven config, else the settings of the project's profile, else
+/// ~/.m2/settings.xml. `timeout_secs` defaults to 30 minutes.
+#[command]
+pub async fn prefetch_project_dependencies(
+    id: String,
+    maven_config_id: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<DependencyPrefetchResult, String> {
+    let project = load_projects()?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Project, &id).localized())?;
+    let settings = prefetch_settings(&project, maven_config_id).await?;
+    let local_repository = settings
+        .as_deref()
+        .and_then(parse_maven_local_repository)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".m2").join("repository").to_string_lossy().to_string()))
+        .ok_or("Could not determine the local Maven repository")?;
+
+    let cache_size_before = local_repository_size(PathBuf::from(&local_repository)).await;
+    let mvn = maven_executable(Path::new(&project.path));
+    let mut cmd = tokio::process::Command::new(&mvn);
+    cmd.arg("-B");
+    if let Some(settings) = &settings {
+        cmd.arg("-s").arg(settings);
+    }
+    cmd.arg("dependency:go-offline")
+        .current_dir(&project.path)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    let began = Instant::now();
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(PREFETCH_TIMEOUT_SECS));
+    let (success, output_tail, error) = match tokio::time::timeout(timeout, cmd.output()).await {
+        Ok(Ok(output)) => {
+            let tail = output_tail(&output.stdout, &output.stderr, OUTPUT_TAIL_LINES);
+            let error = (!output.status.success())
+                .then(|| format!("Maven exited with {}", output.status.code().unwrap_or(-1)));
+            (output.status.success(), tail, error)
+        }
+        Ok(Err(e)) => return Err(format!("Failed to run {}: {}", mvn.display(), e)),
+        Err(_) => (false, Vec::new(), Some(format!("Timed out after {}s", timeout.as_secs()))),
+    };
+    let cache_size_after = local_repository_size(PathBuf::from(&local_repository)).await;
+
+    Ok(DependencyPrefetchResult {
+        project_id: id,
+        settings_path: settings.map(|s| s.to_string_lossy().to_string()),
+        local_repository,
+        success,
+        duration_secs: began.elapsed().as_secs(),
+        cache_size_before,
+        cache_size_after,
+        cache_size_delta: cache_size_after as i64 - cache_size_before as i64,
+        output_tail,
+        error,
+    })
+}
+
+// ============================================
+// Build Output Cleanup
+// ============================================
+
+/// Kind of a build output directory, None for anything else (e.g. a Java package named target)
+fn build_dir_kind(path: &Path) -> Option<BuildDirKind> {
+    let parent = path.parent()?;
+    match path.file_name()?.to_str()? {
+        "target" if parent.join("pom.xml").is_file() => Some(BuildDirKind::Target),
+        "node_modules" if parent.join("package.json").is_file() => Some(BuildDirKind::NodeModules),
+        _ => None,
+    }
+}
+
+/// Build output directories below `root`, without descending into them or into .git
+fn find_build_dirs(root: &Path) -> Vec<(PathBuf, BuildDirKind)> {
+    let mut found = Vec::new();
+    let mut walker = WalkDir::new(root).min_depth(1).into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if entry.file_name() == ".git" {
+            walker.skip_current_dir();
+        } else if let Some(kind) = build_dir_kind(entry.path()) {
+            found.push((entry.path().to_path_buf(), kind));
+            walker.skip_current_dir();
+        }
+    }
+    found
+}
+
+fn scan_build_dirs(projects: &[AemProject], min_size_bytes: u64) -> Vec<BuildDirEntry> {
+    let mut entries: Vec<BuildDirEntry> = projects
+        .iter()
+        .flat_map(|project| {
+            find_build_dirs(Path::new(&project.path))
+                .into_iter()
+                .map(move |(path, kind)| BuildDirEntry {
+                    project_id: project.id.clone(),
+                    project_name: project.name.clone(),
+                    size_bytes: dir_size(&path),
+                    modified_at: fs::metadata(&path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+                    path: path.to_string_lossy().to_string(),
+                    kind,
+                })
+        })
+        .filter(|entry| entry.size_bytes >= min_size_bytes)
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.size_bytes));
+    entries
+}
+
+/// Find target/ and node_modules/ directories in registered projects (all, or `project_ids`),
+/// largest first. `min_size_mb` hides small ones.
+#[command]
+pub async fn scan_project_build_dirs(
+    project_ids: Option<Vec<String>>,
+    min_size_mb: Option<u64>,
+) -> Result<Vec<BuildDirEntry>, String> {
+    let projects: Vec<AemProject> = load_projects()?
+        .into_iter()
+        .filter(|p| project_ids.as_ref().is_none_or(|ids| ids.contains(&p.id)))
+        .collect();
+    let min_size_bytes = min_size_mb.unwrap_or(0) * 1024 * 1024;
+    tokio::task::spawn_blocking(move || scan_build_dirs(&projects, min_size_bytes))
+        .await
+        .map_err(|e| format!("Scan failed: {}", e))
+}
+
+/// Delete selected build output directories. Only target/ and node_modules/ directories inside
+/// a registered project are removed; anything else is reported as an error.
+#[command]
+pub async fn delete_project_build_dirs(paths: Vec<String>) -> Result<BuildDirCleanupResult, String> {
+    let roots: Vec<PathBuf> = load_projects()?
+        .iter()
+        .filter_map(|p| fs::canonicalize(&p.path).ok())
+        .collect();
+    let mut result = BuildDirCleanupResult::default();
+    for path in paths {
+        // Resolve `..` so the project check cannot be bypassed; symlinks are never followed
+        let dir = match fs::canonicalize(&path) {
+            Ok(dir) if !Path::new(&path).is_symlink() => dir,
+            _ => PathBuf::new(),
+        };
+        let in_project = roots.iter().any(|root| dir.starts_with(root) && dir != *root);
+        if !in_project || build_dir_kind(&dir).is_none() {
+            result.errors.push(format!("{} is not a build output directory of a registered project", path));
+            continue;
+        }
+        let size = dir_size(&dir);
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => {
+                result.bytes_reclaimed += size;
+                result.deleted.push(path);
+            }
+            Err(e) => result.errors.push(format!("Failed to delete {}: {}", path, e)),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pom() {
+        let pom = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Generated by aem-project-archetype version 47 -->
+<project>
+    <parent>
+        <groupId>com.adobe.aem</groupId>
+        <artifactId>parent</artifactId>
+        <version>1</version>
+    </parent>
+    <groupId>com.mysite</groupId>
+    <artifactId>mysite</artifactId>
+    <version>1.0.0-SNAPSHOT</version>
+    <name>My Site</name>
+    <modules>
+        <module>core</module>
+        <module>ui.apps</module>
+    </modules>
+    <properties>
+        <aem.host>localhost</aem.host>
+        <aem.port>4502</aem.port>
+        <aem.publish.port>4503</aem.publish.port>
+    </properties>
+    <dependencyManagement>
+        <dependencies>
+            <dependency><groupId>org.osgi</groupId><artifactId>osgi.core</artifactId><version>6.0.0</version></dependency>
+        </dependencies>
+    </dependencyManagement>
+    <profiles>
+        <profile><modules><module>it.tests</module></modules></profile>
+    </profiles>
+</project>"#;
+
+        let info = parse_pom(pom);
+        assert_eq!(info.group_id.as_deref(), Some("com.mysite"));
+        assert_eq!(info.artifact_id.as_deref(), Some("mysite"));
+        assert_eq!(info.version.as_deref(), Some("1.0.0-SNAPSHOT"));
+        assert_eq!(info.archetype_version.as_deref(), Some("47"));
+        assert_eq!(info.modules, vec!["core", "ui.apps"]);
+        assert_eq!(info.aem_port, Some(4502));
+        assert_eq!(info.aem_publish_port, Some(4503));
+        assert_eq!(info.aem_publish_host, None);
+    }
+
+    #[test]
+    fn test_output_tail() {
+        let stdout = b"[INFO] Scanning for projects...\n\n[INFO] BUILD SUCCESS\n[INFO] Total time: 42 s\n";
+        assert_eq!(
+            output_tail(stdout, b"WARNING: deprecated\n", 3),
+            vec!["[INFO] BUILD SUCCESS", "[INFO] Total time: 42 s", "WARNING: deprecated"]
+        );
+        assert!(output_tail(b"", b"", 3).is_empty());
+    }
+
+    #[test]
+    fn test_find_build_dirs() {
+        let root = std::env::temp_dir().join(format!("aem-env-project-{}", uuid::Uuid::new_v4()));
+        for dir in [
+            "core/target/classes",
+            "ui.frontend/node_modules/webpack/node_modules",
+            "core/src/main/java/com/mysite/target",
+            ".git/target",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in ["pom.xml", "core/pom.xml", "ui.frontend/package.json", ".git/pom.xml"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let mut found = find_build_dirs(&root);
+        found.sort_by_key(|(path, _)| path.clone());
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(
+            found,
+            vec![
+                (root.join("core/target"), BuildDirKind::Target),
+                (root.join("ui.frontend/node_modules"), BuildDirKind::NodeModules),
+            ]
+        );
+    }
+}
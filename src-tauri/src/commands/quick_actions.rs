@@ -0,0 +1,240 @@
+// Instance Quick Action Commands
+// One-click maintenance actions (reindex, restart bundle, remove Sling jobs, clear recent
+// requests) performed as authenticated HTTP calls against a running instance
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::command;
+
+use crate::commands::instance::{get_instance_credentials, load_instances, AemInstance};
+use crate::i18n::{Entity, Message, MessageCode};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum QuickAction {
+    /// Set reindex=true on /oak:index/<index_name>
+    Reindex { index_name: String },
+    /// Stop and start a bundle
+    RestartBundle { symbolic_name: String },
+    /// Delete queued and assigned jobs of a topic below /var/eventing/jobs
+    RemoveSlingJobs { topic: String },
+    /// Clear the Recent Requests console
+    ClearRecentRequests,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickActionResult {
+    pub action: QuickAction,
+    pub success: bool,
+    pub message: String,
+    #[serde(flatten)]
+    pub message_code: MessageCode,
+}
+
+impl QuickAction {
+    /// Text the UI shows in its confirmation dialog
+    pub fn confirmation(&self) -> String {
+        match self {
+            QuickAction::Reindex { index_name } => format!(
+                "Reindex /oak:index/{}? Queries using the index may return incomplete results until it finishes.",
+                index_name
+            ),
+            QuickAction::RestartBundle { symbolic_name } => {
+                format!("Restart bundle {}? Its services are unavailable while it restarts.", symbolic_name)
+            }
+            QuickAction::RemoveSlingJobs { topic } => {
+                format!("Remove all Sling jobs of topic {}? Removed jobs are not processed.", topic)
+            }
+            QuickAction::ClearRecentRequests => "Clear the recent requests list?".to_string(),
+        }
+    }
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Job node paths from a QueryBuilder response
+fn job_paths(json: &serde_json::Value) -> Vec<String> {
+    json.get("hits")
+        .and_then(|h| h.as_array())
+        .map(|hits| {
+            hits.iter()
+                .filter_map(|h| h.get("path").and_then(|p| p.as_str()))
+                .filter(|p| p.starts_with("/var/eventing/jobs/"))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn post_form(
+    client: &reqwest::Client,
+    url: &str,
+    credentials: &(String, String),
+    form: &[(&str, &str)],
+) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("{} returned HTTP {}", url, response.status().as_u16()))
+    }
+}
+
+async fn perform(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    action: &QuickAction,
+) -> Result<(String, MessageCode), String> {
+    let base_url = instance.base_url();
+
+    match action {
+        QuickAction::Reindex { index_name } => {
+            let url = format!("{}/oak:index/{}", base_url, index_name);
+            post_form(client, &url, credentials, &[("reindex", "true"), ("reindex@TypeHint", "Boolean")]).await?;
+            Ok((
+                format!("Reindex of {} triggered", index_name),
+                MessageCode::new("reindex_triggered").with("index", index_name),
+            ))
+        }
+        QuickAction::RestartBundle { symbolic_name } => {
+            let url = format!("{}/system/console/bundles/{}", base_url, symbolic_name);
+            post_form(client, &url, credentials, &[("action", "stop")]).await?;
+            post_form(client, &url, credentials, &[("action", "start")]).await?;
+            Ok((
+                format!("Bundle {} restarted", symbolic_name),
+                MessageCode::new("bundle_restarted").with("bundle", symbolic_name),
+            ))
+        }
+        QuickAction::RemoveSlingJobs { topic } => {
+            let query = format!("{}/bin/querybuilder.json", base_url);
+            let json: serde_json::Value = client
+                .get(&query)
+                .basic_auth(&credentials.0, Some(&credentials.1))
+                .query(&[
+                    ("path", "/var/eventing/jobs"),
+                    ("property", "event.job.topic"),
+                    ("property.value", topic.as_str()),
+                    ("p.limit", "-1"),
+                ])
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse job query: {}", e))?;
+
+            let paths = job_paths(&json);
+            if paths.is_empty() {
+                return Ok((
+                    format!("No jobs found for topic {}", topic),
+                    MessageCode::new("sling_jobs_not_found").with("topic", topic),
+                ));
+            }
+
+            let mut form: Vec<(&str, &str)> = vec![(":operation", "delete")];
+            form.extend(paths.iter().map(|p| (":applyTo", p.as_str())));
+            post_form(client, &format!("{}/var/eventing/jobs", base_url), credentials, &form).await?;
+            Ok((
+                format!("Removed {} job(s) of topic {}", paths.len(), topic),
+                MessageCode::new("sling_jobs_removed")
+                    .with("count", paths.len())
+                    .with("topic", topic),
+            ))
+        }
+        QuickAction::ClearRecentRequests => {
+            let url = format!("{}/system/console/requests", base_url);
+            post_form(client, &url, credentials, &[("clear", "clear")]).await?;
+            Ok(("Recent requests cleared".to_string(), MessageCode::new("recent_requests_cleared")))
+        }
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Confirmation text for an action, shown before `run_quick_action`
+#[command]
+pub async fn get_quick_action_confirmation(action: QuickAction) -> Result<String, String> {
+    Ok(action.confirmation())
+}
+
+/// Run a maintenance action on an instance. `confirmed` must be true; the UI sets it
+/// after the user accepted the confirmation text.
+#[command]
+pub async fn run_quick_action(
+    id: String,
+    action: QuickAction,
+    confirmed: bool,
+) -> Result<QuickActionResult, String> {
+    if !confirmed {
+        return Err(format!("Confirmation required: {}", action.confirmation()));
+    }
+
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+    if instance.probe_without_auth {
+        return Err("Quick actions need credentials, but this instance is probed without authentication".to_string());
+    }
+
+    let (username, password, _) = get_instance_credentials(&instance.id)?;
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(30)))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let (success, message, message_code) = match perform(&client, &instance, &(username, password), &action).await {
+        Ok((message, message_code)) => (true, message, message_code),
+        Err(e) => {
+            let message_code = MessageCode::new("quick_action_failed").with("detail", &e);
+            (false, e, message_code)
+        }
+    };
+
+    Ok(QuickActionResult {
+        action,
+        success,
+        message,
+        message_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_paths() {
+        let json = serde_json::json!({
+            "success": true,
+            "hits": [
+                {"path": "/var/eventing/jobs/assigned/abc/com.example.sync/2025/10/16/10/15/job_1"},
+                {"path": "/content/unexpected"}
+            ]
+        });
+        assert_eq!(
+            job_paths(&json),
+            vec!["/var/eventing/jobs/assigned/abc/com.example.sync/2025/10/16/10/15/job_1"]
+        );
+
+        let action: QuickAction =
+            serde_json::from_str(r#"{"action": "restart_bundle", "symbolic_name": "com.example.core"}"#).unwrap();
+        assert!(action.confirmation().contains("com.example.core"));
+    }
+}
@@ -0,0 +1,465 @@
+// Startup Readiness Commands
+// Per-instance readiness rules (bundle active, URL status) and the start-and-wait flow
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle};
+
+use crate::commands::addons::expected_startup;
+use crate::commands::certificates::http_client_builder;
+use crate::commands::instance::{
+    check_aem_http_ready, check_port_open, get_instance_credentials, load_instances, save_instances, start_instance,
+    stop_instance, wait_for_shutdown, AemInstance, AemInstanceStatus, AemInstanceType,
+};
+use crate::commands::profile::get_startup_config;
+use crate::commands::startup_times::record_running;
+use crate::commands::window::emit_to_windows;
+use crate::i18n::{Entity, Message, MessageCode};
+
+// ============================================
+// Data Types
+// ============================================
+
+/// A condition that must hold before an instance counts as ready
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReadinessRule {
+    /// The bundle with this symbolic name is Active
+    BundleActive { symbolic_name: String },
+    /// GET `path` answers with `status` (200 when omitted)
+    UrlStatus {
+        path: String,
+        #[serde(default)]
+        status: Option<u16>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessCheck {
+    pub rule: ReadinessRule,
+    pub passed: bool,
+    pub detail: String,
+    #[serde(flatten)]
+    pub message_code: MessageCode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartWaitResult {
+    pub instance_id: String,
+    pub ready: bool,
+    pub timed_out: bool,
+    pub elapsed_secs: u64,
+    /// Results of the instance's readiness rules at the last poll
+    pub checks: Vec<ReadinessCheck>,
+}
+
+/// Body of the ready webhook POST
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadyWebhookPayload {
+    /// Always "instance_ready"
+    pub event: String,
+    pub instance_id: String,
+    pub name: String,
+    pub instance_type: AemInstanceType,
+    pub base_url: String,
+    pub host: String,
+    pub port: u16,
+    pub run_modes: Vec<String>,
+    pub aem_version: Option<String>,
+    /// Seconds from start to Running (None for test deliveries)
+    pub startup_secs: Option<u64>,
+    pub ready_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPhase {
+    Stopping,
+    /// Stopped; waiting for the port to be released
+    WaitingForPort,
+    Starting,
+    Running,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartProgress {
+    pub instance_id: String,
+    pub phase: RestartPhase,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+    /// Seconds since the restart began
+    pub elapsed_secs: u64,
+}
+
+/// Event emitted with a `RestartProgress` whenever a restart enters a new phase
+pub const RESTART_PROGRESS_EVENT: &str = "instance-restart-progress";
+
+/// How often the start-and-wait flow polls the instance
+const READINESS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Deliveries of the ready webhook before giving up, and the pause between them
+const WEBHOOK_ATTEMPTS: u32 = 3;
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// ============================================
+// Helpers
+// ============================================
+
+/// State of a bundle in a /system/console/bundles/<name>.json response
+fn bundle_state<'a>(json: &'a serde_json::Value, symbolic_name: &str) -> Option<&'a str> {
+    json.get("data")?
+        .as_array()?
+        .iter()
+        .find(|b| b.get("symbolicName").and_then(|n| n.as_str()) == Some(symbolic_name))?
+        .get("state")?
+        .as_str()
+}
+
+async fn evaluate_rule(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: Option<&(String, String)>,
+    rule: &ReadinessRule,
+) -> ReadinessCheck {
+    let (passed, detail, message_code) = match rule {
+        ReadinessRule::BundleActive { symbolic_name } => match credentials {
+            None => (
+                false,
+                "Bundle checks need credentials".to_string(),
+                MessageCode::new("readiness_credentials_required"),
+            ),
+            Some((username, password)) => {
+                let url = format!("{}/system/console/bundles/{}.json", instance.base_url(), symbolic_name);
+                let response = client.get(&url).basic_auth(username, Some(password)).send().await;
+                let json = match response {
+                    Ok(resp) if resp.status().is_success() => resp.json::<serde_json::Value>().await.ok(),
+                    _ => None,
+                };
+                let code = MessageCode::new("readiness_bundle_state").with("bundle", symbolic_name);
+                match json.as_ref().and_then(|j| bundle_state(j, symbolic_name)) {
+                    Some("Active") => (true, "Active".to_string(), code.with("state", "Active")),
+                    Some(state) => (false, state.to_string(), code.with("state", state)),
+                    None => (
+                        false,
+                        "Bundle not found".to_string(),
+                        MessageCode::new("readiness_bundle_not_found").with("bundle", symbolic_name),
+                    ),
+                }
+            }
+        },
+        ReadinessRule::UrlStatus { path, status } => {
+            let expected = status.unwrap_or(200);
+            let mut request = client.get(format!("{}{}", instance.base_url(), path));
+            if let Some((username, password)) = credentials {
+                request = request.basic_auth(username, Some(password));
+            }
+            match request.send().await {
+                Ok(resp) => {
+                    let actual = resp.status().as_u16();
+                    let code = MessageCode::new("readiness_url_status")
+                        .with("path", path)
+                        .with("status", actual)
+                        .with("expected", expected);
+                    (actual == expected, format!("HTTP {}", actual), code)
+                }
+                Err(e) => {
+                    let code = MessageCode::new("readiness_url_failed").with("path", path).with("detail", &e);
+                    (false, e.to_string(), code)
+                }
+            }
+        }
+    };
+
+    ReadinessCheck {
+        rule: rule.clone(),
+        passed,
+        detail,
+        message_code,
+    }
+}
+
+/// Evaluate all readiness rules of an instance
+async fn evaluate_readiness(instance: &AemInstance) -> Result<Vec<ReadinessCheck>, String> {
+    let client = instance
+        .apply_http_overrides(
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .no_proxy()
+                .redirect(reqwest::redirect::Policy::none()),
+        )
+        .build()
+        .map_err(|e| e.to_string())?;
+    let credentials = if instance.probe_without_auth {
+        None
+    } else {
+        get_instance_credentials(&instance.id).ok().map(|(u, p, _)| (u, p))
+    };
+
+    let mut checks = Vec::new();
+    for rule in &instance.readiness_rules {
+        checks.push(evaluate_rule(&client, instance, credentials.as_ref(), rule).await);
+    }
+    Ok(checks)
+}
+
+/// Poll until the instance answers HTTP and its readiness rules pass, or `timeout` elapses
+pub(crate) async fn wait_until_ready(
+    app: &AppHandle,
+    instance: &AemInstance,
+    timeout: Duration,
+) -> Result<StartWaitResult, String> {
+    let started = Instant::now();
+    let mut checks = Vec::new();
+    loop {
+        tokio::time::sleep(READINESS_POLL_INTERVAL).await;
+
+        if check_aem_http_ready(instance, 3000).await {
+            checks = evaluate_readiness(instance).await?;
+            if checks.iter().all(|c| c.passed) {
+                break;
+            }
+        }
+
+        if started.elapsed() >= timeout {
+            return Ok(StartWaitResult {
+                instance_id: instance.id.clone(),
+                ready: false,
+                timed_out: true,
+                elapsed_secs: started.elapsed().as_secs(),
+                checks,
+            });
+        }
+    }
+
+    let mut instances = load_instances()?;
+    if let Some(stored) = instances.iter_mut().find(|i| i.id == instance.id) {
+        stored.status = AemInstanceStatus::Running;
+        stored.updated_at = chrono::Utc::now().to_rfc3339();
+        save_instances(&instances)?;
+    }
+    record_running(app, std::slice::from_ref(&instance.id));
+
+    Ok(StartWaitResult {
+        instance_id: instance.id.clone(),
+        ready: true,
+        timed_out: false,
+        elapsed_secs: started.elapsed().as_secs(),
+        checks,
+    })
+}
+
+fn ready_payload(instance: &AemInstance, startup_secs: Option<u64>) -> ReadyWebhookPayload {
+    ReadyWebhookPayload {
+        event: "instance_ready".to_string(),
+        instance_id: instance.id.clone(),
+        name: instance.name.clone(),
+        instance_type: instance.instance_type.clone(),
+        base_url: instance.base_url(),
+        host: instance.host.clone(),
+        port: instance.port,
+        run_modes: instance.run_modes.clone(),
+        aem_version: instance.aem_version.clone(),
+        startup_secs,
+        ready_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// POST `payload` to `url`; returns the HTTP status
+async fn post_ready_webhook(url: &str, payload: &ReadyWebhookPayload) -> Result<u16, String> {
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| format!("Ready webhook {} failed: {}", url, e))?;
+    let status = response.status().as_u16();
+    if response.status().is_success() {
+        Ok(status)
+    } else {
+        Err(format!("Ready webhook {} returned HTTP {}", url, status))
+    }
+}
+
+/// Deliver the ready webhook of an instance that just reached Running, in the background.
+/// A CI script may start listening late, so failed deliveries are retried a few times.
+pub(crate) fn notify_ready_webhook(instance_id: &str, startup_secs: Option<u64>) {
+    let Ok(instance) = find_instance(instance_id) else {
+        return;
+    };
+    let Some(url) = instance.ready_webhook_url.clone().filter(|u| !u.trim().is_empty()) else {
+        return;
+    };
+    let payload = ready_payload(&instance, startup_secs);
+    tauri::async_runtime::spawn(async move {
+        for attempt in 1..=WEBHOOK_ATTEMPTS {
+            match post_ready_webhook(&url, &payload).await {
+                Ok(_) => return,
+                Err(e) if attempt == WEBHOOK_ATTEMPTS => println!("[AEM] {}", e),
+                Err(_) => tokio::time::sleep(WEBHOOK_RETRY_DELAY).await,
+            }
+        }
+    });
+}
+
+fn find_instance(id: &str) -> Result<AemInstance, String> {
+    load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, id).localized())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Send a test delivery of the instance's ready webhook; returns the HTTP status
+#[command]
+pub async fn send_test_ready_webhook(id: String) -> Result<u16, String> {
+    let instance = find_instance(&id)?;
+    let url = instance
+        .ready_webhook_url
+        .clone()
+        .filter(|u| !u.trim().is_empty())
+        .ok_or_else(|| format!("{} has no ready webhook URL", instance.name))?;
+    post_ready_webhook(&url, &ready_payload(&instance, None)).await
+}
+
+/// Evaluate the instance's readiness rules now
+#[command]
+pub async fn check_instance_readiness(id: String) -> Result<Vec<ReadinessCheck>, String> {
+    evaluate_readiness(&find_instance(&id)?).await
+}
+
+/// Start an instance and wait until it answers HTTP and all of its readiness rules pass.
+/// Waits for `timeout_secs`, defaulting to the expected startup time (type and add-ons).
+#[command]
+pub async fn start_instance_and_wait(
+    app: AppHandle,
+    id: String,
+    timeout_secs: Option<u64>,
+) -> Result<StartWaitResult, String> {
+    let instance = find_instance(&id)?;
+    let timeout = timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| expected_startup(&instance));
+
+    start_instance(id).await?;
+    wait_until_ready(&app, &instance, timeout).await
+}
+
+/// Stop an instance, wait for its port to be released, start it again and wait until it is ready.
+/// Emits `RESTART_PROGRESS_EVENT` for each phase (stopping → waiting for port → starting → running).
+#[command]
+pub async fn restart_instance(
+    app: AppHandle,
+    id: String,
+    timeout_secs: Option<u64>,
+) -> Result<StartWaitResult, String> {
+    let began = Instant::now();
+    let emit = |phase: RestartPhase, message: Option<(String, MessageCode)>| {
+        let (message, message_code) = message.unzip();
+        let _ = emit_to_windows(
+            &app,
+            RESTART_PROGRESS_EVENT,
+            RestartProgress {
+                instance_id: id.clone(),
+                phase,
+                message,
+                message_code,
+                elapsed_secs: began.elapsed().as_secs(),
+            },
+            Some(&id),
+            None,
+        );
+    };
+    let fail = |error: String| {
+        let code = MessageCode::new("restart_failed").with("detail", &error);
+        emit(RestartPhase::Failed, Some((error.clone(), code)));
+        error
+    };
+
+    let instance = find_instance(&id)?;
+    if check_port_open(&instance.host, instance.port, 500) {
+        emit(RestartPhase::Stopping, None);
+        stop_instance(id.clone()).await.map_err(fail)?;
+    }
+
+    // The stop may have been a shutdown request, so wait until the port is actually free
+    emit(RestartPhase::WaitingForPort, None);
+    let stop_timeout = Duration::from_secs(get_startup_config().stop_timeout_secs);
+    if !wait_for_shutdown(&find_instance(&id)?, stop_timeout).await {
+        return Err(fail(format!(
+            "Port {} is still in use after {}s",
+            instance.port,
+            stop_timeout.as_secs()
+        )));
+    }
+
+    emit(RestartPhase::Starting, None);
+    start_instance(id.clone()).await.map_err(fail)?;
+
+    let instance = find_instance(&id)?;
+    let timeout = timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| expected_startup(&instance));
+    let result = wait_until_ready(&app, &instance, timeout).await.map_err(fail)?;
+    if result.ready {
+        emit(RestartPhase::Running, None);
+    } else {
+        emit(
+            RestartPhase::Failed,
+            Some((
+                format!("Not ready after {}s", result.elapsed_secs),
+                MessageCode::new("restart_not_ready").with("seconds", result.elapsed_secs),
+            )),
+        );
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_progress_serde() {
+        let progress = RestartProgress {
+            instance_id: "author".to_string(),
+            phase: RestartPhase::WaitingForPort,
+            message: None,
+            message_code: None,
+            elapsed_secs: 12,
+        };
+        let json = serde_json::to_value(&progress).unwrap();
+        assert_eq!(json["phase"], "waiting_for_port");
+        assert_eq!(json["elapsed_secs"], 12);
+    }
+
+    #[test]
+    fn test_readiness_rules() {
+        let rules: Vec<ReadinessRule> = serde_json::from_str(
+            r#"[{"type": "bundle_active", "symbolic_name": "com.example.core"},
+                {"type": "url_status", "path": "/content/site/en.html"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rules[1],
+            ReadinessRule::UrlStatus {
+                path: "/content/site/en.html".to_string(),
+                status: None
+            }
+        );
+
+        let json = serde_json::json!({
+            "data": [{"symbolicName": "com.example.core", "state": "Resolved"}]
+        });
+        assert_eq!(bundle_state(&json, "com.example.core"), Some("Resolved"));
+        assert_eq!(bundle_state(&json, "com.example.other"), None);
+    }
+}
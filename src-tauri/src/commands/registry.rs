@@ -0,0 +1,387 @@
+// Registry Credential Commands
+// npm registry tokens and Maven server credentials stored per profile in the credential store
+// and written into ~/.npmrc and ~/.m2/settings.xml on switch, inside marked blocks that the
+// next switch replaces
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::command;
+
+use crate::commands::credential_store::{read_secret, remove_secret, write_secret};
+use crate::commands::profile::{get_profile, profile_ids};
+use crate::i18n::{Entity, Message};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RegistryCredentials {
+    #[serde(default)]
+    pub npm: Option<NpmRegistry>,
+    #[serde(default)]
+    pub maven_servers: Vec<MavenServerCredential>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpmRegistry {
+    /// Registry URL, e.g. https://npm.client.com/repository/npm/
+    pub registry: String,
+    /// Only route this scope (e.g. @client) to the registry; all packages when None
+    pub scope: Option<String>,
+    #[serde(default)]
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MavenServerCredential {
+    /// Server id the repositories/mirrors of settings.xml or the pom refer to
+    pub id: String,
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RegistrySyncResult {
+    pub npmrc_path: Option<String>,
+    pub settings_path: Option<String>,
+    pub npm_registry: Option<String>,
+    pub maven_servers: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+const NPMRC_BEGIN: &str = "# >>> aem-env-manager registry >>>";
+const NPMRC_END: &str = "# <<< aem-env-manager registry <<<";
+const SETTINGS_BEGIN: &str = "<!-- >>> aem-env-manager registry >>> -->";
+const SETTINGS_END: &str = "<!-- <<< aem-env-manager registry <<< -->";
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+/// Plaintext file of earlier versions
+fn get_registry_credentials_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join(".registry-credentials")
+}
+
+/// Account of a profile's registry credentials in the credential store; kept apart from the
+/// profiles so profile exports carry no tokens
+fn registry_account(profile_id: &str) -> String {
+    format!("registry:{}", profile_id)
+}
+
+/// Credential store accounts of every profile's registry credentials
+pub(crate) fn registry_accounts() -> Vec<String> {
+    profile_ids().iter().map(|id| registry_account(id)).collect()
+}
+
+fn store_registry_credentials(profile_id: &str, credentials: Option<&RegistryCredentials>) -> Result<(), String> {
+    let account = registry_account(profile_id);
+    match credentials {
+        Some(credentials) => {
+            let secret = serde_json::to_string(credentials)
+                .map_err(|e| format!("Failed to serialize registry credentials: {}", e))?;
+            write_secret(&account, &secret)
+        }
+        None => remove_secret(&account),
+    }
+}
+
+/// Move the plaintext file into the credential store once per run; the file is only deleted
+/// when every profile's entry was moved
+fn migrate_registry_file() {
+    static MIGRATED: OnceLock<()> = OnceLock::new();
+    MIGRATED.get_or_init(|| {
+        let path = get_registry_credentials_file();
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return;
+        };
+        let legacy: HashMap<String, RegistryCredentials> = match serde_json::from_str(&content) {
+            Ok(legacy) => legacy,
+            Err(e) => {
+                println!("[AEM] Registry credentials not migrated: {}", e);
+                return;
+            }
+        };
+        for (profile_id, credentials) in &legacy {
+            if let Err(e) = store_registry_credentials(profile_id, Some(credentials)) {
+                println!("[AEM] Failed to migrate registry credentials of {}: {}", profile_id, e);
+                return;
+            }
+        }
+        if let Err(e) = std::fs::remove_file(&path) {
+            println!("[AEM] Failed to remove the plaintext registry credentials file: {}", e);
+        }
+    });
+}
+
+fn load_registry_credentials(profile_id: &str) -> Result<Option<RegistryCredentials>, String> {
+    migrate_registry_file();
+    let Some(secret) = read_secret(&registry_account(profile_id))? else {
+        return Ok(None);
+    };
+    serde_json::from_str(&secret)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse registry credentials: {}", e))
+}
+
+/// Store or, with None, remove the registry credentials of a profile
+fn save_registry_credentials(profile_id: &str, credentials: Option<&RegistryCredentials>) -> Result<(), String> {
+    migrate_registry_file();
+    store_registry_credentials(profile_id, credentials)
+}
+
+/// Forget the registry credentials of a deleted profile
+pub(crate) fn delete_registry_credentials(profile_id: &str) -> Result<(), String> {
+    save_registry_credentials(profile_id, None)
+}
+
+fn home_file(relative: &[&str]) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(relative.iter().fold(home, |path, part| path.join(part)))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// `content` without the block between `begin` and `end` (markers and their lines included)
+fn strip_block(content: &str, begin: &str, end: &str) -> String {
+    let Some(start) = content.find(begin) else {
+        return content.to_string();
+    };
+    let Some(stop) = content[start..].find(end).map(|i| start + i + end.len()) else {
+        return content.to_string();
+    };
+    let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let start = if content[line_start..start].trim().is_empty() { line_start } else { start };
+    let stop = if content[stop..].starts_with('\n') { stop + 1 } else { stop };
+    format!("{}{}", &content[..start], &content[stop..])
+}
+
+/// Key npm expects the token under: the registry URL without its protocol
+fn npm_auth_key(registry: &str) -> String {
+    let without_scheme = registry.split_once("://").map(|(_, rest)| rest).unwrap_or(registry);
+    format!("//{}/", without_scheme.trim_end_matches('/'))
+}
+
+/// `.npmrc` with the managed block replaced by `npm` (or removed). The block goes last so it
+/// overrides a `registry=` set earlier in the file.
+fn apply_npmrc(content: &str, npm: Option<&NpmRegistry>) -> String {
+    let mut result = strip_block(content, NPMRC_BEGIN, NPMRC_END);
+    let Some(npm) = npm else {
+        return result;
+    };
+    if !result.is_empty() && !result.ends_with('\n') {
+        result.push('\n');
+    }
+    result.push_str(NPMRC_BEGIN);
+    result.push('\n');
+    match npm.scope.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(scope) => {
+            let scope = if scope.starts_with('@') { scope.to_string() } else { format!("@{}", scope) };
+            result.push_str(&format!("{}:registry={}\n", scope, npm.registry));
+        }
+        None => result.push_str(&format!("registry={}\n", npm.registry)),
+    }
+    if !npm.token.is_empty() {
+        result.push_str(&format!("{}:_authToken={}\n", npm_auth_key(&npm.registry), npm.token));
+    }
+    result.push_str(NPMRC_END);
+    result.push('\n');
+    result
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// settings.xml with the managed `<server>` entries replaced by `servers`. Server ids already
+/// declared outside the block are skipped (Maven rejects duplicates) and returned.
+fn apply_settings_servers(content: &str, servers: &[MavenServerCredential]) -> Result<(String, Vec<String>), String> {
+    let content = strip_block(content, SETTINGS_BEGIN, SETTINGS_END);
+    let existing_servers = match (content.find("<servers>"), content.find("</servers>")) {
+        (Some(start), Some(end)) if start < end => &content[start..end],
+        _ => "",
+    };
+    let (duplicates, servers): (Vec<_>, Vec<_>) = servers
+        .iter()
+        .partition(|s| existing_servers.contains(&format!("<id>{}</id>", escape_xml(&s.id))));
+    let duplicates: Vec<String> = duplicates.into_iter().map(|s| s.id.clone()).collect();
+    if servers.is_empty() {
+        return Ok((content, duplicates));
+    }
+
+    let entries: String = servers
+        .iter()
+        .map(|s| {
+            format!(
+                concat!(
+                    "    <server>\n",
+                    "      <id>{}</id>\n",
+                    "      <username>{}</username>\n",
+                    "      <password>{}</password>\n",
+                    "    </server>\n"
+                ),
+                escape_xml(&s.id),
+                escape_xml(&s.username),
+                escape_xml(&s.password)
+            )
+        })
+        .collect();
+    let (position, block) = if let Some(position) = content.find("</servers>") {
+        (position, format!("{}\n{}  {}\n  ", SETTINGS_BEGIN, entries, SETTINGS_END))
+    } else if let Some(position) = content.rfind("</settings>") {
+        (
+            position,
+            format!("  {}\n  <servers>\n{}  </servers>\n  {}\n", SETTINGS_BEGIN, entries, SETTINGS_END),
+        )
+    } else {
+        return Err("settings.xml has no <settings> element".to_string());
+    };
+    Ok((format!("{}{}{}", &content[..position], block, &content[position..]), duplicates))
+}
+
+/// Write (or with `None`, remove) the managed registry blocks of ~/.npmrc and ~/.m2/settings.xml
+pub(crate) fn sync_registry_credentials(credentials: Option<&RegistryCredentials>) -> Result<RegistrySyncResult, String> {
+    let mut result = RegistrySyncResult::default();
+
+    let npmrc = home_file(&[".npmrc"])?;
+    let npm = credentials.and_then(|c| c.npm.as_ref());
+    let current = std::fs::read_to_string(&npmrc).unwrap_or_default();
+    let updated = apply_npmrc(&current, npm);
+    if updated != current {
+        std::fs::write(&npmrc, &updated).map_err(|e| format!("Failed to write {}: {}", npmrc.display(), e))?;
+    }
+    result.npmrc_path = Some(npmrc.to_string_lossy().to_string());
+    result.npm_registry = npm.map(|n| n.registry.clone());
+
+    let settings = home_file(&[".m2", "settings.xml"])?;
+    let servers = credentials.map(|c| c.maven_servers.as_slice()).unwrap_or_default();
+    if settings.exists() || !servers.is_empty() {
+        let current = std::fs::read_to_string(&settings)
+            .unwrap_or_else(|_| "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<settings>\n</settings>\n".to_string());
+        let (updated, duplicates) = apply_settings_servers(&current, servers)?;
+        if updated != current {
+            if let Some(parent) = settings.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create .m2 directory: {}", e))?;
+            }
+            std::fs::write(&settings, &updated)
+                .map_err(|e| format!("Failed to write {}: {}", settings.display(), e))?;
+        }
+        result.settings_path = Some(settings.to_string_lossy().to_string());
+        result.maven_servers = servers
+            .iter()
+            .filter(|s| !duplicates.contains(&s.id))
+            .map(|s| s.id.clone())
+            .collect();
+        result.warnings.extend(
+            duplicates
+                .into_iter()
+                .map(|id| format!("settings.xml already has a server named {}; left unchanged", id)),
+        );
+    }
+
+    Ok(result)
+}
+
+/// Apply the registry credentials of a profile, removing those of the previous one
+pub(crate) fn apply_profile_registry(profile_id: &str) -> Result<RegistrySyncResult, String> {
+    sync_registry_credentials(load_registry_credentials(profile_id)?.as_ref())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+#[command]
+pub async fn get_profile_registry_credentials(profile_id: String) -> Result<RegistryCredentials, String> {
+    Ok(load_registry_credentials(&profile_id)?.unwrap_or_default())
+}
+
+/// Store the npm registry and Maven server credentials of a profile (applied on the next switch)
+#[command]
+pub async fn set_profile_registry_credentials(
+    profile_id: String,
+    credentials: RegistryCredentials,
+) -> Result<(), String> {
+    get_profile(profile_id.clone())
+        .await?
+        .ok_or_else(|| Message::NotFound(Entity::Profile, &profile_id).localized())?;
+    if let Some(ref npm) = credentials.npm {
+        if !npm.registry.starts_with("http://") && !npm.registry.starts_with("https://") {
+            return Err(format!("Invalid npm registry URL: {}", npm.registry));
+        }
+    }
+    if let Some(server) = credentials.maven_servers.iter().find(|s| s.id.trim().is_empty()) {
+        return Err(format!("Maven server for user {} has no id", server.username));
+    }
+
+    let empty = credentials.npm.is_none() && credentials.maven_servers.is_empty();
+    save_registry_credentials(&profile_id, (!empty).then_some(&credentials))
+}
+
+/// Write a profile's registry credentials into ~/.npmrc and ~/.m2/settings.xml now
+#[command]
+pub async fn apply_profile_registry_credentials(profile_id: String) -> Result<RegistrySyncResult, String> {
+    apply_profile_registry(&profile_id)
+}
+
+/// Remove everything the app wrote into ~/.npmrc and ~/.m2/settings.xml
+#[command]
+pub async fn clear_registry_credentials() -> Result<RegistrySyncResult, String> {
+    sync_registry_credentials(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_npmrc_round_trip() {
+        let original = "save-exact=true\nregistry=https://registry.npmjs.org/";
+        let npm = NpmRegistry {
+            registry: "https://npm.client.com/repo/npm".to_string(),
+            scope: Some("client".to_string()),
+            token: "abc".to_string(),
+        };
+        let applied = apply_npmrc(original, Some(&npm));
+        assert!(applied.contains("@client:registry=https://npm.client.com/repo/npm\n"));
+        assert!(applied.contains("//npm.client.com/repo/npm/:_authToken=abc\n"));
+
+        let reapplied = apply_npmrc(&applied, Some(&npm));
+        assert_eq!(reapplied, applied);
+        assert_eq!(apply_npmrc(&applied, None), format!("{}\n", original));
+    }
+
+    #[test]
+    fn test_apply_settings_servers_round_trip() {
+        let original = "<settings>\n  <servers>\n    <server>\n      <id>adobe</id>\n    </server>\n  </servers>\n</settings>\n";
+        let server = |id: &str, password: &str| MavenServerCredential {
+            id: id.to_string(),
+            username: "dev".to_string(),
+            password: password.to_string(),
+        };
+        let servers = vec![server("client-nexus", "p<&"), server("adobe", "y")];
+        let (applied, duplicates) = apply_settings_servers(original, &servers).unwrap();
+        assert_eq!(duplicates, vec!["adobe".to_string()]);
+        assert!(applied.contains("<id>client-nexus</id>"));
+        assert!(applied.contains("<password>p&lt;&amp;</password>"));
+        assert_eq!(applied.matches("<servers>").count(), 1);
+        assert_eq!(apply_settings_servers(&applied, &[]).unwrap().0, original);
+
+        let bare = "<settings>\n</settings>\n";
+        let (applied, _) = apply_settings_servers(bare, &servers[..1]).unwrap();
+        assert!(applied.contains("<servers>"));
+        assert_eq!(apply_settings_servers(&applied, &[]).unwrap().0, bare);
+    }
+}
@@ -0,0 +1,297 @@
+// Environment Report Commands
+// Renders profiles, versions, instances and licenses as Markdown or HTML for sharing
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+use crate::commands::instance::{detect_all_instances_status, load_instances};
+use crate::commands::license::{calculate_license_status, list_aem_licenses};
+use crate::commands::profile::{get_active_profile, list_profiles};
+use crate::commands::version::{get_current_java_version, get_current_maven_config, get_current_node_version};
+use crate::i18n::Locale;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportResult {
+    pub format: ReportFormat,
+    pub content: String,
+    /// Where the report was written (when an output path was given)
+    pub file_path: Option<String>,
+}
+
+/// Everything a report shows, gathered once and rendered per format
+#[derive(Debug, Default)]
+struct EnvironmentSnapshot {
+    generated_at: String,
+    active_profile: Option<String>,
+    java_version: Option<String>,
+    node_version: Option<String>,
+    maven_config: Option<String>,
+    /// (name, java, node, is active)
+    profiles: Vec<(String, String, String, bool)>,
+    /// (name, type, url, status)
+    instances: Vec<(String, String, String, String)>,
+    /// (name, product, expiry, status)
+    licenses: Vec<(String, String, String, String)>,
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Serialized name of a unit enum variant (e.g. "port_conflict")
+fn serde_label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn or_dash(value: &Option<String>) -> String {
+    value.clone().filter(|v| !v.is_empty()).unwrap_or_else(|| "-".to_string())
+}
+
+async fn collect_snapshot() -> Result<EnvironmentSnapshot, String> {
+    let locale = Locale::current();
+    let active = get_active_profile().await?;
+
+    let profiles = list_profiles()
+        .await?
+        .into_iter()
+        .map(|p| {
+            let is_active = active.as_ref().map(|a| a.id == p.id).unwrap_or(false);
+            (p.name, or_dash(&p.java_version), or_dash(&p.node_version), is_active)
+        })
+        .collect();
+
+    let statuses = detect_all_instances_status().await.unwrap_or_default();
+    let instances = load_instances()?
+        .into_iter()
+        .map(|i| {
+            let status = statuses
+                .iter()
+                .find(|s| s.instance_id == i.id)
+                .map(|s| serde_label(&s.status))
+                .unwrap_or_else(|| serde_label(&i.status));
+            (
+                i.display_label(locale),
+                serde_label(&i.instance_type),
+                format!("http://{}:{}", i.host, i.port),
+                status,
+            )
+        })
+        .collect();
+
+    let licenses = list_aem_licenses()
+        .await?
+        .into_iter()
+        .map(|l| {
+            let expiry = match calculate_license_status(&l).1 {
+                Some(days) if days >= 0 => format!("{} ({} days)", or_dash(&l.expiry_date), days),
+                _ => or_dash(&l.expiry_date),
+            };
+            (l.name.clone(), l.product_name.clone(), expiry, serde_label(&l.status))
+        })
+        .collect();
+
+    Ok(EnvironmentSnapshot {
+        generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        active_profile: active.map(|p| p.name),
+        java_version: get_current_java_version().await.ok().flatten(),
+        node_version: get_current_node_version().await.ok().flatten(),
+        maven_config: get_current_maven_config().await.ok().flatten().map(|m| m.name),
+        profiles,
+        instances,
+        licenses,
+    })
+}
+
+/// Markdown table (pipes in cells are escaped)
+fn markdown_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let cell = |s: &str| s.replace('|', "\\|");
+    let mut out = format!("| {} |\n|{}\n", headers.join(" | "), " --- |".repeat(headers.len()));
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| cell(c)).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = String::from("<table>\n<tr>");
+    for header in headers {
+        out.push_str(&format!("<th>{}</th>", escape_html(header)));
+    }
+    out.push_str("</tr>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for cell in row {
+            out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+/// A report section as (title, headers, rows)
+type ReportSection = (&'static str, Vec<&'static str>, Vec<Vec<String>>);
+
+fn report_sections(snapshot: &EnvironmentSnapshot) -> Vec<ReportSection> {
+    vec![
+        (
+            "Current Environment",
+            vec!["Item", "Value"],
+            vec![
+                vec!["Active profile".to_string(), or_dash(&snapshot.active_profile)],
+                vec!["Java".to_string(), or_dash(&snapshot.java_version)],
+                vec!["Node.js".to_string(), or_dash(&snapshot.node_version)],
+                vec!["Maven settings".to_string(), or_dash(&snapshot.maven_config)],
+            ],
+        ),
+        (
+            "Profiles",
+            vec!["Name", "Java", "Node.js", "Active"],
+            snapshot
+                .profiles
+                .iter()
+                .map(|(name, java, node, active)| {
+                    vec![name.clone(), java.clone(), node.clone(), if *active { "yes" } else { "" }.to_string()]
+                })
+                .collect(),
+        ),
+        (
+            "Instances",
+            vec!["Name", "Type", "URL", "Status"],
+            snapshot
+                .instances
+                .iter()
+                .map(|(a, b, c, d)| vec![a.clone(), b.clone(), c.clone(), d.clone()])
+                .collect(),
+        ),
+        (
+            "Licenses",
+            vec!["Name", "Product", "Expires", "Status"],
+            snapshot
+                .licenses
+                .iter()
+                .map(|(a, b, c, d)| vec![a.clone(), b.clone(), c.clone(), d.clone()])
+                .collect(),
+        ),
+    ]
+}
+
+fn render_markdown(snapshot: &EnvironmentSnapshot) -> String {
+    let mut out = format!(
+        "# AEM Environment Report\n\n_Generated {}_\n",
+        snapshot.generated_at
+    );
+    for (title, headers, rows) in report_sections(snapshot) {
+        out.push_str(&format!("\n## {}\n\n", title));
+        if rows.is_empty() {
+            out.push_str("_None_\n");
+        } else {
+            out.push_str(&markdown_table(&headers, &rows));
+        }
+    }
+    out
+}
+
+fn render_html(snapshot: &EnvironmentSnapshot) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>AEM Environment Report</title>\n\
+         <style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>\n\
+         </head>\n<body>\n<h1>AEM Environment Report</h1>\n<p><em>Generated {}</em></p>\n",
+        escape_html(&snapshot.generated_at)
+    );
+    for (title, headers, rows) in report_sections(snapshot) {
+        out.push_str(&format!("<h2>{}</h2>\n", title));
+        if rows.is_empty() {
+            out.push_str("<p><em>None</em></p>\n");
+        } else {
+            out.push_str(&html_table(&headers, &rows));
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Render the current environment state as a Markdown or HTML report,
+/// optionally writing it to `output_path`
+#[command]
+pub async fn export_environment_report(
+    format: ReportFormat,
+    output_path: Option<String>,
+) -> Result<ReportResult, String> {
+    let snapshot = collect_snapshot().await?;
+    let content = match format {
+        ReportFormat::Markdown => render_markdown(&snapshot),
+        ReportFormat::Html => render_html(&snapshot),
+    };
+
+    let file_path = match output_path.filter(|p| !p.is_empty()) {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            fs::write(&path, &content).map_err(|e| format!("Failed to write report: {}", e))?;
+            Some(path.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+
+    Ok(ReportResult {
+        format,
+        content,
+        file_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_report() {
+        let snapshot = EnvironmentSnapshot {
+            generated_at: "2024-05-01 10:00".to_string(),
+            java_version: Some("11".to_string()),
+            instances: vec![(
+                "Author <dev>".to_string(),
+                "author".to_string(),
+                "http://localhost:4502".to_string(),
+                "running".to_string(),
+            )],
+            ..Default::default()
+        };
+
+        let markdown = render_markdown(&snapshot);
+        assert!(markdown.contains("| Java | 11 |"));
+        assert!(markdown.contains("| Author <dev> | author | http://localhost:4502 | running |"));
+        assert!(markdown.contains("## Licenses\n\n_None_"));
+
+        let html = render_html(&snapshot);
+        assert!(html.contains("<td>Author &lt;dev&gt;</td>"));
+    }
+}
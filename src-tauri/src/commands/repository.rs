@@ -0,0 +1,417 @@
+// Repository Commands
+// Fresh start of an instance: stop it, move crx-quickstart to the trash (or delete it) and
+// optionally unpack a new one from the quickstart JAR. The JAR and license stay in place.
+// Named snapshots keep copies of crx-quickstart that can be restored later.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::command;
+
+use crate::commands::activity::{record_activity, ActivityKind};
+use crate::commands::instance::{
+    check_port_open, find_quickstart_jar, load_instances, save_instances, stop_instance, wait_for_shutdown,
+    AemInstance, AemInstanceStatus,
+};
+use crate::commands::profile::{get_active_profile, get_startup_config};
+use crate::commands::settings::copy_tree;
+use crate::commands::trash::{move_to_trash, restore_from_trash, TrashKind};
+use crate::i18n::{Entity, Message};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepositoryResetMode {
+    /// Move crx-quickstart to the trash so it can be restored until the retention window ends
+    #[default]
+    Archive,
+    /// Delete crx-quickstart permanently
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryResetResult {
+    pub instance_id: String,
+    pub mode: RepositoryResetMode,
+    /// Trash entry holding the old crx-quickstart (Archive mode)
+    pub trash_entry_id: Option<String>,
+    /// Size of the removed crx-quickstart
+    pub removed_bytes: u64,
+    pub unpacked: bool,
+    pub elapsed_secs: u64,
+}
+
+/// A named copy of an instance's crx-quickstart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositorySnapshot {
+    pub id: String,
+    pub instance_id: String,
+    /// Unique per instance, e.g. "after-setup" or "with-test-content"
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRestoreResult {
+    pub snapshot: RepositorySnapshot,
+    /// Trash entry holding the crx-quickstart that was replaced
+    pub trash_entry_id: Option<String>,
+    pub elapsed_secs: u64,
+}
+
+/// Limit for `java -jar <quickstart> -unpack`
+const UNPACK_TIMEOUT: Duration = Duration::from_secs(600);
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_snapshots_dir() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("snapshots")
+}
+
+fn get_snapshots_index() -> PathBuf {
+    get_snapshots_dir().join("index.json")
+}
+
+/// Where the crx-quickstart copy of a snapshot is kept
+fn snapshot_content_dir(snapshot: &RepositorySnapshot) -> PathBuf {
+    get_snapshots_dir()
+        .join(&snapshot.instance_id)
+        .join(&snapshot.id)
+        .join("crx-quickstart")
+}
+
+pub(crate) fn load_snapshots() -> Result<Vec<RepositorySnapshot>, String> {
+    let file_path = get_snapshots_index();
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read snapshot index: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse snapshot index: {}", e))
+}
+
+pub(crate) fn save_snapshots(snapshots: &[RepositorySnapshot]) -> Result<(), String> {
+    fs::create_dir_all(get_snapshots_dir()).map_err(|e| format!("Failed to create snapshot directory: {}", e))?;
+    let content =
+        serde_json::to_string_pretty(snapshots).map_err(|e| format!("Failed to serialize snapshot index: {}", e))?;
+    fs::write(get_snapshots_index(), content).map_err(|e| format!("Failed to write snapshot index: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn find_instance(id: &str) -> Result<AemInstance, String> {
+    load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, id).localized())
+}
+
+/// The quickstart JAR of an instance whose `path` is either the JAR or its directory
+fn quickstart_jar(instance: &AemInstance) -> Result<PathBuf, String> {
+    let path = instance.host_path();
+    let jar = if path.is_dir() { find_quickstart_jar(&path)? } else { path };
+    if !jar.is_file() {
+        return Err(format!("Quickstart JAR not found: {}", jar.display()));
+    }
+    Ok(jar)
+}
+
+/// Java of the active profile, falling back to `java` on the PATH
+async fn java_executable() -> PathBuf {
+    get_active_profile()
+        .await
+        .ok()
+        .flatten()
+        .and_then(|p| p.java_path)
+        .filter(|p| !p.is_empty())
+        .map(|home| PathBuf::from(home).join("bin").join("java"))
+        .filter(|java| java.exists())
+        .unwrap_or_else(|| PathBuf::from("java"))
+}
+
+/// Stop the instance if it is up and wait until its port is free
+async fn stop_and_wait(instance: &AemInstance) -> Result<(), String> {
+    if check_port_open(&instance.host, instance.port, 500) {
+        stop_instance(instance.id.clone()).await?;
+    }
+    let stop_timeout = Duration::from_secs(get_startup_config().stop_timeout_secs);
+    if !wait_for_shutdown(&find_instance(&instance.id)?, stop_timeout).await {
+        return Err(format!(
+            "{} is still running after {}s; the repository was not touched",
+            instance.name,
+            stop_timeout.as_secs()
+        ));
+    }
+    Ok(())
+}
+
+/// Record the instance as stopped after its repository was replaced
+fn mark_stopped(id: &str) -> Result<(), String> {
+    let mut instances = load_instances()?;
+    if let Some(stored) = instances.iter_mut().find(|i| i.id == id) {
+        stored.status = AemInstanceStatus::Stopped;
+        stored.process_id = None;
+        save_instances(&instances)?;
+    }
+    Ok(())
+}
+
+/// Snapshot names are compared trimmed and case-insensitively
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Unpack a fresh crx-quickstart next to the JAR without starting the instance
+async fn unpack_quickstart(jar: &std::path::Path) -> Result<(), String> {
+    let working_dir = jar.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| PathBuf::from("."));
+    let mut cmd = tokio::process::Command::new(java_executable().await);
+    cmd.arg("-jar").arg(jar).arg("-unpack").current_dir(&working_dir).kill_on_drop(true);
+
+    let output = tokio::time::timeout(UNPACK_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| format!("Unpacking did not finish within {}s", UNPACK_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Failed to run java: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Unpacking failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Stop an instance and replace its repository with a fresh one. crx-quickstart is moved to
+/// the trash (`mode` Archive, the default) or deleted; with `unpack` a new crx-quickstart is
+/// unpacked from the quickstart JAR, otherwise the next start unpacks it.
+#[command]
+pub async fn reset_instance_repository(
+    id: String,
+    mode: Option<RepositoryResetMode>,
+    unpack: Option<bool>,
+) -> Result<RepositoryResetResult, String> {
+    let began = Instant::now();
+    let mode = mode.unwrap_or_default();
+    let instance = find_instance(&id)?;
+    if instance.wsl_distro.is_some() && unpack.unwrap_or(false) {
+        return Err("Unpacking is not supported for instances inside WSL".to_string());
+    }
+    let crx_dir = instance.crx_quickstart_dir();
+    // Check the JAR before anything is removed so the instance can always start again
+    let jar = quickstart_jar(&instance)?;
+
+    stop_and_wait(&instance).await?;
+
+    let mut result = RepositoryResetResult {
+        instance_id: id.clone(),
+        mode,
+        trash_entry_id: None,
+        removed_bytes: 0,
+        unpacked: false,
+        elapsed_secs: 0,
+    };
+    if crx_dir.exists() {
+        match mode {
+            RepositoryResetMode::Archive => {
+                let label = format!("{} repository", instance.name);
+                let entry = move_to_trash(TrashKind::Repository, &label, Some(&crx_dir), None)?;
+                result.removed_bytes = entry.size_bytes;
+                result.trash_entry_id = Some(entry.id);
+            }
+            RepositoryResetMode::Delete => {
+                result.removed_bytes = crate::platform::common::dir_size(&crx_dir);
+                fs::remove_dir_all(&crx_dir)
+                    .map_err(|e| format!("Failed to delete {}: {}", crx_dir.display(), e))?;
+            }
+        }
+    }
+
+    mark_stopped(&id)?;
+
+    if unpack.unwrap_or(false) {
+        unpack_quickstart(&jar).await?;
+        result.unpacked = true;
+    }
+    result.elapsed_secs = began.elapsed().as_secs();
+    record_activity(&id, ActivityKind::RepositoryReset, "Repository reset");
+    Ok(result)
+}
+
+/// Snapshots of one instance (or all), newest first
+#[command]
+pub async fn list_repository_snapshots(instance_id: Option<String>) -> Result<Vec<RepositorySnapshot>, String> {
+    let mut snapshots: Vec<RepositorySnapshot> = load_snapshots()?
+        .into_iter()
+        .filter(|s| instance_id.as_ref().is_none_or(|id| &s.instance_id == id))
+        .collect();
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(snapshots)
+}
+
+/// Copy the crx-quickstart of a stopped instance into a named snapshot
+#[command]
+pub async fn create_repository_snapshot(
+    instance_id: String,
+    name: String,
+    description: Option<String>,
+) -> Result<RepositorySnapshot, String> {
+    let instance = find_instance(&instance_id)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Snapshot name is required".to_string());
+    }
+    let mut snapshots = load_snapshots()?;
+    if snapshots.iter().any(|s| s.instance_id == instance_id && same_name(&s.name, &name)) {
+        return Err(format!("{} already has a snapshot named {}", instance.name, name));
+    }
+    // A running repository is not consistent on disk
+    if check_port_open(&instance.host, instance.port, 500) {
+        return Err(format!("Stop {} before taking a snapshot", instance.name));
+    }
+    let crx_dir = instance.crx_quickstart_dir();
+    if !crx_dir.is_dir() {
+        return Err(format!("crx-quickstart not found at {}", crx_dir.display()));
+    }
+
+    let mut snapshot = RepositorySnapshot {
+        id: uuid::Uuid::new_v4().to_string(),
+        instance_id,
+        name,
+        description: description.filter(|d| !d.trim().is_empty()),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        size_bytes: 0,
+    };
+    let target = snapshot_content_dir(&snapshot);
+    let mut errors = Vec::new();
+    let (_, bytes) = copy_tree(&crx_dir, &target, &mut errors);
+    if !errors.is_empty() {
+        let _ = fs::remove_dir_all(target.parent().unwrap_or(&target));
+        return Err(format!("Failed to copy crx-quickstart: {}", errors.join("; ")));
+    }
+    snapshot.size_bytes = bytes;
+
+    snapshots.push(snapshot.clone());
+    save_snapshots(&snapshots)?;
+    record_activity(
+        &snapshot.instance_id,
+        ActivityKind::SnapshotCreated,
+        format!("Snapshot {} taken", snapshot.name),
+    );
+    Ok(snapshot)
+}
+
+/// Stop the instance and replace its crx-quickstart with a copy of the snapshot. The copy is
+/// renamed into place once complete and the current crx-quickstart goes to the trash; the
+/// snapshot stays available.
+#[command]
+pub async fn restore_repository_snapshot(snapshot_id: String) -> Result<SnapshotRestoreResult, String> {
+    let began = Instant::now();
+    let snapshot = load_snapshots()?
+        .into_iter()
+        .find(|s| s.id == snapshot_id)
+        .ok_or_else(|| format!("Snapshot {} not found", snapshot_id))?;
+    let source = snapshot_content_dir(&snapshot);
+    if !source.is_dir() {
+        return Err(format!("Snapshot files are missing at {}", source.display()));
+    }
+    let instance = find_instance(&snapshot.instance_id)?;
+    stop_and_wait(&instance).await?;
+
+    // Copy next to crx-quickstart first, so a failed copy leaves the current repository in place
+    let crx_dir = instance.crx_quickstart_dir();
+    let staging = crx_dir.with_file_name(format!("crx-quickstart.restore-{}", uuid::Uuid::new_v4()));
+    let mut errors = Vec::new();
+    copy_tree(&source, &staging, &mut errors);
+    if !errors.is_empty() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Failed to restore snapshot {}: {}", snapshot.name, errors.join("; ")));
+    }
+
+    let mut trash_entry_id = None;
+    if crx_dir.exists() {
+        let label = format!("{} repository (before restoring {})", instance.name, snapshot.name);
+        match move_to_trash(TrashKind::Repository, &label, Some(&crx_dir), None) {
+            Ok(entry) => trash_entry_id = Some(entry.id),
+            Err(e) => {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+        }
+    }
+    if let Err(e) = fs::rename(&staging, &crx_dir) {
+        let _ = fs::remove_dir_all(&staging);
+        let mut message = format!("Failed to restore snapshot {}: {}", snapshot.name, e);
+        if let Some(id) = trash_entry_id {
+            if let Err(restore_error) = restore_from_trash(id).await {
+                message.push_str(&format!(
+                    " (the previous repository could not be put back and is in the trash: {})",
+                    restore_error
+                ));
+            }
+        }
+        return Err(message);
+    }
+    mark_stopped(&instance.id)?;
+    record_activity(
+        &instance.id,
+        ActivityKind::SnapshotRestored,
+        format!("Snapshot {} restored", snapshot.name),
+    );
+
+    Ok(SnapshotRestoreResult {
+        snapshot,
+        trash_entry_id,
+        elapsed_secs: began.elapsed().as_secs(),
+    })
+}
+
+#[command]
+pub async fn delete_repository_snapshot(snapshot_id: String) -> Result<bool, String> {
+    let mut snapshots = load_snapshots()?;
+    let Some(index) = snapshots.iter().position(|s| s.id == snapshot_id) else {
+        return Ok(false);
+    };
+    let snapshot = snapshots.remove(index);
+    let dir = snapshot_content_dir(&snapshot).parent().map(PathBuf::from);
+    let record = serde_json::to_value(&snapshot).map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    move_to_trash(TrashKind::Snapshot, &snapshot.name, dir.as_deref(), Some(record))?;
+    save_snapshots(&snapshots)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quickstart_jar() {
+        let dir = std::env::temp_dir().join(format!("aem-env-manager-reset-{}", uuid::Uuid::new_v4()));
+        let mut instance: AemInstance = serde_json::from_value(serde_json::json!({
+            "name": "Author", "instance_type": "author", "host": "localhost", "port": 4502
+        }))
+        .unwrap();
+        instance.path = dir.to_string_lossy().to_string();
+        std::fs::create_dir_all(dir.join("crx-quickstart")).unwrap();
+        std::fs::write(dir.join("license.properties"), "license").unwrap();
+        assert!(quickstart_jar(&instance).is_err());
+
+        std::fs::write(dir.join("aem-author-p4502.jar"), "jar").unwrap();
+        assert_eq!(quickstart_jar(&instance).unwrap(), dir.join("aem-author-p4502.jar"));
+        instance.path = dir.join("aem-author-p4502.jar").to_string_lossy().to_string();
+        assert_eq!(instance.crx_quickstart_dir(), dir.join("crx-quickstart"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
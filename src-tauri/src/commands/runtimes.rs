@@ -0,0 +1,291 @@
+// Runtime Downloads
+// Temurin JDKs (Adoptium API) and Node.js builds (nodejs.org) fetched through the shared
+// download cache with their published SHA-256 and unpacked into the app data directory,
+// which the Java and Node scans include
+
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::commands::certificates::http_client_builder;
+use crate::commands::downloads::download;
+use crate::commands::progress::ProgressReporter;
+use crate::platform::PlatformOps;
+
+/// Manager id that makes the install commands download the runtime themselves
+pub(crate) const DOWNLOAD_MANAGER_ID: &str = "download";
+
+const ADOPTIUM_API: &str = "https://api.adoptium.net/v3/assets/latest";
+const NODE_DIST: &str = "https://nodejs.org/dist";
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RuntimeKind {
+    Java,
+    Node,
+}
+
+impl RuntimeKind {
+    fn dir_name(self) -> &'static str {
+        match self {
+            RuntimeKind::Java => "java",
+            RuntimeKind::Node => "node",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumAsset {
+    binary: AdoptiumBinary,
+    /// e.g. "jdk-21.0.2+13"
+    release_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumBinary {
+    package: AdoptiumPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumPackage {
+    link: String,
+    checksum: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeRelease {
+    /// e.g. "v20.11.0"
+    version: String,
+}
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+/// Directory the downloaded runtimes of `kind` are unpacked into
+pub(crate) fn runtimes_dir(kind: RuntimeKind) -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("runtimes").join(kind.dir_name())
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn http_client() -> Result<reqwest::Client, String> {
+    http_client_builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Operating system and architecture names used by Adoptium
+fn adoptium_platform() -> (&'static str, &'static str) {
+    let os = match std::env::consts::OS {
+        "macos" => "mac",
+        "windows" => "windows",
+        _ => "linux",
+    };
+    let arch = match std::env::consts::ARCH {
+        "aarch64" => "aarch64",
+        _ => "x64",
+    };
+    (os, arch)
+}
+
+/// Archive name of a Node.js build for this platform, e.g. "node-v20.11.0-darwin-arm64.tar.gz"
+fn node_archive_name(version: &str) -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win",
+        _ => "linux",
+    };
+    let arch = match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        _ => "x64",
+    };
+    let extension = if os == "win" { "zip" } else { "tar.gz" };
+    format!("node-{}-{}-{}.{}", version, os, arch, extension)
+}
+
+/// Checksum of `file_name` in a SHASUMS256.txt listing
+fn find_checksum(shasums: &str, file_name: &str) -> Option<String> {
+    shasums.lines().find_map(|line| {
+        let (checksum, name) = line.split_once(char::is_whitespace)?;
+        (name.trim() == file_name).then(|| checksum.to_string())
+    })
+}
+
+/// Newest release in the nodejs.org index matching `version` ("20", "20.11" or "20.11.0")
+fn resolve_node_version(releases: &[NodeRelease], version: &str) -> Option<String> {
+    let wanted = version.trim().trim_start_matches('v');
+    releases
+        .iter()
+        .map(|r| r.version.as_str())
+        .find(|v| {
+            let v = v.trim_start_matches('v');
+            v == wanted || v.starts_with(&format!("{}.", wanted))
+        })
+        .map(str::to_string)
+}
+
+/// Unpack a .zip or .tar.gz archive into `dest`
+fn unpack(archive: &Path, url: &str, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    if url.ends_with(".zip") {
+        let file = fs::File::open(archive).map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+        zip.extract(dest).map_err(|e| format!("Failed to unpack archive: {}", e))
+    } else {
+        let status = Command::new("tar")
+            .arg("-xzf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dest)
+            .status()
+            .map_err(|e| format!("Failed to run tar: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("tar failed to unpack {}", archive.display()))
+        }
+    }
+}
+
+/// Unpack a downloaded archive to `runtimes_dir(kind)/name`; archives hold a single top-level
+/// directory, which becomes that directory
+fn install_archive(kind: RuntimeKind, archive: &Path, url: &str, name: &str) -> Result<PathBuf, String> {
+    let runtimes = runtimes_dir(kind);
+    let target = runtimes.join(name);
+    let staging = runtimes.join(format!(".staging-{}", uuid::Uuid::new_v4()));
+
+    let result = unpack(archive, url, &staging).and_then(|_| {
+        let entries: Vec<PathBuf> = fs::read_dir(&staging)
+            .map_err(|e| format!("Failed to read {}: {}", staging.display(), e))?
+            .flatten()
+            .map(|e| e.path())
+            .collect();
+        let root = match entries.as_slice() {
+            [single] if single.is_dir() => single.clone(),
+            _ => staging.clone(),
+        };
+        fs::rename(&root, &target).map_err(|e| format!("Failed to move {} into place: {}", name, e))
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result.map(|_| target)
+}
+
+// ============================================
+// Installation
+// ============================================
+
+/// Download and unpack the latest Temurin JDK of `major`; returns its directory
+pub(crate) async fn install_jdk(
+    app: &AppHandle,
+    major: &str,
+    vendor: &str,
+    operation_id: Option<String>,
+) -> Result<PathBuf, String> {
+    if !vendor.is_empty() && !vendor.eq_ignore_ascii_case("temurin") {
+        return Err(format!("Only Temurin JDKs can be downloaded directly, not {}", vendor));
+    }
+
+    let (os, arch) = adoptium_platform();
+    let url = format!(
+        "{}/{}/hotspot?architecture={}&image_type=jdk&os={}&vendor=eclipse",
+        ADOPTIUM_API, major, arch, os
+    );
+    let assets: Vec<AdoptiumAsset> = http_client()?
+        .get(&url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to look up Java {}: {}", major, e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Adoptium response: {}", e))?;
+    let asset = assets
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No Temurin JDK {} for {} {}", major, os, arch))?;
+
+    let target = runtimes_dir(RuntimeKind::Java).join(&asset.release_name);
+    if target.exists() {
+        return Ok(target);
+    }
+
+    let package = asset.binary.package;
+    let mut progress = ProgressReporter::new(app, operation_id, &asset.release_name);
+    let entry = download(&package.link, package.checksum.as_deref(), Some(&mut progress)).await?;
+    install_archive(RuntimeKind::Java, Path::new(&entry.path), &package.link, &asset.release_name)
+}
+
+/// Download and unpack a Node.js build (`version` may be a major or minor version, the newest
+/// matching release is used); returns its directory
+pub(crate) async fn install_node(
+    app: &AppHandle,
+    version: &str,
+    operation_id: Option<String>,
+) -> Result<PathBuf, String> {
+    let client = http_client()?;
+    let releases: Vec<NodeRelease> = client
+        .get(format!("{}/index.json", NODE_DIST))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to look up Node {}: {}", version, e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Node.js release index: {}", e))?;
+    let version =
+        resolve_node_version(&releases, version).ok_or_else(|| format!("Node {} is not a known release", version))?;
+
+    let target = runtimes_dir(RuntimeKind::Node).join(&version);
+    if target.exists() {
+        return Ok(target);
+    }
+
+    let archive_name = node_archive_name(&version);
+    let shasums = client
+        .get(format!("{}/{}/SHASUMS256.txt", NODE_DIST, version))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to fetch checksums of Node {}: {}", version, e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to fetch checksums of Node {}: {}", version, e))?;
+    let checksum = find_checksum(&shasums, &archive_name)
+        .ok_or_else(|| format!("Node {} has no build {}", version, archive_name))?;
+
+    let url = format!("{}/{}/{}", NODE_DIST, version, archive_name);
+    let mut progress = ProgressReporter::new(app, operation_id, &archive_name);
+    let entry = download(&url, Some(&checksum), Some(&mut progress)).await?;
+    install_archive(RuntimeKind::Node, Path::new(&entry.path), &url, &version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_checksum_and_resolve_node_version() {
+        let shasums = "aa11  node-v20.11.0-darwin-arm64.tar.gz\nbb22  node-v20.11.0-linux-x64.tar.gz\n";
+        assert_eq!(find_checksum(shasums, "node-v20.11.0-linux-x64.tar.gz").as_deref(), Some("bb22"));
+        assert_eq!(find_checksum(shasums, "node-v20.11.0-win-x64.zip"), None);
+
+        let releases: Vec<NodeRelease> = ["v21.6.1", "v20.11.1", "v20.11.0", "v20.9.0"]
+            .iter()
+            .map(|v| NodeRelease { version: v.to_string() })
+            .collect();
+        assert_eq!(resolve_node_version(&releases, "20").as_deref(), Some("v20.11.1"));
+        assert_eq!(resolve_node_version(&releases, "v20.11.0").as_deref(), Some("v20.11.0"));
+        assert_eq!(resolve_node_version(&releases, "2"), None);
+    }
+}
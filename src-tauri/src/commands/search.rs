@@ -0,0 +1,291 @@
+// Global Search Commands
+// One query over instances, profiles, licenses, Maven configs and projects, matched by name,
+// path and tags (instance run modes) and ranked for the global search box
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::commands::instance::load_instances;
+use crate::commands::license::list_aem_licenses;
+use crate::commands::profile::list_profiles;
+use crate::commands::project::load_projects;
+use crate::commands::version::list_maven_configs;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResultKind {
+    Instance,
+    Profile,
+    License,
+    MavenConfig,
+    Project,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub id: String,
+    pub title: String,
+    /// Secondary line, e.g. host:port of an instance or the path of a project
+    pub subtitle: Option<String>,
+    /// Field the query matched best, e.g. "name", "path" or "tag"
+    pub matched_field: String,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Name,
+    Tag,
+    Path,
+}
+
+impl FieldKind {
+    fn weight(self) -> u32 {
+        match self {
+            FieldKind::Name => 3,
+            FieldKind::Tag => 2,
+            FieldKind::Path => 1,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            FieldKind::Name => "name",
+            FieldKind::Tag => "tag",
+            FieldKind::Path => "path",
+        }
+    }
+}
+
+/// Searchable entity before scoring
+struct Candidate {
+    kind: SearchResultKind,
+    id: String,
+    title: String,
+    subtitle: Option<String>,
+    fields: Vec<(FieldKind, String)>,
+}
+
+/// Results returned when the caller sets no limit
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+// ============================================
+// Helpers
+// ============================================
+
+/// How well `token` (lowercase) matches `value`: exact, prefix, word prefix, substring
+fn match_score(token: &str, value: &str) -> u32 {
+    let value = value.to_lowercase();
+    if value == token {
+        100
+    } else if value.starts_with(token) {
+        80
+    } else if value
+        .match_indices(token)
+        .any(|(i, _)| value[..i].ends_with(|c: char| !c.is_alphanumeric()))
+    {
+        60
+    } else if value.contains(token) {
+        40
+    } else {
+        0
+    }
+}
+
+/// Every whitespace-separated token has to match some field; the score adds up the best
+/// weighted match of each token
+fn score_candidate(tokens: &[String], candidate: &Candidate) -> Option<(u32, FieldKind)> {
+    let mut total = 0;
+    let mut best_field: Option<(u32, FieldKind)> = None;
+    for token in tokens {
+        let (score, field) = candidate
+            .fields
+            .iter()
+            .map(|(field, value)| (match_score(token, value) * field.weight(), *field))
+            .max_by_key(|(score, _)| *score)
+            .filter(|(score, _)| *score > 0)?;
+        total += score;
+        if best_field.is_none_or(|(best, _)| score > best) {
+            best_field = Some((score, field));
+        }
+    }
+    best_field.map(|(_, field)| (total, field))
+}
+
+fn rank(query: &str, candidates: Vec<Candidate>, limit: usize) -> Vec<SearchResult> {
+    let tokens: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if tokens.is_empty() {
+        return Vec::new();
+    }
+    let mut results: Vec<SearchResult> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let (score, field) = score_candidate(&tokens, &candidate)?;
+            Some(SearchResult {
+                kind: candidate.kind,
+                id: candidate.id,
+                title: candidate.title,
+                subtitle: candidate.subtitle,
+                matched_field: field.label().to_string(),
+                score,
+            })
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    results.truncate(limit);
+    results
+}
+
+async fn collect_candidates() -> Result<Vec<Candidate>, String> {
+    let mut candidates = Vec::new();
+
+    for instance in load_instances()? {
+        let mut fields = vec![
+            (FieldKind::Name, instance.name.clone()),
+            (FieldKind::Path, instance.path.clone()),
+            (FieldKind::Path, format!("{}:{}", instance.host, instance.port)),
+        ];
+        fields.extend(instance.run_modes.iter().map(|m| (FieldKind::Tag, m.clone())));
+        candidates.push(Candidate {
+            kind: SearchResultKind::Instance,
+            subtitle: Some(format!("{}:{}", instance.host, instance.port)),
+            id: instance.id,
+            title: instance.name,
+            fields,
+        });
+    }
+
+    // A broken profile, license or Maven config store should not hide the other results
+    for profile in list_profiles().await.unwrap_or_default() {
+        let mut fields = vec![(FieldKind::Name, profile.name.clone())];
+        fields.extend(profile.description.iter().map(|d| (FieldKind::Tag, d.clone())));
+        fields.extend(profile.java_path.iter().map(|p| (FieldKind::Path, p.clone())));
+        fields.extend(profile.node_path.iter().map(|p| (FieldKind::Path, p.clone())));
+        candidates.push(Candidate {
+            kind: SearchResultKind::Profile,
+            subtitle: profile.description.clone(),
+            id: profile.id,
+            title: profile.name,
+            fields,
+        });
+    }
+
+    for license in list_aem_licenses().await.unwrap_or_default() {
+        let mut fields = vec![
+            (FieldKind::Name, license.name.clone()),
+            (FieldKind::Tag, license.product_name.clone()),
+        ];
+        fields.extend(license.customer_name.iter().map(|c| (FieldKind::Tag, c.clone())));
+        fields.extend(license.license_file_path.iter().map(|p| (FieldKind::Path, p.clone())));
+        candidates.push(Candidate {
+            kind: SearchResultKind::License,
+            subtitle: Some(license.product_name.clone()),
+            id: license.id,
+            title: license.name,
+            fields,
+        });
+    }
+
+    for config in list_maven_configs().await.unwrap_or_default() {
+        let mut fields = vec![
+            (FieldKind::Name, config.name.clone()),
+            (FieldKind::Path, config.path.clone()),
+        ];
+        fields.extend(config.local_repository.iter().map(|p| (FieldKind::Path, p.clone())));
+        candidates.push(Candidate {
+            kind: SearchResultKind::MavenConfig,
+            subtitle: Some(config.path.clone()),
+            id: config.id,
+            title: config.name,
+            fields,
+        });
+    }
+
+    for project in load_projects().unwrap_or_default() {
+        let mut fields = vec![
+            (FieldKind::Name, project.name.clone()),
+            (FieldKind::Path, project.path.clone()),
+        ];
+        fields.extend(project.pom.artifact_id.iter().map(|a| (FieldKind::Tag, a.clone())));
+        fields.extend(project.pom.group_id.iter().map(|g| (FieldKind::Tag, g.clone())));
+        candidates.push(Candidate {
+            kind: SearchResultKind::Project,
+            subtitle: Some(project.path.clone()),
+            id: project.id,
+            title: project.name,
+            fields,
+        });
+    }
+
+    Ok(candidates)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Search instances, profiles, licenses, Maven configs and projects by name, path and tags,
+/// best matches first (at most `limit`, default 50)
+#[command]
+pub async fn search(query: String, limit: Option<usize>) -> Result<Vec<SearchResult>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let candidates = collect_candidates().await?;
+    Ok(rank(&query, candidates, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(kind: SearchResultKind, name: &str, fields: &[(FieldKind, &str)]) -> Candidate {
+        let mut all = vec![(FieldKind::Name, name.to_string())];
+        all.extend(fields.iter().map(|(field, value)| (*field, value.to_string())));
+        Candidate {
+            kind,
+            id: name.to_lowercase(),
+            title: name.to_string(),
+            subtitle: None,
+            fields: all,
+        }
+    }
+
+    #[test]
+    fn test_match_score() {
+        assert_eq!(match_score("author", "Author"), 100);
+        assert_eq!(match_score("auth", "Author 6.5"), 80);
+        assert_eq!(match_score("6.5", "Author 6.5"), 60);
+        assert_eq!(match_score("thor", "Author"), 40);
+        assert_eq!(match_score("publish", "Author"), 0);
+    }
+
+    #[test]
+    fn test_rank() {
+        let candidates = vec![
+            candidate(SearchResultKind::Project, "wknd", &[(FieldKind::Path, "/work/author-tools")]),
+            candidate(SearchResultKind::Instance, "Author", &[(FieldKind::Tag, "local")]),
+            candidate(SearchResultKind::Instance, "Publish", &[(FieldKind::Tag, "local")]),
+        ];
+        let results = rank("author", candidates, 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Author");
+        assert_eq!(results[0].matched_field, "name");
+        assert_eq!(results[1].kind, SearchResultKind::Project);
+        assert_eq!(results[1].matched_field, "path");
+
+        let candidates = vec![
+            candidate(SearchResultKind::Instance, "Author", &[(FieldKind::Tag, "local")]),
+            candidate(SearchResultKind::Instance, "Publish", &[(FieldKind::Tag, "local")]),
+        ];
+        let results = rank("local pub", candidates, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Publish");
+        assert!(rank("  ", Vec::new(), 10).is_empty());
+    }
+}
@@ -0,0 +1,852 @@
+// Security Commands
+// Password generation, rotation and changes for local AEM users, with rotation history,
+// and a security audit of local instances
+
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::command;
+
+use crate::commands::credential_store::{list_stored_credentials, write_stored_credentials, DEFAULT_CREDENTIAL};
+use crate::commands::groups::group_instance_ids;
+use crate::commands::instance::{
+    get_instance_credentials, load_instances, save_stored_credentials, AemInstance, AemInstanceType, CredentialSource,
+};
+use crate::i18n::{Entity, Message, MessageCode};
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+/// Last password rotation of a user on an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordRotation {
+    pub instance_id: String,
+    pub username: String,
+    pub rotated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordRotationResult {
+    pub username: String,
+    pub success: bool,
+    /// The new password, shown once so it can be handed over (None on failure)
+    pub password: Option<String>,
+    /// The app's stored credentials for the instance were updated
+    pub vault_updated: bool,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordChangeResult {
+    pub instance_id: String,
+    pub username: String,
+    pub success: bool,
+    /// Stored credentials of the instance that were updated to the new password
+    pub updated_credentials: Vec<String>,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not applicable to the instance type, or the instance could not be reached
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityFinding {
+    pub check: String,
+    pub title: String,
+    pub severity: Severity,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// Translation of `detail`
+    #[serde(flatten)]
+    pub message_code: MessageCode,
+    pub recommendation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityAuditResult {
+    pub instance_id: String,
+    pub timestamp: String,
+    /// 100 minus the weight of every failed check (never below 0)
+    pub score: u32,
+    pub findings: Vec<SecurityFinding>,
+}
+
+impl Severity {
+    fn weight(self) -> u32 {
+        match self {
+            Severity::Critical => 40,
+            Severity::High => 20,
+            Severity::Medium => 10,
+            Severity::Low => 5,
+        }
+    }
+}
+
+/// Well-known accounts shipped with AEM or used in tutorials
+const DEFAULT_ACCOUNTS: [(&str, &str); 2] = [("admin", "admin"), ("author", "author")];
+
+/// Endpoints the dispatcher must not pass through, including common filter bypasses
+const PROTECTED_ENDPOINTS: [&str; 8] = [
+    "/system/console",
+    "/crx/de/index.jsp",
+    "/bin/querybuilder.json",
+    "/bin/querybuilder.json/a.css",
+    "/bin/querybuilder.json;%0aa.css",
+    "/libs/granite/security/currentuser.json",
+    "/content.infinity.json",
+    "/etc/packages.json",
+];
+
+/// A rotation older than this is reported
+const ROTATION_MAX_AGE_DAYS: i64 = 90;
+
+const DEFAULT_PASSWORD_LENGTH: usize = 20;
+const MIN_PASSWORD_LENGTH: usize = 12;
+
+const LOWERCASE: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"23456789";
+/// Symbols that need no quoting in shells, URLs or JSON
+const SYMBOLS: &[u8] = b"-_.!@#%+=";
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_rotations_file() -> PathBuf {
+    crate::platform::current_platform()
+        .get_data_dir()
+        .join("password_rotations.json")
+}
+
+pub(crate) fn load_rotations() -> Vec<PasswordRotation> {
+    fs::read_to_string(get_rotations_file())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn record_rotation(instance_id: &str, username: &str) -> Result<(), String> {
+    let mut rotations = load_rotations();
+    rotations.retain(|r| !(r.instance_id == instance_id && r.username == username));
+    rotations.push(PasswordRotation {
+        instance_id: instance_id.to_string(),
+        username: username.to_string(),
+        rotated_at: chrono::Utc::now().to_rfc3339(),
+    });
+
+    let file_path = get_rotations_file();
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&rotations).map_err(|e| format!("Failed to serialize rotations: {}", e))?;
+    fs::write(&file_path, content).map_err(|e| format!("Failed to write rotations: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Random password with at least one character of every class (ambiguous 0/O/1/l/I left out)
+pub(crate) fn new_password(length: usize, symbols: bool) -> String {
+    let length = length.max(MIN_PASSWORD_LENGTH);
+    let mut classes = vec![LOWERCASE, UPPERCASE, DIGITS];
+    if symbols {
+        classes.push(SYMBOLS);
+    }
+    let all: Vec<u8> = classes.concat();
+
+    let mut rng = OsRng;
+    let mut chars: Vec<u8> = classes.iter().map(|c| c[rng.gen_range(0..c.len())]).collect();
+    while chars.len() < length {
+        chars.push(all[rng.gen_range(0..all.len())]);
+    }
+    chars.shuffle(&mut rng);
+
+    String::from_utf8(chars).unwrap_or_default()
+}
+
+/// Home path of a user (e.g. /home/users/a/abc123) via QueryBuilder
+async fn find_user_path(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    username: &str,
+) -> Result<String, String> {
+    find_authorizable_path(client, instance, credentials, "/home/users", "rep:User", username)
+        .await?
+        .ok_or_else(|| format!("User {} not found", username))
+}
+
+/// Path of the user or group with `id` under `root`; None when there is none
+async fn find_authorizable_path(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    root: &str,
+    node_type: &str,
+    id: &str,
+) -> Result<Option<String>, String> {
+    let json: serde_json::Value = client
+        .get(format!("{}/bin/querybuilder.json", instance.base_url()))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .query(&[
+            ("path", root),
+            ("type", node_type),
+            ("property", "rep:authorizableId"),
+            ("property.value", id),
+            ("p.limit", "1"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse user query: {}", e))?;
+
+    Ok(json
+        .get("hits")
+        .and_then(|h| h.as_array())
+        .and_then(|hits| hits.first())
+        .and_then(|h| h.get("path"))
+        .and_then(|p| p.as_str())
+        .map(str::to_string))
+}
+
+/// Create a user with `password` and add it to `group` through the Granite user API
+pub(crate) async fn create_user(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    username: &str,
+    password: &str,
+    group: &str,
+) -> Result<(), String> {
+    let response = client
+        .post(format!("{}/libs/granite/security/post/authorizables", instance.base_url()))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(&[("createUser", ""), ("authorizableId", username), ("rep:password", password)])
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Creating user {} returned HTTP {}", username, response.status().as_u16()));
+    }
+
+    let group_path = find_authorizable_path(client, instance, credentials, "/home/groups", "rep:Group", group)
+        .await?
+        .ok_or_else(|| format!("Group {} not found", group))?;
+    let response = client
+        .post(format!("{}{}.rw.html", instance.base_url(), group_path))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(&[("addMembers", username)])
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Adding {} to {} returned HTTP {}", username, group, response.status().as_u16()))
+    }
+}
+
+/// Delete a user through the Granite user API; a user that no longer exists counts as deleted
+pub(crate) async fn delete_user(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    username: &str,
+) -> Result<(), String> {
+    let Some(user_path) =
+        find_authorizable_path(client, instance, credentials, "/home/users", "rep:User", username).await?
+    else {
+        return Ok(());
+    };
+    let response = client
+        .post(format!("{}{}.rw.html", instance.base_url(), user_path))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(&[("deleteAuthorizable", "")])
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Deleting user {} returned HTTP {}", username, response.status().as_u16()))
+    }
+}
+
+/// Set a user's password through the user management endpoint
+async fn set_user_password(
+    client: &reqwest::Client,
+    instance: &AemInstance,
+    credentials: &(String, String),
+    username: &str,
+    password: &str,
+) -> Result<(), String> {
+    let user_path = find_user_path(client, instance, credentials, username).await?;
+    let response = client
+        .post(format!("{}{}.rw.html", instance.base_url(), user_path))
+        .basic_auth(&credentials.0, Some(&credentials.1))
+        .form(&[("rep:password", password), (":currentPassword", credentials.1.as_str())])
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Password change returned HTTP {}", response.status().as_u16()))
+    }
+}
+
+/// Update every stored credential of the instance that logs in as `username`, recording the
+/// names updated and the failures in `change`
+fn sync_stored_credentials(instance_id: &str, password: &str, change: &mut PasswordChange) {
+    let stored = match list_stored_credentials(instance_id) {
+        Ok(stored) => stored,
+        Err(e) => {
+            change.errors.push(format!("Failed to read stored credentials: {}", e));
+            return;
+        }
+    };
+    for (name, (stored_username, _)) in stored {
+        if stored_username != change.username {
+            continue;
+        }
+        match write_stored_credentials(instance_id, &name, &change.username, password) {
+            Ok(()) => change.updated_credentials.push(name),
+            Err(e) => change.errors.push(format!("Failed to update stored credential {}: {}", name, e)),
+        }
+    }
+}
+
+/// Outcome of a password change that went through on the instance
+struct PasswordChange {
+    username: String,
+    updated_credentials: Vec<String>,
+    /// Local bookkeeping that failed afterwards
+    errors: Vec<String>,
+}
+
+/// Set the password of `username` (the account the app authenticates with when None) on one
+/// instance and update the stored credentials that use it
+async fn change_password_on(
+    instance_id: &str,
+    username: Option<&str>,
+    new_password: &str,
+) -> Result<PasswordChange, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == instance_id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, instance_id).localized())?;
+    if instance.instance_type == AemInstanceType::Dispatcher {
+        return Err("Dispatchers have no AEM users".to_string());
+    }
+    let (login, password, source) = get_instance_credentials(&instance.id)?;
+    let credentials = (login, password);
+    let username = username.unwrap_or(&credentials.0).to_string();
+
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(15)))
+        .build()
+        .map_err(|e| e.to_string())?;
+    set_user_password(&client, &instance, &credentials, &username, new_password).await?;
+
+    // The password changed on AEM: from here on failures are reported, never returned early
+    let mut change = PasswordChange {
+        username,
+        updated_credentials: Vec::new(),
+        errors: Vec::new(),
+    };
+    sync_stored_credentials(&instance.id, new_password, &mut change);
+    // The app logged in with the default account from the settings, which no longer works here
+    if source == CredentialSource::DefaultAccount && change.username == credentials.0 {
+        match save_stored_credentials(&instance.id, &change.username, new_password) {
+            Ok(()) => change.updated_credentials.push(DEFAULT_CREDENTIAL.to_string()),
+            Err(e) => change.errors.push(format!("Failed to store credentials: {}", e)),
+        }
+    }
+    if let Err(e) = record_rotation(&instance.id, &change.username) {
+        change.errors.push(format!("Failed to record the rotation: {}", e));
+    }
+    Ok(change)
+}
+
+fn finding(
+    check: &str,
+    title: &str,
+    severity: Severity,
+    status: CheckStatus,
+    (detail, message_code): (String, MessageCode),
+    recommendation: &str,
+) -> SecurityFinding {
+    SecurityFinding {
+        check: check.to_string(),
+        title: title.to_string(),
+        severity,
+        status,
+        detail,
+        message_code,
+        recommendation: recommendation.to_string(),
+    }
+}
+
+fn unreachable() -> (String, MessageCode) {
+    ("Instance not reachable".to_string(), MessageCode::new("audit_unreachable"))
+}
+
+fn audit_score(findings: &[SecurityFinding]) -> u32 {
+    let penalty: u32 = findings
+        .iter()
+        .filter(|f| f.status == CheckStatus::Fail)
+        .map(|f| f.severity.weight())
+        .sum();
+    100u32.saturating_sub(penalty)
+}
+
+async fn check_default_passwords(client: &reqwest::Client, instance: &AemInstance) -> SecurityFinding {
+    let mut accepted = Vec::new();
+    for (username, password) in DEFAULT_ACCOUNTS {
+        let response = client
+            .get(format!("{}/libs/granite/security/currentuser.json", instance.base_url()))
+            .basic_auth(username, Some(password))
+            .send()
+            .await;
+        let Ok(response) = response else {
+            return finding(
+                "default_passwords",
+                "Default passwords",
+                Severity::Critical,
+                CheckStatus::Skipped,
+                unreachable(),
+                "Start the instance and run the audit again",
+            );
+        };
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        if body.get("authorizableId").and_then(|a| a.as_str()) == Some(username) {
+            accepted.push(username);
+        }
+    }
+
+    let status = if accepted.is_empty() { CheckStatus::Pass } else { CheckStatus::Fail };
+    finding(
+        "default_passwords",
+        "Default passwords",
+        Severity::Critical,
+        status,
+        if accepted.is_empty() {
+            (
+                "No default account accepted its default password".to_string(),
+                MessageCode::new("audit_default_passwords_rejected"),
+            )
+        } else {
+            (
+                format!("Default password accepted for: {}", accepted.join(", ")),
+                MessageCode::new("audit_default_passwords_accepted").with("accounts", accepted.join(", ")),
+            )
+        },
+        "Rotate the passwords of these accounts (rotate_user_passwords)",
+    )
+}
+
+/// Anonymous Sling POST into /content/usergenerated; a probe node that gets created
+/// is removed again with the instance credentials
+async fn check_anonymous_write(client: &reqwest::Client, instance: &AemInstance) -> SecurityFinding {
+    let probe = format!("/content/usergenerated/aem-env-audit-{}", uuid::Uuid::new_v4());
+    let response = client
+        .post(format!("{}{}", instance.base_url(), probe))
+        .form(&[("jcr:primaryType", "nt:unstructured")])
+        .send()
+        .await;
+
+    let (status, detail) = match response {
+        Ok(resp) if resp.status().is_success() => {
+            if let Ok((username, password, _)) = get_instance_credentials(&instance.id) {
+                let _ = client
+                    .post(format!("{}{}", instance.base_url(), probe))
+                    .basic_auth(&username, Some(&password))
+                    .form(&[(":operation", "delete")])
+                    .send()
+                    .await;
+            }
+            let code = MessageCode::new("audit_anonymous_post_created").with("path", &probe);
+            (CheckStatus::Fail, (format!("Anonymous POST created {}", probe), code))
+        }
+        Ok(resp) => {
+            let status = resp.status().as_u16();
+            let code = MessageCode::new("audit_anonymous_post_rejected").with("status", status);
+            (CheckStatus::Pass, (format!("Anonymous POST rejected (HTTP {})", status), code))
+        }
+        Err(_) => (CheckStatus::Skipped, unreachable()),
+    };
+
+    finding(
+        "anonymous_write",
+        "Anonymous write access via Sling POST",
+        Severity::Critical,
+        status,
+        detail,
+        "Remove write permissions of the anonymous user and everyone group below /content",
+    )
+}
+
+async fn check_crxde_on_publish(client: &reqwest::Client, instance: &AemInstance) -> SecurityFinding {
+    let (status, detail) = if instance.instance_type != AemInstanceType::Publish {
+        (
+            CheckStatus::Skipped,
+            ("Only checked on publish instances".to_string(), MessageCode::new("audit_publish_only")),
+        )
+    } else {
+        match client.get(format!("{}/crx/de/index.jsp", instance.base_url())).send().await {
+            Ok(resp) if resp.status().as_u16() == 200 => (
+                CheckStatus::Fail,
+                ("CRXDE Lite answers on publish".to_string(), MessageCode::new("audit_crxde_answers")),
+            ),
+            Ok(resp) => {
+                let status = resp.status().as_u16();
+                let code = MessageCode::new("audit_crxde_status").with("status", status);
+                (CheckStatus::Pass, (format!("CRXDE Lite returned HTTP {}", status), code))
+            }
+            Err(_) => (CheckStatus::Skipped, unreachable()),
+        }
+    };
+
+    finding(
+        "crxde_publish",
+        "CRXDE Lite enabled on publish",
+        Severity::High,
+        status,
+        detail,
+        "Disable the WebDAV/DavEx servlet and CRXDE support bundle on publish",
+    )
+}
+
+async fn check_dispatcher_bypass(client: &reqwest::Client, instance: &AemInstance) -> SecurityFinding {
+    let (status, detail) = if instance.instance_type != AemInstanceType::Dispatcher {
+        (
+            CheckStatus::Skipped,
+            ("Only checked on dispatcher instances".to_string(), MessageCode::new("audit_dispatcher_only")),
+        )
+    } else {
+        let mut exposed = Vec::new();
+        let mut reachable = false;
+        for endpoint in PROTECTED_ENDPOINTS {
+            if let Ok(resp) = client.get(format!("{}{}", instance.base_url(), endpoint)).send().await {
+                reachable = true;
+                if resp.status().as_u16() == 200 {
+                    exposed.push(endpoint);
+                }
+            }
+        }
+        match (reachable, exposed.is_empty()) {
+            (false, _) => (
+                CheckStatus::Skipped,
+                ("Dispatcher not reachable".to_string(), MessageCode::new("audit_dispatcher_unreachable")),
+            ),
+            (true, true) => (
+                CheckStatus::Pass,
+                ("All protected endpoints are blocked".to_string(), MessageCode::new("audit_endpoints_blocked")),
+            ),
+            (true, false) => {
+                let endpoints = exposed.join(", ");
+                let code = MessageCode::new("audit_endpoints_exposed").with("endpoints", &endpoints);
+                (CheckStatus::Fail, (format!("Reachable through the dispatcher: {}", endpoints), code))
+            }
+        }
+    };
+
+    finding(
+        "dispatcher_bypass",
+        "Dispatcher filter bypass on known endpoints",
+        Severity::High,
+        status,
+        detail,
+        "Deny these paths in the dispatcher /filter section (check with check_dispatcher_path)",
+    )
+}
+
+/// Last rotation of the account the app uses, from the rotation history
+fn check_rotation_age(instance: &AemInstance) -> SecurityFinding {
+    let username = get_instance_credentials(&instance.id).map(|(u, _, _)| u).ok();
+    let rotated_at = username.as_ref().and_then(|u| {
+        load_rotations()
+            .into_iter()
+            .find(|r| r.instance_id == instance.id && &r.username == u)
+            .and_then(|r| chrono::DateTime::parse_from_rfc3339(&r.rotated_at).ok())
+    });
+
+    let (status, detail) = match (username, rotated_at) {
+        (None, _) => (
+            CheckStatus::Skipped,
+            ("No credentials for this instance".to_string(), MessageCode::new("audit_no_credentials")),
+        ),
+        (Some(user), None) => {
+            let code = MessageCode::new("audit_password_never_rotated").with("username", &user);
+            (CheckStatus::Fail, (format!("Password of {} was never rotated", user), code))
+        }
+        (Some(user), Some(at)) => {
+            let age = (chrono::Utc::now() - at.with_timezone(&chrono::Utc)).num_days();
+            let status = if age > ROTATION_MAX_AGE_DAYS { CheckStatus::Fail } else { CheckStatus::Pass };
+            let code = MessageCode::new("audit_password_rotated")
+                .with("username", &user)
+                .with("days", age);
+            (status, (format!("Password of {} rotated {} day(s) ago", user, age), code))
+        }
+    };
+
+    finding(
+        "password_rotation",
+        "Password rotation",
+        Severity::Low,
+        status,
+        detail,
+        "Rotate the password regularly",
+    )
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Generate a strong password (at least 12 characters)
+#[command]
+pub async fn generate_password(length: Option<usize>, include_symbols: Option<bool>) -> Result<String, String> {
+    Ok(new_password(
+        length.unwrap_or(DEFAULT_PASSWORD_LENGTH),
+        include_symbols.unwrap_or(true),
+    ))
+}
+
+/// Give the selected users of an instance new generated passwords. When the account the
+/// app authenticates with is rotated, the stored credentials are updated; it is rotated
+/// last so the other changes still use the old password.
+#[command]
+pub async fn rotate_user_passwords(
+    id: String,
+    usernames: Vec<String>,
+    length: Option<usize>,
+) -> Result<Vec<PasswordRotationResult>, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+    let (username, password, _) = get_instance_credentials(&instance.id)?;
+    let credentials = (username, password);
+
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(15)))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut ordered = usernames;
+    ordered.sort();
+    ordered.dedup();
+    ordered.sort_by_key(|u| *u == credentials.0);
+
+    let mut results = Vec::new();
+    for user in ordered {
+        let new = new_password(length.unwrap_or(DEFAULT_PASSWORD_LENGTH), true);
+        let mut result = PasswordRotationResult {
+            username: user.clone(),
+            success: false,
+            password: None,
+            vault_updated: false,
+            error: None,
+            message_code: None,
+        };
+
+        match set_user_password(&client, &instance, &credentials, &user, &new).await {
+            // The password changed on AEM: local failures are reported, never returned early,
+            // so the new password is not lost
+            Ok(()) => {
+                let mut local_errors = Vec::new();
+                if user == credentials.0 {
+                    match save_stored_credentials(&instance.id, &user, &new) {
+                        Ok(()) => result.vault_updated = true,
+                        Err(e) => local_errors.push(format!("Failed to update stored credentials: {}", e)),
+                    }
+                }
+                if let Err(e) = record_rotation(&instance.id, &user) {
+                    local_errors.push(format!("Failed to record the rotation: {}", e));
+                }
+                result.success = true;
+                result.password = Some(new);
+                result.error = (!local_errors.is_empty()).then(|| local_errors.join("; "));
+                result.message_code = Some(match result.error {
+                    Some(ref error) => MessageCode::new("password_rotated_with_errors")
+                        .with("username", &user)
+                        .with("detail", error),
+                    None => MessageCode::new("password_rotated").with("username", &user),
+                });
+            }
+            Err(e) => {
+                result.message_code =
+                    Some(MessageCode::new("password_rotation_failed").with("username", &user).with("detail", &e));
+                result.error = Some(e);
+            }
+        }
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Set the password of a user (the admin account the app authenticates with when `username`
+/// is omitted) on each of the given instances, plus the members of `group_id`, through the
+/// Granite user API, and update the stored credentials of that user. Instances are changed
+/// one after another; a failure on one does not stop the others.
+#[command]
+pub async fn change_instance_password(
+    instance_ids: Vec<String>,
+    group_id: Option<String>,
+    username: Option<String>,
+    new_password: String,
+) -> Result<Vec<PasswordChangeResult>, String> {
+    if new_password.is_empty() {
+        return Err("The new password must not be empty".to_string());
+    }
+    let mut instance_ids = instance_ids;
+    if let Some(group_id) = group_id {
+        for id in group_instance_ids(&group_id)? {
+            if !instance_ids.contains(&id) {
+                instance_ids.push(id);
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    for instance_id in instance_ids {
+        let outcome = change_password_on(&instance_id, username.as_deref(), &new_password).await;
+        results.push(match outcome {
+            Ok(change) => {
+                let error = (!change.errors.is_empty()).then(|| change.errors.join("; "));
+                let message_code = match error {
+                    Some(ref error) => MessageCode::new("password_changed_with_errors").with("detail", error),
+                    None => MessageCode::new("password_changed"),
+                };
+                PasswordChangeResult {
+                    instance_id,
+                    message_code: Some(message_code.with("username", &change.username)),
+                    username: change.username,
+                    success: true,
+                    updated_credentials: change.updated_credentials,
+                    error,
+                }
+            }
+            Err(e) => PasswordChangeResult {
+                instance_id,
+                username: username.clone().unwrap_or_default(),
+                success: false,
+                updated_credentials: Vec::new(),
+                message_code: Some(MessageCode::new("password_change_failed").with("detail", &e)),
+                error: Some(e),
+            },
+        });
+    }
+    Ok(results)
+}
+
+/// Last rotation per user, optionally for one instance
+#[command]
+pub async fn list_password_rotations(instance_id: Option<String>) -> Result<Vec<PasswordRotation>, String> {
+    Ok(load_rotations()
+        .into_iter()
+        .filter(|r| instance_id.as_ref().map(|id| &r.instance_id == id).unwrap_or(true))
+        .collect())
+}
+
+/// Check a local instance for default passwords, anonymous write access, CRXDE on publish,
+/// dispatcher filter bypasses and stale passwords; returns scored findings
+#[command]
+pub async fn audit_instance_security(id: String) -> Result<SecurityAuditResult, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+    let client = instance
+        .apply_http_overrides(
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(reqwest::redirect::Policy::none()),
+        )
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut findings = Vec::new();
+    if instance.instance_type != AemInstanceType::Dispatcher {
+        findings.push(check_default_passwords(&client, &instance).await);
+        findings.push(check_anonymous_write(&client, &instance).await);
+    }
+    findings.push(check_crxde_on_publish(&client, &instance).await);
+    findings.push(check_dispatcher_bypass(&client, &instance).await);
+    if instance.instance_type != AemInstanceType::Dispatcher {
+        findings.push(check_rotation_age(&instance));
+    }
+
+    Ok(SecurityAuditResult {
+        instance_id: id,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        score: audit_score(&findings),
+        findings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_password() {
+        let password = new_password(24, true);
+        assert_eq!(password.len(), 24);
+        for class in [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS] {
+            assert!(password.bytes().any(|b| class.contains(&b)));
+        }
+
+        let short = new_password(4, false);
+        assert_eq!(short.len(), MIN_PASSWORD_LENGTH);
+        assert!(!short.bytes().any(|b| SYMBOLS.contains(&b)));
+    }
+
+    #[test]
+    fn test_audit_score() {
+        let check = |severity, status| finding("c", "t", severity, status, (String::new(), MessageCode::default()), "");
+        let findings = vec![
+            check(Severity::Critical, CheckStatus::Fail),
+            check(Severity::High, CheckStatus::Pass),
+            check(Severity::High, CheckStatus::Skipped),
+            check(Severity::Low, CheckStatus::Fail),
+        ];
+        assert_eq!(audit_score(&findings), 55);
+
+        let all_failed = vec![check(Severity::Critical, CheckStatus::Fail); 3];
+        assert_eq!(audit_score(&all_failed), 0);
+    }
+}
@@ -0,0 +1,317 @@
+// Service Pack Installer
+// Guided install of AEM 6.5 service packs and cumulative fix packs: checks the package
+// against the instance version, installs it through Package Manager, waits for the bundle
+// restarts to settle and records the new product version on the instance
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::aem_catalog::{parse_product_version, ReleaseKind};
+use crate::commands::instance::{
+    check_aem_http_ready, fetch_version_info, get_instance_credentials, load_instances, save_instances, AemInstance,
+};
+use crate::commands::packages::post_package;
+use crate::commands::readiness::wait_until_ready;
+use crate::i18n::{Entity, Message, MessageCode};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServicePackKind {
+    ServicePack,
+    CumulativeFixPack,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicePackInfo {
+    pub kind: ServicePackKind,
+    pub group: String,
+    pub name: String,
+    /// Package version, e.g. 6.5.21.0
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServicePackPhase {
+    Verifying,
+    /// Uploading and installing through Package Manager (one request)
+    Installing,
+    /// Installed; waiting for the bundles to restart and the instance to be ready
+    WaitingForRestart,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicePackProgress {
+    pub instance_id: String,
+    pub phase: ServicePackPhase,
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+    pub elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicePackInstallResult {
+    pub instance_id: String,
+    pub package: ServicePackInfo,
+    pub previous_version: String,
+    /// Product version after the restart (None when the instance did not become ready)
+    pub new_version: Option<String>,
+    pub ready: bool,
+    pub elapsed_secs: u64,
+    /// Package Manager response log
+    pub log: String,
+}
+
+/// Event emitted with a `ServicePackProgress` whenever an install enters a new phase
+pub const SERVICE_PACK_PROGRESS_EVENT: &str = "service-pack-progress";
+
+/// Default wait for the instance to be ready again after the install
+const DEFAULT_RESTART_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How long to wait for the bundle restarts to take the instance down after the install
+const RESTART_ONSET_TIMEOUT: Duration = Duration::from_secs(120);
+
+// ============================================
+// Helpers
+// ============================================
+
+/// `<entry key="..">..</entry>` pairs of META-INF/vault/properties.xml
+fn parse_package_properties(xml: &str) -> HashMap<String, String> {
+    let Ok(pattern) = regex::Regex::new(r#"<entry key="([^"]+)">([^<]*)</entry>"#) else {
+        return HashMap::new();
+    };
+    pattern
+        .captures_iter(xml)
+        .map(|c| (c[1].to_string(), c[2].trim().to_string()))
+        .collect()
+}
+
+fn read_package_info(path: &Path) -> Result<ServicePackInfo, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Not a content package: {}", e))?;
+    let mut xml = String::new();
+    archive
+        .by_name("META-INF/vault/properties.xml")
+        .map_err(|_| "Not a content package: META-INF/vault/properties.xml is missing".to_string())?
+        .read_to_string(&mut xml)
+        .map_err(|e| format!("Failed to read package properties: {}", e))?;
+
+    let properties = parse_package_properties(&xml);
+    let get = |key: &str| properties.get(key).cloned().unwrap_or_default();
+    let (group, name, version) = (get("group"), get("name"), get("version"));
+    let label = format!("{} {}", group, name).to_lowercase();
+    let kind = if label.contains("cumulativefixpack") || label.contains("cfp") {
+        ServicePackKind::CumulativeFixPack
+    } else if label.contains("servicepack") || label.contains("service-pkg") {
+        ServicePackKind::ServicePack
+    } else {
+        return Err(format!("{}:{} is not an AEM service pack or cumulative fix pack", group, name));
+    };
+    Ok(ServicePackInfo {
+        kind,
+        group,
+        name,
+        version,
+    })
+}
+
+/// Check that `package` applies to an instance running `instance_version`: service packs
+/// must be newer than the installed one, fix packs must match it
+fn check_precondition(package: &ServicePackInfo, instance_version: &str) -> Result<(), String> {
+    let Some((ReleaseKind::OnPremise, installed, Some(installed_sp))) = parse_product_version(instance_version) else {
+        return Err(format!("Instance runs {}, not AEM 6.x with a service pack level", instance_version));
+    };
+    let Some((_, target, Some(target_sp))) = parse_product_version(&package.version) else {
+        return Err(format!("Unrecognized package version {}", package.version));
+    };
+    let line = |v: &str| v.split('.').take(2).collect::<Vec<_>>().join(".");
+    if line(&installed) != line(&target) {
+        return Err(format!("{} is for AEM {}, the instance runs {}", package.name, line(&target), installed));
+    }
+    match package.kind {
+        ServicePackKind::ServicePack if target_sp <= installed_sp => Err(format!(
+            "Service pack {} is already installed (instance runs {})",
+            target_sp, instance_version
+        )),
+        ServicePackKind::CumulativeFixPack if target_sp != installed_sp => Err(format!(
+            "{} needs service pack {}, the instance runs service pack {}",
+            package.name, target_sp, installed_sp
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn product_version(instance: &AemInstance, credentials: &(String, String)) -> Option<String> {
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(10)))
+        .build()
+        .ok()?;
+    fetch_version_info(&client, &instance.base_url(), &credentials.0, &credentials.1)
+        .await
+        .map(|info| info.product_version)
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Read a service pack or fix pack package and check it against the instance version
+#[command]
+pub async fn check_service_pack(id: String, package_path: String) -> Result<ServicePackInfo, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+    let package = read_package_info(Path::new(&package_path))?;
+    let (username, password, _) = get_instance_credentials(&instance.id)?;
+    let version = product_version(&instance, &(username, password))
+        .await
+        .ok_or_else(|| format!("Could not read the version of {}; is it running?", instance.name))?;
+    check_precondition(&package, &version)?;
+    Ok(package)
+}
+
+/// Install a service pack or fix pack on a running AEM 6.5 instance: verify, install through
+/// Package Manager, wait until the instance is ready again and record the new version.
+/// Progress is emitted as `SERVICE_PACK_PROGRESS_EVENT`.
+#[command]
+pub async fn install_service_pack(
+    app: AppHandle,
+    id: String,
+    package_path: String,
+    timeout_secs: Option<u64>,
+) -> Result<ServicePackInstallResult, String> {
+    let began = Instant::now();
+    let emit = |phase: ServicePackPhase, message: Option<(String, MessageCode)>| {
+        let (message, message_code) = message.unzip();
+        let _ = app.emit(
+            SERVICE_PACK_PROGRESS_EVENT,
+            ServicePackProgress {
+                instance_id: id.clone(),
+                phase,
+                message,
+                message_code,
+                elapsed_secs: began.elapsed().as_secs(),
+            },
+        );
+    };
+    let fail = |error: String| {
+        let code = MessageCode::new("service_pack_failed").with("detail", &error);
+        emit(ServicePackPhase::Failed, Some((error.clone(), code)));
+        error
+    };
+
+    emit(ServicePackPhase::Verifying, None);
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, &id).localized())?;
+    let path = Path::new(&package_path);
+    let package = read_package_info(path).map_err(fail)?;
+    let (username, password, _) = get_instance_credentials(&instance.id).map_err(fail)?;
+    let credentials = (username, password);
+    let previous_version = product_version(&instance, &credentials)
+        .await
+        .ok_or_else(|| fail(format!("Could not read the version of {}; is it running?", instance.name)))?;
+    check_precondition(&package, &previous_version).map_err(fail)?;
+
+    emit(
+        ServicePackPhase::Installing,
+        Some((
+            format!("{} {}", package.name, package.version),
+            MessageCode::new("service_pack_installing")
+                .with("package", &package.name)
+                .with("version", &package.version),
+        )),
+    );
+    let log = post_package(&instance, &credentials, path, true, true, None).await.map_err(fail)?;
+
+    // The install restarts most bundles; wait for the instance to go down before waiting for it
+    emit(ServicePackPhase::WaitingForRestart, None);
+    let onset = Instant::now();
+    while onset.elapsed() < RESTART_ONSET_TIMEOUT && check_aem_http_ready(&instance, 3000).await {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+    let timeout = timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_RESTART_TIMEOUT);
+    let wait = wait_until_ready(&app, &instance, timeout).await.map_err(fail)?;
+
+    let new_version = if wait.ready { product_version(&instance, &credentials).await } else { None };
+    if let Some(ref version) = new_version {
+        let mut instances = load_instances()?;
+        if let Some(stored) = instances.iter_mut().find(|i| i.id == id) {
+            stored.aem_version = Some(version.clone());
+            stored.updated_at = chrono::Utc::now().to_rfc3339();
+            save_instances(&instances)?;
+        }
+    }
+
+    if wait.ready {
+        let message = new_version
+            .clone()
+            .map(|v| (v.clone(), MessageCode::new("service_pack_installed").with("version", v)));
+        emit(ServicePackPhase::Done, message);
+    } else {
+        emit(
+            ServicePackPhase::Failed,
+            Some((
+                format!("Not ready after {}s", wait.elapsed_secs),
+                MessageCode::new("service_pack_not_ready").with("seconds", wait.elapsed_secs),
+            )),
+        );
+    }
+    Ok(ServicePackInstallResult {
+        instance_id: id.clone(),
+        package,
+        previous_version,
+        new_version,
+        ready: wait.ready,
+        elapsed_secs: began.elapsed().as_secs(),
+        log,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_precondition() {
+        let xml = r#"<properties>
+<entry key="group">adobe/cq650/servicepack</entry>
+<entry key="name">aem-service-pkg</entry>
+<entry key="version">6.5.21.0</entry>
+</properties>"#;
+        let properties = parse_package_properties(xml);
+        assert_eq!(properties.get("version").map(String::as_str), Some("6.5.21.0"));
+
+        let service_pack = ServicePackInfo {
+            kind: ServicePackKind::ServicePack,
+            group: properties["group"].clone(),
+            name: properties["name"].clone(),
+            version: properties["version"].clone(),
+        };
+        assert!(check_precondition(&service_pack, "6.5.17.0").is_ok());
+        assert!(check_precondition(&service_pack, "6.5.21.0").is_err());
+        assert!(check_precondition(&service_pack, "6.4.8.0").is_err());
+        assert!(check_precondition(&service_pack, "2024.11.18598.20241113T125352Z-241000").is_err());
+
+        let fix_pack = ServicePackInfo {
+            kind: ServicePackKind::CumulativeFixPack,
+            version: "6.5.17.2".to_string(),
+            ..service_pack
+        };
+        assert!(check_precondition(&fix_pack, "6.5.17.0").is_ok());
+        assert!(check_precondition(&fix_pack, "6.5.16.0").is_err());
+    }
+}
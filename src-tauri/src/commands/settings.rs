@@ -1,15 +1,24 @@
 // Settings Management Commands
 // Provides configuration, export/import, and reset functionality
 
+use chrono::{Local, Timelike};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tauri::command;
 use walkdir::WalkDir;
+use zip::result::ZipError;
 use zip::write::SimpleFileOptions;
-use zip::ZipWriter;
-
+use zip::{AesMode, ZipWriter};
+
+use crate::commands::environment::{
+    check_shell_configured, get_java_symlink_path, get_node_symlink_path, get_shell_config_path, remove_shell_config,
+    snapshot_environment,
+};
+use crate::i18n::MessageCode;
+use crate::platform::common::{dir_size, get_default_app_data_dir, set_data_dir_override};
 use crate::platform::PlatformOps;
 
 // ============================================
@@ -55,6 +64,11 @@ pub struct ExportResult {
     pub profiles_count: usize,
     pub instances_count: usize,
     pub error: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+    /// Entries are AES-256 encrypted with the export password
+    #[serde(default)]
+    pub encrypted: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,6 +78,8 @@ pub struct ImportResult {
     pub instances_imported: usize,
     pub configs_imported: bool,
     pub errors: Vec<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -73,6 +89,60 @@ pub struct ResetResult {
     pub instances_deleted: usize,
     pub configs_reset: bool,
     pub error: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
+}
+
+/// File written by the nightly configuration export
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoExportFile {
+    pub file_path: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoExportResult {
+    pub export: ExportResult,
+    /// Older automatic exports removed by retention
+    pub removed: Vec<String>,
+}
+
+/// Shortest password accepted for encrypted exports
+const MIN_EXPORT_PASSWORD_LENGTH: usize = 8;
+
+/// Automatic exports are named <prefix><YYYYmmdd-HHMMSS>.zip
+const AUTO_EXPORT_PREFIX: &str = "aem-env-manager-auto-";
+
+/// What a targeted reset deletes (dry run) or deleted
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TargetedResetResult {
+    pub dry_run: bool,
+    /// Files, directories and symlinks (and the shell config block) affected
+    pub items: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Where application data currently lives
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataLocation {
+    pub current: String,
+    pub default: String,
+    pub is_custom: bool,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataMigrationResult {
+    pub success: bool,
+    pub from: String,
+    pub to: String,
+    pub files_copied: usize,
+    pub bytes_copied: u64,
+    /// JSON files whose stored paths were rewritten to the new location
+    pub paths_updated: usize,
+    pub verified: bool,
+    pub errors: Vec<String>,
 }
 
 // ============================================
@@ -134,13 +204,206 @@ pub async fn save_scan_paths(paths: ScanPaths) -> Result<(), String> {
         .map_err(|e| format!("Failed to write scan paths: {}", e))
 }
 
+// ============================================
+// Data Directory Relocation
+// ============================================
+
+/// Check that `target` can receive the data directory currently at `source`
+fn validate_migration_target(source: &Path, target: &Path) -> Result<(), String> {
+    if !target.is_absolute() {
+        return Err("Target directory must be an absolute path".to_string());
+    }
+    if target == source {
+        return Err("Target is already the data directory".to_string());
+    }
+    if target.starts_with(source) || source.starts_with(target) {
+        return Err("Target must not be inside the current data directory (or contain it)".to_string());
+    }
+    if target.exists() {
+        let mut entries = fs::read_dir(target)
+            .map_err(|e| format!("Failed to read target directory: {}", e))?;
+        if entries.next().is_some() {
+            return Err("Target directory must be empty".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Replace `from` with `to` in JSON text, both as written and JSON-escaped (Windows backslashes)
+fn rewrite_paths(content: &str, from: &str, to: &str) -> Option<String> {
+    let escape = |s: &str| s.replace('\\', "\\\\");
+    let (from_escaped, to_escaped) = (escape(from), escape(to));
+
+    let rewritten = if from_escaped != from && content.contains(&from_escaped) {
+        content.replace(&from_escaped, &to_escaped)
+    } else {
+        content.replace(from, to)
+    };
+
+    (rewritten != content).then_some(rewritten)
+}
+
+/// Copy every file below `source` into `target`, returning (files, bytes)
+pub(crate) fn copy_tree(source: &Path, target: &Path, errors: &mut Vec<String>) -> (usize, u64) {
+    let mut files = 0;
+    let mut bytes = 0;
+
+    for entry in WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
+        let Ok(rel_path) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        let dest = target.join(rel_path);
+
+        if entry.file_type().is_dir() {
+            if let Err(e) = fs::create_dir_all(&dest) {
+                errors.push(format!("Failed to create {}: {}", dest.display(), e));
+            }
+        } else if entry.file_type().is_file() {
+            match fs::copy(entry.path(), &dest) {
+                Ok(size) => {
+                    files += 1;
+                    bytes += size;
+                }
+                Err(e) => errors.push(format!("Failed to copy {}: {}", rel_path.display(), e)),
+            }
+        }
+    }
+
+    (files, bytes)
+}
+
+/// Compare file sizes between the trees and check rewritten JSON still parses
+fn verify_tree(source: &Path, target: &Path, errors: &mut Vec<String>) -> bool {
+    let mut ok = true;
+
+    for entry in WalkDir::new(source)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(rel_path) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        let dest = target.join(rel_path);
+        let is_json = rel_path.extension().map(|ext| ext == "json").unwrap_or(false);
+
+        let intact = if is_json {
+            fs::read_to_string(&dest)
+                .ok()
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+                .is_some()
+        } else {
+            let source_len = entry.metadata().map(|m| m.len()).ok();
+            let dest_len = fs::metadata(&dest).map(|m| m.len()).ok();
+            source_len.is_some() && source_len == dest_len
+        };
+
+        if !intact {
+            errors.push(format!("Verification failed for {}", rel_path.display()));
+            ok = false;
+        }
+    }
+
+    ok
+}
+
+/// Get the current and default data directory locations
+#[command]
+pub async fn get_data_location() -> Result<DataLocation, String> {
+    let current = get_data_dir();
+    let default = get_default_app_data_dir().unwrap_or_else(|| current.clone());
+
+    Ok(DataLocation {
+        current: current.to_string_lossy().to_string(),
+        default: default.to_string_lossy().to_string(),
+        is_custom: current != default,
+        size_bytes: dir_size(&current),
+    })
+}
+
+/// Move the data directory to `target_dir`: copy, update stored paths, verify, then switch.
+/// The old directory is left in place so the user can remove it once satisfied.
+#[command]
+pub async fn migrate_data_dir(target_dir: String) -> Result<DataMigrationResult, String> {
+    let source = get_data_dir();
+    let target = PathBuf::from(target_dir.trim());
+    validate_migration_target(&source, &target)?;
+
+    fs::create_dir_all(&target)
+        .map_err(|e| format!("Failed to create target directory: {}", e))?;
+
+    let mut errors = Vec::new();
+    let (files_copied, bytes_copied) = copy_tree(&source, &target, &mut errors);
+
+    // Point stored paths (backups, downloads, ...) at the new location
+    let from = source.to_string_lossy().to_string();
+    let to = target.to_string_lossy().to_string();
+    let mut paths_updated = 0;
+    for entry in WalkDir::new(&target)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().map(|ext| ext == "json").unwrap_or(false))
+    {
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if let Some(rewritten) = rewrite_paths(&content, &from, &to) {
+            match fs::write(entry.path(), rewritten) {
+                Ok(()) => paths_updated += 1,
+                Err(e) => errors.push(format!("Failed to update {}: {}", entry.path().display(), e)),
+            }
+        }
+    }
+
+    let verified = errors.is_empty() && verify_tree(&source, &target, &mut errors);
+
+    // Only switch over once the copy is complete and verified
+    if verified {
+        let default = get_default_app_data_dir();
+        let data_dir = (default.as_ref() != Some(&target)).then(|| to.clone());
+        crate::commands::profile::save_app_config(serde_json::json!({ "data_dir": data_dir })).await?;
+        set_data_dir_override(data_dir.map(PathBuf::from));
+    }
+
+    Ok(DataMigrationResult {
+        success: verified,
+        from,
+        to,
+        files_copied,
+        bytes_copied,
+        paths_updated,
+        verified,
+        errors,
+    })
+}
+
 // ============================================
 // Export/Import Configuration
 // ============================================
 
+/// Readable message for zip entry errors, telling apart missing and wrong passwords
+fn zip_entry_error(e: ZipError) -> String {
+    match e {
+        ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => {
+            "This export is password protected; a password is required to import it".to_string()
+        }
+        ZipError::InvalidPassword => "Wrong password for this export".to_string(),
+        e => format!("Failed to read zip entry: {}", e),
+    }
+}
+
 /// Export all configuration to a ZIP file
+/// With a password, every entry is AES-256 encrypted (readable by 7-Zip and similar tools)
 #[command]
-pub async fn export_all_config(export_path: String) -> Result<ExportResult, String> {
+pub async fn export_all_config(export_path: String, password: Option<String>) -> Result<ExportResult, String> {
+    let password = password.filter(|p| !p.is_empty());
+    if password.as_ref().is_some_and(|p| p.chars().count() < MIN_EXPORT_PASSWORD_LENGTH) {
+        return Err(format!(
+            "Export password must be at least {} characters",
+            MIN_EXPORT_PASSWORD_LENGTH
+        ));
+    }
+
     let export_path = PathBuf::from(export_path);
     let data_dir = get_data_dir();
     let config_dir = get_config_dir();
@@ -153,6 +416,10 @@ pub async fn export_all_config(export_path: String) -> Result<ExportResult, Stri
     let options = SimpleFileOptions::default()
         .compression_method(zip::CompressionMethod::Deflated)
         .unix_permissions(0o644);
+    let options = match password.as_deref() {
+        Some(password) => options.with_aes_encryption(AesMode::Aes256, password),
+        None => options,
+    };
 
     let mut profiles_count = 0;
     let mut instances_count = 0;
@@ -246,18 +513,44 @@ pub async fn export_all_config(export_path: String) -> Result<ExportResult, Stri
     zip.finish()
         .map_err(|e| format!("Failed to finalize zip: {}", e))?;
 
+    let file_path = export_path.to_string_lossy().to_string();
+    let message_code = MessageCode::new("config_exported")
+        .with("path", &file_path)
+        .with("profiles", profiles_count)
+        .with("instances", instances_count);
     Ok(ExportResult {
         success: true,
-        file_path: Some(export_path.to_string_lossy().to_string()),
+        file_path: Some(file_path),
         profiles_count,
         instances_count,
         error: None,
+        message_code: Some(message_code),
+        encrypted: password.is_some(),
     })
 }
 
+/// Whether an export archive is password protected (the UI asks for the password before importing)
+#[command]
+pub async fn is_config_export_encrypted(import_path: String) -> Result<bool, String> {
+    archive_is_encrypted(Path::new(&import_path))
+}
+
+fn archive_is_encrypted(path: &Path) -> Result<bool, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open import file: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i).map_err(zip_entry_error)?;
+        if entry.encrypted() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 /// Import configuration from a ZIP file
 #[command]
-pub async fn import_all_config(import_path: String) -> Result<ImportResult, String> {
+pub async fn import_all_config(import_path: String, password: Option<String>) -> Result<ImportResult, String> {
     let import_path = PathBuf::from(import_path);
     let data_dir = get_data_dir();
     let config_dir = get_config_dir();
@@ -274,8 +567,11 @@ pub async fn import_all_config(import_path: String) -> Result<ImportResult, Stri
     let mut errors = Vec::new();
 
     for i in 0..archive.len() {
-        let mut file = archive.by_index(i)
-            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let entry = match password.as_deref().filter(|p| !p.is_empty()) {
+            Some(password) => archive.by_index_decrypt(i, password.as_bytes()),
+            None => archive.by_index(i),
+        };
+        let mut file = entry.map_err(zip_entry_error)?;
 
         let name = file.name().to_string();
 
@@ -317,15 +613,153 @@ pub async fn import_all_config(import_path: String) -> Result<ImportResult, Stri
         }
     }
 
+    let code = if errors.is_empty() { "config_imported" } else { "config_imported_with_errors" };
+    let message_code = MessageCode::new(code)
+        .with("profiles", profiles_imported)
+        .with("instances", instances_imported)
+        .with("errors", errors.len());
     Ok(ImportResult {
         success: errors.is_empty(),
         profiles_imported,
         instances_imported,
         configs_imported,
         errors,
+        message_code: Some(message_code),
     })
 }
 
+// ============================================
+// Scheduled Export
+// ============================================
+
+/// Automatic exports in `dir`, newest first (the timestamped names sort chronologically)
+fn list_auto_export_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .map(|n| n.to_string_lossy())
+                        .is_some_and(|n| n.starts_with(AUTO_EXPORT_PREFIX) && n.ends_with(".zip"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files.reverse();
+    files
+}
+
+/// Content of `name` (e.g. config/config.json) in the newest automatic export that has it
+pub(crate) fn read_auto_export_entry(dir: &Path, name: &str) -> Option<(PathBuf, Vec<u8>)> {
+    list_auto_export_files(dir).into_iter().find_map(|path| {
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).ok()?).ok()?;
+        let mut entry = archive.by_name(name).ok()?;
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).ok()?;
+        Some((path, content))
+    })
+}
+
+/// Local time encoded in an automatic export's file name
+fn auto_export_time(path: &Path) -> Option<chrono::NaiveDateTime> {
+    let name = path.file_stem()?.to_string_lossy();
+    let stamp = name.strip_prefix(AUTO_EXPORT_PREFIX)?;
+    chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S").ok()
+}
+
+/// An export is due once a day after `hour`, unless one was already written today
+fn auto_export_due(latest: Option<chrono::NaiveDateTime>, now: chrono::NaiveDateTime, hour: u32) -> bool {
+    now.hour() >= hour.min(23) && latest.is_none_or(|last| last.date() < now.date())
+}
+
+/// When the scheduler writes the next automatic export (None when disabled)
+pub(crate) fn next_auto_export_at() -> Option<chrono::NaiveDateTime> {
+    let config = crate::commands::profile::get_startup_config();
+    let dir = config.auto_export_dir.filter(|d| !d.is_empty())?;
+    let latest = list_auto_export_files(Path::new(&dir)).first().and_then(|p| auto_export_time(p));
+    let now = Local::now().naive_local();
+
+    if auto_export_due(latest, now, config.auto_export_hour) {
+        return Some(now);
+    }
+    let today = now.date().and_hms_opt(config.auto_export_hour.min(23), 0, 0)?;
+    if latest.is_some_and(|last| last.date() >= now.date()) {
+        Some(today + chrono::Duration::days(1))
+    } else {
+        Some(today)
+    }
+}
+
+/// Export into `dir` and remove automatic exports beyond `keep`
+async fn run_auto_export(dir: &Path, keep: usize) -> Result<AutoExportResult, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create export directory: {}", e))?;
+
+    let file_name = format!("{}{}.zip", AUTO_EXPORT_PREFIX, Local::now().format("%Y%m%d-%H%M%S"));
+    let export = export_all_config(dir.join(file_name).to_string_lossy().to_string(), None).await?;
+
+    let mut removed = Vec::new();
+    for old in list_auto_export_files(dir).into_iter().skip(keep.max(1)) {
+        if fs::remove_file(&old).is_ok() {
+            removed.push(old.to_string_lossy().to_string());
+        }
+    }
+
+    Ok(AutoExportResult { export, removed })
+}
+
+/// Background task that writes the nightly export when `auto_export_dir` is set
+pub async fn config_export_scheduler() {
+    loop {
+        let config = crate::commands::profile::get_startup_config();
+        if let Some(dir) = config.auto_export_dir.filter(|d| !d.is_empty()) {
+            let dir = PathBuf::from(dir);
+            let latest = list_auto_export_files(&dir).first().and_then(|p| auto_export_time(p));
+
+            if auto_export_due(latest, Local::now().naive_local(), config.auto_export_hour) {
+                if let Err(e) = run_auto_export(&dir, config.auto_export_keep).await {
+                    println!("[AEM] Scheduled config export failed: {}", e);
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+    }
+}
+
+/// Run the automatic export now (into the configured directory)
+#[command]
+pub async fn run_auto_export_now() -> Result<AutoExportResult, String> {
+    let config = crate::commands::profile::get_startup_config();
+    let dir = config
+        .auto_export_dir
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| "No automatic export directory configured".to_string())?;
+    run_auto_export(Path::new(&dir), config.auto_export_keep).await
+}
+
+/// Automatic exports in the configured directory, newest first
+#[command]
+pub async fn list_auto_exports() -> Result<Vec<AutoExportFile>, String> {
+    let config = crate::commands::profile::get_startup_config();
+    let Some(dir) = config.auto_export_dir.filter(|d| !d.is_empty()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(list_auto_export_files(Path::new(&dir))
+        .into_iter()
+        .map(|path| AutoExportFile {
+            created_at: auto_export_time(&path)
+                .map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .unwrap_or_default(),
+            size_bytes: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            file_path: path.to_string_lossy().to_string(),
+        })
+        .collect())
+}
+
 // ============================================
 // Reset Configuration
 // ============================================
@@ -390,9 +824,106 @@ pub async fn reset_all_config() -> Result<ResetResult, String> {
         instances_deleted,
         configs_reset: true,
         error: None,
+        message_code: Some(
+            MessageCode::new("config_reset")
+                .with("profiles", profiles_deleted)
+                .with("instances", instances_deleted),
+        ),
     })
 }
 
+// ============================================
+// Targeted Reset
+// ============================================
+
+/// Existing paths among `paths`, as shown in reset listings
+fn existing_paths(paths: &[PathBuf]) -> Vec<&PathBuf> {
+    paths.iter().filter(|p| p.exists() || p.is_symlink()).collect()
+}
+
+/// List (dry run) or delete the given files, directories and symlinks
+fn reset_paths(paths: &[PathBuf], dry_run: bool) -> TargetedResetResult {
+    let mut result = TargetedResetResult {
+        dry_run,
+        items: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for path in existing_paths(paths) {
+        if !dry_run {
+            let removed = if path.is_dir() && !path.is_symlink() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            if let Err(e) = removed {
+                result.errors.push(format!("Failed to delete {}: {}", path.display(), e));
+                continue;
+            }
+        }
+        result.items.push(path.to_string_lossy().to_string());
+    }
+    result
+}
+
+/// Reset registered instances only: the instance list, stored credentials,
+/// metrics history and password rotation history. Profiles are kept.
+#[command]
+pub async fn reset_instances(dry_run: bool) -> Result<TargetedResetResult, String> {
+    // Keychain entries are keyed by instance id, so remove them while the list still exists
+    let mut keychain_errors = Vec::new();
+    if !dry_run {
+        for instance in crate::commands::instance::load_instances().unwrap_or_default() {
+            if let Err(e) = crate::commands::credential_store::remove_stored_credentials(&instance.id) {
+                keychain_errors.push(format!("Failed to delete credentials of {}: {}", instance.name, e));
+            }
+        }
+    }
+
+    let data_dir = get_data_dir();
+    let paths = [
+        data_dir.join("instances.json"),
+        data_dir.join(".credentials"),
+        data_dir.join(".credentials.enc"),
+        data_dir.join(".credentials.key"),
+        data_dir.join("metrics"),
+        data_dir.join("password_rotations.json"),
+    ];
+    let mut result = reset_paths(&paths, dry_run);
+    result.errors.extend(keychain_errors);
+    Ok(result)
+}
+
+/// Reset the Java/Node symlinks and the managed shell config block
+/// (both are snapshotted first and can be restored from the environment snapshots)
+#[command]
+pub async fn reset_environment(dry_run: bool) -> Result<TargetedResetResult, String> {
+    if !dry_run {
+        snapshot_environment("before-reset-environment")
+            .map_err(|e| format!("Environment not reset, taking a snapshot failed: {}", e))?;
+    }
+    let mut result = reset_paths(&[get_java_symlink_path()?, get_node_symlink_path()?], dry_run);
+
+    if check_shell_configured()? {
+        let item = format!("Managed block in {}", get_shell_config_path()?.display());
+        if dry_run {
+            result.items.push(item);
+        } else {
+            match remove_shell_config().await {
+                Ok(_) => result.items.push(item),
+                Err(e) => result.errors.push(e),
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Reset the license registry
+#[command]
+pub async fn reset_licenses(dry_run: bool) -> Result<TargetedResetResult, String> {
+    Ok(reset_paths(&[get_data_dir().join("aem_licenses.json")], dry_run))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -403,4 +934,80 @@ mod tests {
         assert!(!paths.java_paths.is_empty());
         assert!(!paths.node_paths.is_empty());
     }
+
+    #[test]
+    fn test_rewrite_paths() {
+        let json = r#"{"path":"C:\\Users\\dev\\AppData\\aem-env-manager\\backups\\a.zip"}"#;
+        let rewritten = rewrite_paths(json, "C:\\Users\\dev\\AppData\\aem-env-manager", "D:\\aem-data").unwrap();
+        assert_eq!(rewritten, r#"{"path":"D:\\aem-data\\backups\\a.zip"}"#);
+
+        let json = r#"{"path":"/home/dev/.local/share/aem-env-manager/downloads/aem.jar"}"#;
+        let rewritten = rewrite_paths(json, "/home/dev/.local/share/aem-env-manager", "/mnt/data/aem").unwrap();
+        assert_eq!(rewritten, r#"{"path":"/mnt/data/aem/downloads/aem.jar"}"#);
+
+        assert!(rewrite_paths(json, "/opt/other", "/mnt/data/aem").is_none());
+    }
+
+    #[test]
+    fn test_encrypted_export_round_trip() {
+        let path = std::env::temp_dir().join(format!("aem-env-manager-test-{}.zip", uuid::Uuid::new_v4()));
+        let file = fs::File::create(&path).unwrap();
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default().with_aes_encryption(AesMode::Aes256, "correct horse");
+        zip.start_file("config/config.json", options).unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.finish().unwrap();
+
+        assert!(archive_is_encrypted(&path).unwrap());
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        let missing = archive.by_index(0).map(|_| ()).map_err(zip_entry_error).unwrap_err();
+        assert!(missing.contains("password is required"));
+        let wrong = archive.by_index_decrypt(0, b"wrong password").map(|_| ()).map_err(zip_entry_error);
+        assert_eq!(wrong.unwrap_err(), "Wrong password for this export");
+
+        let mut content = String::new();
+        archive.by_index_decrypt(0, b"correct horse").unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "{}");
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_reset_paths() {
+        let dir = std::env::temp_dir().join(format!("aem-env-manager-reset-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("metrics")).unwrap();
+        fs::write(dir.join("metrics").join("a.json"), "[]").unwrap();
+        fs::write(dir.join("instances.json"), "[]").unwrap();
+        let paths = [dir.join("instances.json"), dir.join("metrics"), dir.join("missing.json")];
+
+        let dry_run = reset_paths(&paths, true);
+        assert_eq!(dry_run.items.len(), 2);
+        assert!(dir.join("instances.json").exists());
+
+        let reset = reset_paths(&paths, false);
+        assert_eq!(reset.items, dry_run.items);
+        assert!(!dir.join("instances.json").exists() && !dir.join("metrics").exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_auto_export_due() {
+        let at = |s: &str| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        assert!(!auto_export_due(None, at("2026-10-16 01:00"), 2));
+        assert!(auto_export_due(None, at("2026-10-16 03:00"), 2));
+        assert!(!auto_export_due(Some(at("2026-10-16 02:10")), at("2026-10-16 23:00"), 2));
+        assert!(auto_export_due(Some(at("2026-10-15 02:10")), at("2026-10-16 02:00"), 2));
+
+        let path = Path::new("/backups/aem-env-manager-auto-20261016-021000.zip");
+        assert_eq!(auto_export_time(path), Some(at("2026-10-16 02:10")));
+    }
+
+    #[test]
+    fn test_validate_migration_target() {
+        let source = std::env::temp_dir().join("aem-env-manager-src");
+        assert!(validate_migration_target(&source, &source).is_err());
+        assert!(validate_migration_target(&source, &source.join("nested")).is_err());
+        assert!(validate_migration_target(&source, Path::new("relative/dir")).is_err());
+        assert!(validate_migration_target(&source, &std::env::temp_dir().join("aem-env-manager-missing-target")).is_ok());
+    }
 }
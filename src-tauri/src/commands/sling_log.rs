@@ -0,0 +1,281 @@
+// Sling Log Commands
+// Logger configurations of a running instance (the ones the Sling Log Support console lists),
+// read from the configuration status dump and changed through the Felix configuration manager
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::command;
+
+use crate::commands::instance::{get_instance_credentials, load_instances, AemInstance};
+use crate::i18n::{Entity, Message};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlingLoggerConfig {
+    pub pid: String,
+    /// Logger categories, e.g. com.example.core (empty for the root logger)
+    pub loggers: Vec<String>,
+    /// trace, debug, info, warn, error or off
+    pub level: String,
+    pub file: Option<String>,
+    pub additive: bool,
+    /// The global org.apache.sling.commons.log.LogManager configuration
+    pub is_root: bool,
+}
+
+const ROOT_PID: &str = "org.apache.sling.commons.log.LogManager";
+const LOGGER_FACTORY_PID: &str = "org.apache.sling.commons.log.LogManager.factory.config";
+/// PID the configuration manager replaces with a generated one when saving a new factory config
+const NEW_CONFIG_PID: &str = "[Temporary PID replaced by real PID upon save]";
+
+const PROP_LEVEL: &str = "org.apache.sling.commons.log.level";
+const PROP_FILE: &str = "org.apache.sling.commons.log.file";
+const PROP_NAMES: &str = "org.apache.sling.commons.log.names";
+const PROP_ADDITIVE: &str = "org.apache.sling.commons.log.additiv";
+
+const LOG_LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+// ============================================
+// Helpers
+// ============================================
+
+fn normalize_level(level: &str) -> Result<String, String> {
+    let level = level.trim().to_lowercase();
+    if LOG_LEVELS.contains(&level.as_str()) {
+        Ok(level)
+    } else {
+        Err(format!("Invalid log level '{}': use one of {}", level, LOG_LEVELS.join(", ")))
+    }
+}
+
+/// `[a, b]` or `a` as printed by the configuration status dump
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Logger configurations from /system/console/status-Configurations.txt, where each
+/// configuration starts with `PID = ...` followed by indented `key = value` lines
+fn parse_logger_configs(text: &str) -> Vec<SlingLoggerConfig> {
+    let mut configs = Vec::new();
+    let mut current: Option<(String, Vec<(String, String)>)> = None;
+
+    let mut finish = |entry: Option<(String, Vec<(String, String)>)>| {
+        let Some((pid, properties)) = entry else {
+            return;
+        };
+        let is_root = pid == ROOT_PID;
+        if !is_root && !pid.starts_with(LOGGER_FACTORY_PID) {
+            return;
+        }
+        let get = |key: &str| properties.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        configs.push(SlingLoggerConfig {
+            loggers: get(PROP_NAMES).map(|v| parse_list(&v)).unwrap_or_default(),
+            level: get(PROP_LEVEL).unwrap_or_else(|| "info".to_string()).to_lowercase(),
+            file: get(PROP_FILE).filter(|f| !f.is_empty()),
+            additive: get(PROP_ADDITIVE).is_some_and(|v| v == "true"),
+            is_root,
+            pid,
+        });
+    };
+
+    for line in text.lines() {
+        if let Some(pid) = line.strip_prefix("PID = ") {
+            finish(current.take());
+            current = Some((pid.trim().to_string(), Vec::new()));
+        } else if line.starts_with(' ') || line.starts_with('\t') {
+            if let (Some((_, properties)), Some((key, value))) = (current.as_mut(), line.split_once(" = ")) {
+                properties.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+    }
+    finish(current.take());
+
+    configs.sort_by(|a, b| b.is_root.cmp(&a.is_root).then_with(|| a.loggers.cmp(&b.loggers)));
+    configs
+}
+
+/// Form for the configuration manager; the root config has no logger names
+fn config_form(config: &SlingLoggerConfig) -> Vec<(&'static str, String)> {
+    let mut properties = vec![PROP_LEVEL, PROP_FILE];
+    if !config.is_root {
+        properties.extend([PROP_NAMES, PROP_ADDITIVE]);
+    }
+
+    let mut form = vec![
+        ("apply", "true".to_string()),
+        ("action", "ajaxConfigManager".to_string()),
+        ("propertylist", properties.join(",")),
+        (PROP_LEVEL, config.level.clone()),
+        (PROP_FILE, config.file.clone().unwrap_or_else(|| "logs/error.log".to_string())),
+    ];
+    if !config.is_root {
+        form.extend(config.loggers.iter().map(|logger| (PROP_NAMES, logger.clone())));
+        form.push((PROP_ADDITIVE, config.additive.to_string()));
+    }
+    form
+}
+
+struct Connection {
+    instance: AemInstance,
+    credentials: (String, String),
+    client: reqwest::Client,
+}
+
+fn connect(id: &str) -> Result<Connection, String> {
+    let instance = load_instances()?
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| Message::NotFound(Entity::Instance, id).localized())?;
+    let (username, password, _) = get_instance_credentials(&instance.id)?;
+    let client = instance
+        .apply_http_overrides(reqwest::Client::builder().timeout(Duration::from_secs(30)))
+        .build()
+        .map_err(|e| e.to_string())?;
+    Ok(Connection {
+        instance,
+        credentials: (username, password),
+        client,
+    })
+}
+
+async fn fetch_logger_configs(connection: &Connection) -> Result<Vec<SlingLoggerConfig>, String> {
+    let url = format!("{}/system/console/status-Configurations.txt", connection.instance.base_url());
+    let response = connection
+        .client
+        .get(&url)
+        .basic_auth(&connection.credentials.0, Some(&connection.credentials.1))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status().as_u16()));
+    }
+    let text = response.text().await.map_err(|e| format!("Failed to read configurations: {}", e))?;
+    Ok(parse_logger_configs(&text))
+}
+
+async fn save_config(connection: &Connection, pid: &str, form: &[(&str, String)]) -> Result<(), String> {
+    let url = format!("{}/system/console/configMgr/{}", connection.instance.base_url(), pid);
+    let response = connection
+        .client
+        .post(&url)
+        .basic_auth(&connection.credentials.0, Some(&connection.credentials.1))
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    // The configuration manager redirects back to the console after saving
+    if response.status().is_success() || response.status().is_redirection() {
+        Ok(())
+    } else {
+        Err(format!("{} returned HTTP {}", url, response.status().as_u16()))
+    }
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Logger configurations of a running instance, root logger first
+#[command]
+pub async fn list_sling_loggers(id: String) -> Result<Vec<SlingLoggerConfig>, String> {
+    fetch_logger_configs(&connect(&id)?).await
+}
+
+/// Change the level of an existing logger configuration
+#[command]
+pub async fn set_sling_log_level(id: String, pid: String, level: String) -> Result<SlingLoggerConfig, String> {
+    let level = normalize_level(&level)?;
+    let connection = connect(&id)?;
+    let mut config = fetch_logger_configs(&connection)
+        .await?
+        .into_iter()
+        .find(|c| c.pid == pid)
+        .ok_or_else(|| format!("No logger configuration {} on {}", pid, connection.instance.name))?;
+
+    config.level = level;
+    save_config(&connection, &pid, &config_form(&config)).await?;
+    Ok(config)
+}
+
+/// Create a logger configuration, e.g. DEBUG for com.example.core into logs/example.log.
+/// `file` defaults to logs/error.log.
+#[command]
+pub async fn create_sling_logger(
+    id: String,
+    loggers: Vec<String>,
+    level: String,
+    file: Option<String>,
+    additive: Option<bool>,
+) -> Result<Vec<SlingLoggerConfig>, String> {
+    let loggers: Vec<String> = loggers
+        .iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if loggers.is_empty() {
+        return Err("At least one logger name is required".to_string());
+    }
+    let config = SlingLoggerConfig {
+        pid: NEW_CONFIG_PID.to_string(),
+        loggers,
+        level: normalize_level(&level)?,
+        file: file.filter(|f| !f.trim().is_empty()),
+        additive: additive.unwrap_or(false),
+        is_root: false,
+    };
+
+    let connection = connect(&id)?;
+    let mut form = config_form(&config);
+    form.push(("factoryPid", LOGGER_FACTORY_PID.to_string()));
+    save_config(&connection, NEW_CONFIG_PID, &form).await?;
+    fetch_logger_configs(&connection).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_logger_configs() {
+        let text = "\
+*** Configurations:
+PID = org.apache.sling.commons.log.LogManager.factory.config.3f9c
+Factory PID = org.apache.sling.commons.log.LogManager.factory.config
+BundleLocation = slinginstall:org.apache.sling.commons.log-5.4.0.jar
+  org.apache.sling.commons.log.additiv = false
+  org.apache.sling.commons.log.file = logs/example.log
+  org.apache.sling.commons.log.level = DEBUG
+  org.apache.sling.commons.log.names = [com.example.core, com.example.it]
+
+PID = org.apache.sling.commons.log.LogManager
+  org.apache.sling.commons.log.file = logs/error.log
+  org.apache.sling.commons.log.level = info
+
+PID = com.day.cq.mailer.DefaultMailService
+  smtp.host = localhost
+";
+        let configs = parse_logger_configs(text);
+        assert_eq!(configs.len(), 2);
+        assert!(configs[0].is_root);
+        assert_eq!(configs[0].level, "info");
+        assert_eq!(configs[1].loggers, vec!["com.example.core", "com.example.it"]);
+        assert_eq!(configs[1].level, "debug");
+        assert_eq!(configs[1].file.as_deref(), Some("logs/example.log"));
+
+        let form = config_form(&configs[1]);
+        assert_eq!(form.iter().filter(|(k, _)| *k == PROP_NAMES).count(), 2);
+        assert!(normalize_level("Warn").is_ok());
+        assert!(normalize_level("verbose").is_err());
+    }
+}
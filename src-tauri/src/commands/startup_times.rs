@@ -0,0 +1,282 @@
+// Instance Startup Times
+// How long each instance takes from start to Running, with regression detection
+// (a startup that gets much slower is often an early sign of repository bloat)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::automation::{trigger_automation, AutomationEvent};
+use crate::commands::readiness::notify_ready_webhook;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupRun {
+    pub started_at: String,
+    pub ready_at: String,
+    pub duration_secs: u64,
+    /// Median of the previous runs this one was compared with
+    pub baseline_secs: Option<u64>,
+    pub regressed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupStats {
+    pub instance_id: String,
+    /// Oldest first
+    pub runs: Vec<StartupRun>,
+    pub last_secs: Option<u64>,
+    pub median_secs: Option<u64>,
+    pub min_secs: Option<u64>,
+    pub max_secs: Option<u64>,
+    /// Whether the most recent startup was flagged as a regression
+    pub regressed: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StartupHistory {
+    /// Start time (RFC 3339) of instances started but not yet seen running
+    #[serde(default)]
+    pending: HashMap<String, String>,
+    #[serde(default)]
+    runs: HashMap<String, Vec<StartupRun>>,
+}
+
+/// Event emitted (with the run) when a startup regressed and notifications are enabled
+pub const STARTUP_REGRESSION_EVENT: &str = "startup-regression";
+
+/// Runs kept per instance
+const MAX_RUNS: usize = 30;
+
+/// Number of previous runs the baseline is taken from
+const BASELINE_RUNS: usize = 10;
+
+/// Previous runs needed before regressions are flagged
+const MIN_BASELINE_RUNS: usize = 3;
+
+/// A startup regressed when it took this much longer than the baseline...
+const REGRESSION_FACTOR: f64 = 1.5;
+
+/// ...and at least this many seconds longer
+const REGRESSION_MIN_SECS: u64 = 30;
+
+/// Pending starts older than this are dropped instead of recorded
+const MAX_STARTUP_SECS: i64 = 2 * 60 * 60;
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_history_file() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("startup_times.json")
+}
+
+fn load_history() -> StartupHistory {
+    std::fs::read_to_string(get_history_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_history(history: &StartupHistory) -> Result<(), String> {
+    let file_path = get_history_file();
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(history).map_err(|e| format!("Failed to serialize startup times: {}", e))?;
+    std::fs::write(&file_path, content).map_err(|e| format!("Failed to write startup times: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+fn median(values: &[u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    Some(sorted[sorted.len() / 2])
+}
+
+/// Baseline of the previous runs and whether `duration_secs` regressed against it
+fn evaluate_run(previous: &[StartupRun], duration_secs: u64) -> (Option<u64>, bool) {
+    let recent: Vec<u64> = previous
+        .iter()
+        .rev()
+        .take(BASELINE_RUNS)
+        .map(|r| r.duration_secs)
+        .collect();
+    let baseline = median(&recent);
+    let regressed = recent.len() >= MIN_BASELINE_RUNS
+        && baseline.is_some_and(|b| {
+            duration_secs as f64 >= b as f64 * REGRESSION_FACTOR && duration_secs >= b + REGRESSION_MIN_SECS
+        });
+    (baseline, regressed)
+}
+
+fn stats_for(instance_id: &str, runs: Vec<StartupRun>) -> StartupStats {
+    let durations: Vec<u64> = runs.iter().map(|r| r.duration_secs).collect();
+    StartupStats {
+        instance_id: instance_id.to_string(),
+        last_secs: durations.last().copied(),
+        median_secs: median(&durations),
+        min_secs: durations.iter().min().copied(),
+        max_secs: durations.iter().max().copied(),
+        regressed: runs.last().is_some_and(|r| r.regressed),
+        runs,
+    }
+}
+
+/// Remember that an instance was just started
+pub(crate) fn mark_started(instance_id: &str) {
+    let mut history = load_history();
+    history
+        .pending
+        .insert(instance_id.to_string(), chrono::Utc::now().to_rfc3339());
+    if let Err(e) = save_history(&history) {
+        println!("[AEM] Failed to record start of {}: {}", instance_id, e);
+    }
+}
+
+/// Instances started but not yet seen running, with their start time
+pub(crate) fn pending_starts() -> HashMap<String, String> {
+    load_history().pending
+}
+
+/// Record the startup of an instance seen running, if a start is pending
+pub(crate) fn mark_running(instance_id: &str) -> Option<StartupRun> {
+    let mut history = load_history();
+    let started_at = history.pending.remove(instance_id)?;
+
+    let now = chrono::Utc::now();
+    let elapsed = chrono::DateTime::parse_from_rfc3339(&started_at)
+        .ok()
+        .map(|s| now.signed_duration_since(s).num_seconds())
+        .filter(|secs| (0..=MAX_STARTUP_SECS).contains(secs));
+
+    let run = elapsed.map(|secs| {
+        let runs = history.runs.entry(instance_id.to_string()).or_default();
+        let (baseline_secs, regressed) = evaluate_run(runs, secs as u64);
+        let run = StartupRun {
+            started_at,
+            ready_at: now.to_rfc3339(),
+            duration_secs: secs as u64,
+            baseline_secs,
+            regressed,
+        };
+        runs.push(run.clone());
+        if runs.len() > MAX_RUNS {
+            runs.drain(..runs.len() - MAX_RUNS);
+        }
+        run
+    });
+
+    if let Err(e) = save_history(&history) {
+        println!("[AEM] Failed to record startup of {}: {}", instance_id, e);
+    }
+    run
+}
+
+/// Record startups of the running instances and notify about regressions
+pub(crate) fn record_running(app: &AppHandle, instance_ids: &[String]) {
+    let notify = crate::commands::profile::get_startup_config().show_notifications;
+    for instance_id in instance_ids {
+        let Some(run) = mark_running(instance_id) else {
+            continue;
+        };
+        notify_ready_webhook(instance_id, Some(run.duration_secs));
+        trigger_automation(AutomationEvent::InstanceStarted {
+            instance_id: instance_id.clone(),
+        });
+        if run.regressed {
+            println!(
+                "[AEM] Startup of {} took {}s (usually {}s)",
+                instance_id,
+                run.duration_secs,
+                run.baseline_secs.unwrap_or(0)
+            );
+            if notify {
+                let _ = app.emit(
+                    STARTUP_REGRESSION_EVENT,
+                    serde_json::json!({ "instance_id": instance_id, "run": run }),
+                );
+            }
+        }
+    }
+}
+
+/// Remove the startup history of an instance
+pub(crate) fn delete_startup_times(instance_id: &str) -> Result<(), String> {
+    let mut history = load_history();
+    let pending = history.pending.remove(instance_id).is_some();
+    if history.runs.remove(instance_id).is_some() || pending {
+        save_history(&history)?;
+    }
+    Ok(())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Startup time statistics for one instance, or for every instance with history
+#[command]
+pub async fn get_startup_stats(instance_id: Option<String>) -> Result<Vec<StartupStats>, String> {
+    let mut history = load_history();
+    let mut stats: Vec<StartupStats> = match instance_id {
+        Some(id) => {
+            let runs = history.runs.remove(&id).unwrap_or_default();
+            vec![stats_for(&id, runs)]
+        }
+        None => history.runs.into_iter().map(|(id, runs)| stats_for(&id, runs)).collect(),
+    };
+    stats.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+    Ok(stats)
+}
+
+/// Clear the startup history of an instance
+#[command]
+pub async fn clear_startup_stats(instance_id: String) -> Result<bool, String> {
+    delete_startup_times(&instance_id)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(duration_secs: u64) -> StartupRun {
+        StartupRun {
+            started_at: String::new(),
+            ready_at: String::new(),
+            duration_secs,
+            baseline_secs: None,
+            regressed: false,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_run() {
+        assert_eq!(evaluate_run(&[run(100), run(110)], 400), (Some(110), false));
+
+        let previous = vec![run(100), run(120), run(110)];
+        assert_eq!(evaluate_run(&previous, 130), (Some(110), false));
+        assert_eq!(evaluate_run(&previous, 170), (Some(110), true));
+
+        // Fast instances need a noticeable absolute slowdown too
+        let quick = vec![run(10), run(12), run(11)];
+        assert_eq!(evaluate_run(&quick, 30), (Some(11), false));
+
+        let stats = stats_for("author", previous);
+        assert_eq!((stats.median_secs, stats.min_secs, stats.max_secs), (Some(110), Some(100), Some(120)));
+    }
+}
@@ -0,0 +1,648 @@
+// Tool Config Commands
+// Imports instances and profiles from CLI-based setups: AEM Compose (aemc)
+// `aem/default/etc/aem.yml` and `aem.env`-style environment files.
+// Exports registered instances as docker-compose or aemc YAML for CI and CLI-only setups.
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{command, Url};
+
+use crate::commands::instance::{
+    add_instance, build_jvm_args, load_instances, save_stored_credentials, AemInstance, AemInstanceStatus,
+    AemInstanceType,
+};
+use crate::commands::profile::{create_profile, EnvironmentProfile};
+
+// ============================================
+// Data Types
+// ============================================
+
+/// Instance definition read from a tool config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedInstance {
+    pub name: String,
+    pub instance_type: AemInstanceType,
+    pub host: String,
+    pub port: u16,
+    /// Local installation directory (empty for remote instances)
+    pub path: String,
+    pub run_modes: Vec<String>,
+    pub java_opts: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// File the definition came from
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolImportPreview {
+    pub source_files: Vec<String>,
+    pub instances: Vec<ImportedInstance>,
+    /// Non-instance variables from env files (JAVA_HOME, MAVEN_OPTS, ...) for the profile
+    pub env_vars: HashMap<String, String>,
+    /// Instances already registered with the same host and port
+    pub already_registered: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolImportResult {
+    pub instances: Vec<AemInstance>,
+    pub profile: Option<EnvironmentProfile>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolExportFormat {
+    DockerCompose,
+    Aemc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolExportResult {
+    pub format: ToolExportFormat,
+    pub content: String,
+    /// File written, when an output path was given
+    pub output_path: Option<String>,
+    pub exported: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Java version assumed for exported JVM arguments (container images and aemc default to 11)
+const EXPORT_JAVA_MAJOR: u32 = 11;
+
+/// aemc config below the project directory
+const AEMC_CONFIG: &str = "aem/default/etc/aem.yml";
+
+/// Where aemc unpacks local instances unless `instance.local.unpack_dir` says otherwise
+const AEMC_UNPACK_DIR: &str = "aem/home/var/instance";
+
+/// Env files checked in the project directory
+const ENV_FILES: [&str; 3] = ["aem.env", ".aem.env", "aem/default/etc/aem.env"];
+
+/// Instance settings in env files: AEM_<NAME>_<SUFFIX>
+const ENV_INSTANCE_SUFFIXES: [&str; 5] = ["_HTTP_URL", "_USER", "_PASSWORD", "_RUN_MODES", "_JVM_OPTS"];
+
+// ============================================
+// Helpers
+// ============================================
+
+pub(crate) fn is_local_host(host: &str) -> bool {
+    matches!(host, "localhost" | "127.0.0.1" | "::1")
+}
+
+/// Author unless the name or port says publish
+fn guess_type(name: &str, port: u16) -> AemInstanceType {
+    let name = name.to_lowercase();
+    if name.contains("publish") || (!name.contains("author") && port == 4503) {
+        AemInstanceType::Publish
+    } else {
+        AemInstanceType::Author
+    }
+}
+
+/// Host and port of an instance URL
+fn parse_http_url(url: &str) -> Option<(String, u16)> {
+    let url = Url::parse(url.trim()).ok()?;
+    Some((url.host_str()?.to_string(), url.port_or_known_default()?))
+}
+
+/// KEY=VALUE lines (optionally `export`ed and quoted); comments and blank lines are ignored
+fn parse_env_file(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| {
+            let l = l.strip_prefix("export ").unwrap_or(l);
+            let (key, value) = l.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Resolve aemc's Go template expressions (`[[ .Env.X | default "y" ]]`) with the given
+/// environment, falling back to the default value. Like aemc, the bare value is substituted
+/// into the text before it is parsed as YAML.
+fn resolve_templates(content: &str, env: &HashMap<String, String>) -> String {
+    let expr = Regex::new(r"\[\[(.*?)\]\]").unwrap();
+    let env_ref = Regex::new(r"\.Env\.(\w+)").unwrap();
+    let default = Regex::new(r#"\|\s*default\s+(?:"([^"]*)"|'([^']*)'|(\S+))"#).unwrap();
+
+    expr.replace_all(content, |caps: &Captures| {
+        let inner = &caps[1];
+        env_ref
+            .captures(inner)
+            .and_then(|c| env.get(&c[1]).cloned())
+            .or_else(|| {
+                default
+                    .captures(inner)
+                    .and_then(|d| d.get(1).or(d.get(2)).or(d.get(3)))
+                    .map(|m| m.as_str().to_string())
+            })
+            .unwrap_or_default()
+    })
+    .into_owned()
+}
+
+fn yaml_str(value: &serde_yaml::Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        serde_yaml::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn yaml_list(value: &serde_yaml::Value, key: &str) -> Vec<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_sequence())
+        .map(|items| items.iter().filter_map(|i| i.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// Instances of an aemc `aem.yml`
+fn parse_aemc_config(
+    content: &str,
+    env: &HashMap<String, String>,
+    project_dir: &Path,
+    source: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<ImportedInstance>, String> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&resolve_templates(content, env))
+        .map_err(|e| format!("Failed to parse {}: {}", source, e))?;
+
+    let unpack_dir = yaml
+        .get("instance")
+        .and_then(|i| i.get("local"))
+        .and_then(|l| yaml_str(l, "unpack_dir"))
+        .unwrap_or_else(|| AEMC_UNPACK_DIR.to_string());
+
+    let Some(configs) = yaml
+        .get("instance")
+        .and_then(|i| i.get("config"))
+        .and_then(|c| c.as_mapping())
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut instances = Vec::new();
+    for (name, config) in configs {
+        let Some(name) = name.as_str() else { continue };
+        if config.get("active").and_then(|a| a.as_bool()) == Some(false) {
+            continue;
+        }
+        let Some((host, port)) = yaml_str(config, "http_url").as_deref().and_then(parse_http_url) else {
+            warnings.push(format!("{}: instance {} has no usable http_url", source, name));
+            continue;
+        };
+
+        let jvm_opts = yaml_list(config, "jvm_opts");
+        let path = if is_local_host(&host) {
+            project_dir.join(&unpack_dir).join(name).to_string_lossy().to_string()
+        } else {
+            String::new()
+        };
+
+        instances.push(ImportedInstance {
+            name: name.to_string(),
+            instance_type: guess_type(name, port),
+            host,
+            port,
+            path,
+            run_modes: yaml_list(config, "run_modes"),
+            java_opts: (!jvm_opts.is_empty()).then(|| jvm_opts.join(" ")),
+            username: yaml_str(config, "user"),
+            password: yaml_str(config, "password"),
+            source: source.to_string(),
+        });
+    }
+    Ok(instances)
+}
+
+/// Instance name of an env key (AEM_LOCAL_AUTHOR_HTTP_URL -> local_author)
+fn env_instance_key(key: &str) -> Option<(String, &'static str)> {
+    let rest = key.strip_prefix("AEM_")?;
+    ENV_INSTANCE_SUFFIXES
+        .iter()
+        .find_map(|suffix| rest.strip_suffix(suffix).map(|name| (name.to_lowercase(), *suffix)))
+        .filter(|(name, _)| !name.is_empty())
+}
+
+/// Instances defined by AEM_<NAME>_HTTP_URL (and friends) in an env file
+fn parse_env_instances(env: &HashMap<String, String>, source: &str) -> Vec<ImportedInstance> {
+    let mut names: Vec<String> = env
+        .keys()
+        .filter_map(|k| env_instance_key(k))
+        .filter(|(_, suffix)| *suffix == "_HTTP_URL")
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let get = |suffix: &str| {
+                env.get(&format!("AEM_{}{}", name.to_uppercase(), suffix))
+                    .filter(|v| !v.is_empty())
+                    .cloned()
+            };
+            let (host, port) = parse_http_url(&get("_HTTP_URL")?)?;
+            Some(ImportedInstance {
+                instance_type: guess_type(&name, port),
+                host,
+                port,
+                path: String::new(),
+                run_modes: get("_RUN_MODES")
+                    .map(|r| r.split(',').map(|m| m.trim().to_string()).filter(|m| !m.is_empty()).collect())
+                    .unwrap_or_default(),
+                java_opts: get("_JVM_OPTS"),
+                username: get("_USER"),
+                password: get("_PASSWORD"),
+                source: source.to_string(),
+                name,
+            })
+        })
+        .collect()
+}
+
+fn preview(project_dir: &Path) -> Result<ToolImportPreview, String> {
+    if !project_dir.is_dir() {
+        return Err(format!("Directory not found: {}", project_dir.display()));
+    }
+
+    let mut source_files = Vec::new();
+    let mut warnings = Vec::new();
+    let mut env = HashMap::new();
+    let mut instances: Vec<ImportedInstance> = Vec::new();
+
+    for name in ENV_FILES {
+        let path = project_dir.join(name);
+        if let Ok(content) = fs::read_to_string(&path) {
+            let vars = parse_env_file(&content);
+            instances.extend(parse_env_instances(&vars, name));
+            env.extend(vars);
+            source_files.push(path.to_string_lossy().to_string());
+        }
+    }
+
+    let aemc_path: PathBuf = project_dir.join(AEMC_CONFIG);
+    if let Ok(content) = fs::read_to_string(&aemc_path) {
+        let aemc = parse_aemc_config(&content, &env, project_dir, AEMC_CONFIG, &mut warnings)?;
+        // aem.yml is the full definition; it replaces env-only entries of the same instance
+        instances.retain(|i| !aemc.iter().any(|a| a.host == i.host && a.port == i.port));
+        instances.extend(aemc);
+        source_files.push(aemc_path.to_string_lossy().to_string());
+    }
+
+    if source_files.is_empty() {
+        return Err(format!(
+            "No aemc config ({}) or env file ({}) found in {}",
+            AEMC_CONFIG,
+            ENV_FILES.join(", "),
+            project_dir.display()
+        ));
+    }
+
+    let registered = load_instances().unwrap_or_default();
+    let already_registered = instances
+        .iter()
+        .filter(|i| registered.iter().any(|r| r.host == i.host && r.port == i.port))
+        .map(|i| i.name.clone())
+        .collect();
+
+    let env_vars = env
+        .into_iter()
+        .filter(|(k, _)| env_instance_key(k).is_none())
+        .collect();
+
+    Ok(ToolImportPreview {
+        source_files,
+        instances,
+        env_vars,
+        already_registered,
+        warnings,
+    })
+}
+
+/// Lowercase name with runs of other characters replaced by `separator`
+fn slug(name: &str, separator: char) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with(separator) {
+            slug.push(separator);
+        }
+    }
+    slug.trim_end_matches(separator).to_string()
+}
+
+/// JVM arguments without the run mode and port properties (exported separately)
+fn export_jvm_opts(instance: &AemInstance) -> Vec<String> {
+    build_jvm_args(instance, Some(EXPORT_JAVA_MAJOR))
+        .into_iter()
+        .filter(|a| !a.starts_with("-Dsling.run.modes=") && !a.starts_with("-Dhttp.port="))
+        .collect()
+}
+
+fn docker_compose_yaml(instances: &[&AemInstance]) -> Result<String, String> {
+    use serde_yaml::{Mapping, Value};
+
+    let mut services = Mapping::new();
+    let mut volumes = Mapping::new();
+    for instance in instances {
+        let name = slug(&instance.name, '-');
+        let volume = format!("{}-data", name);
+
+        let mut environment = Mapping::new();
+        environment.insert("JAVA_OPTS".into(), build_jvm_args(instance, Some(EXPORT_JAVA_MAJOR)).join(" ").into());
+
+        let mut service = Mapping::new();
+        service.insert("image".into(), "${AEM_IMAGE:-aem-quickstart:latest}".into());
+        service.insert("ports".into(), vec![format!("{0}:{0}", instance.port)].into());
+        service.insert("environment".into(), Value::Mapping(environment));
+        service.insert("volumes".into(), vec![format!("{}:/opt/aem/crx-quickstart", volume)].into());
+
+        services.insert(name.into(), Value::Mapping(service));
+        volumes.insert(volume.into(), Value::Mapping(Mapping::new()));
+    }
+
+    let mut root = Mapping::new();
+    root.insert("services".into(), Value::Mapping(services));
+    root.insert("volumes".into(), Value::Mapping(volumes));
+    let yaml = serde_yaml::to_string(&root).map_err(|e| format!("Failed to serialize docker-compose: {}", e))?;
+
+    Ok(format!(
+        "# Generated by AEM Environment Manager\n\
+         # There is no public AEM image: AEM_IMAGE must run the quickstart JAR with $JAVA_OPTS\n{}",
+        yaml
+    ))
+}
+
+fn aemc_yaml(instances: &[&AemInstance]) -> Result<String, String> {
+    use serde_yaml::{Mapping, Value};
+
+    let mut configs = Mapping::new();
+    for instance in instances {
+        // aemc derives author/publish from the instance id
+        let type_name = if instance.instance_type == AemInstanceType::Publish { "publish" } else { "author" };
+        let mut id = slug(&instance.name, '_');
+        if !id.contains(type_name) {
+            id = format!("{}_{}", id, type_name);
+        }
+        let env_prefix = format!("AEM_{}", id.to_uppercase());
+        let run_modes: Vec<String> = instance
+            .run_modes
+            .iter()
+            .filter(|m| m.as_str() != "author" && m.as_str() != "publish")
+            .cloned()
+            .collect();
+
+        // Credentials stay out of the file; aemc reads them from the environment
+        let mut config = Mapping::new();
+        config.insert("http_url".into(), format!("http://{}:{}", instance.host, instance.port).into());
+        config.insert("user".into(), format!("[[.Env.{}_USER | default \"admin\" ]]", env_prefix).into());
+        config.insert("password".into(), format!("[[.Env.{}_PASSWORD | default \"admin\" ]]", env_prefix).into());
+        config.insert("run_modes".into(), run_modes.into());
+        config.insert("jvm_opts".into(), export_jvm_opts(instance).into());
+        configs.insert(id.into(), Value::Mapping(config));
+    }
+
+    let mut instance_section = Mapping::new();
+    instance_section.insert("config".into(), Value::Mapping(configs));
+    let mut root = Mapping::new();
+    root.insert("instance".into(), Value::Mapping(instance_section));
+
+    serde_yaml::to_string(&root).map_err(|e| format!("Failed to serialize aemc config: {}", e))
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Show what `import_tool_config` would create for a project directory
+#[command]
+pub async fn preview_tool_config_import(project_dir: String) -> Result<ToolImportPreview, String> {
+    preview(Path::new(&project_dir))
+}
+
+/// Create instances (and optionally a profile using them) from the aemc/env configs of
+/// a project directory. Instances already registered with the same host and port are skipped.
+#[command]
+pub async fn import_tool_config(project_dir: String, with_profile: bool) -> Result<ToolImportResult, String> {
+    let dir = Path::new(&project_dir);
+    let preview = preview(dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut created = Vec::new();
+    for imported in preview.instances {
+        if preview.already_registered.contains(&imported.name) {
+            continue;
+        }
+        let instance = add_instance(AemInstance {
+            id: String::new(),
+            name: imported.name.clone(),
+            display_name: None,
+            localized_description: None,
+            instance_type: imported.instance_type,
+            host: imported.host,
+            port: imported.port,
+            path: imported.path,
+            java_opts: imported.java_opts,
+            run_modes: imported.run_modes,
+            status: AemInstanceStatus::Unknown,
+            profile_id: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            probe_without_auth: false,
+            wsl_distro: None,
+            addons: Vec::new(),
+            readiness_rules: Vec::new(),
+            process_id: None,
+            launch_mode: Default::default(),
+            debug_port: None,
+            aem_version: None,
+            ready_webhook_url: None,
+            dispatcher: None,
+            http_headers: Default::default(),
+            http_proxy: None,
+        })
+        .await?;
+
+        if let (Some(username), Some(password)) = (&imported.username, &imported.password) {
+            save_stored_credentials(&instance.id, username, password)?;
+        }
+        created.push(instance);
+    }
+
+    let profile = if with_profile && !created.is_empty() {
+        let first_of = |t: AemInstanceType| created.iter().find(|i| i.instance_type == t).map(|i| i.id.clone());
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Imported".to_string());
+        Some(
+            create_profile(EnvironmentProfile {
+                id: String::new(),
+                name,
+                description: Some(format!("Imported from {}", preview.source_files.join(", "))),
+                display_name: None,
+                localized_description: None,
+                java_version: None,
+                java_manager_id: None,
+                java_path: preview.env_vars.get("JAVA_HOME").cloned(),
+                node_version: None,
+                node_manager_id: None,
+                node_path: None,
+                maven_config_id: None,
+                author_instance_id: first_of(AemInstanceType::Author),
+                publish_instance_id: first_of(AemInstanceType::Publish),
+                env_vars: (!preview.env_vars.is_empty()).then(|| preview.env_vars.clone()),
+                aio_config: None,
+                created_at: String::new(),
+                updated_at: String::new(),
+                last_used_at: None,
+                is_active: false,
+            })
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ToolImportResult {
+        instances: created,
+        profile,
+        skipped: preview.already_registered,
+    })
+}
+
+/// Generate a docker-compose.yml or aemc `aem.yml` from registered instances (all, or the
+/// given ids) and optionally write it to `output_path`. Dispatcher instances are not exported.
+#[command]
+pub async fn export_tool_config(
+    format: ToolExportFormat,
+    instance_ids: Option<Vec<String>>,
+    output_path: Option<String>,
+) -> Result<ToolExportResult, String> {
+    let instances = load_instances()?;
+    let mut warnings = Vec::new();
+
+    let selected: Vec<&AemInstance> = instances
+        .iter()
+        .filter(|i| instance_ids.as_ref().is_none_or(|ids| ids.contains(&i.id)))
+        .filter(|i| {
+            let dispatcher = i.instance_type == AemInstanceType::Dispatcher;
+            if dispatcher {
+                warnings.push(format!("{}: dispatcher instances are not exported", i.name));
+            }
+            !dispatcher
+        })
+        .collect();
+    if selected.is_empty() {
+        return Err("No author or publish instances to export".to_string());
+    }
+
+    let content = match format {
+        ToolExportFormat::DockerCompose => docker_compose_yaml(&selected)?,
+        ToolExportFormat::Aemc => aemc_yaml(&selected)?,
+    };
+
+    if let Some(ref path) = output_path {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create output directory: {}", e))?;
+        }
+        fs::write(path, &content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+
+    Ok(ToolExportResult {
+        format,
+        content,
+        output_path,
+        exported: selected.iter().map(|i| i.name.clone()).collect(),
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_aemc_config() {
+        let content = r#"
+instance:
+  config:
+    local_author:
+      active: [[.Env.AEM_AUTHOR_ACTIVE | default true ]]
+      http_url: [[.Env.AEM_AUTHOR_HTTP_URL | default "http://127.0.0.1:4502" ]]
+      user: [[.Env.AEM_AUTHOR_USER | default "admin" ]]
+      password: [[.Env.AEM_AUTHOR_PASSWORD | default "admin" ]]
+      run_modes: [ local ]
+      jvm_opts:
+        - -server
+        - -Djava.awt.headless=true
+    local_publish:
+      active: [[.Env.AEM_PUBLISH_ACTIVE | default true ]]
+      http_url: [[.Env.AEM_PUBLISH_HTTP_URL | default "http://127.0.0.1:4503" ]]
+"#;
+        let env = HashMap::from([("AEM_PUBLISH_ACTIVE".to_string(), "false".to_string())]);
+        let mut warnings = Vec::new();
+        let instances =
+            parse_aemc_config(content, &env, Path::new("/work/site"), AEMC_CONFIG, &mut warnings).unwrap();
+
+        assert_eq!(instances.len(), 1);
+        let author = &instances[0];
+        assert_eq!((author.host.as_str(), author.port), ("127.0.0.1", 4502));
+        assert_eq!(author.instance_type, AemInstanceType::Author);
+        assert_eq!(author.java_opts.as_deref(), Some("-server -Djava.awt.headless=true"));
+        assert_eq!(author.run_modes, vec!["local"]);
+        assert!(author.path.ends_with("aem/home/var/instance/local_author"));
+    }
+
+    #[test]
+    fn test_parse_env_instances() {
+        let env = parse_env_file(
+            "# AEM\nexport AEM_PUBLISH_HTTP_URL=\"http://localhost:4503\"\nAEM_PUBLISH_RUN_MODES=local, dev\nJAVA_HOME=/opt/jdk11\n",
+        );
+        let instances = parse_env_instances(&env, "aem.env");
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].name, "publish");
+        assert_eq!(instances[0].instance_type, AemInstanceType::Publish);
+        assert_eq!(instances[0].run_modes, vec!["local", "dev"]);
+        assert_eq!(env_instance_key("JAVA_HOME"), None);
+    }
+
+    #[test]
+    fn test_aemc_export_round_trip() {
+        let instance: AemInstance = serde_json::from_value(serde_json::json!({
+            "name": "Local Publish", "instance_type": "publish", "host": "localhost", "port": 4503,
+            "run_modes": ["publish", "local"], "java_opts": "-Xmx2g"
+        }))
+        .unwrap();
+
+        let yaml = aemc_yaml(&[&instance]).unwrap();
+        let mut warnings = Vec::new();
+        let imported = parse_aemc_config(&yaml, &HashMap::new(), Path::new("/work"), AEMC_CONFIG, &mut warnings).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].name, "local_publish");
+        assert_eq!(imported[0].port, 4503);
+        assert_eq!(imported[0].run_modes, vec!["local"]);
+        assert_eq!(imported[0].java_opts.as_deref(), Some("-Xmx2g"));
+        assert_eq!(imported[0].password.as_deref(), Some("admin"));
+
+        let compose = docker_compose_yaml(&[&instance]).unwrap();
+        assert!(compose.contains("local-publish:") && compose.contains("4503:4503"));
+    }
+}
@@ -0,0 +1,322 @@
+// Trash Commands
+// Deleted Maven configs, profiles, instances, instance directories, repository snapshots and
+// reset repositories are moved to a per-app trash folder and can be restored until the
+// retention window expires
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+use crate::commands::instance::{delete_instance_data, load_instances, save_instances, AemInstance};
+use crate::commands::profile::get_startup_config;
+use crate::commands::repository::{load_snapshots, save_snapshots, RepositorySnapshot};
+use crate::commands::settings::copy_tree;
+use crate::platform::PlatformOps;
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    MavenConfig,
+    Profile,
+    /// Instance registration, optionally with its installation directory
+    Instance,
+    /// crx-quickstart of an instance replaced by a repository reset
+    Repository,
+    /// Repository snapshot with its crx-quickstart copy
+    Snapshot,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub kind: TrashKind,
+    pub label: String,
+    /// Where the file or directory was before deletion
+    pub original_path: Option<String>,
+    /// Where it is kept inside the trash folder
+    pub trashed_path: Option<String>,
+    /// Stored record for entries that are not files (the instance registration or snapshot)
+    #[serde(default)]
+    pub record: Option<serde_json::Value>,
+    pub deleted_at: String,
+    pub expires_at: String,
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+// ============================================
+// Storage Helpers
+// ============================================
+
+fn get_trash_dir() -> PathBuf {
+    let platform = crate::platform::current_platform();
+    platform.get_data_dir().join("trash")
+}
+
+fn get_trash_index() -> PathBuf {
+    get_trash_dir().join("index.json")
+}
+
+fn load_entries() -> Result<Vec<TrashEntry>, String> {
+    let file_path = get_trash_index();
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read trash index: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse trash index: {}", e))
+}
+
+fn save_entries(entries: &[TrashEntry]) -> Result<(), String> {
+    fs::create_dir_all(get_trash_dir()).map_err(|e| format!("Failed to create trash directory: {}", e))?;
+    let content =
+        serde_json::to_string_pretty(entries).map_err(|e| format!("Failed to serialize trash index: {}", e))?;
+    fs::write(get_trash_index(), content).map_err(|e| format!("Failed to write trash index: {}", e))
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Rename, falling back to copy and delete across file systems
+fn move_path(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    if from.is_dir() {
+        let mut errors = Vec::new();
+        copy_tree(from, to, &mut errors);
+        if !errors.is_empty() {
+            let _ = fs::remove_dir_all(to);
+            return Err(errors.join("; "));
+        }
+        fs::remove_dir_all(from).map_err(|e| format!("Failed to remove {}: {}", from.display(), e))
+    } else {
+        fs::copy(from, to).map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+        fs::remove_file(from).map_err(|e| format!("Failed to remove {}: {}", from.display(), e))
+    }
+}
+
+fn remove_trashed(entry: &TrashEntry) {
+    if let Some(ref path) = entry.trashed_path {
+        let path = Path::new(path);
+        let _ = if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    }
+    let _ = fs::remove_dir(get_trash_dir().join(&entry.id));
+}
+
+/// Remove an entry for good. Purging an instance also removes the data kept for a restore.
+fn purge_entry(entry: &TrashEntry) {
+    remove_trashed(entry);
+    if entry.kind == TrashKind::Instance {
+        if let Some(id) = record_id(entry) {
+            delete_instance_data(&id);
+        }
+    }
+}
+
+fn record_id(entry: &TrashEntry) -> Option<String> {
+    entry.record.as_ref()?.get("id")?.as_str().map(String::from)
+}
+
+/// Register the stored instance or snapshot of an entry again
+fn restore_record(entry: &TrashEntry) -> Result<(), String> {
+    if !matches!(entry.kind, TrashKind::Instance | TrashKind::Snapshot) {
+        return Ok(());
+    }
+    let record = entry.record.clone().ok_or("Trash entry has no stored record")?;
+    if entry.kind == TrashKind::Instance {
+        let instance: AemInstance =
+            serde_json::from_value(record).map_err(|e| format!("Failed to parse instance: {}", e))?;
+        let mut instances = load_instances()?;
+        if instances.iter().any(|i| i.id == instance.id) {
+            return Err(format!("Instance {} is already registered", instance.name));
+        }
+        instances.push(instance);
+        save_instances(&instances)
+    } else {
+        let snapshot: RepositorySnapshot =
+            serde_json::from_value(record).map_err(|e| format!("Failed to parse snapshot: {}", e))?;
+        let mut snapshots = load_snapshots()?;
+        if snapshots
+            .iter()
+            .any(|s| s.id == snapshot.id || (s.instance_id == snapshot.instance_id && s.name == snapshot.name))
+        {
+            return Err(format!("Snapshot {} already exists", snapshot.name));
+        }
+        snapshots.push(snapshot);
+        save_snapshots(&snapshots)
+    }
+}
+
+/// Undo `restore_record` after the files could not be moved back
+fn unregister_record(entry: &TrashEntry) -> Result<(), String> {
+    let Some(id) = record_id(entry) else {
+        return Ok(());
+    };
+    match entry.kind {
+        TrashKind::Instance => {
+            let mut instances = load_instances()?;
+            instances.retain(|i| i.id != id);
+            save_instances(&instances)
+        }
+        TrashKind::Snapshot => {
+            let mut snapshots = load_snapshots()?;
+            snapshots.retain(|s| s.id != id);
+            save_snapshots(&snapshots)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn is_expired(entry: &TrashEntry, now: chrono::DateTime<chrono::Utc>) -> bool {
+    chrono::DateTime::parse_from_rfc3339(&entry.expires_at)
+        .map(|expires| expires <= now)
+        .unwrap_or(false)
+}
+
+/// Move `path` (if given) into the trash and record the entry
+pub(crate) fn move_to_trash(
+    kind: TrashKind,
+    label: &str,
+    path: Option<&Path>,
+    record: Option<serde_json::Value>,
+) -> Result<TrashEntry, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+    let retention_days = get_startup_config().trash_retention_days.max(1);
+
+    let mut entry = TrashEntry {
+        id: id.clone(),
+        kind,
+        label: label.to_string(),
+        original_path: None,
+        trashed_path: None,
+        record,
+        deleted_at: now.to_rfc3339(),
+        expires_at: (now + chrono::Duration::days(retention_days as i64)).to_rfc3339(),
+        size_bytes: 0,
+    };
+
+    if let Some(path) = path.filter(|p| p.exists()) {
+        let name = path.file_name().map(|n| n.to_os_string()).unwrap_or_else(|| "item".into());
+        let target = get_trash_dir().join(&id).join(name);
+        entry.size_bytes = crate::platform::common::dir_size(path);
+        move_path(path, &target)?;
+        entry.original_path = Some(path.to_string_lossy().to_string());
+        entry.trashed_path = Some(target.to_string_lossy().to_string());
+    }
+
+    let mut entries = load_entries()?;
+    entries.push(entry.clone());
+    save_entries(&entries)?;
+    Ok(entry)
+}
+
+/// Permanently delete entries past their retention window; returns how many were removed
+pub(crate) fn purge_expired_trash() -> Result<usize, String> {
+    let now = chrono::Utc::now();
+    let (expired, kept): (Vec<_>, Vec<_>) = load_entries()?.into_iter().partition(|e| is_expired(e, now));
+    if expired.is_empty() {
+        return Ok(0);
+    }
+    expired.iter().for_each(purge_entry);
+    save_entries(&kept)?;
+    Ok(expired.len())
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+/// Trash entries, newest first (expired entries are purged first)
+#[command]
+pub async fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    purge_expired_trash()?;
+    let mut entries = load_entries()?;
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+/// Move an entry back to where it was deleted from
+#[command]
+pub async fn restore_from_trash(id: String) -> Result<TrashEntry, String> {
+    let mut entries = load_entries()?;
+    let index = entries
+        .iter()
+        .position(|e| e.id == id)
+        .ok_or_else(|| format!("Trash entry not found: {}", id))?;
+    let entry = entries[index].clone();
+
+    if let Some(ref original) = entry.original_path {
+        if Path::new(original).exists() {
+            return Err(format!("Cannot restore: {} already exists", original));
+        }
+    }
+
+    // The stored record goes back first so a failed move leaves nothing half-restored
+    restore_record(&entry)?;
+
+    if let (Some(original), Some(trashed)) = (&entry.original_path, &entry.trashed_path) {
+        if let Err(e) = move_path(Path::new(trashed), Path::new(original)) {
+            unregister_record(&entry)?;
+            return Err(format!("Failed to restore {}: {}", entry.label, e));
+        }
+    }
+
+    remove_trashed(&entry);
+    entries.remove(index);
+    save_entries(&entries)?;
+    Ok(entry)
+}
+
+/// Permanently delete one entry, or everything when no id is given
+#[command]
+pub async fn empty_trash(id: Option<String>) -> Result<usize, String> {
+    let (removed, kept): (Vec<_>, Vec<_>) = load_entries()?
+        .into_iter()
+        .partition(|e| id.as_ref().is_none_or(|id| &e.id == id));
+    removed.iter().for_each(purge_entry);
+    save_entries(&kept)?;
+    Ok(removed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_path_and_expiry() {
+        let dir = std::env::temp_dir().join(format!("aem-env-manager-trash-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("instance/crx-quickstart")).unwrap();
+        fs::write(dir.join("instance/crx-quickstart/a.txt"), "a").unwrap();
+
+        move_path(&dir.join("instance"), &dir.join("trash/1/instance")).unwrap();
+        assert!(!dir.join("instance").exists());
+        assert!(dir.join("trash/1/instance/crx-quickstart/a.txt").exists());
+        let _ = fs::remove_dir_all(&dir);
+
+        let now = chrono::Utc::now();
+        let entry = TrashEntry {
+            id: "1".to_string(),
+            kind: TrashKind::Profile,
+            label: "Dev".to_string(),
+            original_path: None,
+            trashed_path: None,
+            record: None,
+            deleted_at: now.to_rfc3339(),
+            expires_at: (now + chrono::Duration::days(1)).to_rfc3339(),
+            size_bytes: 0,
+        };
+        assert!(!is_expired(&entry, now));
+        assert!(is_expired(&entry, now + chrono::Duration::days(2)));
+    }
+}
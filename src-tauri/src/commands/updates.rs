@@ -0,0 +1,348 @@
+// App Update Commands
+// User control over the updater plugin: release channel, deferring or skipping a version,
+// staged rollout, release notes from the GitHub release feed and manual check/install
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::commands::certificates::http_client_builder;
+use crate::commands::profile::{get_startup_config, save_app_config};
+
+// ============================================
+// Data Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+/// Update preferences, stored in the app config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePolicy {
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// Automatic checks stay quiet until this time (RFC 3339)
+    #[serde(default)]
+    pub deferred_until: Option<String>,
+    /// Version the user chose to skip; automatic checks ignore it
+    #[serde(default)]
+    pub skipped_version: Option<String>,
+    /// Stable per-install value (0-99) compared with a release's rollout percentage
+    #[serde(default)]
+    pub rollout_bucket: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppUpdateCheck {
+    pub channel: UpdateChannel,
+    pub current_version: String,
+    /// Newer version published on the channel
+    pub version: Option<String>,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+    /// Whether the UI should offer the update now (false when deferred, skipped or
+    /// not yet rolled out to this install; manual checks ignore all three)
+    pub offer: bool,
+    /// Why an available update is not offered
+    pub held_back_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNote {
+    pub version: String,
+    pub name: String,
+    pub date: Option<String>,
+    pub prerelease: bool,
+    pub notes: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+/// Event emitted while an update downloads
+pub const UPDATE_PROGRESS_EVENT: &str = "update-download-progress";
+
+const RELEASES_API: &str = "https://api.github.com/repos/yjwu-leadstec/aem-env-manager/releases";
+
+const DEFAULT_DEFER_DAYS: u32 = 7;
+
+/// Rolling release the release workflow keeps the beta channel manifest in
+const BETA_MANIFEST_TAG: &str = "beta";
+
+impl UpdateChannel {
+    /// Update manifest of the channel; beta builds are published under a rolling `beta` tag
+    fn endpoint(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => {
+                "https://github.com/yjwu-leadstec/aem-env-manager/releases/latest/download/latest.json"
+            }
+            UpdateChannel::Beta => "https://github.com/yjwu-leadstec/aem-env-manager/releases/download/beta/latest.json",
+        }
+    }
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+async fn save_policy(policy: &UpdatePolicy) -> Result<(), String> {
+    save_app_config(serde_json::json!({ "update_policy": policy })).await
+}
+
+/// Policy with a rollout bucket assigned (persisted on first use)
+async fn load_policy() -> Result<UpdatePolicy, String> {
+    let mut policy = get_startup_config().update_policy;
+    if policy.rollout_bucket.is_none() {
+        policy.rollout_bucket = Some(rand::thread_rng().gen_range(0..100));
+        save_policy(&policy).await?;
+    }
+    Ok(policy)
+}
+
+/// Why an automatic check should not offer `version`, or None to offer it
+fn held_back_reason(
+    policy: &UpdatePolicy,
+    version: &str,
+    rollout_percentage: Option<u64>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<String> {
+    if policy.skipped_version.as_deref() == Some(version) {
+        return Some(format!("Version {} was skipped", version));
+    }
+    if let Some(until) = policy
+        .deferred_until
+        .as_deref()
+        .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+    {
+        if until > now {
+            return Some(format!("Updates are deferred until {}", until.to_rfc3339()));
+        }
+    }
+    if let Some(percentage) = rollout_percentage {
+        if u64::from(policy.rollout_bucket.unwrap_or(0)) >= percentage {
+            return Some(format!("Version {} is rolled out to {}% of installs so far", version, percentage));
+        }
+    }
+    None
+}
+
+async fn check_channel(app: &AppHandle, channel: UpdateChannel) -> Result<Option<Update>, String> {
+    let endpoint = Url::parse(channel.endpoint()).map_err(|e| e.to_string())?;
+    app.updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(|e| format!("Failed to configure updater: {}", e))?
+        .build()
+        .map_err(|e| format!("Failed to configure updater: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+/// Release notes from the GitHub releases API response
+fn parse_releases(json: &serde_json::Value, channel: UpdateChannel, limit: usize) -> Vec<ReleaseNote> {
+    json.as_array()
+        .map(|releases| {
+            releases
+                .iter()
+                .filter(|r| !r.get("draft").and_then(|d| d.as_bool()).unwrap_or(false))
+                .filter_map(|r| {
+                    let prerelease = r.get("prerelease").and_then(|p| p.as_bool()).unwrap_or(false);
+                    if prerelease && channel == UpdateChannel::Stable {
+                        return None;
+                    }
+                    let tag = r
+                        .get("tag_name")
+                        .and_then(|t| t.as_str())
+                        .filter(|t| *t != BETA_MANIFEST_TAG)?;
+                    let text = |key: &str| r.get(key).and_then(|v| v.as_str()).map(str::to_string);
+                    Some(ReleaseNote {
+                        version: tag.trim_start_matches('v').to_string(),
+                        name: text("name").filter(|n| !n.is_empty()).unwrap_or_else(|| tag.to_string()),
+                        date: text("published_at"),
+                        prerelease,
+                        notes: text("body").unwrap_or_default(),
+                        url: text("html_url").unwrap_or_default(),
+                    })
+                })
+                .take(limit)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ============================================
+// Tauri Commands
+// ============================================
+
+#[command]
+pub async fn get_update_policy() -> Result<UpdatePolicy, String> {
+    load_policy().await
+}
+
+#[command]
+pub async fn set_update_channel(channel: UpdateChannel) -> Result<UpdatePolicy, String> {
+    let mut policy = load_policy().await?;
+    policy.channel = channel;
+    save_policy(&policy).await?;
+    Ok(policy)
+}
+
+/// Keep automatic checks quiet for `days` (default 7); 0 clears the deferral
+#[command]
+pub async fn defer_updates(days: Option<u32>) -> Result<UpdatePolicy, String> {
+    let mut policy = load_policy().await?;
+    let days = days.unwrap_or(DEFAULT_DEFER_DAYS);
+    policy.deferred_until =
+        (days > 0).then(|| (chrono::Utc::now() + chrono::Duration::days(days as i64)).to_rfc3339());
+    save_policy(&policy).await?;
+    Ok(policy)
+}
+
+/// Skip a version in automatic checks (None clears it)
+#[command]
+pub async fn skip_update_version(version: Option<String>) -> Result<UpdatePolicy, String> {
+    let mut policy = load_policy().await?;
+    policy.skipped_version = version;
+    save_policy(&policy).await?;
+    Ok(policy)
+}
+
+/// Check the configured channel. Manual checks offer any newer version;
+/// automatic ones respect deferral, skipped version and staged rollout.
+#[command]
+pub async fn check_app_update(app: AppHandle, manual: bool) -> Result<AppUpdateCheck, String> {
+    let policy = load_policy().await?;
+    let update = check_channel(&app, policy.channel).await?;
+
+    let Some(update) = update else {
+        return Ok(AppUpdateCheck {
+            channel: policy.channel,
+            current_version: app.package_info().version.to_string(),
+            version: None,
+            date: None,
+            notes: None,
+            offer: false,
+            held_back_reason: None,
+        });
+    };
+
+    let rollout = update.raw_json.get("rollout_percentage").and_then(|p| p.as_u64());
+    let held_back_reason = if manual {
+        None
+    } else {
+        held_back_reason(&policy, &update.version, rollout, chrono::Utc::now())
+    };
+
+    Ok(AppUpdateCheck {
+        channel: policy.channel,
+        current_version: update.current_version.clone(),
+        version: Some(update.version.clone()),
+        date: update.date.map(|d| d.to_string()),
+        notes: update.body.clone(),
+        offer: held_back_reason.is_none(),
+        held_back_reason,
+    })
+}
+
+/// Download and install the newest version of the channel, emitting progress events.
+/// The app has to be relaunched afterwards.
+#[command]
+pub async fn install_app_update(app: AppHandle) -> Result<String, String> {
+    let policy = load_policy().await?;
+    let update = check_channel(&app, policy.channel)
+        .await?
+        .ok_or_else(|| "No update available".to_string())?;
+
+    let mut downloaded = 0u64;
+    update
+        .download_and_install(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let _ = app.emit(UPDATE_PROGRESS_EVENT, DownloadProgress { downloaded, total });
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    Ok(update.version)
+}
+
+/// Release notes of the configured channel, newest first
+#[command]
+pub async fn get_release_notes(limit: Option<usize>) -> Result<Vec<ReleaseNote>, String> {
+    let policy = load_policy().await?;
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(15))
+        .user_agent("aem-env-manager")
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let json: serde_json::Value = client
+        .get(RELEASES_API)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch release notes: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Failed to fetch release notes: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release notes: {}", e))?;
+
+    Ok(parse_releases(&json, policy.channel, limit.unwrap_or(10)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_held_back_reason() {
+        let now = chrono::Utc::now();
+        let mut policy = UpdatePolicy {
+            rollout_bucket: Some(42),
+            ..Default::default()
+        };
+        assert_eq!(held_back_reason(&policy, "1.2.0", None, now), None);
+        assert_eq!(held_back_reason(&policy, "1.2.0", Some(50), now), None);
+        assert!(held_back_reason(&policy, "1.2.0", Some(20), now).is_some());
+
+        policy.deferred_until = Some((now + chrono::Duration::days(1)).to_rfc3339());
+        assert!(held_back_reason(&policy, "1.2.0", None, now).unwrap().contains("deferred"));
+        policy.deferred_until = Some((now - chrono::Duration::days(1)).to_rfc3339());
+        assert_eq!(held_back_reason(&policy, "1.2.0", None, now), None);
+
+        policy.skipped_version = Some("1.2.0".to_string());
+        assert!(held_back_reason(&policy, "1.2.0", None, now).is_some());
+        assert_eq!(held_back_reason(&policy, "1.3.0", None, now), None);
+    }
+
+    #[test]
+    fn test_parse_releases() {
+        let json = serde_json::json!([
+            {"tag_name": "v1.3.0-beta.1", "name": "", "prerelease": true, "draft": false, "body": "Beta"},
+            {"tag_name": "v1.2.0", "name": "1.2.0", "prerelease": false, "draft": false, "body": "Fixes",
+             "published_at": "2026-09-01T10:00:00Z", "html_url": "https://example.com/v1.2.0"},
+            {"tag_name": "v1.4.0", "prerelease": false, "draft": true},
+            {"tag_name": "beta", "name": "Beta channel", "prerelease": true, "draft": false}
+        ]);
+        let stable = parse_releases(&json, UpdateChannel::Stable, 10);
+        assert_eq!(stable.len(), 1);
+        assert_eq!(stable[0].version, "1.2.0");
+
+        let beta = parse_releases(&json, UpdateChannel::Beta, 10);
+        assert_eq!(beta.len(), 2);
+        assert_eq!(beta[0].name, "v1.3.0-beta.1");
+    }
+}
@@ -1,17 +1,22 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Command;
-use tauri::command;
+use tauri::{command, AppHandle};
 
 #[cfg(target_os = "macos")]
 use crate::platform::macos::{JenvManager, NvmManager, SdkmanManager};
 
 #[cfg(target_os = "windows")]
-use crate::platform::windows::{FnmManager, JabbaManager, NvmWindowsManager, VoltaManager};
+use crate::platform::windows::{
+    FnmManager, JabbaManager, NvmWindowsManager, VoltaManager, WindowsPackageManager,
+};
 
 #[cfg(target_os = "linux")]
 use crate::platform::linux::{JenvManager, NvmManager, SdkmanManager};
 
+use crate::commands::operations::{coalesce, CancelToken};
+use crate::commands::runtimes::{install_jdk, install_node, runtimes_dir, RuntimeKind, DOWNLOAD_MANAGER_ID};
+use crate::i18n::MessageCode;
 use crate::platform::common::VersionManagerOps;
 use crate::platform::PlatformOps;
 
@@ -39,6 +44,9 @@ pub enum VersionManagerType {
     Fnm,
     Volta,
     NvmWindows,
+    Scoop,
+    Chocolatey,
+    Winget,
     Manual,
 }
 
@@ -50,6 +58,27 @@ pub struct JavaVersion {
     pub is_default: bool,
     pub is_current: bool,
     pub full_version: Option<String>,
+    /// False when the JDK violates the configured vendor/version policy
+    #[serde(default = "default_true")]
+    pub is_preferred: bool,
+    /// Why the JDK is not preferred
+    #[serde(default)]
+    pub policy_note: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Organization-wide JDK preferences (an empty vendor list allows any vendor)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JavaPolicy {
+    /// Allowed vendors, e.g. ["temurin"]; matched case-insensitively with common aliases
+    #[serde(default)]
+    pub preferred_vendors: Vec<String>,
+    /// Minimum Java major version
+    #[serde(default)]
+    pub min_version: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +103,8 @@ pub struct VersionSwitchResult {
     pub previous_version: Option<String>,
     pub current_version: String,
     pub message: Option<String>,
+    #[serde(flatten)]
+    pub message_code: Option<MessageCode>,
     pub error: Option<String>,
 }
 
@@ -117,17 +148,23 @@ fn get_current_java_symlink_target() -> Option<PathBuf> {
     None
 }
 
-/// Scan system for installed Java versions
+/// Scan system for installed Java versions (concurrent calls share one scan)
 #[command]
 pub async fn scan_java_versions() -> Result<Vec<JavaVersion>, String> {
+    coalesce("scan_java_versions", scan_java_versions_with).await
+}
+
+async fn scan_java_versions_with(token: CancelToken) -> Result<Vec<JavaVersion>, String> {
     let platform = crate::platform::current_platform();
-    let scan_paths = platform.get_java_scan_paths();
+    let mut scan_paths = platform.get_java_scan_paths();
+    scan_paths.push(runtimes_dir(RuntimeKind::Java));
     let current_symlink_target = get_current_java_symlink_target();
     let mut versions = Vec::new();
 
     for base_path in scan_paths {
         if let Ok(entries) = std::fs::read_dir(&base_path) {
             for entry in entries.flatten() {
+                token.check()?;
                 let path = entry.path();
                 if path.is_dir() {
                     // Check for java binary
@@ -161,6 +198,8 @@ pub async fn scan_java_versions() -> Result<Vec<JavaVersion>, String> {
                                 is_default: false,
                                 is_current,
                                 full_version: version_info.2,
+                                is_preferred: true,
+                                policy_note: None,
                             });
                         }
                     }
@@ -176,11 +215,13 @@ pub async fn scan_java_versions() -> Result<Vec<JavaVersion>, String> {
     // Sort by version
     versions.sort_by(|a, b| b.version.cmp(&a.version));
 
+    apply_java_policy(&mut versions);
+
     Ok(versions)
 }
 
 /// Parse Java version from installation directory
-fn parse_java_version(java_home: &PathBuf) -> Option<(String, String, Option<String>)> {
+pub(crate) fn parse_java_version(java_home: &PathBuf) -> Option<(String, String, Option<String>)> {
     // Try to get version from release file first
     let release_file = java_home.join("release");
     if release_file.exists() {
@@ -266,6 +307,77 @@ fn extract_version_from_path(name: &str) -> Option<String> {
     re_parts.first().map(|s| s.to_string())
 }
 
+/// Canonical vendor key so "Eclipse Adoptium" matches a "temurin" preference; unknown
+/// vendors are reduced to their lowercase letters and digits
+fn vendor_key(vendor: &str) -> String {
+    // GraalVM reports "Oracle Corporation" in some builds, so it is checked before Oracle
+    const ALIASES: [(&str, &str); 17] = [
+        ("temurin", "temurin"),
+        ("adoptium", "temurin"),
+        ("adoptopenjdk", "temurin"),
+        ("zulu", "zulu"),
+        ("azul", "zulu"),
+        ("corretto", "corretto"),
+        ("amazon", "corretto"),
+        ("graalvm", "graalvm"),
+        ("microsoft", "microsoft"),
+        ("oracle", "oracle"),
+        ("liberica", "liberica"),
+        ("bellsoft", "liberica"),
+        ("sapmachine", "sapmachine"),
+        ("sap se", "sapmachine"),
+        ("semeru", "semeru"),
+        ("red hat", "redhat"),
+        ("redhat", "redhat"),
+    ];
+
+    let vendor = vendor.to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| vendor.contains(alias))
+        .map(|(_, key)| key.to_string())
+        .unwrap_or_else(|| vendor.chars().filter(|c| c.is_ascii_alphanumeric()).collect())
+}
+
+/// Whether a JDK vendor string satisfies a preferred vendor; vendors without an alias match
+/// when either normalized name contains the other
+fn vendor_matches(preferred: &str, vendor: &str) -> bool {
+    let (preferred, vendor) = (vendor_key(preferred), vendor_key(vendor));
+    !preferred.is_empty() && !vendor.is_empty() && (vendor.contains(&preferred) || preferred.contains(&vendor))
+}
+
+/// Describe why a JDK does not satisfy the policy (None when it does)
+pub(crate) fn java_policy_violation(java: &JavaVersion, policy: &JavaPolicy) -> Option<String> {
+    if let Some(min) = policy.min_version {
+        // Versions are stored as found ("17.0.9", "1.8.0_392"), compare the major version
+        let major = extract_java_major_version(&java.version).parse::<u32>().unwrap_or(0);
+        if major < min {
+            return Some(format!("Java {} is below the minimum version {}", java.version, min));
+        }
+    }
+
+    if !policy.preferred_vendors.is_empty() {
+        if !policy.preferred_vendors.iter().any(|v| vendor_matches(v, &java.vendor)) {
+            return Some(format!(
+                "{} is not a preferred vendor ({})",
+                java.vendor,
+                policy.preferred_vendors.join(", ")
+            ));
+        }
+    }
+
+    None
+}
+
+/// Mark JDKs that violate the configured policy
+fn apply_java_policy(versions: &mut [JavaVersion]) {
+    let policy = crate::commands::profile::get_startup_config().java_policy;
+    for java in versions.iter_mut() {
+        java.policy_note = java_policy_violation(java, &policy);
+        java.is_preferred = java.policy_note.is_none();
+    }
+}
+
 /// Get current Java version from JAVA_HOME or java -version
 #[command]
 pub async fn get_current_java_version() -> Result<Option<String>, String> {
@@ -326,11 +438,25 @@ pub async fn switch_java_version(
         .set_java_home(std::path::Path::new(&target.path))
         .map_err(|e| format!("Failed to set JAVA_HOME: {}", e))?;
 
+    let (message, message_code) = match &target.policy_note {
+        Some(note) => (
+            format!("JAVA_HOME set to {} (warning: {})", target.path, note),
+            MessageCode::new("java_home_set_with_warning")
+                .with("path", &target.path)
+                .with("warning", note),
+        ),
+        None => (
+            format!("JAVA_HOME set to {}", target.path),
+            MessageCode::new("java_home_set").with("path", &target.path),
+        ),
+    };
+
     Ok(VersionSwitchResult {
         success: true,
         previous_version: previous,
         current_version: version,
-        message: Some(format!("JAVA_HOME set to {}", target.path)),
+        message: Some(message),
+        message_code: Some(message_code),
         error: None,
     })
 }
@@ -373,6 +499,11 @@ async fn switch_java_with_manager(
         previous_version: previous,
         current_version: version.to_string(),
         message: Some(format!("Switched to Java {} via {}", version, manager)),
+        message_code: Some(
+            MessageCode::new("java_switched")
+                .with("version", version)
+                .with("manager", manager),
+        ),
         error: None,
     })
 }
@@ -400,17 +531,23 @@ fn get_current_node_symlink_target() -> Option<PathBuf> {
     None
 }
 
-/// Scan system for installed Node versions
+/// Scan system for installed Node versions (concurrent calls share one scan)
 #[command]
 pub async fn scan_node_versions() -> Result<Vec<NodeVersion>, String> {
+    coalesce("scan_node_versions", scan_node_versions_with).await
+}
+
+async fn scan_node_versions_with(token: CancelToken) -> Result<Vec<NodeVersion>, String> {
     let platform = crate::platform::current_platform();
-    let scan_paths = platform.get_node_scan_paths();
+    let mut scan_paths = platform.get_node_scan_paths();
+    scan_paths.push(runtimes_dir(RuntimeKind::Node));
     let current_symlink_target = get_current_node_symlink_target();
     let mut versions = Vec::new();
 
     for base_path in scan_paths {
         if let Ok(entries) = std::fs::read_dir(&base_path) {
             for entry in entries.flatten() {
+                token.check()?;
                 let path = entry.path();
                 if path.is_dir() {
                     // Check for node binary
@@ -483,7 +620,7 @@ fn extract_node_version_from_path(path: &PathBuf) -> Option<String> {
 }
 
 /// Compare semantic version strings
-fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+pub(crate) fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
     let parse = |v: &str| -> Vec<u32> {
         v.trim_start_matches('v')
             .split('.')
@@ -631,6 +768,11 @@ async fn switch_node_with_manager(
         previous_version: previous,
         current_version: version.to_string(),
         message: Some(format!("Switched to Node {} via {}", version, manager)),
+        message_code: Some(
+            MessageCode::new("node_switched")
+                .with("version", version)
+                .with("manager", manager),
+        ),
         error: None,
     })
 }
@@ -726,6 +868,23 @@ pub async fn detect_version_managers() -> Result<Vec<VersionManager>, String> {
             is_active: nvm.is_installed(),
             path: std::env::var("NVM_HOME").ok(),
         });
+
+        // Package managers (Scoop, Chocolatey, winget) provide both Java and Node
+        for package_manager in WindowsPackageManager::ALL {
+            let is_installed = package_manager.is_installed();
+            managers.push(VersionManager {
+                id: package_manager.id().to_string(),
+                name: package_manager.name().to_string(),
+                manager_type: match package_manager {
+                    WindowsPackageManager::Scoop => VersionManagerType::Scoop,
+                    WindowsPackageManager::Chocolatey => VersionManagerType::Chocolatey,
+                    WindowsPackageManager::Winget => VersionManagerType::Winget,
+                },
+                is_installed,
+                is_active: is_installed,
+                path: package_manager.path().map(|p| p.to_string_lossy().to_string()),
+            });
+        }
     }
 
     #[cfg(target_os = "linux")]
@@ -797,6 +956,20 @@ pub async fn get_managed_versions(
 
     #[cfg(target_os = "windows")]
     {
+        // Package managers report package names and versions directly
+        if let Some(package_manager) = WindowsPackageManager::from_id(&manager_id) {
+            return Ok(package_manager
+                .list_packages(&tool_type)?
+                .into_iter()
+                .map(|p| InstalledVersion {
+                    version: p.version,
+                    path: String::new(),
+                    is_default: false,
+                    vendor: Some(p.name),
+                })
+                .collect());
+        }
+
         versions = match (manager_id.as_str(), tool_type.as_str()) {
             ("jabba", "java") => {
                 let jabba = JabbaManager::new();
@@ -923,7 +1096,7 @@ fn get_current_maven_settings() -> Result<Option<String>, String> {
 
 /// Parse Maven settings.xml to extract localRepository path
 /// Returns default ~/.m2/repository if not configured or if the value is a placeholder
-fn parse_maven_local_repository(settings_path: &std::path::Path) -> Option<String> {
+pub(crate) fn parse_maven_local_repository(settings_path: &std::path::Path) -> Option<String> {
     let content = std::fs::read_to_string(settings_path).ok()?;
 
     // First, remove all XML comments to avoid matching commented-out examples
@@ -1263,8 +1436,13 @@ pub async fn delete_maven_config(config_id: String) -> Result<bool, String> {
         }
     }
 
-    std::fs::remove_file(&config_path)
-        .map_err(|e| format!("Failed to delete Maven config: {}", e))?;
+    crate::commands::trash::move_to_trash(
+        crate::commands::trash::TrashKind::MavenConfig,
+        &config_id,
+        Some(&config_path),
+        None,
+    )
+    .map_err(|e| format!("Failed to delete Maven config: {}", e))?;
 
     Ok(true)
 }
@@ -1454,16 +1632,34 @@ pub async fn get_maven_config_path(config_id: String) -> Result<String, String>
 }
 
 // ============================================
-// Installation Commands (Placeholder)
+// Installation Commands
 // ============================================
 
-/// Install a new Java version
+/// Install a new Java version. With the "download" manager the JDK is downloaded through the
+/// shared download cache, emitting transfer progress under `operation_id`.
 #[command]
 pub async fn install_java_version(
+    app: AppHandle,
     version: String,
     vendor: String,
     manager_id: String,
+    operation_id: Option<String>,
 ) -> Result<bool, String> {
+    if manager_id == DOWNLOAD_MANAGER_ID {
+        install_jdk(&app, &extract_java_major_version(&version), &vendor_key(&vendor), operation_id).await?;
+        return Ok(true);
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(package_manager) = WindowsPackageManager::from_id(&manager_id) {
+        let major = extract_java_major_version(&version);
+        let package = package_manager
+            .java_package(&vendor_key(&vendor), &major)
+            .ok_or_else(|| format!("{} has no known package for {} Java {}", package_manager.name(), vendor, major))?;
+        package_manager.install(&package, None)?;
+        return Ok(true);
+    }
+
     // This would typically trigger the version manager to download and install
     // For now, we return an error indicating this requires user action
     Err(format!(
@@ -1472,9 +1668,26 @@ pub async fn install_java_version(
     ))
 }
 
-/// Install a new Node version
+/// Install a new Node version. With the "download" manager the build is downloaded through the
+/// shared download cache, emitting transfer progress under `operation_id`.
 #[command]
-pub async fn install_node_version(version: String, manager_id: String) -> Result<bool, String> {
+pub async fn install_node_version(
+    app: AppHandle,
+    version: String,
+    manager_id: String,
+    operation_id: Option<String>,
+) -> Result<bool, String> {
+    if manager_id == DOWNLOAD_MANAGER_ID {
+        install_node(&app, &version, operation_id).await?;
+        return Ok(true);
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(package_manager) = WindowsPackageManager::from_id(&manager_id) {
+        package_manager.install(package_manager.node_package(), Some(version.trim_start_matches('v')))?;
+        return Ok(true);
+    }
+
     // This would typically trigger the version manager to download and install
     Err(format!(
         "Please use {} to install Node {}. Run the appropriate install command.",
@@ -1490,7 +1703,14 @@ pub async fn install_node_version(version: String, manager_id: String) -> Result
 /// This allows users to manually add Java installations not detected by version managers
 #[command]
 pub async fn validate_java_path(path: String) -> Result<JavaVersion, String> {
-    let java_home = PathBuf::from(&path);
+    let mut java = inspect_java_home(&path)?;
+    apply_java_policy(std::slice::from_mut(&mut java));
+    Ok(java)
+}
+
+/// Check that `path` is a Java installation and read its version info
+fn inspect_java_home(path: &str) -> Result<JavaVersion, String> {
+    let java_home = PathBuf::from(path);
 
     // Check if path exists
     if !java_home.exists() {
@@ -1547,6 +1767,8 @@ pub async fn validate_java_path(path: String) -> Result<JavaVersion, String> {
             is_default: false,
             is_current,
             full_version: version_info.2,
+            is_preferred: true,
+            policy_note: None,
         })
     } else {
         // If we can't parse version, try running java -version
@@ -1588,6 +1810,8 @@ pub async fn validate_java_path(path: String) -> Result<JavaVersion, String> {
             is_default: false,
             is_current,
             full_version: None,
+            is_preferred: true,
+            policy_note: None,
         })
     }
 }
@@ -1715,6 +1939,8 @@ pub async fn scan_java_in_path(path: String) -> Result<Vec<JavaVersion>, String>
                     is_default: false,
                     is_current,
                     full_version: version_info.2,
+                    is_preferred: true,
+                    policy_note: None,
                 });
             }
             // Don't scan subdirectories of a valid Java installation
@@ -1741,6 +1967,8 @@ pub async fn scan_java_in_path(path: String) -> Result<Vec<JavaVersion>, String>
     // Sort by version
     versions.sort_by(|a, b| b.version.cmp(&a.version));
 
+    apply_java_policy(&mut versions);
+
     Ok(versions)
 }
 
@@ -1923,4 +2151,58 @@ mod tests {
             Some("17.30.15".to_string())
         );
     }
+
+    #[test]
+    fn test_java_policy_violation() {
+        let java = JavaVersion {
+            version: "11".to_string(),
+            vendor: "Eclipse Adoptium".to_string(),
+            path: "/opt/jdk-11".to_string(),
+            is_default: false,
+            is_current: false,
+            full_version: None,
+            is_preferred: true,
+            policy_note: None,
+        };
+
+        let temurin_only = JavaPolicy {
+            preferred_vendors: vec!["Temurin".to_string()],
+            min_version: None,
+        };
+        assert!(java_policy_violation(&java, &temurin_only).is_none());
+
+        let zulu_only = JavaPolicy {
+            preferred_vendors: vec!["zulu".to_string()],
+            min_version: None,
+        };
+        assert!(java_policy_violation(&java, &zulu_only).is_some());
+
+        assert!(vendor_matches("oracle", "Oracle Corporation"));
+        assert!(vendor_matches("Liberica", "BellSoft"));
+        assert!(vendor_matches("SAP", "SAP SE"));
+        assert!(vendor_matches("Acme JDK", "acme"));
+        assert!(!vendor_matches("oracle", "Eclipse Adoptium"));
+        assert!(!vendor_matches("", "Unknown"));
+
+        let min_17 = JavaPolicy {
+            preferred_vendors: vec![],
+            min_version: Some(17),
+        };
+        assert!(java_policy_violation(&java, &min_17).is_some());
+
+        let java_21 = JavaVersion {
+            version: "21.0.2".to_string(),
+            ..java.clone()
+        };
+        assert!(java_policy_violation(&java_21, &min_17).is_none());
+        let java_8 = JavaVersion {
+            version: "1.8.0_392".to_string(),
+            ..java
+        };
+        let min_8 = JavaPolicy {
+            preferred_vendors: vec![],
+            min_version: Some(8),
+        };
+        assert!(java_policy_violation(&java_8, &min_8).is_none());
+    }
 }
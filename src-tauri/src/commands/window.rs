@@ -1,6 +1,76 @@
 // Window management commands
+// The main window plus additional windows scoped to an instance (log viewer, metrics)
+// or a profile (profile editor)
 
-use tauri::Manager;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+/// What an additional window shows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowKind {
+    LogViewer,
+    Metrics,
+    ProfileEditor,
+}
+
+/// Context of an additional window; the window reads it with `get_window_context`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowContext {
+    pub label: String,
+    pub kind: WindowKind,
+    pub instance_id: Option<String>,
+    pub profile_id: Option<String>,
+}
+
+/// Label prefix of context windows (matched by the capability's window list)
+const CONTEXT_WINDOW_PREFIX: &str = "ctx-";
+
+/// Open context windows; entries are removed when their window is destroyed
+static CONTEXT_WINDOWS: Mutex<Vec<WindowContext>> = Mutex::new(Vec::new());
+
+impl WindowKind {
+    fn slug(self) -> &'static str {
+        match self {
+            WindowKind::LogViewer => "logs",
+            WindowKind::Metrics => "metrics",
+            WindowKind::ProfileEditor => "profile",
+        }
+    }
+
+    fn size(self) -> (f64, f64) {
+        match self {
+            WindowKind::LogViewer => (1000.0, 700.0),
+            WindowKind::Metrics => (900.0, 600.0),
+            WindowKind::ProfileEditor => (720.0, 680.0),
+        }
+    }
+}
+
+/// Window label for a context; one window per kind and target
+fn context_label(kind: WindowKind, target: &str) -> String {
+    let target: String = target
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}{}-{}", CONTEXT_WINDOW_PREFIX, kind.slug(), target)
+}
+
+/// Percent-encoding of a query parameter value
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn registered_windows() -> Vec<WindowContext> {
+    CONTEXT_WINDOWS.lock().map(|w| w.clone()).unwrap_or_default()
+}
 
 /// Hide window and remove from Dock (macOS)
 #[tauri::command]
@@ -35,3 +105,145 @@ pub async fn show_from_tray(app: tauri::AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Open (or focus) a window scoped to an instance or profile.
+/// Log viewer and metrics windows need `instance_id`, the profile editor `profile_id`.
+#[tauri::command]
+pub async fn open_context_window(
+    app: tauri::AppHandle,
+    kind: WindowKind,
+    instance_id: Option<String>,
+    profile_id: Option<String>,
+) -> Result<WindowContext, String> {
+    let (query, target) = match (kind, &instance_id, &profile_id) {
+        (WindowKind::ProfileEditor, _, Some(profile)) => ("profile", profile.clone()),
+        (WindowKind::LogViewer | WindowKind::Metrics, Some(instance), _) => ("instance", instance.clone()),
+        (WindowKind::ProfileEditor, _, None) => return Err("The profile editor needs a profile".to_string()),
+        _ => return Err("This window needs an instance".to_string()),
+    };
+    let label = context_label(kind, &target);
+
+    if let Some(window) = app.get_webview_window(&label) {
+        window.unminimize().map_err(|e| e.to_string())?;
+        window.set_focus().map_err(|e| e.to_string())?;
+        if let Some(context) = registered_windows().into_iter().find(|w| w.label == label) {
+            return Ok(context);
+        }
+    }
+
+    let context = WindowContext {
+        label: label.clone(),
+        kind,
+        instance_id: instance_id.filter(|_| query == "instance"),
+        profile_id: profile_id.filter(|_| query == "profile"),
+    };
+
+    if app.get_webview_window(&label).is_none() {
+        let (width, height) = kind.size();
+        let url = format!("window/{}?{}={}", kind.slug(), query, encode_query_value(&target));
+        let window = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+            .title("AEM Environment Manager")
+            .inner_size(width, height)
+            .build()
+            .map_err(|e| format!("Failed to open window: {}", e))?;
+
+        let destroyed_label = label.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Destroyed = event {
+                if let Ok(mut windows) = CONTEXT_WINDOWS.lock() {
+                    windows.retain(|w| w.label != destroyed_label);
+                }
+            }
+        });
+    }
+
+    if let Ok(mut windows) = CONTEXT_WINDOWS.lock() {
+        windows.retain(|w| w.label != label);
+        windows.push(context.clone());
+    }
+    Ok(context)
+}
+
+/// Context of the calling window (None for the main window)
+#[tauri::command]
+pub async fn get_window_context(window: tauri::Window) -> Result<Option<WindowContext>, String> {
+    Ok(registered_windows().into_iter().find(|w| w.label == window.label()))
+}
+
+/// All open context windows
+#[tauri::command]
+pub async fn list_context_windows() -> Result<Vec<WindowContext>, String> {
+    Ok(registered_windows())
+}
+
+/// Close a context window
+#[tauri::command]
+pub async fn close_context_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+    if !label.starts_with(CONTEXT_WINDOW_PREFIX) {
+        return Err("Only context windows can be closed this way".to_string());
+    }
+    if let Some(window) = app.get_webview_window(&label) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Emit an event to the main window and the context windows of an instance or profile only
+/// (every context window when neither is given). Returns the labels that received it.
+pub(crate) fn emit_to_windows<S: Serialize>(
+    app: &tauri::AppHandle,
+    event: &str,
+    payload: S,
+    instance_id: Option<&str>,
+    profile_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut labels = vec!["main".to_string()];
+    labels.extend(
+        registered_windows()
+            .into_iter()
+            .filter(|w| match (instance_id, profile_id) {
+                (Some(instance), _) => w.instance_id.as_deref() == Some(instance),
+                (None, Some(profile)) => w.profile_id.as_deref() == Some(profile),
+                (None, None) => true,
+            })
+            .map(|w| w.label),
+    );
+
+    for label in &labels {
+        app.emit_to(label.as_str(), event, &payload)
+            .map_err(|e| format!("Failed to emit {}: {}", event, e))?;
+    }
+    Ok(labels)
+}
+
+/// Relay an event from the frontend to the main window and the matching context windows
+#[tauri::command]
+pub async fn emit_to_context_windows(
+    app: tauri::AppHandle,
+    event: String,
+    payload: serde_json::Value,
+    instance_id: Option<String>,
+    profile_id: Option<String>,
+) -> Result<Vec<String>, String> {
+    emit_to_windows(&app, &event, payload, instance_id.as_deref(), profile_id.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_label() {
+        assert_eq!(
+            context_label(WindowKind::LogViewer, "6f1c-42ab"),
+            "ctx-logs-6f1c-42ab"
+        );
+        assert_eq!(context_label(WindowKind::ProfileEditor, "a/b c"), "ctx-profile-a_b_c");
+    }
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("6f1c-42ab"), "6f1c-42ab");
+        assert_eq!(encode_query_value("a&b=c d/é"), "a%26b%3Dc%20d%2F%C3%A9");
+    }
+}
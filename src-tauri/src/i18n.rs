@@ -0,0 +1,277 @@
+// Backend Localization
+// Localized display names for data models and locale-aware backend messages
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ============================================
+// Data Types
+// ============================================
+
+/// Supported UI locales (mirrors the frontend i18n resources)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    ZhCn,
+    ZhTw,
+}
+
+impl Locale {
+    /// Parse a language tag such as "en", "zh-CN" or "zh_TW"
+    pub fn from_tag(tag: &str) -> Self {
+        let tag = tag.trim().to_lowercase().replace('_', "-");
+        match tag.as_str() {
+            "zh-tw" | "zh-hk" | "zh-hant" => Locale::ZhTw,
+            t if t.starts_with("zh") => Locale::ZhCn,
+            _ => Locale::En,
+        }
+    }
+
+    /// Locale configured in the app settings
+    pub fn current() -> Self {
+        Self::from_tag(&crate::commands::get_startup_config().language)
+    }
+
+    fn is_chinese(self) -> bool {
+        matches!(self, Locale::ZhCn | Locale::ZhTw)
+    }
+}
+
+/// Optional bilingual text attached to user-facing records
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LocalizedText {
+    #[serde(default)]
+    pub zh: Option<String>,
+    #[serde(default)]
+    pub en: Option<String>,
+}
+
+impl LocalizedText {
+    /// Pick the text for a locale, falling back to the other language and then `fallback`
+    pub fn resolve(&self, locale: Locale, fallback: &str) -> String {
+        let (preferred, other) = if locale.is_chinese() {
+            (&self.zh, &self.en)
+        } else {
+            (&self.en, &self.zh)
+        };
+
+        // An empty translation counts as missing
+        let text = |t: &Option<String>| t.as_deref().filter(|s| !s.trim().is_empty());
+        text(preferred).or(text(other)).unwrap_or(fallback).to_string()
+    }
+}
+
+/// Machine-readable form of a result message, flattened into result structs next to their
+/// English `message`. The frontend renders `message_key` (a key of its locale files) with
+/// `params` interpolated; `code` stays stable for programmatic checks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MessageCode {
+    #[serde(default)]
+    pub code: String,
+    #[serde(default)]
+    pub message_key: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl MessageCode {
+    /// Code in snake_case, e.g. "profile_switched" with key "backend.profileSwitched"
+    pub fn new(code: &str) -> Self {
+        let mut key = String::from("backend.");
+        let mut upper = false;
+        for c in code.chars() {
+            if c == '_' {
+                upper = true;
+            } else if upper {
+                key.extend(c.to_uppercase());
+                upper = false;
+            } else {
+                key.push(c);
+            }
+        }
+        Self {
+            code: code.to_string(),
+            message_key: key,
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Add an interpolation parameter
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Kinds of records referenced in backend messages
+#[derive(Debug, Clone, Copy)]
+pub enum Entity {
+    Profile,
+    Instance,
+    License,
+    Project,
+}
+
+impl Entity {
+    fn label(self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (Entity::Profile, Locale::En) => "Profile",
+            (Entity::Profile, Locale::ZhCn) => "配置",
+            (Entity::Profile, Locale::ZhTw) => "設定檔",
+            (Entity::Instance, Locale::En) => "Instance",
+            (Entity::Instance, _) => "实例",
+            (Entity::License, Locale::En) => "License",
+            (Entity::License, Locale::ZhCn) => "许可证",
+            (Entity::License, Locale::ZhTw) => "授權",
+            (Entity::Project, Locale::En) => "Project",
+            (Entity::Project, Locale::ZhCn) => "项目",
+            (Entity::Project, Locale::ZhTw) => "專案",
+        }
+    }
+}
+
+/// Backend-generated messages that are shown to the user
+#[derive(Debug, Clone)]
+pub enum Message<'a> {
+    NotFound(Entity, &'a str),
+    TrayShow,
+    TrayHide,
+    TrayQuit,
+    TrayInstances,
+    TrayNoInstances,
+    TrayStart,
+    TrayStop,
+    InstanceFailed(&'a str),
+    InstancePortConflict(&'a str),
+    InstanceStoppedUnexpectedly(&'a str),
+    BundlesInactive(&'a str, u32),
+    LicenseExpiring(&'a str, i64),
+    LicenseExpired(&'a str),
+}
+
+impl Message<'_> {
+    /// Format the message for a specific locale
+    pub fn format(&self, locale: Locale) -> String {
+        match self {
+            Message::NotFound(entity, id) => match locale {
+                Locale::En => format!("{} {} not found", entity.label(locale), id),
+                Locale::ZhCn => format!("未找到{} {}", entity.label(locale), id),
+                Locale::ZhTw => format!("找不到{} {}", entity.label(locale), id),
+            },
+            Message::TrayShow => match locale {
+                Locale::En => "Show Window",
+                Locale::ZhCn => "显示窗口",
+                Locale::ZhTw => "顯示視窗",
+            }
+            .to_string(),
+            Message::TrayHide => match locale {
+                Locale::En => "Hide Window",
+                Locale::ZhCn => "隐藏窗口",
+                Locale::ZhTw => "隱藏視窗",
+            }
+            .to_string(),
+            Message::TrayQuit => match locale {
+                Locale::En => "Quit",
+                Locale::ZhCn => "退出",
+                Locale::ZhTw => "結束",
+            }
+            .to_string(),
+            Message::TrayInstances => match locale {
+                Locale::En => "Instances",
+                Locale::ZhCn => "实例",
+                Locale::ZhTw => "實例",
+            }
+            .to_string(),
+            Message::TrayNoInstances => match locale {
+                Locale::En => "No instances",
+                Locale::ZhCn => "暂无实例",
+                Locale::ZhTw => "尚無實例",
+            }
+            .to_string(),
+            Message::TrayStart => match locale {
+                Locale::En => "Start",
+                Locale::ZhCn => "启动",
+                Locale::ZhTw => "啟動",
+            }
+            .to_string(),
+            Message::TrayStop => match locale {
+                Locale::En => "Stop",
+                Locale::ZhCn => "停止",
+                Locale::ZhTw => "停止",
+            }
+            .to_string(),
+            Message::InstanceFailed(name) => match locale {
+                Locale::En => format!("{} is in an error state", name),
+                Locale::ZhCn => format!("{} 出现错误", name),
+                Locale::ZhTw => format!("{} 發生錯誤", name),
+            },
+            Message::InstancePortConflict(name) => match locale {
+                Locale::En => format!("The port of {} is used by another process", name),
+                Locale::ZhCn => format!("{} 的端口被其他进程占用", name),
+                Locale::ZhTw => format!("{} 的連接埠被其他程序佔用", name),
+            },
+            Message::InstanceStoppedUnexpectedly(name) => match locale {
+                Locale::En => format!("{} stopped unexpectedly", name),
+                Locale::ZhCn => format!("{} 意外停止", name),
+                Locale::ZhTw => format!("{} 意外停止", name),
+            },
+            Message::BundlesInactive(name, count) => match locale {
+                Locale::En => format!("{} has {} bundles that are not active", name, count),
+                Locale::ZhCn => format!("{} 有 {} 个 Bundle 未激活", name, count),
+                Locale::ZhTw => format!("{} 有 {} 個 Bundle 未啟用", name, count),
+            },
+            Message::LicenseExpiring(name, days) => match locale {
+                Locale::En => format!("License {} expires in {} days", name, days),
+                Locale::ZhCn => format!("许可证 {} 将在 {} 天后过期", name, days),
+                Locale::ZhTw => format!("授權 {} 將在 {} 天後過期", name, days),
+            },
+            Message::LicenseExpired(name) => match locale {
+                Locale::En => format!("License {} has expired", name),
+                Locale::ZhCn => format!("许可证 {} 已过期", name),
+                Locale::ZhTw => format!("授權 {} 已過期", name),
+            },
+        }
+    }
+
+    /// Format the message using the configured app locale
+    pub fn localized(&self) -> String {
+        self.format(Locale::current())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("en"), Locale::En);
+        assert_eq!(Locale::from_tag("zh-CN"), Locale::ZhCn);
+        assert_eq!(Locale::from_tag("zh_TW"), Locale::ZhTw);
+        assert_eq!(Locale::from_tag(""), Locale::En);
+    }
+
+    #[test]
+    fn test_localized_text_fallback() {
+        let text = LocalizedText {
+            zh: None,
+            en: Some("Author".to_string()),
+        };
+        assert_eq!(text.resolve(Locale::ZhCn, "x"), "Author");
+        assert_eq!(LocalizedText::default().resolve(Locale::En, "x"), "x");
+
+        let empty_zh = LocalizedText {
+            zh: Some(" ".to_string()),
+            en: Some("Author".to_string()),
+        };
+        assert_eq!(empty_zh.resolve(Locale::ZhCn, "x"), "Author");
+    }
+
+    #[test]
+    fn test_message_code() {
+        let code = MessageCode::new("java_symlink_updated").with("path", "/opt/jdk");
+        assert_eq!(code.message_key, "backend.javaSymlinkUpdated");
+        let json = serde_json::to_value(&code).unwrap();
+        assert_eq!(json["params"]["path"], "/opt/jdk");
+    }
+}
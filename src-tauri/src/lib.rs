@@ -1,11 +1,14 @@
 // AEM Environment Manager - Tauri Backend Library
 
+mod cli;
 mod commands;
+mod i18n;
+mod notifications;
 mod platform;
+mod tray;
 
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     Manager, RunEvent,
 };
@@ -23,46 +26,181 @@ use commands::{
     scan_maven_settings, scan_maven_settings_in_path, scan_node_in_path, scan_node_versions,
     switch_java_version, switch_maven_config, switch_node_version, validate_java_path, validate_node_path,
     // Instance commands
-    add_instance, check_instance_health, delete_instance, detect_all_instances_status,
-    detect_instance_status, get_credentials, get_instance, get_instance_urls, list_instances,
-    open_in_browser, parse_jar_file, scan_aem_instances, scan_directory_for_jars, start_instance,
-    stop_instance, store_credentials, update_instance,
+    add_instance, check_instance_health, delete_credential, delete_instance, detect_all_instances_status,
+    detect_instance_status, get_credentials, get_instance, get_instance_console_log, get_instance_urls, list_credentials,
+    list_instances, list_wsl_distributions, open_in_browser, open_instance_console, open_instance_folder,
+    open_instance_terminal, parse_jar_file, scan_aem_instances, scan_directory_for_jars, start_instance,
+    start_instance_debug, stop_instance, store_credentials, test_credentials, update_instance,
+    // Add-on commands
+    detect_instance_addons,
+    // Readiness commands
+    check_instance_readiness, restart_instance, start_instance_and_wait, send_test_ready_webhook,
+    // Quick action commands
+    get_quick_action_confirmation, run_quick_action,
+    // Security commands
+    audit_instance_security, change_instance_password, generate_password, list_password_rotations,
+    rotate_user_passwords,
+    // Content package commands
+    export_anonymized_package,
     // License commands
     add_aem_license, associate_license_with_instance, check_license_file, delete_aem_license,
     get_aem_license, get_license_statistics, get_licenses_for_instance, import_license_from_file,
-    list_aem_licenses, parse_license_file, read_license_file, scan_default_license_locations,
-    scan_license_files, update_aem_license, validate_aem_license,
+    license_reminder_scheduler, list_aem_licenses, parse_license_file, read_license_file,
+    scan_default_license_locations, scan_license_files, update_aem_license, validate_aem_license,
+    // Metrics commands
+    clear_instance_metrics, get_instance_metrics, get_instance_uptime, get_jmx_metrics, prune_instance_metrics,
+    // Diagnostics commands
+    capture_heap_dump, capture_thread_dump, collect_diagnostics, delete_heap_dump, list_heap_dumps,
+    // Download commands
+    clear_download_cache, download_to_cache, list_download_cache,
+    // Project commands
+    delete_project, delete_project_build_dirs, find_project_for_path, list_projects, prefetch_project_dependencies,
+    refresh_project, register_project, scan_project_build_dirs, update_project,
+    // Git commands
+    branch_watcher, get_all_projects_git_status, get_project_git_status, suggest_branch_profile,
+    // Dispatcher commands
+    analyze_dispatcher_cache, check_dispatcher_path, flush_dispatcher, inspect_dispatcher_config,
+    set_dispatcher_endpoint,
+    // Report commands
+    export_environment_report,
+    // Tool config commands
+    export_tool_config, import_tool_config, preview_tool_config_import,
+    // IDE commands
+    generate_ide_config, get_ide_environment,
+    // Maintenance commands
+    cleanup_instance_files, get_instance_disk_usage, maintenance_scheduler, run_maintenance,
     // Settings commands
-    export_all_config, import_all_config, load_scan_paths, reset_all_config, save_scan_paths,
+    config_export_scheduler, export_all_config, get_data_location, import_all_config, is_config_export_encrypted,
+    list_auto_exports,
+    load_scan_paths, migrate_data_dir, reset_all_config, reset_environment, reset_instances, reset_licenses,
+    run_auto_export_now, save_scan_paths,
     // Environment commands
-    check_environment_status, get_current_symlinks, get_profile_environment,
-    initialize_environment, remove_java_symlink, remove_node_symlink, remove_shell_config,
-    set_java_symlink, set_node_symlink,
+    check_environment_status, clear_quarantine, create_env_snapshot, delete_env_snapshot, generate_shell_aliases,
+    get_current_symlinks, get_profile_environment, initialize_environment, list_env_snapshots, remove_java_symlink,
+    remove_node_symlink, remove_shell_config, restore_env_snapshot, set_java_symlink, set_node_symlink,
     // Window commands
-    hide_to_tray, show_from_tray,
+    close_context_window, emit_to_context_windows, get_window_context, hide_to_tray, list_context_windows,
+    open_context_window, show_from_tray,
+    // Update commands
+    check_app_update, defer_updates, get_release_notes, get_update_policy, install_app_update,
+    set_update_channel, skip_update_version,
+    // Lifecycle commands
+    get_running_instances_for_quit, get_startup_recovery_report, reconcile_instance_states, resolve_quit,
+    // Integrity commands
+    get_integrity_report, run_integrity_check,
+    // Instance group commands
+    assign_instances_to_group, check_instance_group_health, create_instance_group, delete_instance_group,
+    list_instance_groups, remove_instances_from_group, rename_instance_group, start_instance_group,
+    stop_instance_group,
+    // Trash commands
+    empty_trash, list_trash, restore_from_trash,
+    // Startup time commands
+    clear_startup_stats, get_startup_stats,
+    // Dashboard commands
+    get_dashboard_state,
+    // Search commands
+    search,
+    // Operation commands
+    cancel_operation, list_running_operations,
+    // Global npm package commands
+    list_global_package_snapshots, restore_global_packages, snapshot_global_packages,
+    // aio CLI commands
+    apply_profile_aio_config, detect_aio_cli, get_aio_config, run_aio_command,
+    // Certificate commands
+    add_app_trusted_ca, import_ca_into_jdk, list_app_trusted_cas, remove_app_trusted_ca,
+    // Log commands
+    export_instance_logs, list_instance_logs, list_log_tails, start_log_tail, stop_log_tail,
+    // Registry credential commands
+    apply_profile_registry_credentials, clear_registry_credentials, get_profile_registry_credentials,
+    set_profile_registry_credentials,
+    // AEM catalog commands
+    download_aem_release, get_aem_catalog, get_aem_installation_overview, refresh_aem_catalog,
+    // Sling log commands
+    create_sling_logger, list_sling_loggers, set_sling_log_level,
+    // Service pack commands
+    check_service_pack, install_service_pack,
+    // Package Manager commands
+    build_package,
+    download_package,
+    install_package,
+    list_packages,
+    upload_package,
+    // Action catalog commands
+    list_actions,
+    // Content sync commands
+    sync_content,
+    // Automation commands
+    automation_scheduler,
+    delete_automation_job,
+    list_automation_jobs,
+    list_automation_runs,
+    run_automation_job,
+    save_automation_job,
+    // Content seed commands
+    apply_content_seed,
+    delete_content_seed,
+    list_content_seeds,
+    save_content_seed,
+    // Bundle commands
+    control_bundle,
+    install_bundle,
+    list_bundles,
+    // Repository commands
+    create_repository_snapshot,
+    delete_repository_snapshot,
+    list_repository_snapshots,
+    reset_instance_repository,
+    restore_repository_snapshot,
+    // Port planning commands
+    allocate_workspace_ports,
+    get_port_convention,
+    list_port_allocations,
+    release_workspace_ports,
+    set_port_convention,
+    // Share commands
+    copy_instance_url,
+    expose_instance_on_lan,
+    get_instance_qr_code,
+    list_lan_exposures,
+    list_shared_users,
+    revoke_shared_users_now,
+    stop_lan_exposure,
+    // Activity commands
+    get_instance_activity,
+    // App lock commands
+    get_app_lock_status,
+    guard_invoke_handler,
+    lock_app,
+    set_app_lock_pin,
+    unlock_app,
+    // Benchmark commands
+    benchmark_jvm_options,
+    // Credential storage commands
+    get_credential_storage_status,
+    rotate_credentials_key,
 };
 
+pub use cli::run_cli;
+
 /// Initialize and run the Tauri application
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Use the relocated data directory before anything touches storage
+    platform::set_data_dir_override(get_startup_config().data_dir.map(std::path::PathBuf::from));
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
-            // Create tray menu items
-            let show_i = MenuItemBuilder::with_id("show", "显示窗口 / Show").build(app)?;
-            let hide_i = MenuItemBuilder::with_id("hide", "隐藏窗口 / Hide").build(app)?;
-            let quit_i = MenuItemBuilder::with_id("quit", "退出 / Quit").build(app)?;
-
-            // Build tray menu
-            let menu = MenuBuilder::new(app)
-                .item(&show_i)
-                .item(&hide_i)
-                .separator()
-                .item(&quit_i)
-                .build()?;
+            // Check the JSON stores and restore corrupt ones before anything loads them
+            commands::integrity::check_integrity_on_startup(app.handle());
+
+            // Build tray menu; the health scheduler fills in the instances
+            let menu = tray::build_menu(app, &[])?;
 
             // Load tray icon (monochrome template icon for menu bar)
             // Try file path first (for development), fallback to embedded bytes (for production)
@@ -71,7 +209,7 @@ pub fn run() {
             })?;
 
             // Build tray icon
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(tray::TRAY_ID)
                 .icon(icon)
                 .icon_as_template(true) // macOS: use as template for automatic light/dark adaptation
                 .menu(&menu)
@@ -100,10 +238,13 @@ pub fn run() {
                         }
                     }
                     "quit" => {
-                        // Use std::process::exit to bypass the ExitRequested event handler
-                        std::process::exit(0);
+                        // Ask the UI first when local instances are still running
+                        tauri::async_runtime::spawn(commands::lifecycle::request_quit(app.clone()));
+                    }
+                    id => {
+                        // Start/stop items of the instances submenu
+                        tray::handle_menu_event(app, id);
                     }
-                    _ => {}
                 })
                 .on_tray_icon_event(|tray, event| {
                     // Left click on tray icon shows the window
@@ -142,9 +283,34 @@ pub fn run() {
                 }
             }
 
+            // Delete temporary users of shared URLs that expired while the app was closed
+            tauri::async_runtime::spawn(commands::share::revoke_expired_shared_users());
+
+            // Fix instance states left behind by a crashed previous session
+            tauri::async_runtime::spawn(commands::lifecycle::recover_state_on_startup(app.handle().clone()));
+
+            // Scheduled log/temp cleanup (no-op unless enabled in settings)
+            tauri::async_runtime::spawn(maintenance_scheduler());
+
+            // Nightly configuration export (no-op unless an export directory is set)
+            tauri::async_runtime::spawn(config_export_scheduler());
+
+            // Profile suggestions/auto-switching when a project's branch changes
+            tauri::async_runtime::spawn(branch_watcher(app.handle().clone()));
+
+            // Tray badge and instance menu reflecting whether instances are stopped, running or unhealthy
+            tauri::async_runtime::spawn(tray::health_scheduler(app.handle().clone()));
+
+            // User-defined automation jobs with a schedule trigger
+            tauri::async_runtime::spawn(automation_scheduler());
+
+            // Desktop reminders of licenses nearing expiry
+            tauri::async_runtime::spawn(license_reminder_scheduler(app.handle().clone()));
+
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        // Destructive commands are rejected while the app is locked
+        .invoke_handler(guard_invoke_handler(tauri::generate_handler![
             // Profile commands
             list_profiles,
             get_profile,
@@ -195,17 +361,44 @@ pub fn run() {
             update_instance,
             delete_instance,
             start_instance,
+            start_instance_debug,
             stop_instance,
+            get_instance_console_log,
+            open_instance_terminal,
+            open_instance_folder,
+            open_instance_console,
             check_instance_health,
             detect_instance_status,
             detect_all_instances_status,
             scan_aem_instances,
             scan_directory_for_jars,
             parse_jar_file,
+            list_wsl_distributions,
             store_credentials,
             get_credentials,
+            test_credentials,
+            list_credentials,
+            delete_credential,
             open_in_browser,
             get_instance_urls,
+            // Add-on commands
+            detect_instance_addons,
+            // Readiness commands
+            check_instance_readiness,
+            start_instance_and_wait,
+            restart_instance,
+            send_test_ready_webhook,
+            // Quick action commands
+            get_quick_action_confirmation,
+            run_quick_action,
+            // Security commands
+            generate_password,
+            rotate_user_passwords,
+            change_instance_password,
+            list_password_rotations,
+            audit_instance_security,
+            // Content package commands
+            export_anonymized_package,
             // License commands
             list_aem_licenses,
             get_aem_license,
@@ -222,12 +415,69 @@ pub fn run() {
             import_license_from_file,
             scan_license_files,
             scan_default_license_locations,
+            // Metrics commands
+            get_instance_metrics,
+            clear_instance_metrics,
+            get_instance_uptime,
+            prune_instance_metrics,
+            get_jmx_metrics,
+            // Diagnostics commands
+            collect_diagnostics,
+            capture_thread_dump,
+            capture_heap_dump,
+            list_heap_dumps,
+            delete_heap_dump,
+            // Download commands
+            download_to_cache,
+            list_download_cache,
+            clear_download_cache,
+            // Project commands
+            list_projects,
+            register_project,
+            refresh_project,
+            update_project,
+            delete_project,
+            find_project_for_path,
+            prefetch_project_dependencies,
+            scan_project_build_dirs,
+            delete_project_build_dirs,
+            // Git commands
+            get_project_git_status,
+            get_all_projects_git_status,
+            suggest_branch_profile,
+            // Dispatcher commands
+            inspect_dispatcher_config,
+            check_dispatcher_path,
+            analyze_dispatcher_cache,
+            set_dispatcher_endpoint,
+            flush_dispatcher,
+            // Report commands
+            export_environment_report,
+            // Tool config commands
+            preview_tool_config_import,
+            import_tool_config,
+            export_tool_config,
+            // IDE commands
+            get_ide_environment,
+            generate_ide_config,
+            // Maintenance commands
+            cleanup_instance_files,
+            run_maintenance,
+            get_instance_disk_usage,
             // Settings commands
             load_scan_paths,
             save_scan_paths,
             export_all_config,
             import_all_config,
+            is_config_export_encrypted,
             reset_all_config,
+            reset_instances,
+            reset_environment,
+            reset_licenses,
+            get_data_location,
+            migrate_data_dir,
+            run_auto_export_now,
+            list_auto_exports,
             // Environment commands
             check_environment_status,
             initialize_environment,
@@ -238,10 +488,155 @@ pub fn run() {
             remove_node_symlink,
             get_profile_environment,
             get_current_symlinks,
+            clear_quarantine,
+            generate_shell_aliases,
+            create_env_snapshot,
+            list_env_snapshots,
+            restore_env_snapshot,
+            delete_env_snapshot,
             // Window commands
             hide_to_tray,
             show_from_tray,
-        ])
+            open_context_window,
+            get_window_context,
+            list_context_windows,
+            close_context_window,
+            emit_to_context_windows,
+            // Lifecycle commands
+            get_running_instances_for_quit,
+            resolve_quit,
+            get_startup_recovery_report,
+            reconcile_instance_states,
+            // Integrity commands
+            get_integrity_report,
+            run_integrity_check,
+            // Instance group commands
+            list_instance_groups,
+            create_instance_group,
+            rename_instance_group,
+            delete_instance_group,
+            assign_instances_to_group,
+            remove_instances_from_group,
+            start_instance_group,
+            stop_instance_group,
+            check_instance_group_health,
+            // Update commands
+            get_update_policy,
+            set_update_channel,
+            defer_updates,
+            skip_update_version,
+            check_app_update,
+            install_app_update,
+            get_release_notes,
+            // Trash commands
+            list_trash,
+            restore_from_trash,
+            empty_trash,
+            // Startup time commands
+            get_startup_stats,
+            clear_startup_stats,
+            // Dashboard commands
+            get_dashboard_state,
+            // Search commands
+            search,
+            // Operation commands
+            list_running_operations,
+            cancel_operation,
+            // Global npm package commands
+            snapshot_global_packages,
+            list_global_package_snapshots,
+            restore_global_packages,
+            // aio CLI commands
+            detect_aio_cli,
+            run_aio_command,
+            get_aio_config,
+            apply_profile_aio_config,
+            // Certificate commands
+            import_ca_into_jdk,
+            add_app_trusted_ca,
+            list_app_trusted_cas,
+            remove_app_trusted_ca,
+            // Log commands
+            list_instance_logs,
+            start_log_tail,
+            stop_log_tail,
+            list_log_tails,
+            export_instance_logs,
+            // Registry credential commands
+            get_profile_registry_credentials,
+            set_profile_registry_credentials,
+            apply_profile_registry_credentials,
+            clear_registry_credentials,
+            // AEM catalog commands
+            get_aem_catalog,
+            refresh_aem_catalog,
+            download_aem_release,
+            get_aem_installation_overview,
+            // Sling log commands
+            list_sling_loggers,
+            set_sling_log_level,
+            create_sling_logger,
+            // Service pack commands
+            check_service_pack,
+            install_service_pack,
+            // Package Manager commands
+            list_packages,
+            upload_package,
+            install_package,
+            build_package,
+            download_package,
+            // Action catalog commands
+            list_actions,
+            // Content sync commands
+            sync_content,
+            // Automation commands
+            list_automation_jobs,
+            save_automation_job,
+            delete_automation_job,
+            run_automation_job,
+            list_automation_runs,
+            // Content seed commands
+            list_content_seeds,
+            save_content_seed,
+            delete_content_seed,
+            apply_content_seed,
+            // Bundle commands
+            list_bundles,
+            control_bundle,
+            install_bundle,
+            // Repository commands
+            reset_instance_repository,
+            list_repository_snapshots,
+            create_repository_snapshot,
+            restore_repository_snapshot,
+            delete_repository_snapshot,
+            // Port planning commands
+            get_port_convention,
+            set_port_convention,
+            list_port_allocations,
+            allocate_workspace_ports,
+            release_workspace_ports,
+            // Share commands
+            copy_instance_url,
+            get_instance_qr_code,
+            expose_instance_on_lan,
+            stop_lan_exposure,
+            list_lan_exposures,
+            list_shared_users,
+            revoke_shared_users_now,
+            // Activity commands
+            get_instance_activity,
+            // App lock commands
+            get_app_lock_status,
+            set_app_lock_pin,
+            lock_app,
+            unlock_app,
+            // Benchmark commands
+            benchmark_jvm_options,
+            // Credential storage commands
+            get_credential_storage_status,
+            rotate_credentials_key,
+        ]))
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|_app_handle, event| {
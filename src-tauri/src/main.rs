@@ -2,5 +2,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = aem_env_manager_lib::run_cli(&args) {
+        std::process::exit(code);
+    }
+
     aem_env_manager_lib::run()
 }
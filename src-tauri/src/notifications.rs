@@ -0,0 +1,129 @@
+// Desktop notifications
+// Native notifications for instances that fail, lose their port or stop without the app
+// stopping them, for health checks that find many inactive bundles and for expiring licenses.
+// Only shown while `show_notifications` is enabled.
+
+use std::collections::HashSet;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::activity::{latest_activity, ActivityKind};
+use crate::commands::instance::{AemInstanceStatus, HealthCheckResult};
+use crate::commands::profile::get_startup_config;
+use crate::i18n::{Locale, Message};
+
+const NOTIFICATION_TITLE: &str = "AEM Environment Manager";
+
+/// Health checks with at least this many resolved or installed bundles are reported
+const INACTIVE_BUNDLE_ALERT: u32 = 10;
+
+/// A stop within this many seconds after the app stopped or reset the instance is expected
+const EXPECTED_STOP_WINDOW_SECS: i64 = 10 * 60;
+
+fn notify(app: &AppHandle, message: Message<'_>) {
+    if !get_startup_config().show_notifications {
+        return;
+    }
+    let body = message.format(Locale::current());
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(NOTIFICATION_TITLE)
+        .body(&body)
+        .show()
+    {
+        println!("[AEM] Failed to show notification '{}': {}", body, e);
+    }
+}
+
+/// Notification for a status change of a running instance, None when it is not worth one
+fn state_change_message<'a>(
+    name: &'a str,
+    previous: &AemInstanceStatus,
+    current: &AemInstanceStatus,
+    stop_expected: bool,
+) -> Option<Message<'a>> {
+    if *previous != AemInstanceStatus::Running {
+        return None;
+    }
+    match current {
+        AemInstanceStatus::Error => Some(Message::InstanceFailed(name)),
+        AemInstanceStatus::PortConflict => Some(Message::InstancePortConflict(name)),
+        AemInstanceStatus::Stopped if !stop_expected => Some(Message::InstanceStoppedUnexpectedly(name)),
+        _ => None,
+    }
+}
+
+/// Whether the app itself recently stopped, reset or restored the instance
+fn stop_expected(instance_id: &str) -> bool {
+    latest_activity(instance_id)
+        .filter(|entry| {
+            matches!(
+                entry.kind,
+                ActivityKind::Stopped | ActivityKind::RepositoryReset | ActivityKind::SnapshotRestored
+            )
+        })
+        .and_then(|entry| chrono::DateTime::parse_from_rfc3339(&entry.timestamp).ok())
+        .is_some_and(|at| chrono::Utc::now().timestamp() - at.timestamp() <= EXPECTED_STOP_WINDOW_SECS)
+}
+
+/// Notify about a settled status change seen by the health scheduler
+pub fn status_changed(
+    app: &AppHandle,
+    instance_id: &str,
+    name: &str,
+    previous: &AemInstanceStatus,
+    current: &AemInstanceStatus,
+) {
+    let expected = *current == AemInstanceStatus::Stopped && stop_expected(instance_id);
+    if let Some(message) = state_change_message(name, previous, current, expected) {
+        notify(app, message);
+    }
+}
+
+/// Notify once when a health check finds many inactive bundles; `alerted` holds the instances
+/// already reported and is cleared again when their bundles recover
+pub fn check_bundles(app: &AppHandle, name: &str, result: &HealthCheckResult, alerted: &mut HashSet<String>) {
+    let Some(bundles) = result.bundle_status.as_ref() else {
+        return;
+    };
+    let inactive = bundles.resolved + bundles.installed;
+    if inactive < INACTIVE_BUNDLE_ALERT {
+        alerted.remove(&result.instance_id);
+    } else if alerted.insert(result.instance_id.clone()) {
+        notify(app, Message::BundlesInactive(name, inactive));
+    }
+}
+
+/// Remind of a license that expires within a configured threshold or has expired
+pub fn license_expiry(app: &AppHandle, name: &str, days_until_expiry: i64) {
+    let message = if days_until_expiry < 0 {
+        Message::LicenseExpired(name)
+    } else {
+        Message::LicenseExpiring(name, days_until_expiry)
+    };
+    notify(app, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_change_message() {
+        let running = AemInstanceStatus::Running;
+        let message = |current, expected| {
+            state_change_message("Author", &running, &current, expected).map(|m| m.format(Locale::En))
+        };
+        assert_eq!(
+            message(AemInstanceStatus::Error, false).as_deref(),
+            Some("Author is in an error state")
+        );
+        assert_eq!(
+            message(AemInstanceStatus::Stopped, false).as_deref(),
+            Some("Author stopped unexpectedly")
+        );
+        assert_eq!(message(AemInstanceStatus::Stopped, true), None);
+        assert!(state_change_message("Author", &AemInstanceStatus::Stopped, &running, false).is_none());
+    }
+}
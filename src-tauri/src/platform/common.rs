@@ -1,6 +1,10 @@
 // Common platform abstractions
 
 use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// User-selected data directory (set from the app config at startup)
+static DATA_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
 
 /// Trait for platform-specific shell execution
 pub trait ShellExecutor {
@@ -57,28 +61,57 @@ pub trait PlatformOps: Send + Sync {
     fn append_to_shell_config(&self, content: &str) -> Result<(), String>;
 
     // System operations
-    #[allow(dead_code)]
-    fn open_terminal(&self, cwd: &std::path::Path) -> Result<(), String>;
-    #[allow(dead_code)]
+    /// Open a terminal window in `cwd` whose shell has `env` set
+    fn open_terminal(&self, cwd: &std::path::Path, env: &[(String, String)]) -> Result<(), String>;
     fn open_file_manager(&self, path: &std::path::Path) -> Result<(), String>;
     fn open_browser(&self, url: &str) -> Result<(), String>;
 
     // Process management
     fn kill_process(&self, pid: u32) -> Result<(), String>;
     fn get_process_by_port(&self, port: u16) -> Option<u32>;
+    fn is_process_running(&self, pid: u32) -> bool;
+
+    // Disk space
+    /// Bytes available on the file system holding `path` (None when it cannot be determined)
+    fn get_free_space(&self, path: &std::path::Path) -> Option<u64>;
 
     // Configuration paths
     fn get_config_dir(&self) -> PathBuf;
     fn get_data_dir(&self) -> PathBuf;
     #[allow(dead_code)]
     fn get_cache_dir(&self) -> PathBuf;
+
+    // Downloaded files (Gatekeeper on macOS, no-op elsewhere)
+    /// Remove the quarantine flag recursively; returns whether it was set
+    fn remove_quarantine(&self, _path: &std::path::Path) -> Result<bool, String> {
+        Ok(false)
+    }
+    /// Whether a bundle is notarized (None when not applicable)
+    fn is_notarized(&self, _path: &std::path::Path) -> Option<bool> {
+        None
+    }
 }
 
-/// Get the application data directory
+/// Get the application data directory (the relocated one when configured)
 pub fn get_app_data_dir() -> Option<PathBuf> {
+    if let Some(dir) = DATA_DIR_OVERRIDE.read().ok().and_then(|d| d.clone()) {
+        return Some(dir);
+    }
+    get_default_app_data_dir()
+}
+
+/// Get the default application data directory, ignoring any relocation
+pub fn get_default_app_data_dir() -> Option<PathBuf> {
     dirs::data_dir().map(|p| p.join("aem-env-manager"))
 }
 
+/// Relocate the application data directory (None restores the default)
+pub fn set_data_dir_override(dir: Option<PathBuf>) {
+    if let Ok(mut current) = DATA_DIR_OVERRIDE.write() {
+        *current = dir;
+    }
+}
+
 /// Get the application config directory
 pub fn get_app_config_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|p| p.join("aem-env-manager"))
@@ -99,6 +132,33 @@ pub fn ensure_dir_exists(path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+/// Available bytes from `df -Pk` output (POSIX format, 1024-byte blocks)
+pub fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
+
+/// Free space via `df`, shared by macOS and Linux
+pub fn df_free_space(path: &std::path::Path) -> Option<u64> {
+    let output = std::process::Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df_available(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Total size in bytes of all files below a path (0 if it does not exist)
+pub fn dir_size(path: &std::path::Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
 /// Initialize all application directories
 #[allow(dead_code)]
 pub fn init_app_directories() -> Result<AppDirectories, String> {
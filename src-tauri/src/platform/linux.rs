@@ -1,7 +1,7 @@
 // Linux-specific implementations
 
 use super::common::{
-    get_app_cache_dir, get_app_config_dir, get_app_data_dir, PlatformOps, ShellExecutor,
+    df_free_space, get_app_cache_dir, get_app_config_dir, get_app_data_dir, PlatformOps, ShellExecutor,
 };
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -155,19 +155,21 @@ impl PlatformOps for LinuxPlatform {
         Ok(())
     }
 
-    fn open_terminal(&self, cwd: &Path) -> Result<(), String> {
-        // Try common Linux terminal emulators
+    fn open_terminal(&self, cwd: &Path, env: &[(String, String)]) -> Result<(), String> {
+        // Try common Linux terminal emulators; the shell inherits `env` from the emulator
         let terminals = ["gnome-terminal", "konsole", "xfce4-terminal", "xterm"];
 
         for terminal in &terminals {
+            let mut cmd = Command::new(terminal);
+            cmd.envs(env.iter().map(|(k, v)| (k, v)));
             let result = match *terminal {
-                "gnome-terminal" => Command::new(terminal)
+                "gnome-terminal" => cmd
                     .args(["--working-directory", cwd.to_str().unwrap_or(".")])
                     .spawn(),
-                "konsole" => Command::new(terminal)
+                "konsole" => cmd
                     .args(["--workdir", cwd.to_str().unwrap_or(".")])
                     .spawn(),
-                _ => Command::new(terminal)
+                _ => cmd
                     .current_dir(cwd)
                     .spawn(),
             };
@@ -213,6 +215,20 @@ impl PlatformOps for LinuxPlatform {
         Ok(())
     }
 
+    fn is_process_running(&self, pid: u32) -> bool {
+        // Signal 0 only checks that the process exists
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn get_free_space(&self, path: &Path) -> Option<u64> {
+        df_free_space(path)
+    }
+
     fn get_process_by_port(&self, port: u16) -> Option<u32> {
         // Try lsof first
         let output = Command::new("lsof")
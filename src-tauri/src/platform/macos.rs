@@ -1,7 +1,7 @@
 // macOS-specific implementations
 
 use super::common::{
-    get_app_cache_dir, get_app_config_dir, get_app_data_dir, PlatformOps, ShellExecutor,
+    df_free_space, get_app_cache_dir, get_app_config_dir, get_app_data_dir, PlatformOps, ShellExecutor,
     VersionManagerOps,
 };
 use std::fs::OpenOptions;
@@ -150,11 +150,35 @@ impl PlatformOps for MacOSPlatform {
         Ok(())
     }
 
-    fn open_terminal(&self, cwd: &Path) -> Result<(), String> {
-        Command::new("open")
-            .args(["-a", "Terminal", cwd.to_str().unwrap_or(".")])
-            .spawn()
+    fn open_terminal(&self, cwd: &Path, env: &[(String, String)]) -> Result<(), String> {
+        if env.is_empty() {
+            Command::new("open")
+                .args(["-a", "Terminal", cwd.to_str().unwrap_or(".")])
+                .spawn()
+                .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+            return Ok(());
+        }
+
+        // Terminal.app does not inherit our environment, so the new shell exports it itself
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "'\\''"));
+        let script = env
+            .iter()
+            .map(|(key, value)| format!("export {}={}", key, quote(value)))
+            .chain(std::iter::once(format!("cd {}", quote(cwd.to_string_lossy().as_ref()))))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let apple_script = format!(
+            "tell application \"Terminal\"\n activate\n do script \"{}\"\nend tell",
+            script.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        let output = Command::new("osascript")
+            .arg("-e")
+            .arg(&apple_script)
+            .output()
             .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("Failed to open Terminal: {}", String::from_utf8_lossy(&output.stderr)));
+        }
         Ok(())
     }
 
@@ -191,6 +215,20 @@ impl PlatformOps for MacOSPlatform {
         Ok(())
     }
 
+    fn is_process_running(&self, pid: u32) -> bool {
+        // Signal 0 only checks that the process exists
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn get_free_space(&self, path: &Path) -> Option<u64> {
+        df_free_space(path)
+    }
+
     fn get_process_by_port(&self, port: u16) -> Option<u32> {
         let output = Command::new("lsof")
             .args(["-ti", &format!(":{}", port)])
@@ -228,6 +266,55 @@ impl PlatformOps for MacOSPlatform {
                 .join(".cache/aem-env-manager")
         })
     }
+
+    fn remove_quarantine(&self, path: &Path) -> Result<bool, String> {
+        let listing = Command::new("xattr")
+            .arg("-r")
+            .arg("-l")
+            .arg(path)
+            .output()
+            .map_err(|e| format!("Failed to execute xattr: {}", e))?;
+
+        if !String::from_utf8_lossy(&listing.stdout).contains("com.apple.quarantine") {
+            return Ok(false);
+        }
+
+        let output = Command::new("xattr")
+            .args(["-r", "-d", "com.apple.quarantine"])
+            .arg(path)
+            .output()
+            .map_err(|e| format!("Failed to execute xattr: {}", e))?;
+
+        if output.status.success() {
+            Ok(true)
+        } else {
+            Err(format!(
+                "Failed to remove quarantine from {}: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    fn is_notarized(&self, path: &Path) -> Option<bool> {
+        // Only bundles (.jdk, .app) can be assessed by Gatekeeper
+        let is_bundle = path
+            .extension()
+            .map(|ext| ext == "jdk" || ext == "app")
+            .unwrap_or(false);
+        if !is_bundle {
+            return None;
+        }
+
+        let output = Command::new("spctl")
+            .args(["--assess", "--type", "execute", "-vv"])
+            .arg(path)
+            .output()
+            .ok()?;
+
+        // spctl reports the verdict on stderr, e.g. "source=Notarized Developer ID"
+        Some(String::from_utf8_lossy(&output.stderr).contains("Notarized"))
+    }
 }
 
 /// macOS shell executor using zsh/bash
@@ -13,6 +13,9 @@ pub mod linux;
 // Re-export common traits and types
 pub mod common;
 
+// WSL distributions (only populated on Windows)
+pub mod wsl;
+
 pub use common::*;
 
 // Platform-specific re-exports (for external API use)
@@ -106,15 +106,19 @@ impl PlatformOps for WindowsPlatform {
             PathBuf::from("C:\\Program Files\\Amazon Corretto"),
             PathBuf::from("C:\\Program Files\\Microsoft"),
             PathBuf::from("C:\\Program Files\\Zulu"),
+            // Chocolatey/winget packages that don't use a vendor directory
+            PathBuf::from("C:\\Program Files\\OpenJDK"),
+            PathBuf::from("C:\\Program Files\\BellSoft"),
         ];
 
         // Add user-specific paths
         if let Some(home) = dirs::home_dir() {
             paths.push(home.join(".jabba\\jdk"));
-            paths.push(home.join("scoop\\apps\\openjdk"));
-            paths.push(home.join("scoop\\apps\\temurin"));
         }
 
+        // Scoop keeps each JDK in its own app directory (e.g. temurin17-jdk)
+        paths.extend(scoop_app_dirs("java"));
+
         if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
             paths.push(PathBuf::from(local_app_data).join("Programs\\Eclipse Adoptium"));
         }
@@ -133,10 +137,11 @@ impl PlatformOps for WindowsPlatform {
             paths.push(home.join("AppData\\Local\\fnm_multishells"));
             // volta
             paths.push(home.join(".volta\\tools\\image\\node"));
-            // scoop
-            paths.push(home.join("scoop\\apps\\nodejs"));
         }
 
+        // scoop (nodejs, nodejs-lts)
+        paths.extend(scoop_app_dirs("node"));
+
         paths.into_iter().filter(|p| p.exists()).collect()
     }
 
@@ -184,16 +189,22 @@ impl PlatformOps for WindowsPlatform {
         Ok(())
     }
 
-    fn open_terminal(&self, cwd: &Path) -> Result<(), String> {
-        // Try Windows Terminal first, fall back to cmd
-        let result = Command::new("wt")
-            .args(["-d", cwd.to_str().unwrap_or(".")])
-            .spawn();
+    fn open_terminal(&self, cwd: &Path, env: &[(String, String)]) -> Result<(), String> {
+        // Try Windows Terminal first, fall back to cmd. An already running Windows Terminal
+        // opens the tab with its own environment, so with `env` cmd is used directly.
+        let result = if env.is_empty() {
+            Command::new("wt")
+                .args(["-d", cwd.to_str().unwrap_or(".")])
+                .spawn()
+        } else {
+            Err(std::io::Error::other("environment needs cmd"))
+        };
 
         if result.is_err() {
             // Fall back to cmd
             Command::new("cmd")
-                .args(["/c", "start", "cmd", "/k", "cd", cwd.to_str().unwrap_or(".")])
+                .args(["/c", "start", "cmd", "/k", "cd", "/d", cwd.to_str().unwrap_or(".")])
+                .envs(env.iter().map(|(k, v)| (k, v)))
                 .spawn()
                 .map_err(|e| format!("Failed to open terminal: {}", e))?;
         }
@@ -233,6 +244,33 @@ impl PlatformOps for WindowsPlatform {
         Ok(())
     }
 
+    fn is_process_running(&self, pid: u32) -> bool {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .map(|out| {
+                String::from_utf8_lossy(&out.stdout)
+                    .split_whitespace()
+                    .any(|field| field == pid.to_string())
+            })
+            .unwrap_or(false)
+    }
+
+    fn get_free_space(&self, path: &Path) -> Option<u64> {
+        let script = format!(
+            "(Get-Item -LiteralPath '{}').PSDrive.Free",
+            path.to_string_lossy().replace('\'', "''")
+        );
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+
     fn get_process_by_port(&self, port: u16) -> Option<u32> {
         let output = Command::new("netstat")
             .args(["-ano"])
@@ -652,6 +690,279 @@ impl VersionManagerOps for NvmWindowsManager {
     }
 }
 
+// ============================================
+// Package Managers (Scoop, Chocolatey, winget)
+// ============================================
+
+/// Windows package managers that can provide JDK and Node installations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowsPackageManager {
+    Scoop,
+    Chocolatey,
+    Winget,
+}
+
+/// A package reported by a Windows package manager
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowsPackage {
+    pub name: String,
+    pub version: String,
+}
+
+/// Package name fragments that identify JDK distributions
+const JAVA_PACKAGE_MARKERS: [&str; 8] = [
+    "jdk", "openjdk", "temurin", "zulu", "corretto", "liberica", "adoptium", "semeru",
+];
+
+/// Package name fragments that identify Node.js
+const NODE_PACKAGE_MARKERS: [&str; 2] = ["nodejs", "node.js"];
+
+impl WindowsPackageManager {
+    pub const ALL: [WindowsPackageManager; 3] = [
+        WindowsPackageManager::Scoop,
+        WindowsPackageManager::Chocolatey,
+        WindowsPackageManager::Winget,
+    ];
+
+    /// Look up a package manager by its manager id
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            WindowsPackageManager::Scoop => "scoop",
+            WindowsPackageManager::Chocolatey => "choco",
+            WindowsPackageManager::Winget => "winget",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WindowsPackageManager::Scoop => "Scoop",
+            WindowsPackageManager::Chocolatey => "Chocolatey",
+            WindowsPackageManager::Winget => "winget",
+        }
+    }
+
+    /// Root directory of the package manager, if it has one
+    pub fn path(self) -> Option<PathBuf> {
+        match self {
+            WindowsPackageManager::Scoop => std::env::var("SCOOP")
+                .map(PathBuf::from)
+                .ok()
+                .or_else(|| dirs::home_dir().map(|h| h.join("scoop"))),
+            WindowsPackageManager::Chocolatey => std::env::var("ChocolateyInstall")
+                .map(PathBuf::from)
+                .ok()
+                .or_else(|| Some(PathBuf::from("C:\\ProgramData\\chocolatey"))),
+            WindowsPackageManager::Winget => None,
+        }
+    }
+
+    pub fn is_installed(self) -> bool {
+        Command::new("where")
+            .arg(self.id())
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Installed packages providing `tool` ("java" or "node")
+    pub fn list_packages(self, tool: &str) -> Result<Vec<WindowsPackage>, String> {
+        if !self.is_installed() {
+            return Err(format!("{} is not installed", self.name()));
+        }
+
+        let executor = WindowsShellExecutor::default();
+        let packages = match self {
+            WindowsPackageManager::Scoop => parse_scoop_list(&executor.execute("scoop list")?),
+            WindowsPackageManager::Chocolatey => {
+                parse_choco_list(&executor.execute("choco list --limit-output")?)
+            }
+            WindowsPackageManager::Winget => parse_winget_list(
+                &executor.execute("winget list --accept-source-agreements --disable-interactivity")?,
+            ),
+        };
+
+        Ok(packages
+            .into_iter()
+            .filter(|p| is_tool_package(tool, &p.name))
+            .collect())
+    }
+
+    /// Install a package, optionally pinned to a version
+    pub fn install(self, package: &str, version: Option<&str>) -> Result<(), String> {
+        if !self.is_installed() {
+            return Err(format!("{} is not installed", self.name()));
+        }
+
+        // Name and version are passed as separate arguments, never through a shell
+        let args: Vec<String> = match (self, version) {
+            (WindowsPackageManager::Scoop, Some(v)) => vec!["install".into(), format!("{}@{}", package, v)],
+            (WindowsPackageManager::Scoop, None) => vec!["install".into(), package.into()],
+            (WindowsPackageManager::Chocolatey, _) => {
+                let mut args = vec!["install".into(), package.into()];
+                if let Some(v) = version {
+                    args.extend(["--version".into(), v.into()]);
+                }
+                args.push("-y".into());
+                args
+            }
+            (WindowsPackageManager::Winget, _) => {
+                let mut args = vec!["install".into(), "--id".into(), package.into()];
+                if let Some(v) = version {
+                    args.extend(["--version".into(), v.into()]);
+                }
+                args.extend(["-e", "--accept-package-agreements", "--accept-source-agreements"].map(String::from));
+                args
+            }
+        };
+        // Scoop is a batch shim, which Command only finds with its extension
+        let program = match self {
+            WindowsPackageManager::Scoop => "scoop.cmd",
+            _ => self.id(),
+        };
+
+        let output = Command::new(program)
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to execute command: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = if stderr.trim().is_empty() { String::from_utf8_lossy(&output.stdout) } else { stderr };
+            return Err(format!("{} install failed: {}", self.name(), message.trim()));
+        }
+        Ok(())
+    }
+
+    /// Package id of a JDK distribution for this manager (major version, vendor key)
+    pub fn java_package(self, vendor: &str, major: &str) -> Option<String> {
+        let package = match (self, vendor) {
+            (WindowsPackageManager::Scoop, "temurin") => format!("temurin{}-jdk", major),
+            (WindowsPackageManager::Scoop, "zulu") => format!("zulu{}-jdk", major),
+            (WindowsPackageManager::Scoop, "corretto") => format!("corretto{}-jdk", major),
+            (WindowsPackageManager::Scoop, "microsoft") => format!("microsoft{}-jdk", major),
+            (WindowsPackageManager::Chocolatey, "temurin") => format!("temurin{}", major),
+            (WindowsPackageManager::Chocolatey, "zulu") => format!("zulu{}", major),
+            (WindowsPackageManager::Chocolatey, "corretto") => format!("corretto{}jdk", major),
+            (WindowsPackageManager::Chocolatey, "microsoft") => format!("microsoft-openjdk{}", major),
+            (WindowsPackageManager::Winget, "temurin") => format!("EclipseAdoptium.Temurin.{}.JDK", major),
+            (WindowsPackageManager::Winget, "zulu") => format!("Azul.Zulu.{}.JDK", major),
+            (WindowsPackageManager::Winget, "corretto") => format!("Amazon.Corretto.{}.JDK", major),
+            (WindowsPackageManager::Winget, "microsoft") => format!("Microsoft.OpenJDK.{}", major),
+            _ => return None,
+        };
+        Some(package)
+    }
+
+    /// Package id of Node.js for this manager
+    pub fn node_package(self) -> &'static str {
+        match self {
+            WindowsPackageManager::Scoop => "nodejs",
+            WindowsPackageManager::Chocolatey => "nodejs",
+            WindowsPackageManager::Winget => "OpenJS.NodeJS",
+        }
+    }
+}
+
+fn is_tool_package(tool: &str, name: &str) -> bool {
+    let name = name.to_lowercase();
+    match tool {
+        "java" => JAVA_PACKAGE_MARKERS.iter().any(|m| name.contains(m)),
+        "node" => NODE_PACKAGE_MARKERS.iter().any(|m| name.contains(m)),
+        _ => false,
+    }
+}
+
+/// Parse `scoop list` table output ("Name Version Source ...")
+fn parse_scoop_list(output: &str) -> Vec<WindowsPackage> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("Name") && !l.starts_with("----") && !l.starts_with("Installed"))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some(WindowsPackage {
+                name: parts.next()?.to_string(),
+                version: parts.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `choco list --limit-output` output ("name|version")
+fn parse_choco_list(output: &str) -> Vec<WindowsPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, version) = line.trim().split_once('|')?;
+            Some(WindowsPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `winget list` table output using the column offsets of the header
+/// (names may contain spaces, so the Id and Version columns are sliced by position)
+fn parse_winget_list(output: &str) -> Vec<WindowsPackage> {
+    let lines: Vec<&str> = output.lines().collect();
+    let Some(header_idx) = lines
+        .iter()
+        .position(|l| l.contains("Id") && l.contains("Version"))
+    else {
+        return Vec::new();
+    };
+
+    let header: Vec<char> = lines[header_idx].chars().collect();
+    let column = |name: &str| -> Option<usize> {
+        let header: String = header.iter().collect();
+        header.find(name).map(|byte_idx| header[..byte_idx].chars().count())
+    };
+    let (Some(id_col), Some(version_col)) = (column("Id"), column("Version")) else {
+        return Vec::new();
+    };
+
+    lines[header_idx + 1..]
+        .iter()
+        .filter(|l| !l.trim_start().starts_with('-'))
+        .filter_map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.len() <= version_col {
+                return None;
+            }
+            let id: String = chars[id_col..version_col].iter().collect();
+            let rest: String = chars[version_col..].iter().collect();
+            Some(WindowsPackage {
+                name: id.trim().to_string(),
+                version: rest.split_whitespace().next()?.to_string(),
+            })
+        })
+        .filter(|p| !p.name.is_empty())
+        .collect()
+}
+
+/// Scoop app directories (user and global) that provide `tool`
+fn scoop_app_dirs(tool: &str) -> Vec<PathBuf> {
+    let user_root = WindowsPackageManager::Scoop.path();
+    let global_root = Some(
+        std::env::var("SCOOP_GLOBAL")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("C:\\ProgramData\\scoop")),
+    );
+
+    [user_root, global_root]
+        .into_iter()
+        .flatten()
+        .filter_map(|root| std::fs::read_dir(root.join("apps")).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|e| is_tool_package(tool, &e.file_name().to_string_lossy()))
+        .map(|e| e.path())
+        .collect()
+}
+
 /// Get the platform-specific implementation
 pub fn get_platform() -> WindowsPlatform {
     WindowsPlatform::new()
@@ -677,4 +988,27 @@ mod tests {
         let config_dir = platform.get_config_dir();
         assert!(config_dir.to_string_lossy().contains("aem-env-manager"));
     }
+
+    #[test]
+    fn test_parse_package_lists() {
+        let scoop = "Installed apps:\n\nName          Version   Source Updated\n----          -------   ------ -------\ntemurin17-jdk 17.0.9-9  java   2024-01-01\nnodejs-lts    20.10.0   main   2024-01-01\n";
+        let packages = parse_scoop_list(scoop);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "temurin17-jdk");
+        assert_eq!(packages[0].version, "17.0.9-9");
+
+        let choco = parse_choco_list("temurin17|17.0.9\nnodejs|20.10.0\n");
+        assert_eq!(choco[1].version, "20.10.0");
+
+        let winget = concat!(
+            "Name                       Id                              Version  Source\n",
+            "------------------------------------------------------------------------\n",
+            "Eclipse Temurin JDK 17     EclipseAdoptium.Temurin.17.JDK  17.0.9.9 winget\n",
+        );
+        let packages = parse_winget_list(winget);
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "EclipseAdoptium.Temurin.17.JDK");
+        assert_eq!(packages[0].version, "17.0.9.9");
+        assert!(is_tool_package("java", &packages[0].name));
+    }
 }
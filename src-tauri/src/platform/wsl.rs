@@ -0,0 +1,151 @@
+// WSL (Windows Subsystem for Linux) support
+// Distribution detection, path translation and running commands inside a distribution
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// UNC prefixes Windows uses to expose WSL file systems
+const UNC_PREFIXES: [&str; 2] = ["\\\\wsl.localhost\\", "\\\\wsl$\\"];
+
+/// An installed WSL distribution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WslDistribution {
+    pub name: String,
+    /// Running, Stopped, Installing, ...
+    pub state: String,
+    /// WSL version (1 or 2)
+    pub version: u8,
+    pub is_default: bool,
+}
+
+/// `wsl.exe` writes UTF-16LE to pipes; fall back to UTF-8 for WSL_UTF8=1
+fn decode_output(bytes: &[u8]) -> String {
+    let looks_utf16 = bytes.len() >= 2 && bytes.len().is_multiple_of(2) && bytes[1] == 0;
+    if looks_utf16 {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+/// Parse `wsl.exe -l -v` output
+///   NAME            STATE           VERSION
+/// * Ubuntu-22.04    Running         2
+fn parse_distribution_list(output: &str) -> Vec<WslDistribution> {
+    output
+        .lines()
+        .map(|l| l.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}'))
+        .filter(|l| !l.is_empty() && !l.starts_with("NAME"))
+        .filter_map(|line| {
+            let is_default = line.starts_with('*');
+            let mut parts = line.trim_start_matches('*').split_whitespace();
+            Some(WslDistribution {
+                name: parts.next()?.to_string(),
+                state: parts.next()?.to_string(),
+                version: parts.next()?.parse().ok()?,
+                is_default,
+            })
+        })
+        .collect()
+}
+
+/// List installed WSL distributions (empty when WSL is unavailable)
+pub fn list_distributions() -> Vec<WslDistribution> {
+    if !cfg!(target_os = "windows") {
+        return Vec::new();
+    }
+
+    Command::new("wsl.exe")
+        .args(["-l", "-v"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_distribution_list(&decode_output(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// Windows path of a file inside a distribution (\\wsl.localhost\<distro>\...)
+pub fn to_windows_path(distro: &str, linux_path: &str) -> PathBuf {
+    let relative = linux_path.trim_start_matches('/').replace('/', "\\");
+    PathBuf::from(format!("{}{}\\{}", UNC_PREFIXES[0], distro, relative))
+}
+
+/// Split a \\wsl.localhost\<distro>\... path into distribution and Linux path
+pub fn from_windows_path(path: &str) -> Option<(String, String)> {
+    let rest = UNC_PREFIXES.iter().find_map(|prefix| {
+        path.get(..prefix.len())
+            .filter(|p| p.eq_ignore_ascii_case(prefix))
+            .map(|_| &path[prefix.len()..])
+    })?;
+
+    let (distro, linux) = rest.split_once('\\').unwrap_or((rest, ""));
+    if distro.is_empty() {
+        return None;
+    }
+
+    Some((distro.to_string(), format!("/{}", linux.replace('\\', "/"))))
+}
+
+/// Command that runs `program` inside a distribution, in `cwd`
+pub fn command(distro: &str, cwd: &Path, program: &str) -> Command {
+    let mut cmd = Command::new("wsl.exe");
+    cmd.args(["-d", distro, "--cd"])
+        .arg(cwd)
+        .args(["--", program]);
+    cmd
+}
+
+/// Kill the process listening on a TCP port inside a distribution
+pub fn kill_port(distro: &str, port: u16) -> Result<(), String> {
+    let output = Command::new("wsl.exe")
+        .args(["-d", distro, "--", "sh", "-c"])
+        .arg(format!("fuser -k {}/tcp || kill $(lsof -t -i:{})", port, port))
+        .output()
+        .map_err(|e| format!("Failed to run wsl.exe: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(decode_output(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_distribution_list() {
+        let output = "  NAME            STATE           VERSION\n* Ubuntu-22.04    Running         2\n  Debian          Stopped         1\n";
+        let distros = parse_distribution_list(output);
+        assert_eq!(distros.len(), 2);
+        assert_eq!(distros[0].name, "Ubuntu-22.04");
+        assert!(distros[0].is_default);
+        assert_eq!(distros[1].version, 1);
+
+        let utf16: Vec<u8> = output.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        assert_eq!(decode_output(&utf16), output);
+    }
+
+    #[test]
+    fn test_path_translation() {
+        assert_eq!(
+            from_windows_path("\\\\wsl.localhost\\Ubuntu\\home\\dev\\aem\\author"),
+            Some(("Ubuntu".to_string(), "/home/dev/aem/author".to_string()))
+        );
+        assert_eq!(
+            from_windows_path("\\\\wsl$\\Ubuntu\\opt\\aem"),
+            Some(("Ubuntu".to_string(), "/opt/aem".to_string()))
+        );
+        assert_eq!(from_windows_path("C:\\aem\\author"), None);
+        assert_eq!(
+            to_windows_path("Ubuntu", "/home/dev/aem"),
+            PathBuf::from("\\\\wsl.localhost\\Ubuntu\\home\\dev\\aem")
+        );
+    }
+}
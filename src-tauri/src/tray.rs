@@ -0,0 +1,399 @@
+// Tray status badge and menu
+// A background health scheduler probes all instances, emits per-instance status and health
+// events for the UI, reflects the aggregate state (all stopped / some running / any
+// unhealthy) as a badge on the tray icon and lists instances with start/stop in the tray menu
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, Submenu, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::commands::activity::{record_activity, ActivityKind};
+use crate::commands::instance::{
+    check_instance_health, detect_all_instances_status, load_instances, start_instance, stop_instance,
+    AemInstanceStatus, InstanceStatusResult,
+};
+use crate::commands::lock::check_command_allowed;
+use crate::commands::metrics::{record_sample, MetricSample};
+use crate::commands::profile::get_startup_config;
+use crate::commands::startup_times::record_running;
+use crate::i18n::{Locale, Message};
+use crate::notifications;
+
+/// Id of the tray icon built in `run()`
+pub const TRAY_ID: &str = "main";
+
+/// Event emitted when the aggregate health changes
+pub const ENVIRONMENT_HEALTH_EVENT: &str = "environment-health-changed";
+
+/// Event emitted every scheduler cycle with the status of all instances
+pub const INSTANCE_STATUS_EVENT: &str = "instance-status-updated";
+
+/// Event emitted with the `HealthCheckResult` of each running instance
+pub const INSTANCE_HEALTH_EVENT: &str = "instance-health-updated";
+
+/// Menu id prefixes of the per-instance tray actions, followed by the instance id
+const START_MENU_PREFIX: &str = "instance-start:";
+const STOP_MENU_PREFIX: &str = "instance-stop:";
+
+/// Running instances get a full health check every this many status probes
+const HEALTH_CHECK_EVERY: u32 = 4;
+
+/// An instance as listed in the tray menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuInstance {
+    pub id: String,
+    pub name: String,
+    pub status: AemInstanceStatus,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvironmentHealth {
+    AllStopped,
+    SomeRunning,
+    Unhealthy,
+}
+
+impl EnvironmentHealth {
+    /// Badge color (RGB); None keeps the plain template icon
+    fn badge_color(self) -> Option<[u8; 3]> {
+        match self {
+            EnvironmentHealth::AllStopped => None,
+            EnvironmentHealth::SomeRunning => Some([0x22, 0xc5, 0x5e]),
+            EnvironmentHealth::Unhealthy => Some([0xef, 0x44, 0x44]),
+        }
+    }
+
+    fn tooltip(self) -> &'static str {
+        match self {
+            EnvironmentHealth::AllStopped => "AEM Environment Manager – all instances stopped",
+            EnvironmentHealth::SomeRunning => "AEM Environment Manager – instances running",
+            EnvironmentHealth::Unhealthy => "AEM Environment Manager – an instance needs attention",
+        }
+    }
+}
+
+/// Aggregate state of all instances
+fn aggregate_health(results: &[InstanceStatusResult]) -> EnvironmentHealth {
+    if results
+        .iter()
+        .any(|r| matches!(r.status, AemInstanceStatus::Error | AemInstanceStatus::PortConflict))
+    {
+        EnvironmentHealth::Unhealthy
+    } else if results
+        .iter()
+        .any(|r| matches!(r.status, AemInstanceStatus::Running | AemInstanceStatus::Starting))
+    {
+        EnvironmentHealth::SomeRunning
+    } else {
+        EnvironmentHealth::AllStopped
+    }
+}
+
+/// Label of a settled status; status changes are recorded between these only
+fn settled_status(status: &AemInstanceStatus) -> Option<&'static str> {
+    match status {
+        AemInstanceStatus::Running => Some("running"),
+        AemInstanceStatus::Stopped => Some("stopped"),
+        AemInstanceStatus::Error => Some("error"),
+        AemInstanceStatus::PortConflict => Some("port conflict"),
+        _ => None,
+    }
+}
+
+/// Status dot color (RGB) of an instance in the tray menu
+fn status_color(status: &AemInstanceStatus) -> [u8; 3] {
+    match status {
+        AemInstanceStatus::Running => [0x22, 0xc5, 0x5e],
+        AemInstanceStatus::Starting | AemInstanceStatus::Stopping => [0xf5, 0x9e, 0x0b],
+        AemInstanceStatus::Error | AemInstanceStatus::PortConflict => [0xef, 0x44, 0x44],
+        AemInstanceStatus::Stopped | AemInstanceStatus::Unknown => [0x9c, 0xa3, 0xaf],
+    }
+}
+
+/// Paint a filled circle into an RGBA buffer
+fn fill_circle(rgba: &mut [u8], width: u32, height: u32, (cx, cy, radius): (f64, f64, f64), color: [u8; 3]) {
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f64 - cx, y as f64 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[color[0], color[1], color[2], 0xff]);
+            }
+        }
+    }
+}
+
+/// Copy of the icon with a filled circle in the bottom-right corner
+fn with_badge(icon: &Image<'_>, color: [u8; 3]) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+
+    let radius = width.min(height) as f64 * 0.22;
+    let (cx, cy) = (width as f64 - radius - 0.5, height as f64 - radius - 0.5);
+    fill_circle(&mut rgba, width, height, (cx, cy, radius), color);
+
+    Image::new_owned(rgba, width, height)
+}
+
+/// Small centered dot used as a menu icon
+fn status_dot(color: [u8; 3]) -> Image<'static> {
+    const SIZE: u32 = 16;
+    let mut rgba = vec![0; (SIZE * SIZE * 4) as usize];
+    fill_circle(&mut rgba, SIZE, SIZE, (7.5, 7.5, 5.0), color);
+    Image::new_owned(rgba, SIZE, SIZE)
+}
+
+fn instance_submenu<R: Runtime, M: Manager<R>>(app: &M, instance: &MenuInstance) -> tauri::Result<Submenu<R>> {
+    let locale = Locale::current();
+    let active = matches!(
+        instance.status,
+        AemInstanceStatus::Running | AemInstanceStatus::Starting | AemInstanceStatus::Stopping
+    );
+    let start_id = format!("{}{}", START_MENU_PREFIX, instance.id);
+    let start = MenuItemBuilder::with_id(start_id, Message::TrayStart.format(locale))
+        .enabled(!active)
+        .build(app)?;
+    let stop_id = format!("{}{}", STOP_MENU_PREFIX, instance.id);
+    let stop = MenuItemBuilder::with_id(stop_id, Message::TrayStop.format(locale))
+        .enabled(active)
+        .build(app)?;
+    SubmenuBuilder::new(app, &instance.name)
+        .submenu_icon(status_dot(status_color(&instance.status)))
+        .item(&start)
+        .item(&stop)
+        .build()
+}
+
+/// Tray menu: instances with status dots and start/stop, then show/hide/quit
+/// (labels follow the configured app language)
+pub fn build_menu<R: Runtime, M: Manager<R>>(app: &M, instances: &[MenuInstance]) -> tauri::Result<Menu<R>> {
+    let locale = Locale::current();
+    let mut instances_menu = SubmenuBuilder::new(app, Message::TrayInstances.format(locale));
+    if instances.is_empty() {
+        let none = MenuItemBuilder::new(Message::TrayNoInstances.format(locale))
+            .enabled(false)
+            .build(app)?;
+        instances_menu = instances_menu.item(&none);
+    }
+    for instance in instances {
+        instances_menu = instances_menu.item(&instance_submenu(app, instance)?);
+    }
+
+    let show_i = MenuItemBuilder::with_id("show", Message::TrayShow.format(locale)).build(app)?;
+    let hide_i = MenuItemBuilder::with_id("hide", Message::TrayHide.format(locale)).build(app)?;
+    let quit_i = MenuItemBuilder::with_id("quit", Message::TrayQuit.format(locale)).build(app)?;
+    MenuBuilder::new(app)
+        .item(&instances_menu.build()?)
+        .separator()
+        .item(&show_i)
+        .item(&hide_i)
+        .separator()
+        .item(&quit_i)
+        .build()
+}
+
+/// Instances in registration order with the status from `results` (Unknown when missing)
+fn menu_instances(results: &[InstanceStatusResult]) -> Vec<MenuInstance> {
+    load_instances()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|i| MenuInstance {
+            status: results
+                .iter()
+                .find(|r| r.instance_id == i.id)
+                .map(|r| r.status.clone())
+                .unwrap_or(AemInstanceStatus::Unknown),
+            id: i.id,
+            name: i.name,
+        })
+        .collect()
+}
+
+/// Rebuild the tray menu when the listed instances or their statuses changed
+fn update_menu(app: &AppHandle, instances: Vec<MenuInstance>, last: &mut Option<Vec<MenuInstance>>) {
+    if last.as_ref() == Some(&instances) {
+        return;
+    }
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_menu(app, &instances) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                println!("[AEM] Failed to update tray menu: {}", e);
+            }
+            *last = Some(instances);
+        }
+        Err(e) => println!("[AEM] Failed to build tray menu: {}", e),
+    }
+}
+
+/// Start or stop an instance from its tray submenu; false for other menu ids
+pub fn handle_menu_event(app: &AppHandle, id: &str) -> bool {
+    let (instance_id, start) = if let Some(instance_id) = id.strip_prefix(START_MENU_PREFIX) {
+        (instance_id.to_string(), true)
+    } else if let Some(instance_id) = id.strip_prefix(STOP_MENU_PREFIX) {
+        (instance_id.to_string(), false)
+    } else {
+        return false;
+    };
+
+    if let Err(e) = check_command_allowed(if start { "start_instance" } else { "stop_instance" }) {
+        println!("[AEM] Tray action rejected: {}", e);
+        return true;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = if start {
+            start_instance(instance_id).await
+        } else {
+            stop_instance(instance_id).await
+        };
+        if let Err(e) = result {
+            println!("[AEM] Tray action failed: {}", e);
+        }
+        // Show the new state right away instead of at the next scheduler cycle
+        if let Ok(results) = detect_all_instances_status().await {
+            update_menu(&app, menu_instances(&results), &mut None);
+        }
+    });
+    true
+}
+
+fn update_tray(app: &AppHandle, health: EnvironmentHealth) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let Ok(base) = Image::from_bytes(include_bytes!("../icons/tray-icon.png")) else {
+        return;
+    };
+
+    // A colored badge only survives when the icon is not used as a (monochrome) template
+    let result = match health.badge_color() {
+        Some(color) => tray
+            .set_icon(Some(with_badge(&base, color)))
+            .and_then(|_| tray.set_icon_as_template(false)),
+        None => tray.set_icon(Some(base)).and_then(|_| tray.set_icon_as_template(true)),
+    };
+    if let Err(e) = result.and_then(|_| tray.set_tooltip(Some(health.tooltip()))) {
+        println!("[AEM] Failed to update tray icon: {}", e);
+    }
+}
+
+/// Background task that probes all instances every `health_check_interval` seconds.
+/// Emits the status results every cycle and a full health check of every running instance
+/// every `HEALTH_CHECK_EVERY` cycles, records startup times, metric samples and status changes,
+/// shows desktop notifications and updates the tray badge and menu when the aggregate state or
+/// an instance status changes.
+pub async fn health_scheduler(app: AppHandle) {
+    let mut last: Option<EnvironmentHealth> = None;
+    let mut last_menu: Option<Vec<MenuInstance>> = None;
+    let mut settled: HashMap<String, AemInstanceStatus> = HashMap::new();
+    let mut bundle_alerts: HashSet<String> = HashSet::new();
+    let mut cycle: u32 = 0;
+
+    loop {
+        if let Ok(results) = detect_all_instances_status().await {
+            let running: Vec<String> = results
+                .iter()
+                .filter(|r| r.status == AemInstanceStatus::Running)
+                .map(|r| r.instance_id.clone())
+                .collect();
+            record_running(&app, &running);
+            let _ = app.emit(INSTANCE_STATUS_EVENT, &results);
+
+            let instances = menu_instances(&results);
+            let name_of = |id: &str| {
+                instances
+                    .iter()
+                    .find(|i| i.id == id)
+                    .map_or(id.to_string(), |i| i.name.clone())
+            };
+            for result in &results {
+                let Some(status) = settled_status(&result.status) else {
+                    continue;
+                };
+                match settled.insert(result.instance_id.clone(), result.status.clone()) {
+                    Some(previous) if previous != result.status => {
+                        // Before recording, so the app's own stop is still the latest activity
+                        let name = name_of(&result.instance_id);
+                        notifications::status_changed(&app, &result.instance_id, &name, &previous, &result.status);
+                        let previous = settled_status(&previous).unwrap_or_default();
+                        let message = format!("Status changed from {} to {}", previous, status);
+                        record_activity(&result.instance_id, ActivityKind::StatusChanged, message);
+                    }
+                    _ => {}
+                }
+            }
+
+            // Running instances get a sample from their health check below
+            for result in results.iter().filter(|r| r.status == AemInstanceStatus::Stopped) {
+                let _ = record_sample(&result.instance_id, MetricSample::stopped());
+            }
+
+            let health = aggregate_health(&results);
+            if last != Some(health) {
+                update_tray(&app, health);
+                let _ = app.emit(ENVIRONMENT_HEALTH_EVENT, health);
+                last = Some(health);
+            }
+            update_menu(&app, instances.clone(), &mut last_menu);
+
+            let health_checks = if cycle.is_multiple_of(HEALTH_CHECK_EVERY) { running } else { Vec::new() };
+            for id in health_checks {
+                if let Ok(result) = check_instance_health(id, None).await {
+                    notifications::check_bundles(&app, &name_of(&result.instance_id), &result, &mut bundle_alerts);
+                    let _ = app.emit(INSTANCE_HEALTH_EVENT, &result);
+                }
+            }
+        }
+
+        cycle = cycle.wrapping_add(1);
+        let interval = get_startup_config().health_check_interval.max(5);
+        tokio::time::sleep(Duration::from_secs(interval as u64)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(status: AemInstanceStatus) -> InstanceStatusResult {
+        InstanceStatusResult {
+            instance_id: "i".to_string(),
+            status,
+            checked_at: String::new(),
+            duration_ms: 0,
+            process_id: None,
+            process_name: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_health() {
+        assert_eq!(aggregate_health(&[]), EnvironmentHealth::AllStopped);
+        assert_eq!(
+            aggregate_health(&[result(AemInstanceStatus::Stopped), result(AemInstanceStatus::Starting)]),
+            EnvironmentHealth::SomeRunning
+        );
+        assert_eq!(
+            aggregate_health(&[result(AemInstanceStatus::Running), result(AemInstanceStatus::PortConflict)]),
+            EnvironmentHealth::Unhealthy
+        );
+
+        let icon = Image::new_owned(vec![0; 16 * 16 * 4], 16, 16);
+        let badged = with_badge(&icon, [0xff, 0, 0]);
+        assert_eq!(&badged.rgba()[(12 * 16 + 12) * 4..(12 * 16 + 12) * 4 + 4], &[0xff, 0, 0, 0xff]);
+        assert_eq!(&badged.rgba()[0..4], &[0, 0, 0, 0]);
+
+        let dot = status_dot(status_color(&AemInstanceStatus::Running));
+        assert_eq!(&dot.rgba()[(8 * 16 + 8) * 4..(8 * 16 + 8) * 4 + 4], &[0x22, 0xc5, 0x5e, 0xff]);
+        assert_eq!(&dot.rgba()[0..4], &[0, 0, 0, 0]);
+    }
+}
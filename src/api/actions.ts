@@ -0,0 +1,43 @@
+// Action Catalog API
+// Backend-registered actions (every command with its title, category and arguments) for the
+// command palette and automations
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type ActionArgumentType = 'string' | 'number' | 'boolean' | 'array' | 'object';
+
+export interface ActionArgument {
+  /** Argument name as passed to `invoke` */
+  name: string;
+  type: ActionArgumentType;
+  required: boolean;
+}
+
+export interface ActionDescriptor {
+  /** Command name, e.g. start_instance */
+  id: string;
+  title: string;
+  category: string;
+  arguments: ActionArgument[];
+  /** Deletes, resets, stops or reveals secrets; rejected while the app is locked */
+  destructive?: boolean;
+}
+
+/**
+ * Actions the backend offers, in registration order
+ * @param category - Only actions of this category, e.g. "Instances"
+ */
+export async function listActions(category?: string): Promise<ActionDescriptor[]> {
+  return invoke<ActionDescriptor[]>('list_actions', { category: category ?? null });
+}
+
+/**
+ * Run a catalog action with its arguments
+ */
+export async function runAction<T = unknown>(action: ActionDescriptor, args: Record<string, unknown> = {}): Promise<T> {
+  const missing = action.arguments.filter((a) => a.required && args[a.name] === undefined).map((a) => a.name);
+  if (missing.length > 0) {
+    throw new Error(`${action.title}: missing ${missing.join(', ')}`);
+  }
+  return invoke<T>(action.id, args);
+}
@@ -0,0 +1,42 @@
+// Instance Activity API
+// Timeline of starts, stops, package installs, status changes and repository snapshots
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type ActivityKind =
+  | 'started'
+  | 'stopped'
+  | 'package_installed'
+  /** Status seen by the health scheduler changed, e.g. after a crash */
+  | 'status_changed'
+  | 'snapshot_created'
+  | 'snapshot_restored'
+  | 'repository_reset';
+
+export interface ActivityEntry {
+  instance_id: string;
+  kind: ActivityKind;
+  message: string;
+  timestamp: string;
+}
+
+/**
+ * Activity of one instance (or all), newest first
+ * @param instanceId - Omit for all instances
+ * @param kinds - Only these kinds
+ * @param since - RFC 3339 timestamp
+ * @param limit - Defaults to 100
+ */
+export async function getInstanceActivity(
+  instanceId?: string,
+  kinds?: ActivityKind[],
+  since?: string,
+  limit?: number
+): Promise<ActivityEntry[]> {
+  return invoke<ActivityEntry[]>('get_instance_activity', {
+    instanceId: instanceId ?? null,
+    kinds: kinds ?? null,
+    since: since ?? null,
+    limit: limit ?? null,
+  });
+}
@@ -0,0 +1,55 @@
+// Adobe I/O CLI API
+// aio CLI detection, `aio aem rde` / `aio cloudmanager` commands and per-profile config contexts
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface AioCliInfo {
+  installed: boolean;
+  version: string | null;
+  /** Node version the CLI runs on */
+  node_version: string | null;
+  /** Installed plugins, e.g. @adobe/aio-cli-plugin-aem-rde */
+  plugins: string[];
+}
+
+export interface AioCommandResult {
+  args: string[];
+  success: boolean;
+  exit_code: number | null;
+  /** Parsed stdout when the command printed JSON */
+  json: unknown | null;
+  stdout: string;
+  stderr: string;
+  duration_ms: number;
+}
+
+/**
+ * Detect the aio CLI of the active Node version and its plugins
+ */
+export async function detectAioCli(): Promise<AioCliInfo> {
+  return invoke<AioCliInfo>('detect_aio_cli');
+}
+
+/**
+ * Run an `aio aem rde ...` or `aio cloudmanager ...` command
+ * @param args - Arguments after `aio`, e.g. ['aem', 'rde', 'status']
+ * @param json - Add --json so the output is parsed (default true)
+ */
+export async function runAioCommand(args: string[], json?: boolean): Promise<AioCommandResult> {
+  return invoke<AioCommandResult>('run_aio_command', { args, json: json ?? null });
+}
+
+/**
+ * Current aio configuration, without login tokens
+ */
+export async function getAioConfig(): Promise<Record<string, unknown>> {
+  return invoke<Record<string, unknown>>('get_aio_config');
+}
+
+/**
+ * Apply a profile's aio config context (also done when switching to the profile)
+ * @returns false when the profile has no aio config
+ */
+export async function applyProfileAioConfig(profileId: string): Promise<boolean> {
+  return invoke<boolean>('apply_profile_aio_config', { profileId });
+}
@@ -0,0 +1,87 @@
+// Automation API
+// User-defined jobs (shell command, HTTP call or package install) triggered when an instance
+// has started, a profile was switched or on a schedule, and their run log
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type AutomationTrigger =
+  /** Any instance when instance_id is null */
+  | { kind: 'instance_started'; instance_id?: string | null }
+  /** Any profile when profile_id is null */
+  | { kind: 'profile_switched'; profile_id?: string | null }
+  | { kind: 'schedule'; interval_minutes: number };
+
+/**
+ * `{event}`, `{instance_id}` and `{profile_id}` in URLs, headers, bodies and package fields are
+ * replaced with the triggering event. Shell commands get them as AEM_EVENT, AEM_INSTANCE_ID and
+ * AEM_PROFILE_ID, and their placeholders expand to quoted references to these variables
+ */
+export type AutomationAction =
+  | { kind: 'shell'; command: string; working_dir?: string | null }
+  | { kind: 'http'; method?: string; url: string; headers?: Record<string, string>; body?: string | null }
+  | { kind: 'install_package'; instance_id: string; package_path: string };
+
+export interface AutomationJob {
+  /** Empty when creating a job */
+  id: string;
+  name: string;
+  enabled: boolean;
+  trigger: AutomationTrigger;
+  action: AutomationAction;
+  /** Run time limit in seconds (default 600) */
+  timeout_secs: number | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export type AutomationEvent =
+  | { event: 'instance_started'; instance_id: string }
+  | { event: 'profile_switched'; profile_id: string }
+  | { event: 'schedule' }
+  | { event: 'manual' };
+
+export interface AutomationRun {
+  job_id: string;
+  job_name: string;
+  event: AutomationEvent;
+  started_at: string;
+  duration_ms: number;
+  success: boolean;
+  /** Command output, HTTP response or install log (truncated) */
+  output: string;
+}
+
+/**
+ * Configured automation jobs
+ */
+export async function listAutomationJobs(): Promise<AutomationJob[]> {
+  return invoke<AutomationJob[]>('list_automation_jobs');
+}
+
+/**
+ * Create a job (empty id) or replace the job with the same id
+ */
+export async function saveAutomationJob(job: AutomationJob): Promise<AutomationJob> {
+  return invoke<AutomationJob>('save_automation_job', { job });
+}
+
+/**
+ * Delete a job; its past runs stay in the run log
+ */
+export async function deleteAutomationJob(id: string): Promise<boolean> {
+  return invoke<boolean>('delete_automation_job', { id });
+}
+
+/**
+ * Run a job now, regardless of its trigger and whether it is enabled
+ */
+export async function runAutomationJob(id: string): Promise<AutomationRun> {
+  return invoke<AutomationRun>('run_automation_job', { id });
+}
+
+/**
+ * Logged runs, newest first
+ */
+export async function listAutomationRuns(jobId?: string, limit?: number): Promise<AutomationRun[]> {
+  return invoke<AutomationRun[]>('list_automation_runs', { jobId: jobId ?? null, limit: limit ?? null });
+}
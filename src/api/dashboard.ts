@@ -0,0 +1,55 @@
+// Dashboard API
+// Everything the dashboard shows on load in a single call
+
+import { invoke } from '@tauri-apps/api/core';
+import type { AemInstanceStatus, AemInstanceType } from './instance';
+import type { LicenseStatus } from './license';
+import type { EnvironmentProfile } from './profile';
+import type { MavenConfig } from './version';
+
+export interface DashboardInstance {
+  id: string;
+  name: string;
+  instance_type: AemInstanceType;
+  host: string;
+  port: number;
+  status: AemInstanceStatus;
+  /** When the status was detected; null when it is the persisted status */
+  checked_at: string | null;
+}
+
+export interface LicenseWarning {
+  license_id: string;
+  name: string;
+  status: LicenseStatus;
+  days_until_expiry: number | null;
+  associated_instance_id: string | null;
+}
+
+export type PendingJobKind = 'instance_start' | 'scheduled_maintenance' | 'scheduled_export';
+
+export interface PendingJob {
+  kind: PendingJobKind;
+  label: string;
+  instance_id: string | null;
+  /** Start time for running jobs, planned time for scheduled ones */
+  at: string;
+}
+
+export interface DashboardState {
+  active_profile: EnvironmentProfile | null;
+  java_version: string | null;
+  node_version: string | null;
+  maven_config: MavenConfig | null;
+  instances: DashboardInstance[];
+  license_warnings: LicenseWarning[];
+  pending_jobs: PendingJob[];
+}
+
+/**
+ * Get the dashboard state: active profile, current versions, last detected instance
+ * statuses, license warnings and pending jobs
+ */
+export async function getDashboardState(): Promise<DashboardState> {
+  return invoke<DashboardState>('get_dashboard_state');
+}
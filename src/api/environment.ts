@@ -2,6 +2,7 @@
 // Handles symlink-based version switching and shell configuration
 
 import { invoke } from '@tauri-apps/api/core';
+import type { MessageCode } from './messages';
 
 // ============================================
 // Types
@@ -17,14 +18,14 @@ export interface EnvironmentStatus {
   current_node_path: string | null;
 }
 
-export interface InitResult {
+export interface InitResult extends MessageCode {
   success: boolean;
   message: string;
   env_dir: string;
   shell_config_updated: boolean;
 }
 
-export interface SymlinkResult {
+export interface SymlinkResult extends Partial<MessageCode> {
   success: boolean;
   previous_target: string | null;
   current_target: string;
@@ -125,6 +126,63 @@ export async function getProfileEnvironment(
   });
 }
 
+// ============================================
+// Corporate CA Certificates API
+// ============================================
+
+export interface JdkCaImportResult {
+  alias: string;
+  cacerts_path: string;
+  /** Copy of cacerts taken before the import */
+  backup_path: string;
+  /** keytool output */
+  output: string;
+}
+
+export interface TrustedCertificate {
+  alias: string;
+  path: string;
+  added_at: string | null;
+}
+
+/**
+ * Import a root CA into a JDK's cacerts (backed up first) to fix PKIX errors behind a proxy
+ * @param javaHome - JDK installation directory
+ * @param certPath - PEM or DER certificate file
+ * @param storepass - cacerts password; defaults to "changeit"
+ */
+export async function importCaIntoJdk(
+  javaHome: string,
+  certPath: string,
+  alias: string,
+  storepass?: string
+): Promise<JdkCaImportResult> {
+  return invoke<JdkCaImportResult>('import_ca_into_jdk', {
+    javaHome,
+    certPath,
+    alias,
+    storepass: storepass ?? null,
+  });
+}
+
+/**
+ * Trust a root CA for the app's own downloads and update checks
+ */
+export async function addAppTrustedCa(certPath: string, alias: string): Promise<TrustedCertificate> {
+  return invoke<TrustedCertificate>('add_app_trusted_ca', { certPath, alias });
+}
+
+/**
+ * CAs the app trusts in addition to the system roots
+ */
+export async function listAppTrustedCas(): Promise<TrustedCertificate[]> {
+  return invoke<TrustedCertificate[]>('list_app_trusted_cas');
+}
+
+export async function removeAppTrustedCa(alias: string): Promise<boolean> {
+  return invoke<boolean>('remove_app_trusted_ca', { alias });
+}
+
 // ============================================
 // Convenience Functions
 // ============================================
@@ -157,3 +215,67 @@ export async function updateSymlinks(
 
   return results;
 }
+
+// ============================================
+// Shell Aliases
+// ============================================
+
+export type ShellKind = 'bash' | 'zsh' | 'fish' | 'powershell';
+
+export interface ShellAliasResult extends Partial<MessageCode> {
+  shell: ShellKind;
+  snippet: string;
+  installed: boolean;
+  config_path: string | null;
+  message: string | null;
+}
+
+/**
+ * Generate aemstart/aemstop/aemswitch shell functions, optionally adding them to the managed shell block
+ */
+export async function generateShellAliases(
+  shell: ShellKind | null,
+  install: boolean
+): Promise<ShellAliasResult> {
+  return invoke<ShellAliasResult>('generate_shell_aliases', { shell, install });
+}
+
+// ============================================
+// Environment Snapshots
+// ============================================
+
+export interface EnvSnapshot {
+  name: string;
+  created_at: string;
+  java_symlink: string | null;
+  node_symlink: string | null;
+  /** JAVA_HOME, MAVEN_OPTS, NODE_OPTIONS (null when unset) */
+  variables: Record<string, string | null>;
+  managed_path_entries: string[];
+  shell_block: string | null;
+}
+
+export interface EnvRestoreResult {
+  name: string;
+  restored: string[];
+  errors: string[];
+}
+
+/**
+ * Snapshot the managed environment (symlinks, shell block, JAVA_HOME, MAVEN_OPTS, NODE_OPTIONS)
+ */
+export async function createEnvSnapshot(name: string): Promise<EnvSnapshot> {
+  return invoke<EnvSnapshot>('create_env_snapshot', { name });
+}
+
+export async function listEnvSnapshots(): Promise<EnvSnapshot[]> {
+  return invoke<EnvSnapshot[]>('list_env_snapshots');
+}
+
+export async function restoreEnvSnapshot(name: string): Promise<EnvRestoreResult> {
+  return invoke<EnvRestoreResult>('restore_env_snapshot', { name });
+}
+
+export async function deleteEnvSnapshot(name: string): Promise<boolean> {
+  return invoke<boolean>('delete_env_snapshot', { name });
+}
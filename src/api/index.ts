@@ -8,6 +8,17 @@ export * from './mappers';
 export * from './settings';
 export * from './environment';
 export * from './update';
+export * from './project';
+export * from './window';
+export * from './dashboard';
+export * from './search';
+export * from './aio';
+export * from './actions';
+export * from './automation';
+export * from './seed';
+export * from './activity';
+export * from './lock';
+export * from './messages';
 
 // Re-export API types (snake_case - direct from Rust backend)
 export type {
@@ -2,6 +2,8 @@
 // Tauri IPC bindings for AEM instance lifecycle and management
 
 import { invoke } from '@tauri-apps/api/core';
+import type { MessageCode } from './messages';
+import type { DownloadEntry } from './settings';
 
 // ============================================
 // Types
@@ -31,6 +33,76 @@ export interface AemInstance {
   profile_id: string | null;
   created_at: string;
   updated_at: string;
+  /** WSL distribution the instance lives in (path is then a Linux path) */
+  wsl_distro?: string | null;
+  /** Detected add-ons; they add JVM arguments and startup time */
+  addons?: AemAddon[];
+  /** Extra conditions for "ready" in the start-and-wait flow */
+  readiness_rules?: ReadinessRule[];
+  /** Process last seen serving the port (checked on the next launch); for managed instances the spawned process */
+  process_id?: number | null;
+  /** terminal (default) opens a terminal window; managed runs the process inside the app */
+  launch_mode?: LaunchMode;
+  /** JPDA port for debug starts (30303 when unset) */
+  debug_port?: number | null;
+  /** Product version last read from the instance (updated after service pack installs) */
+  aem_version?: string | null;
+  /** URL POSTed to with the instance's metadata when a start reaches Running (for CI scripts) */
+  ready_webhook_url?: string | null;
+  /** Dispatcher in front of the instance, for cache flushes */
+  dispatcher?: DispatcherEndpoint | null;
+  /** Headers added to every request to the instance, e.g. X-Forwarded-* for a local reverse proxy */
+  http_headers?: Record<string, string>;
+  /** Proxy for requests to the instance (e.g. http://127.0.0.1:8888), instead of the system proxy */
+  http_proxy?: string | null;
+}
+
+export type LaunchMode = 'terminal' | 'managed';
+
+export interface DispatcherEndpoint {
+  /** Dispatcher base URL, e.g. http://localhost:8080 */
+  url: string;
+  /** Flush agent path (/dispatcher/invalidate.cache when unset) */
+  invalidate_path?: string | null;
+  /** Host header for dispatchers that pick the farm by virtual host */
+  host_header?: string | null;
+}
+
+export type ReadinessRule =
+  | { type: 'bundle_active'; symbolic_name: string }
+  /** status defaults to 200 */
+  | { type: 'url_status'; path: string; status?: number | null };
+
+export interface ReadinessCheck extends MessageCode {
+  rule: ReadinessRule;
+  passed: boolean;
+  detail: string;
+}
+
+export interface StartWaitResult {
+  instance_id: string;
+  ready: boolean;
+  timed_out: boolean;
+  elapsed_secs: number;
+  checks: ReadinessCheck[];
+}
+
+export type AemAddon = 'forms' | 'screens';
+
+export interface AddonDetectionResult {
+  instance_id: string;
+  addons: AemAddon[];
+  /** "bundles" (running instance) or "install-folder" */
+  source: string;
+  jvm_args: string[];
+  expected_startup_secs: number;
+}
+
+export interface WslDistribution {
+  name: string;
+  state: string;
+  version: number;
+  is_default: boolean;
 }
 
 export interface HealthCheckResult {
@@ -86,6 +158,13 @@ export async function scanAemInstances(customPaths?: string[]): Promise<ScannedA
   return invoke<ScannedAemInstance[]>('scan_aem_instances', { customPaths: customPaths ?? null });
 }
 
+/**
+ * List WSL distributions instances can be registered in (empty outside Windows)
+ */
+export async function listWslDistributions(): Promise<WslDistribution[]> {
+  return invoke<WslDistribution[]>('list_wsl_distributions');
+}
+
 /**
  * Scan a specific directory for AEM JAR files
  * Looks for JAR files matching AEM patterns:
@@ -149,11 +228,12 @@ export async function updateInstance(
 }
 
 /**
- * Delete an AEM instance
+ * Delete an AEM instance (moved to the trash)
  * @param id - Instance ID
+ * @param deleteFiles - Also move the installation directory to the trash
  */
-export async function deleteInstance(id: string): Promise<boolean> {
-  return invoke<boolean>('delete_instance', { id });
+export async function deleteInstance(id: string, deleteFiles?: boolean): Promise<boolean> {
+  return invoke<boolean>('delete_instance', { id, deleteFiles: deleteFiles ?? null });
 }
 
 // ============================================
@@ -176,12 +256,179 @@ export async function stopInstance(id: string): Promise<boolean> {
   return invoke<boolean>('stop_instance', { id });
 }
 
+/**
+ * Start an AEM instance with the JPDA debug agent; returns the port to attach the debugger to
+ * @param id - Instance ID
+ * @param suspend - Wait for the debugger before AEM starts
+ */
+export async function startInstanceDebug(id: string, suspend?: boolean): Promise<number> {
+  return invoke<number>('start_instance_debug', { id, suspend: suspend ?? null });
+}
+
+/**
+ * Open a terminal in the instance directory with the active profile's JAVA_HOME/PATH and
+ * the instance's run modes (CQ_RUNMODE, CQ_PORT) exported
+ * @param id - Instance ID
+ */
+export async function openInstanceTerminal(id: string): Promise<void> {
+  return invoke<void>('open_instance_terminal', { id });
+}
+
+/**
+ * Last lines of the console output of a managed instance
+ * @param id - Instance ID
+ * @param lines - Defaults to 200
+ */
+export async function getInstanceConsoleLog(id: string, lines?: number): Promise<string> {
+  return invoke<string>('get_instance_console_log', { id, lines: lines ?? null });
+}
+
+/**
+ * Start an AEM instance and wait until it answers HTTP and its readiness rules pass
+ * @param id - Instance ID
+ * @param timeoutSecs - Defaults to the expected startup time (type and add-ons)
+ */
+export async function startInstanceAndWait(id: string, timeoutSecs?: number): Promise<StartWaitResult> {
+  return invoke<StartWaitResult>('start_instance_and_wait', { id, timeoutSecs: timeoutSecs ?? null });
+}
+
+export type RestartPhase = 'stopping' | 'waiting_for_port' | 'starting' | 'running' | 'failed';
+
+/** Payload of RESTART_PROGRESS_EVENT */
+export interface RestartProgress extends Partial<MessageCode> {
+  instance_id: string;
+  phase: RestartPhase;
+  message: string | null;
+  /** Seconds since the restart began */
+  elapsed_secs: number;
+}
+
+/** Event emitted whenever a restart enters a new phase */
+export const RESTART_PROGRESS_EVENT = 'instance-restart-progress';
+
+/**
+ * Stop an instance, wait for its port to be released, start it again and wait until it is ready
+ * @param id - Instance ID
+ * @param timeoutSecs - Startup wait; defaults to the expected startup time
+ */
+export async function restartInstance(id: string, timeoutSecs?: number): Promise<StartWaitResult> {
+  return invoke<StartWaitResult>('restart_instance', { id, timeoutSecs: timeoutSecs ?? null });
+}
+
+/**
+ * Evaluate the instance's readiness rules now
+ * @param id - Instance ID
+ */
+export async function checkInstanceReadiness(id: string): Promise<ReadinessCheck[]> {
+  return invoke<ReadinessCheck[]>('check_instance_readiness', { id });
+}
+
+/**
+ * Send a test delivery of the instance's ready webhook (ready_webhook_url)
+ * @param id - Instance ID
+ * @returns HTTP status of the webhook endpoint
+ */
+export async function sendTestReadyWebhook(id: string): Promise<number> {
+  return invoke<number>('send_test_ready_webhook', { id });
+}
+
 /**
  * Check the health status of an AEM instance
  * @param id - Instance ID
+ * @param credential - Named credential to authenticate with (default credential when omitted)
+ */
+export async function checkInstanceHealth(id: string, credential?: string): Promise<HealthCheckResult> {
+  return invoke<HealthCheckResult>('check_instance_health', { id, credential: credential ?? null });
+}
+
+/**
+ * Detect installed add-ons (Forms, Screens) and record them on the instance
+ * @param id - Instance ID
+ */
+export async function detectInstanceAddons(id: string): Promise<AddonDetectionResult> {
+  return invoke<AddonDetectionResult>('detect_instance_addons', { id });
+}
+
+// ============================================
+// Instance Groups
+// ============================================
+
+export interface InstanceGroup {
+  id: string;
+  name: string;
+  /** Member instances, in the order bulk operations run */
+  instance_ids: string[];
+  created_at: string;
+  updated_at: string;
+}
+
+/** Outcome of a bulk operation on one member of a group */
+export interface GroupMemberResult extends Partial<MessageCode> {
+  instance_id: string;
+  name: string;
+  success: boolean;
+  error: string | null;
+  /** Set by group health checks */
+  health: HealthCheckResult | null;
+}
+
+/**
+ * List instance groups by name
+ */
+export async function listInstanceGroups(): Promise<InstanceGroup[]> {
+  return invoke<InstanceGroup[]>('list_instance_groups');
+}
+
+/**
+ * Create an instance group
+ * @param name - Group name, e.g. "local-dev"
+ * @param instanceIds - Initial members
+ */
+export async function createInstanceGroup(name: string, instanceIds?: string[]): Promise<InstanceGroup> {
+  return invoke<InstanceGroup>('create_instance_group', { name, instanceIds: instanceIds ?? null });
+}
+
+export async function renameInstanceGroup(id: string, name: string): Promise<InstanceGroup> {
+  return invoke<InstanceGroup>('rename_instance_group', { id, name });
+}
+
+/**
+ * Delete a group; its instances are not touched
+ */
+export async function deleteInstanceGroup(id: string): Promise<boolean> {
+  return invoke<boolean>('delete_instance_group', { id });
+}
+
+/**
+ * Add instances to a group (an instance can be in several groups)
+ */
+export async function assignInstancesToGroup(groupId: string, instanceIds: string[]): Promise<InstanceGroup> {
+  return invoke<InstanceGroup>('assign_instances_to_group', { groupId, instanceIds });
+}
+
+export async function removeInstancesFromGroup(groupId: string, instanceIds: string[]): Promise<InstanceGroup> {
+  return invoke<InstanceGroup>('remove_instances_from_group', { groupId, instanceIds });
+}
+
+/**
+ * Start every instance of a group in member order
+ */
+export async function startInstanceGroup(id: string): Promise<GroupMemberResult[]> {
+  return invoke<GroupMemberResult[]>('start_instance_group', { id });
+}
+
+/**
+ * Stop every instance of a group in reverse member order
+ */
+export async function stopInstanceGroup(id: string): Promise<GroupMemberResult[]> {
+  return invoke<GroupMemberResult[]>('stop_instance_group', { id });
+}
+
+/**
+ * Health check of every instance of a group
  */
-export async function checkInstanceHealth(id: string): Promise<HealthCheckResult> {
-  return invoke<HealthCheckResult>('check_instance_health', { id });
+export async function checkInstanceGroupHealth(id: string): Promise<GroupMemberResult[]> {
+  return invoke<GroupMemberResult[]>('check_instance_group_health', { id });
 }
 
 // ============================================
@@ -236,40 +483,139 @@ export async function detectAllInstancesStatus(): Promise<InstanceStatusResult[]
 // Credential Management
 // ============================================
 
+/** Credential used when no name is given */
+export const DEFAULT_CREDENTIAL = 'default';
+
 /**
  * Store credentials for an AEM instance
  * @param instanceId - Instance ID
  * @param username - Username
  * @param password - Password
+ * @param name - Credential name, e.g. "replication" (default credential when omitted)
  */
 export async function storeCredentials(
   instanceId: string,
   username: string,
-  password: string
+  password: string,
+  name?: string
 ): Promise<void> {
-  return invoke<void>('store_credentials', { instanceId, username, password });
+  return invoke<void>('store_credentials', { instanceId, username, password, name: name ?? null });
 }
 
 /**
  * Get stored credentials for an AEM instance
  * @param instanceId - Instance ID
+ * @param name - Credential name (default credential when omitted)
  * @returns Tuple of [username, password] or null if not found
  */
-export async function getCredentials(instanceId: string): Promise<[string, string] | null> {
-  return invoke<[string, string] | null>('get_credentials', { instanceId });
+export async function getCredentials(instanceId: string, name?: string): Promise<[string, string] | null> {
+  return invoke<[string, string] | null>('get_credentials', { instanceId, name: name ?? null });
+}
+
+export interface StoredCredentialInfo {
+  name: string;
+  username: string;
+}
+
+/**
+ * Names and usernames of the credentials stored for an instance
+ */
+export async function listCredentials(instanceId: string): Promise<StoredCredentialInfo[]> {
+  return invoke<StoredCredentialInfo[]>('list_credentials', { instanceId });
+}
+
+/**
+ * Remove one named credential of an instance
+ * @returns Whether the credential existed
+ */
+export async function deleteCredential(instanceId: string, name: string): Promise<boolean> {
+  return invoke<boolean>('delete_credential', { instanceId, name });
+}
+
+export type CredentialCheck = 'valid' | 'rejected' | 'unreachable';
+
+export interface CredentialTestResult extends MessageCode {
+  instance_id: string;
+  /** Name of the tested credential */
+  credential: string;
+  username: string;
+  /** Stored credentials, or the default account per the credentials policy */
+  source: 'stored' | 'default_account';
+  check: CredentialCheck;
+  /** HTTP status, null when the instance did not respond */
+  status: number | null;
+  message: string;
+}
+
+/**
+ * Check the instance's credentials with an authenticated Package Manager request
+ * @param instanceId - Instance ID
+ * @param name - Credential name (default credential when omitted)
+ */
+export async function testCredentials(instanceId: string, name?: string): Promise<CredentialTestResult> {
+  return invoke<CredentialTestResult>('test_credentials', { instanceId, name: name ?? null });
+}
+
+export type CredentialBackend = 'keychain' | 'encrypted_file';
+
+export interface CredentialStorageStatus {
+  backend: CredentialBackend;
+  /** Why the OS keychain could not be used */
+  keychain_error: string | null;
+  /** Entries moved out of the plaintext credentials file */
+  migrated: number;
+  /** Entries moved from the previously used backend (keychain or encrypted file) */
+  backend_migrated: number;
+  /** A plaintext credentials file is left because its migration failed */
+  plaintext_file_present: boolean;
+  /** Current key of the encrypted file, null until it is first written */
+  key_id: number | null;
+  key_created_at: string | null;
+}
+
+/**
+ * Get where credentials are stored (OS keychain or encrypted file) and the migration state
+ */
+export async function getCredentialStorageStatus(): Promise<CredentialStorageStatus> {
+  return invoke<CredentialStorageStatus>('get_credential_storage_status');
+}
+
+/**
+ * Re-encrypt the credentials file with a new key (encrypted-file storage only)
+ */
+export async function rotateCredentialsKey(): Promise<CredentialStorageStatus> {
+  return invoke<CredentialStorageStatus>('rotate_credentials_key');
 }
 
 // ============================================
 // Browser Integration
 // ============================================
 
+export interface BrowserOpenResult {
+  url: string;
+  /** False when the instance is not running; status says why */
+  opened: boolean;
+  status: AemInstanceStatus;
+  /** The instance was started and waited for first */
+  started: boolean;
+}
+
 /**
- * Open an AEM instance in the default browser
+ * Open an AEM instance in the default browser if it is running
  * @param id - Instance ID
  * @param path - Optional path to append to the base URL
+ * @param startIfStopped - Start the instance and wait until it is ready instead of giving up
  */
-export async function openInBrowser(id: string, path?: string): Promise<boolean> {
-  return invoke<boolean>('open_in_browser', { id, path: path ?? null });
+export async function openInBrowser(
+  id: string,
+  path?: string,
+  startIfStopped?: boolean
+): Promise<BrowserOpenResult> {
+  return invoke<BrowserOpenResult>('open_in_browser', {
+    id,
+    path: path ?? null,
+    startIfStopped: startIfStopped ?? null,
+  });
 }
 
 /**
@@ -281,6 +627,37 @@ export async function getInstanceUrls(id: string): Promise<Record<string, string
   return invoke<Record<string, string>>('get_instance_urls', { id });
 }
 
+export type InstanceConsole = 'crxde' | 'felix';
+
+export interface ConsoleOpenResult {
+  /** Opened URL, without credentials */
+  url: string;
+  /** Whether the credentials were passed along (Felix console only) */
+  pre_authenticated: boolean;
+}
+
+/**
+ * Open the instance directory in Finder/Explorer
+ * @param id - Instance ID
+ */
+export async function openInstanceFolder(id: string): Promise<void> {
+  return invoke<void>('open_instance_folder', { id });
+}
+
+/**
+ * Open CRXDE or the Felix console in the browser
+ * @param id - Instance ID
+ * @param authenticate - Pass the instance credentials where possible (default false; the
+ *   browser may keep them in its history)
+ */
+export async function openInstanceConsole(
+  id: string,
+  console: InstanceConsole,
+  authenticate?: boolean
+): Promise<ConsoleOpenResult> {
+  return invoke<ConsoleOpenResult>('open_instance_console', { id, console, authenticate: authenticate ?? null });
+}
+
 // ============================================
 // Convenience Functions
 // ============================================
@@ -310,3 +687,1156 @@ export async function getInstancesByProfile(profileId: string): Promise<AemInsta
   const instances = await listInstances();
   return instances.filter((i) => i.profile_id === profileId);
 }
+
+/** Event emitted (with an EnvironmentHealth payload) when the aggregate instance state changes */
+export const ENVIRONMENT_HEALTH_EVENT = 'environment-health-changed';
+
+export type EnvironmentHealth = 'all_stopped' | 'some_running' | 'unhealthy';
+
+/**
+ * Event emitted by the background health scheduler every `health_check_interval` seconds
+ * with an InstanceStatusResult[] payload covering all instances
+ */
+export const INSTANCE_STATUS_EVENT = 'instance-status-updated';
+
+/** Event emitted by the health scheduler with the HealthCheckResult of each running instance */
+export const INSTANCE_HEALTH_EVENT = 'instance-health-updated';
+
+// ============================================
+// Quick Actions
+// ============================================
+
+export type QuickAction =
+  | { action: 'reindex'; index_name: string }
+  | { action: 'restart_bundle'; symbolic_name: string }
+  | { action: 'remove_sling_jobs'; topic: string }
+  | { action: 'clear_recent_requests' };
+
+export interface QuickActionResult extends MessageCode {
+  action: QuickAction;
+  success: boolean;
+  message: string;
+}
+
+/**
+ * Confirmation text to show before running a quick action
+ */
+export async function getQuickActionConfirmation(action: QuickAction): Promise<string> {
+  return invoke<string>('get_quick_action_confirmation', { action });
+}
+
+/**
+ * Run a maintenance action on an instance (after the user confirmed it)
+ * @param id - Instance ID
+ */
+export async function runQuickAction(
+  id: string,
+  action: QuickAction,
+  confirmed: boolean
+): Promise<QuickActionResult> {
+  return invoke<QuickActionResult>('run_quick_action', { id, action, confirmed });
+}
+
+// ============================================
+// Security
+// ============================================
+
+export interface PasswordRotation {
+  instance_id: string;
+  username: string;
+  rotated_at: string;
+}
+
+export interface PasswordRotationResult extends Partial<MessageCode> {
+  username: string;
+  success: boolean;
+  /** New password, shown once (null on failure) */
+  password: string | null;
+  /** The app's stored credentials were updated */
+  vault_updated: boolean;
+  error: string | null;
+}
+
+/**
+ * Generate a strong password (at least 12 characters)
+ */
+export async function generatePassword(length?: number, includeSymbols?: boolean): Promise<string> {
+  return invoke<string>('generate_password', {
+    length: length ?? null,
+    includeSymbols: includeSymbols ?? null,
+  });
+}
+
+/**
+ * Give the selected local users of an instance new generated passwords
+ * @param id - Instance ID
+ */
+export async function rotateUserPasswords(
+  id: string,
+  usernames: string[],
+  length?: number
+): Promise<PasswordRotationResult[]> {
+  return invoke<PasswordRotationResult[]>('rotate_user_passwords', { id, usernames, length: length ?? null });
+}
+
+export interface PasswordChangeResult extends Partial<MessageCode> {
+  instance_id: string;
+  username: string;
+  success: boolean;
+  /** Names of the stored credentials that were updated to the new password */
+  updated_credentials: string[];
+  error: string | null;
+}
+
+/**
+ * Set a user's password on several instances and update the stored credentials using it
+ * @param instanceIds - Instances to change, one after another
+ * @param username - User to change (the account the app logs in with when omitted)
+ * @param groupId - Also change the members of this instance group
+ */
+export async function changeInstancePassword(
+  instanceIds: string[],
+  newPassword: string,
+  username?: string,
+  groupId?: string
+): Promise<PasswordChangeResult[]> {
+  return invoke<PasswordChangeResult[]>('change_instance_password', {
+    instanceIds,
+    groupId: groupId ?? null,
+    username: username ?? null,
+    newPassword,
+  });
+}
+
+/**
+ * Last password rotation per user, optionally for one instance
+ */
+export async function listPasswordRotations(instanceId?: string): Promise<PasswordRotation[]> {
+  return invoke<PasswordRotation[]>('list_password_rotations', { instanceId: instanceId ?? null });
+}
+
+export type Severity = 'critical' | 'high' | 'medium' | 'low';
+
+export interface SecurityFinding extends MessageCode {
+  check: string;
+  title: string;
+  severity: Severity;
+  status: 'pass' | 'fail' | 'skipped';
+  detail: string;
+  recommendation: string;
+}
+
+export interface SecurityAuditResult {
+  instance_id: string;
+  timestamp: string;
+  /** 100 minus the weight of every failed check */
+  score: number;
+  findings: SecurityFinding[];
+}
+
+/**
+ * Security audit of a local instance: default passwords, anonymous write access,
+ * CRXDE on publish, dispatcher filter bypasses and password rotation age
+ * @param id - Instance ID
+ */
+export async function auditInstanceSecurity(id: string): Promise<SecurityAuditResult> {
+  return invoke<SecurityAuditResult>('audit_instance_security', { id });
+}
+
+// ============================================
+// Content Packages
+// ============================================
+
+export interface ScrubOptions {
+  /** Replace e-mail addresses (default true) */
+  emails?: boolean;
+  /** Blank password/token/secret properties and token-like values (default true) */
+  tokens?: boolean;
+  /** Replace jcr:createdBy, cq:lastModifiedBy, ... with "anonymous" (default true) */
+  users?: boolean;
+  /** Additional property names to blank (`*` wildcard) */
+  properties?: string[];
+}
+
+export interface ContentPackageResult {
+  output_path: string;
+  nodes: number;
+  properties: number;
+  scrubbed: number;
+  /** Binaries, ACLs and properties in unknown namespaces that were left out */
+  skipped: string[];
+}
+
+/**
+ * Export a content subtree as a content package with scrubbed properties
+ * @param id - Instance ID
+ * @param operationId - Identifies the transfer in progress events (generated when omitted)
+ */
+export async function exportAnonymizedPackage(
+  id: string,
+  path: string,
+  outputPath: string,
+  options?: ScrubOptions,
+  operationId?: string
+): Promise<ContentPackageResult> {
+  return invoke<ContentPackageResult>('export_anonymized_package', {
+    id,
+    path,
+    outputPath,
+    options: options ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+// ============================================
+// Log Tailing
+// ============================================
+
+export interface LogFile {
+  name: string;
+  size: number;
+  modified_at: string | null;
+}
+
+export interface LogTail {
+  tail_id: string;
+  instance_id: string;
+  log: string;
+  path: string;
+  started_at: string;
+}
+
+/** Payload of LOG_LINES_EVENT */
+export interface LogLines {
+  tail_id: string;
+  instance_id: string;
+  log: string;
+  lines: string[];
+  /** Lines skipped since the previous event because the log grew too fast */
+  dropped_lines: number;
+  /** Bytes skipped without being read, for the same reason */
+  dropped_bytes: number;
+  /** The log was rotated or truncated and is followed from its start again */
+  rotated: boolean;
+}
+
+/** Event emitted whenever a tailed log has new lines */
+export const LOG_LINES_EVENT = 'log-lines';
+
+/** Log name of the console output of a managed instance */
+export const CONSOLE_LOG = 'console';
+
+/**
+ * Log files of an instance (crx-quickstart/logs, plus the console log of managed instances)
+ */
+export async function listInstanceLogs(instanceId: string): Promise<LogFile[]> {
+  return invoke<LogFile[]>('list_instance_logs', { instanceId });
+}
+
+/**
+ * Follow a log and receive new lines as LOG_LINES_EVENT events
+ * @param log - File name in crx-quickstart/logs (default error.log) or CONSOLE_LOG
+ * @param initialLines - Send this many existing lines first
+ */
+export async function startLogTail(instanceId: string, log?: string, initialLines?: number): Promise<LogTail> {
+  return invoke<LogTail>('start_log_tail', {
+    instanceId,
+    log: log ?? null,
+    initialLines: initialLines ?? null,
+  });
+}
+
+export async function stopLogTail(tailId: string): Promise<boolean> {
+  return invoke<boolean>('stop_log_tail', { tailId });
+}
+
+export async function listLogTails(): Promise<LogTail[]> {
+  return invoke<LogTail[]>('list_log_tails');
+}
+
+export interface LogExportResult {
+  file_path: string;
+  /** Paths relative to crx-quickstart/logs */
+  included: string[];
+  /** Files outside the date range */
+  skipped: string[];
+  total_bytes: number;
+}
+
+/**
+ * Zip the logs of an instance into the app data directory (e.g. for Adobe support)
+ * @param from - Only logs with entries on or after this date (YYYY-MM-DD)
+ * @param to - Only logs with entries on or before this date (YYYY-MM-DD)
+ */
+export async function exportInstanceLogs(instanceId: string, from?: string, to?: string): Promise<LogExportResult> {
+  return invoke<LogExportResult>('export_instance_logs', {
+    instanceId,
+    from: from ?? null,
+    to: to ?? null,
+  });
+}
+
+// ============================================
+// Startup Times
+// ============================================
+
+export interface StartupRun {
+  started_at: string;
+  ready_at: string;
+  duration_secs: number;
+  /** Median of the previous runs this one was compared with */
+  baseline_secs: number | null;
+  regressed: boolean;
+}
+
+export interface StartupStats {
+  instance_id: string;
+  runs: StartupRun[];
+  last_secs: number | null;
+  median_secs: number | null;
+  min_secs: number | null;
+  max_secs: number | null;
+  /** Whether the most recent startup was flagged as a regression */
+  regressed: boolean;
+}
+
+/** Event emitted when a startup took much longer than usual */
+export const STARTUP_REGRESSION_EVENT = 'startup-regression';
+
+/**
+ * Get startup time statistics for one instance, or for all instances with history
+ */
+export async function getStartupStats(instanceId?: string): Promise<StartupStats[]> {
+  return invoke<StartupStats[]>('get_startup_stats', { instanceId: instanceId ?? null });
+}
+
+/**
+ * Clear the startup time history of an instance
+ */
+export async function clearStartupStats(instanceId: string): Promise<boolean> {
+  return invoke<boolean>('clear_startup_stats', { instanceId });
+}
+
+// ============================================
+// AEM Release Catalog
+// ============================================
+
+export type ReleaseKind = 'sdk' | 'on_premise';
+
+export interface AemRelease {
+  kind: ReleaseKind;
+  /** "2024.11.18598" for SDKs, "6.5.21" for service packs */
+  version: string;
+  service_pack: number | null;
+  /** Release month (YYYY-MM) */
+  released: string | null;
+  /** Download without signing in (e.g. a company mirror), when the catalog has one */
+  download_url?: string;
+  /** Hex-encoded SHA-256 of the download */
+  sha256?: string;
+}
+
+export interface AemCatalog {
+  updated: string;
+  releases: AemRelease[];
+}
+
+export interface InstanceReleaseInfo {
+  instance_id: string;
+  instance_name: string;
+  detected_version: string | null;
+  version_source: 'product_info' | 'recorded' | 'jar_name' | null;
+  kind: ReleaseKind | null;
+  service_pack: number | null;
+  release: AemRelease | null;
+  latest: AemRelease | null;
+  /** null when the installed service pack is unknown (stopped 6.5 instance) */
+  update_available: boolean | null;
+}
+
+/**
+ * Known AEM releases (bundled catalog merged with the last refresh), newest first
+ */
+export async function getAemCatalog(): Promise<AemCatalog> {
+  return invoke<AemCatalog>('get_aem_catalog');
+}
+
+/**
+ * Download a newer catalog in the bundled format
+ */
+export async function refreshAemCatalog(url: string): Promise<AemCatalog> {
+  return invoke<AemCatalog>('refresh_aem_catalog', { url });
+}
+
+/**
+ * Download the SDK or service pack of a catalog release into the download cache
+ * @param operationId - Transfer progress is emitted under this id
+ */
+export async function downloadAemRelease(version: string, operationId?: string): Promise<DownloadEntry> {
+  return invoke<DownloadEntry>('download_aem_release', { version, operationId: operationId ?? null });
+}
+
+/**
+ * Release, service pack and available update of every instance
+ */
+export async function getAemInstallationOverview(): Promise<InstanceReleaseInfo[]> {
+  return invoke<InstanceReleaseInfo[]>('get_aem_installation_overview');
+}
+
+// ============================================
+// Sling Log Levels
+// ============================================
+
+export type SlingLogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error' | 'off';
+
+export interface SlingLoggerConfig {
+  pid: string;
+  /** Logger categories (empty for the root logger) */
+  loggers: string[];
+  level: SlingLogLevel;
+  file: string | null;
+  additive: boolean;
+  is_root: boolean;
+}
+
+/**
+ * Logger configurations of a running instance, root logger first
+ */
+export async function listSlingLoggers(id: string): Promise<SlingLoggerConfig[]> {
+  return invoke<SlingLoggerConfig[]>('list_sling_loggers', { id });
+}
+
+/**
+ * Change the level of an existing logger configuration
+ */
+export async function setSlingLogLevel(id: string, pid: string, level: SlingLogLevel): Promise<SlingLoggerConfig> {
+  return invoke<SlingLoggerConfig>('set_sling_log_level', { id, pid, level });
+}
+
+/**
+ * Create a logger configuration
+ * @param file - Log file (default logs/error.log)
+ */
+export async function createSlingLogger(
+  id: string,
+  loggers: string[],
+  level: SlingLogLevel,
+  file?: string,
+  additive?: boolean
+): Promise<SlingLoggerConfig[]> {
+  return invoke<SlingLoggerConfig[]>('create_sling_logger', {
+    id,
+    loggers,
+    level,
+    file: file ?? null,
+    additive: additive ?? null,
+  });
+}
+
+// ============================================
+// Service Packs
+// ============================================
+
+export type ServicePackKind = 'service_pack' | 'cumulative_fix_pack';
+
+export interface ServicePackInfo {
+  kind: ServicePackKind;
+  group: string;
+  name: string;
+  version: string;
+}
+
+export type ServicePackPhase = 'verifying' | 'installing' | 'waiting_for_restart' | 'done' | 'failed';
+
+/** Payload of SERVICE_PACK_PROGRESS_EVENT */
+export interface ServicePackProgress extends Partial<MessageCode> {
+  instance_id: string;
+  phase: ServicePackPhase;
+  message: string | null;
+  elapsed_secs: number;
+}
+
+export interface ServicePackInstallResult {
+  instance_id: string;
+  package: ServicePackInfo;
+  previous_version: string;
+  new_version: string | null;
+  ready: boolean;
+  elapsed_secs: number;
+  /** Package Manager response */
+  log: string;
+}
+
+/** Event emitted whenever a service pack install enters a new phase */
+export const SERVICE_PACK_PROGRESS_EVENT = 'service-pack-progress';
+
+/**
+ * Read a service pack / fix pack package and check that it applies to the instance
+ */
+export async function checkServicePack(id: string, packagePath: string): Promise<ServicePackInfo> {
+  return invoke<ServicePackInfo>('check_service_pack', { id, packagePath });
+}
+
+/**
+ * Install a service pack / fix pack through Package Manager and wait for the restart
+ * @param timeoutSecs - Wait for readiness after the install (default 30 minutes)
+ */
+export async function installServicePack(
+  id: string,
+  packagePath: string,
+  timeoutSecs?: number
+): Promise<ServicePackInstallResult> {
+  return invoke<ServicePackInstallResult>('install_service_pack', {
+    id,
+    packagePath,
+    timeoutSecs: timeoutSecs ?? null,
+  });
+}
+
+// ============================================
+// Package Manager
+// ============================================
+
+export interface PackageInfo {
+  group: string;
+  name: string;
+  version: string;
+  /** Repository path, e.g. /etc/packages/my_packages/site-content-1.0.zip */
+  path: string;
+  download_name: string;
+  size: number;
+  /** Last install time (null when only uploaded) */
+  installed_at: string | null;
+  installed_by: string | null;
+  last_modified: string | null;
+}
+
+/**
+ * Packages in the Package Manager of a running instance
+ * @param credential - Named credential to authenticate with; the package functions below take
+ *   it too and use the default credential when it is omitted
+ */
+export async function listPackages(instanceId: string, credential?: string): Promise<PackageInfo[]> {
+  return invoke<PackageInfo[]>('list_packages', { instanceId, credential: credential ?? null });
+}
+
+export interface PackageUploadResult {
+  group: string;
+  name: string;
+  version: string;
+  installed: boolean;
+  /** Package Manager response (includes the install log) */
+  log: string;
+}
+
+/**
+ * Upload a local package zip without installing it
+ * @param force - Replace an uploaded package of the same name
+ * @param operationId - Identifies the upload in TRANSFER_PROGRESS_EVENT payloads (generated when omitted)
+ */
+export async function uploadPackage(
+  instanceId: string,
+  packagePath: string,
+  force?: boolean,
+  operationId?: string,
+  credential?: string
+): Promise<PackageUploadResult> {
+  return invoke<PackageUploadResult>('upload_package', {
+    instanceId,
+    packagePath,
+    force: force ?? null,
+    credential: credential ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+/**
+ * Upload a local package zip and install it
+ * @param operationId - Identifies the upload in TRANSFER_PROGRESS_EVENT payloads (generated when omitted)
+ */
+export async function installPackage(
+  instanceId: string,
+  packagePath: string,
+  operationId?: string,
+  credential?: string
+): Promise<PackageUploadResult> {
+  return invoke<PackageUploadResult>('install_package', {
+    instanceId,
+    packagePath,
+    credential: credential ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+export interface PackageDownloadResult {
+  output_path: string;
+  size: number;
+}
+
+/**
+ * Build a package defined on the instance and return it as listed afterwards
+ * @param packagePath - Repository path, e.g. /etc/packages/my_packages/site-content-1.0.zip
+ */
+export async function buildPackage(instanceId: string, packagePath: string, credential?: string): Promise<PackageInfo> {
+  return invoke<PackageInfo>('build_package', { instanceId, packagePath, credential: credential ?? null });
+}
+
+/**
+ * Save a built package zip into a local directory
+ * @param operationId - Identifies the download in TRANSFER_PROGRESS_EVENT payloads (generated when omitted)
+ */
+export async function downloadPackage(
+  instanceId: string,
+  packagePath: string,
+  targetDir: string,
+  operationId?: string,
+  credential?: string
+): Promise<PackageDownloadResult> {
+  return invoke<PackageDownloadResult>('download_package', {
+    instanceId,
+    packagePath,
+    targetDir,
+    credential: credential ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+// ============================================
+// Content Sync
+// ============================================
+
+export type ContentSyncPhase = 'creating' | 'building' | 'downloading' | 'installing' | 'done' | 'failed';
+
+/** Payload of CONTENT_SYNC_PROGRESS_EVENT */
+export interface ContentSyncProgress extends Partial<MessageCode> {
+  source_instance_id: string;
+  target_instance_id: string;
+  phase: ContentSyncPhase;
+  message: string | null;
+  elapsed_secs: number;
+}
+
+export interface ContentSyncResult {
+  source_instance_id: string;
+  target_instance_id: string;
+  /** Filter roots actually synced (nested and duplicate paths removed) */
+  paths: string[];
+  package_size: number;
+  elapsed_secs: number;
+  /** Package Manager install log of the target */
+  log: string;
+}
+
+export const CONTENT_SYNC_PROGRESS_EVENT = 'content-sync-progress';
+
+/**
+ * Copy content paths from one instance to another through a temporary package
+ * @param paths - JCR filter roots, e.g. ['/content/mysite']
+ * @param operationId - Identifies the package download and upload in TRANSFER_PROGRESS_EVENT payloads
+ */
+export async function syncContent(
+  sourceInstanceId: string,
+  targetInstanceId: string,
+  paths: string[],
+  operationId?: string
+): Promise<ContentSyncResult> {
+  return invoke<ContentSyncResult>('sync_content', {
+    sourceInstanceId,
+    targetInstanceId,
+    paths,
+    operationId: operationId ?? null,
+  });
+}
+
+// ============================================
+// OSGi Bundles
+// ============================================
+
+export interface BundleInfo {
+  id: number;
+  name: string;
+  symbolic_name: string;
+  version: string;
+  /** Felix state name (Active, Resolved, Installed, Fragment, ...) */
+  state: string;
+  fragment: boolean;
+}
+
+export type BundleAction = 'start' | 'stop' | 'refresh';
+
+/**
+ * Bundles of a running instance, sorted by bundle id
+ */
+export async function listBundles(instanceId: string): Promise<BundleInfo[]> {
+  return invoke<BundleInfo[]>('list_bundles', { instanceId });
+}
+
+/**
+ * Start, stop or refresh a bundle; resolves with its state afterwards
+ */
+export async function controlBundle(
+  instanceId: string,
+  symbolicName: string,
+  action: BundleAction
+): Promise<BundleInfo> {
+  return invoke<BundleInfo>('control_bundle', { instanceId, symbolicName, action });
+}
+
+/**
+ * Upload and install a bundle jar, updating the bundle with the same symbolic name
+ * @param start - Start the bundle after installing (default true)
+ * @param startLevel - Bundle start level (default 20)
+ */
+export async function installBundle(
+  instanceId: string,
+  jarPath: string,
+  start?: boolean,
+  startLevel?: number
+): Promise<BundleInfo> {
+  return invoke<BundleInfo>('install_bundle', {
+    instanceId,
+    jarPath,
+    start: start ?? null,
+    startLevel: startLevel ?? null,
+  });
+}
+
+// ============================================
+// Repository Reset
+// ============================================
+
+/** archive: crx-quickstart goes to the trash and can be restored; delete: removed permanently */
+export type RepositoryResetMode = 'archive' | 'delete';
+
+export interface RepositoryResetResult {
+  instance_id: string;
+  mode: RepositoryResetMode;
+  /** Trash entry holding the old crx-quickstart (archive mode) */
+  trash_entry_id: string | null;
+  removed_bytes: number;
+  unpacked: boolean;
+  elapsed_secs: number;
+}
+
+/**
+ * Stop an instance and start over with a fresh repository; the JAR and license are kept
+ * @param mode - What happens to the old crx-quickstart (default archive)
+ * @param unpack - Unpack a new crx-quickstart right away instead of on the next start
+ */
+export async function resetInstanceRepository(
+  id: string,
+  mode?: RepositoryResetMode,
+  unpack?: boolean
+): Promise<RepositoryResetResult> {
+  return invoke<RepositoryResetResult>('reset_instance_repository', {
+    id,
+    mode: mode ?? null,
+    unpack: unpack ?? null,
+  });
+}
+
+// ============================================
+// Repository Snapshots
+// ============================================
+
+/** A named copy of an instance's crx-quickstart */
+export interface RepositorySnapshot {
+  id: string;
+  instance_id: string;
+  /** Unique per instance, e.g. "after-setup" */
+  name: string;
+  description: string | null;
+  created_at: string;
+  size_bytes: number;
+}
+
+export interface SnapshotRestoreResult {
+  snapshot: RepositorySnapshot;
+  /** Trash entry holding the crx-quickstart that was replaced */
+  trash_entry_id: string | null;
+  elapsed_secs: number;
+}
+
+/**
+ * Snapshots of one instance (or all), newest first
+ */
+export async function listRepositorySnapshots(instanceId?: string): Promise<RepositorySnapshot[]> {
+  return invoke<RepositorySnapshot[]>('list_repository_snapshots', { instanceId: instanceId ?? null });
+}
+
+/**
+ * Copy the crx-quickstart of a stopped instance into a named snapshot
+ */
+export async function createRepositorySnapshot(
+  instanceId: string,
+  name: string,
+  description?: string
+): Promise<RepositorySnapshot> {
+  return invoke<RepositorySnapshot>('create_repository_snapshot', {
+    instanceId,
+    name,
+    description: description ?? null,
+  });
+}
+
+/**
+ * Stop the instance and replace its repository with the snapshot; the current one goes to the trash
+ */
+export async function restoreRepositorySnapshot(snapshotId: string): Promise<SnapshotRestoreResult> {
+  return invoke<SnapshotRestoreResult>('restore_repository_snapshot', { snapshotId });
+}
+
+/**
+ * Delete a snapshot and its files
+ */
+export async function deleteRepositorySnapshot(snapshotId: string): Promise<boolean> {
+  return invoke<boolean>('delete_repository_snapshot', { snapshotId });
+}
+
+// ============================================
+// Thread Dumps
+// ============================================
+
+/** jstack against the local JVM, or the Felix console status printer */
+export type ThreadDumpSource = 'jstack' | 'console';
+
+export interface ThreadDumpResult {
+  instance_id: string;
+  file_path: string;
+  source: ThreadDumpSource;
+  size: number;
+  captured_at: string;
+}
+
+/**
+ * Capture a thread dump and store it with a timestamp in the app's diagnostics directory
+ */
+export async function captureThreadDump(instanceId: string): Promise<ThreadDumpResult> {
+  return invoke<ThreadDumpResult>('capture_thread_dump', { instanceId });
+}
+
+// ============================================
+// Heap Dumps
+// ============================================
+
+export interface HeapDump {
+  id: string;
+  instance_id: string;
+  instance_name: string;
+  file_path: string;
+  size: number;
+  /** jcmd or jmap */
+  tool: string;
+  /** Only reachable objects were dumped */
+  live_only: boolean;
+  captured_at: string;
+}
+
+/**
+ * Write a heap dump of a local instance's JVM; fails up front when disk space is short
+ * @param liveOnly - Dump only reachable objects (default true)
+ */
+export async function captureHeapDump(instanceId: string, liveOnly?: boolean): Promise<HeapDump> {
+  return invoke<HeapDump>('capture_heap_dump', { instanceId, liveOnly: liveOnly ?? null });
+}
+
+/**
+ * Recorded heap dumps, newest first
+ */
+export async function listHeapDumps(instanceId?: string): Promise<HeapDump[]> {
+  return invoke<HeapDump[]>('list_heap_dumps', { instanceId: instanceId ?? null });
+}
+
+/**
+ * Delete a heap dump file and its registry entry
+ */
+export async function deleteHeapDump(id: string): Promise<boolean> {
+  return invoke<boolean>('delete_heap_dump', { id });
+}
+
+// ============================================
+// Metrics History
+// ============================================
+
+/** Stored health sample; older samples are 5-minute averages */
+export interface MetricSample {
+  /** Unix timestamp in seconds */
+  timestamp: number;
+  running: boolean;
+  response_time_ms: number;
+  heap_used_mb: number;
+  heap_max_mb: number;
+  bundles_active: number;
+  bundles_total: number;
+  /** Raw samples averaged into this one */
+  sample_count: number;
+}
+
+export interface UptimeSummary {
+  instance_id: string;
+  first_sample: number | null;
+  last_sample: number | null;
+  /** Seconds covered by samples; unmonitored gaps are not counted */
+  monitored_secs: number;
+  running_secs: number;
+  uptime_percentage: number;
+  avg_response_time_ms: number;
+  avg_heap_used_mb: number;
+  peak_heap_used_mb: number;
+}
+
+export interface MetricsPruneResult {
+  instances: number;
+  removed_samples: number;
+  /** History files of deleted instances */
+  removed_files: number;
+}
+
+/**
+ * Stored health samples of an instance
+ * @param from - RFC 3339 start of the range
+ * @param to - RFC 3339 end of the range
+ * @param maxPoints - Average down to at most this many points
+ */
+export async function getInstanceMetrics(
+  instanceId: string,
+  from?: string,
+  to?: string,
+  maxPoints?: number
+): Promise<MetricSample[]> {
+  return invoke<MetricSample[]>('get_instance_metrics', {
+    instanceId,
+    from: from ?? null,
+    to: to ?? null,
+    maxPoints: maxPoints ?? null,
+  });
+}
+
+/**
+ * Uptime percentage and memory trend of an instance
+ */
+export async function getInstanceUptime(instanceId: string, from?: string, to?: string): Promise<UptimeSummary> {
+  return invoke<UptimeSummary>('get_instance_uptime', { instanceId, from: from ?? null, to: to ?? null });
+}
+
+/**
+ * Apply retention to all stored histories
+ * @param retentionHours - Defaults to the configured metrics retention
+ */
+export async function pruneInstanceMetrics(retentionHours?: number): Promise<MetricsPruneResult> {
+  return invoke<MetricsPruneResult>('prune_instance_metrics', { retentionHours: retentionHours ?? null });
+}
+
+export async function clearInstanceMetrics(instanceId: string): Promise<boolean> {
+  return invoke<boolean>('clear_instance_metrics', { instanceId });
+}
+
+// ============================================
+// JMX Metrics
+// ============================================
+
+export interface GcMetrics {
+  name: string;
+  count: number;
+  time_ms: number;
+}
+
+/** Sling job queue statistics; AllQueues combines every queue */
+export interface JobQueueMetrics {
+  name: string;
+  queued: number;
+  active: number;
+  failed: number;
+}
+
+export interface JmxMetrics {
+  instance_id: string;
+  collected_at: string;
+  memory: MemoryStatus | null;
+  garbage_collectors: GcMetrics[];
+  thread_count: number | null;
+  /** Open JCR sessions */
+  oak_session_count: number | null;
+  job_queues: JobQueueMetrics[];
+}
+
+/**
+ * Key MBeans of a running instance read through the Felix JMX console
+ */
+export async function getJmxMetrics(instanceId: string): Promise<JmxMetrics> {
+  return invoke<JmxMetrics>('get_jmx_metrics', { instanceId });
+}
+
+// ============================================
+// URL Sharing
+// ============================================
+
+export interface SharedUrl {
+  url: string;
+  /** LAN address that replaced localhost, if any */
+  lan_address: string | null;
+  /** True when the URL carries the credentials of a temporary user */
+  embeds_credentials: boolean;
+  /** When the temporary user is deleted again */
+  credentials_expire_at: string | null;
+}
+
+/** Temporary AEM user behind a shared URL with embedded credentials */
+export interface SharedUser {
+  instance_id: string;
+  username: string;
+  created_at: string;
+  expires_at: string;
+}
+
+export interface InstanceQrCode extends SharedUrl {
+  /** Standalone SVG document */
+  svg: string;
+}
+
+/**
+ * Copy an instance URL to the clipboard
+ * @param instanceId - Instance ID
+ * @param path - Page path, e.g. /sites.html
+ * @param embedCredentials - Put the credentials of a new temporary user into the URL
+ * @param useLanAddress - Replace localhost with the LAN address
+ * @param credentialTtlMinutes - Minutes until the temporary user is deleted (60 by default)
+ */
+export async function copyInstanceUrl(
+  instanceId: string,
+  path?: string,
+  embedCredentials?: boolean,
+  useLanAddress?: boolean,
+  credentialTtlMinutes?: number
+): Promise<SharedUrl> {
+  return invoke<SharedUrl>('copy_instance_url', {
+    instanceId,
+    path: path ?? null,
+    embedCredentials: embedCredentials ?? null,
+    useLanAddress: useLanAddress ?? null,
+    credentialTtlMinutes: credentialTtlMinutes ?? null,
+  });
+}
+
+/**
+ * QR code of an instance URL (on the LAN address) for opening it from a phone
+ */
+export async function getInstanceQrCode(
+  instanceId: string,
+  path?: string,
+  embedCredentials?: boolean,
+  credentialTtlMinutes?: number
+): Promise<InstanceQrCode> {
+  return invoke<InstanceQrCode>('get_instance_qr_code', {
+    instanceId,
+    path: path ?? null,
+    embedCredentials: embedCredentials ?? null,
+    credentialTtlMinutes: credentialTtlMinutes ?? null,
+  });
+}
+
+/**
+ * Temporary users behind shared URLs that have not been deleted yet
+ */
+export async function listSharedUsers(): Promise<SharedUser[]> {
+  return invoke<SharedUser[]>('list_shared_users');
+}
+
+/**
+ * Delete the temporary users of an instance (all instances when omitted) before they expire
+ */
+export async function revokeSharedUsersNow(instanceId?: string): Promise<number> {
+  return invoke<number>('revoke_shared_users_now', { instanceId: instanceId ?? null });
+}
+
+/** Built-in forwarder making a local instance reachable from the LAN */
+export interface LanExposure {
+  instance_id: string;
+  /** URL to hand out to colleagues */
+  url: string;
+  listen_port: number;
+  started_at: string;
+  /** How to allow incoming connections through the OS firewall */
+  firewall_hint: string;
+}
+
+/**
+ * Expose a local instance on the LAN address without changing its configuration
+ * @param port - Forwarder port, defaults to the instance port + 10000
+ */
+export async function exposeInstanceOnLan(instanceId: string, port?: number): Promise<LanExposure> {
+  return invoke<LanExposure>('expose_instance_on_lan', { instanceId, port: port ?? null });
+}
+
+/**
+ * Stop the LAN forwarder of an instance
+ */
+export async function stopLanExposure(instanceId: string): Promise<boolean> {
+  return invoke<boolean>('stop_lan_exposure', { instanceId });
+}
+
+export async function listLanExposures(): Promise<LanExposure[]> {
+  return invoke<LanExposure[]>('list_lan_exposures');
+}
+
+// ============================================
+// JVM Benchmark
+// ============================================
+
+export interface JvmOptionsVariant {
+  label: string;
+  /** JVM options for this run; empty uses the instance defaults */
+  java_opts: string;
+}
+
+export interface JvmBenchmarkRun extends Partial<MessageCode> {
+  label: string;
+  java_opts: string;
+  ready: boolean;
+  startup_secs: number;
+  /** Memory read over JMX after the settle period */
+  memory: MemoryStatus | null;
+  error: string | null;
+}
+
+export interface JvmBenchmarkReport {
+  instance_id: string;
+  started_at: string;
+  finished_at: string;
+  /** Runs in variant order; the first is the baseline */
+  runs: JvmBenchmarkRun[];
+  fastest: string | null;
+  lowest_heap: string | null;
+  /** One line per variant compared with the baseline */
+  comparison: string[];
+}
+
+export type BenchmarkPhase = 'starting' | 'settling' | 'stopping' | 'finished';
+
+/** Payload of JVM_BENCHMARK_EVENT */
+export interface JvmBenchmarkProgress {
+  instance_id: string;
+  label: string;
+  phase: BenchmarkPhase;
+}
+
+export const JVM_BENCHMARK_EVENT = 'jvm-benchmark-progress';
+
+/**
+ * Start a stopped instance once per java_opts variant and compare startup time and heap
+ * @param settleSecs - Wait after startup before reading the heap, defaults to 60
+ * @param timeoutSecs - Startup timeout per run, defaults to the expected startup time
+ */
+export async function benchmarkJvmOptions(
+  instanceId: string,
+  variants: JvmOptionsVariant[],
+  settleSecs?: number,
+  timeoutSecs?: number
+): Promise<JvmBenchmarkReport> {
+  return invoke<JvmBenchmarkReport>('benchmark_jvm_options', {
+    instanceId,
+    variants,
+    settleSecs: settleSecs ?? null,
+    timeoutSecs: timeoutSecs ?? null,
+  });
+}
@@ -2,6 +2,7 @@
 // Tauri IPC bindings for AEM license management
 
 import { invoke } from '@tauri-apps/api/core';
+import type { MessageCode } from './messages';
 
 // ============================================
 // Types
@@ -25,7 +26,7 @@ export interface AemLicense {
   updated_at: string;
 }
 
-export interface LicenseValidationResult {
+export interface LicenseValidationResult extends Partial<MessageCode> {
   is_valid: boolean;
   status: LicenseStatus;
   days_until_expiry: number | null;
@@ -40,6 +41,17 @@ export interface LicenseStatistics {
   unknown: number;
 }
 
+/** Payload of LICENSE_REMINDER_EVENT */
+export interface LicenseExpiryReminder {
+  license_id: string;
+  name: string;
+  /** Negative once the license has expired */
+  days_until_expiry: number;
+}
+
+/** Emitted by the daily license check when a license reaches a reminder threshold */
+export const LICENSE_REMINDER_EVENT = 'license-expiry-reminder';
+
 export interface CreateLicenseInput {
   name: string;
   license_key?: string;
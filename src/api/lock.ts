@@ -0,0 +1,36 @@
+// App Lock API
+// PIN-protected read-only mode for shared machines; destructive commands are rejected while locked
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface AppLockStatus {
+  pin_set: boolean;
+  locked: boolean;
+}
+
+/** Emitted with the new AppLockStatus when the app is locked or unlocked */
+export const APP_LOCK_EVENT = 'app-lock-changed';
+
+export async function getAppLockStatus(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>('get_app_lock_status');
+}
+
+/**
+ * Set, change or remove the lock PIN
+ * @param currentPin - Required when a PIN is already set
+ * @param newPin - Omit to remove the PIN (also unlocks the app)
+ */
+export async function setAppLockPin(currentPin?: string, newPin?: string): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>('set_app_lock_pin', { currentPin: currentPin ?? null, newPin: newPin ?? null });
+}
+
+/**
+ * Switch to read-only mode; fails when no PIN is set
+ */
+export async function lockApp(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>('lock_app');
+}
+
+export async function unlockApp(pin: string): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>('unlock_app', { pin });
+}
@@ -1,7 +1,7 @@
 // Type mappers for converting between API (snake_case) and frontend (camelCase) types
 // This provides a clean boundary between Rust backend and React frontend
 
-import type { AemInstance, AemInstanceStatus, HealthCheckResult } from './instance';
+import type { AemAddon, AemInstance, AemInstanceStatus, HealthCheckResult } from './instance';
 import type { EnvironmentProfile, ProfileSwitchResult, AppConfig } from './profile';
 import type { VersionManager, JavaVersion, NodeVersion, MavenConfig } from './version';
 
@@ -22,6 +22,8 @@ export interface FrontendInstance {
   profileId: string | null;
   createdAt: string;
   updatedAt: string;
+  wslDistro?: string | null;
+  addons: AemAddon[];
 }
 
 export interface FrontendProfile {
@@ -88,6 +90,8 @@ export function mapApiInstanceToFrontend(api: AemInstance): FrontendInstance {
     profileId: api.profile_id,
     createdAt: api.created_at,
     updatedAt: api.updated_at,
+    wslDistro: api.wsl_distro ?? null,
+    addons: api.addons ?? [],
   };
 }
 
@@ -106,6 +110,8 @@ export function mapFrontendInstanceToApi(
   if (frontend.runModes !== undefined) result.run_modes = frontend.runModes;
   if (frontend.status !== undefined) result.status = frontend.status;
   if (frontend.profileId !== undefined) result.profile_id = frontend.profileId;
+  if (frontend.wslDistro !== undefined) result.wsl_distro = frontend.wslDistro;
+  if (frontend.addons !== undefined) result.addons = frontend.addons;
 
   return result;
 }
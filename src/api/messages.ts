@@ -0,0 +1,23 @@
+// Backend Result Messages
+// Result structs carry an English `message` plus a machine-readable code and a translation key
+
+import i18n from '../i18n';
+
+export interface MessageCode {
+  /** Stable machine-readable code, e.g. "profile_switched" */
+  code: string;
+  /** Key in the locale files, e.g. "backend.profileSwitched" */
+  message_key: string;
+  /** Interpolation values for the translation */
+  params: Record<string, string>;
+}
+
+/**
+ * Translated message of a backend result, falling back to its English `message`
+ */
+export function backendMessage(result: Partial<MessageCode> & { message?: string | null }): string {
+  if (result.message_key && i18n.exists(result.message_key)) {
+    return i18n.t(result.message_key, result.params ?? {});
+  }
+  return result.message ?? '';
+}
@@ -2,6 +2,7 @@
 // Tauri IPC bindings for environment profile management
 
 import { invoke } from '@tauri-apps/api/core';
+import type { MessageCode } from './messages';
 
 // ============================================
 // Types
@@ -21,14 +22,18 @@ export interface EnvironmentProfile {
   author_instance_id: string | null; // Associated AEM Author instance
   publish_instance_id: string | null; // Associated AEM Publish instance
   env_vars: Record<string, string>;
+  /** aio CLI config applied on switch, e.g. { cloudmanager_orgid: '...', cloudmanager_programid: '...' } */
+  aio_config?: Record<string, string> | null;
   is_active: boolean;
   created_at: string;
   updated_at: string;
 }
 
-export interface ProfileSwitchResult {
+export interface ProfileSwitchResult extends Partial<MessageCode> {
   success: boolean;
   profile_id: string;
+  message: string | null;
+  error: string | null;
   java_switched: boolean;
   node_switched: boolean;
   maven_switched: boolean;
@@ -49,6 +54,22 @@ export interface AppConfig {
   start_minimized: boolean;
   show_notifications: boolean;
   log_level: string;
+  /** UI language tag used for backend-generated messages */
+  language?: string;
+  /** Directory for the nightly configuration export (disabled when null) */
+  auto_export_dir?: string | null;
+  /** Local hour after which the nightly export runs */
+  auto_export_hour?: number;
+  /** Number of automatic exports to keep */
+  auto_export_keep?: number;
+  /** Days deleted items stay in the trash */
+  trash_retention_days?: number;
+  /** Seconds to wait for the stop script to shut an instance down before killing it */
+  stop_timeout_secs?: number;
+  /** Days before expiry at which licenses are reminded of; empty disables reminders */
+  license_reminder_days?: number[];
+  /** Store credentials in the OS keychain; otherwise in the encrypted file (after a restart) */
+  use_os_keychain?: boolean;
 }
 
 // ============================================
@@ -146,7 +167,7 @@ export async function loadAppConfig(): Promise<AppConfig> {
  * Save application configuration
  * @param config - Application configuration
  */
-export async function saveAppConfig(config: AppConfig): Promise<void> {
+export async function saveAppConfig(config: Partial<AppConfig>): Promise<void> {
   return invoke<void>('save_app_config', { config });
 }
 
@@ -178,6 +199,68 @@ export async function duplicateProfile(profileId: string): Promise<EnvironmentPr
   return invoke<EnvironmentProfile>('duplicate_profile', { profileId });
 }
 
+// ============================================
+// Registry Credentials
+// ============================================
+
+export interface NpmRegistry {
+  registry: string;
+  /** Only route this scope (e.g. @client) to the registry; all packages when null */
+  scope: string | null;
+  token: string;
+}
+
+export interface MavenServerCredential {
+  id: string;
+  username: string;
+  password: string;
+}
+
+export interface RegistryCredentials {
+  npm: NpmRegistry | null;
+  maven_servers: MavenServerCredential[];
+}
+
+export interface RegistrySyncResult {
+  npmrc_path: string | null;
+  settings_path: string | null;
+  npm_registry: string | null;
+  maven_servers: string[];
+  warnings: string[];
+}
+
+/**
+ * Get the npm registry and Maven server credentials of a profile
+ */
+export async function getProfileRegistryCredentials(profileId: string): Promise<RegistryCredentials> {
+  return invoke<RegistryCredentials>('get_profile_registry_credentials', { profileId });
+}
+
+/**
+ * Store registry credentials for a profile; they are written to ~/.npmrc and
+ * ~/.m2/settings.xml whenever the profile is switched to
+ */
+export async function setProfileRegistryCredentials(
+  profileId: string,
+  credentials: RegistryCredentials
+): Promise<void> {
+  return invoke<void>('set_profile_registry_credentials', { profileId, credentials });
+}
+
+/**
+ * Write a profile's registry credentials now, replacing those of the previous profile
+ */
+export async function applyProfileRegistryCredentials(profileId: string): Promise<RegistrySyncResult> {
+  return invoke<RegistrySyncResult>('apply_profile_registry_credentials', { profileId });
+}
+
+/**
+ * Remove all registry entries the app wrote to ~/.npmrc and ~/.m2/settings.xml
+ */
+export async function clearRegistryCredentials(): Promise<RegistrySyncResult> {
+  return invoke<RegistrySyncResult>('clear_registry_credentials');
+}
+
 // ============================================
 // Convenience Functions
 // ============================================
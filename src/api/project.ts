@@ -0,0 +1,477 @@
+// AEM Project Registry API
+// Tauri IPC bindings for registered AEM Maven projects
+
+import { invoke } from '@tauri-apps/api/core';
+import type { AemInstance, AemInstanceType, DispatcherEndpoint } from './instance';
+import type { MessageCode } from './messages';
+import type { EnvironmentProfile } from './profile';
+
+// ============================================
+// Types
+// ============================================
+
+export interface PomInfo {
+  group_id: string | null;
+  artifact_id: string | null;
+  version: string | null;
+  name: string | null;
+  archetype_version: string | null;
+  modules: string[];
+  aem_host: string | null;
+  aem_port: number | null;
+  aem_publish_host: string | null;
+  aem_publish_port: number | null;
+}
+
+/** Profile to use while a branch matching `pattern` ("*" wildcard) is checked out */
+export interface BranchProfile {
+  pattern: string;
+  profile_id: string;
+}
+
+export interface AemProject {
+  id: string;
+  name: string;
+  path: string;
+  pom: PomInfo;
+  profile_id: string | null;
+  author_instance_id: string | null;
+  publish_instance_id: string | null;
+  /** Profiles for checked-out branches, first match wins */
+  branch_profiles: BranchProfile[];
+  created_at: string;
+  updated_at: string;
+}
+
+// ============================================
+// Project Operations
+// ============================================
+
+export async function listProjects(): Promise<AemProject[]> {
+  return invoke<AemProject[]>('list_projects');
+}
+
+/**
+ * Register a project directory (must contain pom.xml); links matching instances automatically
+ */
+export async function registerProject(path: string, name?: string): Promise<AemProject> {
+  return invoke<AemProject>('register_project', { path, name: name ?? null });
+}
+
+/**
+ * Re-read the project's pom.xml
+ */
+export async function refreshProject(id: string): Promise<AemProject> {
+  return invoke<AemProject>('refresh_project', { id });
+}
+
+/**
+ * Update name or profile/instance links (only the given fields change)
+ */
+export async function updateProject(
+  id: string,
+  project: Partial<
+    Pick<AemProject, 'name' | 'profile_id' | 'author_instance_id' | 'publish_instance_id' | 'branch_profiles'>
+  >
+): Promise<AemProject> {
+  return invoke<AemProject>('update_project', { id, project });
+}
+
+export async function deleteProject(id: string): Promise<boolean> {
+  return invoke<boolean>('delete_project', { id });
+}
+
+/**
+ * Registered project containing a path, if any
+ */
+export async function findProjectForPath(path: string): Promise<AemProject | null> {
+  return invoke<AemProject | null>('find_project_for_path', { path });
+}
+
+export interface DependencyPrefetchResult {
+  project_id: string;
+  /** settings.xml passed to Maven, null when Maven's defaults applied */
+  settings_path: string | null;
+  local_repository: string;
+  success: boolean;
+  duration_secs: number;
+  cache_size_before: number;
+  cache_size_after: number;
+  /** Bytes added to the local repository */
+  cache_size_delta: number;
+  /** Last lines of Maven's output */
+  output_tail: string[];
+  error: string | null;
+}
+
+/**
+ * Pre-download a project's dependencies (mvn dependency:go-offline) and report the cache growth
+ * @param mavenConfigId - Maven config to use; defaults to the project's profile, then ~/.m2/settings.xml
+ * @param timeoutSecs - Defaults to 30 minutes
+ */
+export async function prefetchProjectDependencies(
+  id: string,
+  mavenConfigId?: string,
+  timeoutSecs?: number
+): Promise<DependencyPrefetchResult> {
+  return invoke<DependencyPrefetchResult>('prefetch_project_dependencies', {
+    id,
+    mavenConfigId: mavenConfigId ?? null,
+    timeoutSecs: timeoutSecs ?? null,
+  });
+}
+
+// ============================================
+// Build Output Cleanup
+// ============================================
+
+export type BuildDirKind = 'target' | 'node_modules';
+
+export interface BuildDirEntry {
+  project_id: string;
+  project_name: string;
+  path: string;
+  kind: BuildDirKind;
+  size_bytes: number;
+  /** Last modification of the directory, roughly the last build */
+  modified_at: string | null;
+}
+
+export interface BuildDirCleanupResult {
+  deleted: string[];
+  bytes_reclaimed: number;
+  errors: string[];
+}
+
+/**
+ * Find target/ and node_modules/ directories in registered projects, largest first
+ * @param projectIds - Projects to scan; all when omitted
+ * @param minSizeMb - Hide directories smaller than this
+ */
+export async function scanProjectBuildDirs(projectIds?: string[], minSizeMb?: number): Promise<BuildDirEntry[]> {
+  return invoke<BuildDirEntry[]>('scan_project_build_dirs', {
+    projectIds: projectIds ?? null,
+    minSizeMb: minSizeMb ?? null,
+  });
+}
+
+/**
+ * Delete selected build output directories (only target/ and node_modules/ inside registered projects)
+ */
+export async function deleteProjectBuildDirs(paths: string[]): Promise<BuildDirCleanupResult> {
+  return invoke<BuildDirCleanupResult>('delete_project_build_dirs', { paths });
+}
+
+// ============================================
+// Git Status
+// ============================================
+
+export interface CommitInfo {
+  id: string;
+  short_id: string;
+  summary: string;
+  author: string;
+  time: string;
+}
+
+export interface GitStatus {
+  project_id: string;
+  branch: string | null;
+  detached: boolean;
+  head: CommitInfo | null;
+  staged: number;
+  modified: number;
+  untracked: number;
+  upstream: string | null;
+  ahead: number;
+  behind: number;
+  /** Shown before a build-and-install, e.g. uncommitted changes */
+  warning: string | null;
+}
+
+export async function getProjectGitStatus(id: string): Promise<GitStatus> {
+  return invoke<GitStatus>('get_project_git_status', { id });
+}
+
+/**
+ * Git status of every registered project that is a git repository
+ */
+export async function getAllProjectsGitStatus(): Promise<GitStatus[]> {
+  return invoke<GitStatus[]>('get_all_projects_git_status');
+}
+
+// ============================================
+// Branch Profiles
+// ============================================
+
+/** Event emitted when the branch of a project with branch mappings changes */
+export const BRANCH_CHANGED_EVENT = 'project-branch-changed';
+
+export interface BranchProfileSuggestion {
+  project_id: string;
+  project_name: string;
+  branch: string | null;
+  profile_id: string | null;
+  matched_pattern: string | null;
+  active_profile_id: string | null;
+  switch_needed: boolean;
+  /** Switched automatically because auto_switch_profile is enabled */
+  switched: boolean;
+}
+
+/**
+ * Profile mapped to the project's checked-out branch
+ */
+export async function suggestBranchProfile(id: string): Promise<BranchProfileSuggestion> {
+  return invoke<BranchProfileSuggestion>('suggest_branch_profile', { id });
+}
+
+// ============================================
+// Dispatcher Configuration
+// ============================================
+
+export interface DispatcherRule {
+  id: string;
+  /** "allow" or "deny" */
+  rule_type: string;
+  /** url, path, method, extension, glob, ...; regex values keep their single quotes */
+  properties: Record<string, string>;
+}
+
+export interface DispatcherFarm {
+  name: string;
+  virtualhosts: string[];
+  docroot: string | null;
+  filters: DispatcherRule[];
+  cache_rules: DispatcherRule[];
+}
+
+export interface DispatcherConfig {
+  config_file: string;
+  farms: DispatcherFarm[];
+  warnings: string[];
+}
+
+export interface DispatcherPathCheck {
+  farm: string;
+  url: string;
+  allowed: boolean;
+  matched_filter: string | null;
+  cacheable: boolean;
+  matched_cache_rule: string | null;
+  reason: string;
+}
+
+/**
+ * Farms, virtual hosts, filters and cache rules of a project's dispatcher module
+ * or a dispatcher instance (id of either)
+ */
+export async function inspectDispatcherConfig(id: string): Promise<DispatcherConfig> {
+  return invoke<DispatcherConfig>('inspect_dispatcher_config', { id });
+}
+
+/**
+ * Whether a URL passes each farm's filters and would be cached
+ */
+export async function checkDispatcherPath(
+  id: string,
+  url: string,
+  host?: string
+): Promise<DispatcherPathCheck[]> {
+  return invoke<DispatcherPathCheck[]>('check_dispatcher_path', { id, url, host: host ?? null });
+}
+
+export interface PathCount {
+  path: string;
+  count: number;
+}
+
+export interface DispatcherCacheStats {
+  log_file: string;
+  window_minutes: number | null;
+  requests: number;
+  hits: number;
+  misses: number;
+  uncached: number;
+  /** hits / (hits + misses) */
+  hit_ratio: number;
+  top_misses: PathCount[];
+  first_request: string | null;
+  last_request: string | null;
+}
+
+/**
+ * Cache hit ratio and top MISS paths from a dispatcher instance's log (or an explicit
+ * log file) over the last `windowMinutes` (whole log when omitted)
+ */
+export async function analyzeDispatcherCache(options: {
+  id?: string;
+  logPath?: string;
+  windowMinutes?: number;
+  top?: number;
+}): Promise<DispatcherCacheStats> {
+  return invoke<DispatcherCacheStats>('analyze_dispatcher_cache', {
+    id: options.id ?? null,
+    logPath: options.logPath ?? null,
+    windowMinutes: options.windowMinutes ?? null,
+    top: options.top ?? null,
+  });
+}
+
+export interface DispatcherFlushResult extends MessageCode {
+  path: string;
+  success: boolean;
+  status: number | null;
+  message: string;
+}
+
+/**
+ * Set (or with null remove) the dispatcher an instance's cache flushes go to
+ */
+export async function setDispatcherEndpoint(
+  instanceId: string,
+  endpoint: DispatcherEndpoint | null
+): Promise<DispatcherEndpoint | null> {
+  return invoke<DispatcherEndpoint | null>('set_dispatcher_endpoint', { instanceId, endpoint });
+}
+
+/**
+ * Invalidate the dispatcher cache for content paths through the instance's dispatcher
+ * endpoint (dispatcher instances use their own URL when none is set)
+ */
+export async function flushDispatcher(instanceId: string, paths: string[]): Promise<DispatcherFlushResult[]> {
+  return invoke<DispatcherFlushResult[]>('flush_dispatcher', { instanceId, paths });
+}
+
+// ============================================
+// Tool config import (aemc, env files)
+// ============================================
+
+export interface ImportedInstance {
+  name: string;
+  instance_type: AemInstanceType;
+  host: string;
+  port: number;
+  /** Local installation directory (empty for remote instances) */
+  path: string;
+  run_modes: string[];
+  java_opts: string | null;
+  username: string | null;
+  password: string | null;
+  source: string;
+}
+
+export interface ToolImportPreview {
+  source_files: string[];
+  instances: ImportedInstance[];
+  /** Non-instance variables (JAVA_HOME, MAVEN_OPTS, ...) used for the profile */
+  env_vars: Record<string, string>;
+  /** Names of instances already registered with the same host and port */
+  already_registered: string[];
+  warnings: string[];
+}
+
+export interface ToolImportResult {
+  instances: AemInstance[];
+  profile: EnvironmentProfile | null;
+  skipped: string[];
+}
+
+/**
+ * Read aem/default/etc/aem.yml (aemc) and aem.env files of a project directory
+ */
+export async function previewToolConfigImport(projectDir: string): Promise<ToolImportPreview> {
+  return invoke<ToolImportPreview>('preview_tool_config_import', { projectDir });
+}
+
+/**
+ * Create the previewed instances, optionally with a profile that uses them
+ */
+export async function importToolConfig(projectDir: string, withProfile: boolean): Promise<ToolImportResult> {
+  return invoke<ToolImportResult>('import_tool_config', { projectDir, withProfile });
+}
+
+export type ToolExportFormat = 'docker_compose' | 'aemc';
+
+export interface ToolExportResult {
+  format: ToolExportFormat;
+  content: string;
+  output_path: string | null;
+  exported: string[];
+  warnings: string[];
+}
+
+/**
+ * Generate docker-compose.yml or aemc aem.yml from registered instances
+ * (all author/publish instances unless ids are given); written when outputPath is set
+ */
+export async function exportToolConfig(
+  format: ToolExportFormat,
+  options: { instanceIds?: string[]; outputPath?: string } = {}
+): Promise<ToolExportResult> {
+  return invoke<ToolExportResult>('export_tool_config', {
+    format,
+    instanceIds: options.instanceIds ?? null,
+    outputPath: options.outputPath ?? null,
+  });
+}
+
+// ============================================
+// Port Planning
+// ============================================
+
+/** Ports of slot n are the base ports plus n * step */
+export interface PortConvention {
+  author_base: number;
+  publish_base: number;
+  dispatcher_base: number;
+  /** Debug port of the author; the publish uses the next port */
+  debug_base: number;
+  step: number;
+}
+
+export interface PortSet {
+  author: number;
+  publish: number;
+  dispatcher: number;
+  author_debug: number;
+  publish_debug: number;
+}
+
+export interface PortAllocation {
+  /** Project id or any other name the instances are grouped by */
+  workspace: string;
+  slot: number;
+  ports: PortSet;
+  created_at: string;
+}
+
+export async function getPortConvention(): Promise<PortConvention> {
+  return invoke<PortConvention>('get_port_convention');
+}
+
+/**
+ * Change the convention; existing allocations keep their ports
+ */
+export async function setPortConvention(convention: PortConvention): Promise<PortConvention> {
+  return invoke<PortConvention>('set_port_convention', { convention });
+}
+
+export async function listPortAllocations(): Promise<PortAllocation[]> {
+  return invoke<PortAllocation[]>('list_port_allocations');
+}
+
+/**
+ * Ports for a workspace: its recorded slot or the next free one
+ * @param dryRun - Only preview the slot without recording it
+ */
+export async function allocateWorkspacePorts(workspace: string, dryRun?: boolean): Promise<PortAllocation> {
+  return invoke<PortAllocation>('allocate_workspace_ports', { workspace, dryRun: dryRun ?? null });
+}
+
+/**
+ * Forget a workspace's allocation so its slot can be reused
+ */
+export async function releaseWorkspacePorts(workspace: string): Promise<boolean> {
+  return invoke<boolean>('release_workspace_ports', { workspace });
+}
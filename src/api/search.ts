@@ -0,0 +1,26 @@
+// Search API
+// Global search over instances, profiles, licenses, Maven configs and projects
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type SearchResultKind = 'instance' | 'profile' | 'license' | 'maven_config' | 'project';
+
+export interface SearchResult {
+  kind: SearchResultKind;
+  id: string;
+  title: string;
+  /** Secondary line, e.g. host:port of an instance or the path of a project */
+  subtitle: string | null;
+  /** Field the query matched best: "name", "path" or "tag" */
+  matched_field: string;
+  score: number;
+}
+
+/**
+ * Search by name, path and tags, best matches first
+ * @param query - Whitespace-separated terms; every term has to match
+ * @param limit - Maximum number of results (default 50)
+ */
+export async function search(query: string, limit?: number): Promise<SearchResult[]> {
+  return invoke<SearchResult[]>('search', { query, limit: limit ?? null });
+}
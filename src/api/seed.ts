@@ -0,0 +1,72 @@
+// Content Seed API
+// Named sets of content packages and Sling POST fixtures applied to an instance in one go
+
+import { invoke } from '@tauri-apps/api/core';
+import type { MessageCode } from './messages';
+
+export type SeedStep =
+  /** Local content package zip, uploaded and installed */
+  | { kind: 'package'; path: string }
+  /**
+   * Node created or updated through the Sling POST servlet. Arrays become multi-value
+   * properties, numbers and booleans keep their type, null removes the property.
+   */
+  | { kind: 'sling_post'; path: string; primary_type?: string | null; properties?: Record<string, unknown> };
+
+export interface ContentSeed {
+  /** Empty when creating a seed */
+  id: string;
+  name: string;
+  description: string | null;
+  /** Applied in order */
+  steps: SeedStep[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SeedStepResult extends MessageCode {
+  step: SeedStep;
+  success: boolean;
+  message: string;
+}
+
+export interface ContentSeedResult {
+  seed_id: string;
+  instance_id: string;
+  /** False when a step failed; steps after it were not applied */
+  success: boolean;
+  steps: SeedStepResult[];
+}
+
+/**
+ * Configured content seeds
+ */
+export async function listContentSeeds(): Promise<ContentSeed[]> {
+  return invoke<ContentSeed[]>('list_content_seeds');
+}
+
+/**
+ * Create a seed (empty id) or replace the seed with the same id
+ */
+export async function saveContentSeed(seed: ContentSeed): Promise<ContentSeed> {
+  return invoke<ContentSeed>('save_content_seed', { seed });
+}
+
+/**
+ * Delete a seed
+ */
+export async function deleteContentSeed(id: string): Promise<boolean> {
+  return invoke<boolean>('delete_content_seed', { id });
+}
+
+/**
+ * Apply a seed to a running instance, stopping at the first failed step.
+ * Package uploads are reported as `transfer-progress` events under operationId.
+ */
+export async function applyContentSeed(
+  instanceId: string,
+  seedId: string,
+  operationId?: string
+): Promise<ContentSeedResult> {
+  return invoke<ContentSeedResult>('apply_content_seed', { instanceId, seedId, operationId: operationId ?? null });
+}
@@ -4,6 +4,7 @@
 import { invoke } from '@tauri-apps/api/core';
 import { open, save } from '@tauri-apps/plugin-dialog';
 import i18n from '../i18n';
+import type { MessageCode } from './messages';
 
 // ============================================
 // Types
@@ -18,15 +19,17 @@ export interface ScanPaths {
   logs_dir: string;
 }
 
-export interface ExportResult {
+export interface ExportResult extends Partial<MessageCode> {
   success: boolean;
   file_path: string | null;
   profiles_count: number;
   instances_count: number;
   error: string | null;
+  /** Entries are AES-256 encrypted with the export password */
+  encrypted?: boolean;
 }
 
-export interface ImportResult {
+export interface ImportResult extends Partial<MessageCode> {
   success: boolean;
   profiles_imported: number;
   instances_imported: number;
@@ -34,7 +37,7 @@ export interface ImportResult {
   errors: string[];
 }
 
-export interface ResetResult {
+export interface ResetResult extends Partial<MessageCode> {
   success: boolean;
   profiles_deleted: number;
   instances_deleted: number;
@@ -116,8 +119,9 @@ export async function selectSaveFile(
 
 /**
  * Export all configuration with file dialog
+ * With a password (at least 8 characters) the archive is AES-256 encrypted
  */
-export async function exportConfiguration(): Promise<ExportResult> {
+export async function exportConfiguration(password?: string): Promise<ExportResult> {
   const savePath = await selectSaveFile(
     i18n.t('common.dialog.exportConfig'),
     'aem-env-manager-backup.zip',
@@ -134,13 +138,16 @@ export async function exportConfiguration(): Promise<ExportResult> {
     };
   }
 
-  return invoke<ExportResult>('export_all_config', { exportPath: savePath });
+  return invoke<ExportResult>('export_all_config', { exportPath: savePath, password: password ?? null });
 }
 
 /**
  * Import configuration with file dialog
+ * `promptPassword` is called for encrypted exports; returning null cancels the import
  */
-export async function importConfiguration(): Promise<ImportResult> {
+export async function importConfiguration(
+  promptPassword?: () => Promise<string | null>
+): Promise<ImportResult> {
   const filePath = await selectFile(i18n.t('common.dialog.importConfig'), [
     { name: 'ZIP Archive', extensions: ['zip'] },
   ]);
@@ -155,21 +162,42 @@ export async function importConfiguration(): Promise<ImportResult> {
     };
   }
 
-  return invoke<ImportResult>('import_all_config', { importPath: filePath });
+  let password: string | null = null;
+  if (promptPassword && (await isConfigExportEncrypted(filePath))) {
+    password = await promptPassword();
+    if (password === null) {
+      return {
+        success: false,
+        profiles_imported: 0,
+        instances_imported: 0,
+        configs_imported: false,
+        errors: [i18n.t('common.dialog.cancelled')],
+      };
+    }
+  }
+
+  return invoke<ImportResult>('import_all_config', { importPath: filePath, password });
+}
+
+/**
+ * Whether an export archive is password protected
+ */
+export async function isConfigExportEncrypted(importPath: string): Promise<boolean> {
+  return invoke<boolean>('is_config_export_encrypted', { importPath });
 }
 
 /**
  * Export configuration to specific path (without dialog)
  */
-export async function exportToPath(exportPath: string): Promise<ExportResult> {
-  return invoke<ExportResult>('export_all_config', { exportPath });
+export async function exportToPath(exportPath: string, password?: string): Promise<ExportResult> {
+  return invoke<ExportResult>('export_all_config', { exportPath, password: password ?? null });
 }
 
 /**
  * Import configuration from specific path (without dialog)
  */
-export async function importFromPath(importPath: string): Promise<ImportResult> {
-  return invoke<ImportResult>('import_all_config', { importPath });
+export async function importFromPath(importPath: string, password?: string): Promise<ImportResult> {
+  return invoke<ImportResult>('import_all_config', { importPath, password: password ?? null });
 }
 
 // ============================================
@@ -182,3 +210,304 @@ export async function importFromPath(importPath: string): Promise<ImportResult>
 export async function resetAllConfiguration(): Promise<ResetResult> {
   return invoke<ResetResult>('reset_all_config');
 }
+
+export interface TargetedResetResult {
+  dry_run: boolean;
+  /** Paths (and the shell config block) deleted, or that would be deleted in a dry run */
+  items: string[];
+  errors: string[];
+}
+
+/**
+ * Reset registered instances only (instance list, credentials, metrics, rotation history)
+ */
+export async function resetInstances(dryRun: boolean): Promise<TargetedResetResult> {
+  return invoke<TargetedResetResult>('reset_instances', { dryRun });
+}
+
+/**
+ * Reset the Java/Node symlinks and the managed shell config block
+ */
+export async function resetEnvironment(dryRun: boolean): Promise<TargetedResetResult> {
+  return invoke<TargetedResetResult>('reset_environment', { dryRun });
+}
+
+/**
+ * Reset the license registry
+ */
+export async function resetLicenses(dryRun: boolean): Promise<TargetedResetResult> {
+  return invoke<TargetedResetResult>('reset_licenses', { dryRun });
+}
+
+// ============================================
+// Data Directory Relocation
+// ============================================
+
+export interface DataLocation {
+  current: string;
+  default: string;
+  is_custom: boolean;
+  size_bytes: number;
+}
+
+export interface DataMigrationResult {
+  success: boolean;
+  from: string;
+  to: string;
+  files_copied: number;
+  bytes_copied: number;
+  paths_updated: number;
+  verified: boolean;
+  errors: string[];
+}
+
+/**
+ * Get the current and default data directory locations
+ */
+export async function getDataLocation(): Promise<DataLocation> {
+  return invoke<DataLocation>('get_data_location');
+}
+
+/**
+ * Copy the data directory to a new (empty) location, verify it and switch to it
+ */
+export async function migrateDataDir(targetDir: string): Promise<DataMigrationResult> {
+  return invoke<DataMigrationResult>('migrate_data_dir', { targetDir });
+}
+
+// ============================================
+// Scheduled Export
+// ============================================
+
+export interface AutoExportFile {
+  file_path: string;
+  created_at: string;
+  size_bytes: number;
+}
+
+export interface AutoExportResult {
+  export: ExportResult;
+  /** Older automatic exports removed by retention */
+  removed: string[];
+}
+
+/**
+ * Write an automatic export into the configured directory now
+ */
+export async function runAutoExportNow(): Promise<AutoExportResult> {
+  return invoke<AutoExportResult>('run_auto_export_now');
+}
+
+/**
+ * Automatic exports in the configured directory, newest first
+ */
+export async function listAutoExports(): Promise<AutoExportFile[]> {
+  return invoke<AutoExportFile[]>('list_auto_exports');
+}
+
+// ============================================
+// Download Cache
+// ============================================
+
+export interface DownloadEntry {
+  url: string;
+  file_name: string;
+  path: string;
+  size: number;
+  sha256: string;
+  downloaded_at: string;
+  last_used_at: string;
+}
+
+export interface DownloadCacheInfo {
+  cache_dir: string;
+  total_bytes: number;
+  limit_bytes: number;
+  entries: DownloadEntry[];
+}
+
+/** Payload of TRANSFER_PROGRESS_EVENT, shared by downloads and package exports */
+export interface TransferProgress {
+  operation_id: string;
+  label: string;
+  /** null when the server does not send a length */
+  total_bytes: number | null;
+  done_bytes: number;
+  /** Average since the transfer started */
+  bytes_per_sec: number;
+  eta_secs: number | null;
+  finished: boolean;
+  /** Set when the transfer failed; finished is true then as well */
+  error: string | null;
+}
+
+/** Event emitted with a TransferProgress while a transfer runs, and once when it ends */
+export const TRANSFER_PROGRESS_EVENT = 'transfer-progress';
+
+/**
+ * Download a file through the shared cache (or reuse the cached copy)
+ * @param sha256 - Expected checksum; a mismatching file is discarded
+ * @param operationId - Identifies the transfer in progress events (generated when omitted)
+ */
+export async function downloadToCache(url: string, sha256?: string, operationId?: string): Promise<DownloadEntry> {
+  return invoke<DownloadEntry>('download_to_cache', {
+    url,
+    sha256: sha256 ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+/**
+ * List cached downloads
+ */
+export async function listDownloadCache(): Promise<DownloadCacheInfo> {
+  return invoke<DownloadCacheInfo>('list_download_cache');
+}
+
+/**
+ * Remove one cached download, or the whole cache when no URL is given; returns bytes freed
+ */
+export async function clearDownloadCache(url?: string): Promise<number> {
+  return invoke<number>('clear_download_cache', { url: url ?? null });
+}
+
+// ============================================
+// Environment Report
+// ============================================
+
+export type ReportFormat = 'markdown' | 'html';
+
+export interface ReportResult {
+  format: ReportFormat;
+  content: string;
+  file_path: string | null;
+}
+
+/**
+ * Render the current environment (profiles, versions, instances, licenses) as a report
+ */
+export async function exportEnvironmentReport(
+  format: ReportFormat,
+  outputPath?: string
+): Promise<ReportResult> {
+  return invoke<ReportResult>('export_environment_report', { format, outputPath: outputPath ?? null });
+}
+
+// ============================================
+// IDE Integration
+// ============================================
+
+export type IdeTarget = 'vscode' | 'intellij';
+
+export interface IdeInstance {
+  id: string;
+  name: string;
+  instance_type: string;
+  url: string;
+  in_profile: boolean;
+}
+
+export interface IdeEnvironment {
+  profile_id: string | null;
+  profile_name: string | null;
+  java_home: string | null;
+  java_version: string | null;
+  node_path: string | null;
+  maven_settings: string | null;
+  instances: IdeInstance[];
+}
+
+export interface IdeConfigResult {
+  files_written: string[];
+  jdk_table_entry: string | null;
+  warnings: string[];
+}
+
+/**
+ * Get the active profile's environment as seen by IDE integrations
+ */
+export async function getIdeEnvironment(): Promise<IdeEnvironment> {
+  return invoke<IdeEnvironment>('get_ide_environment');
+}
+
+/**
+ * Generate VS Code / IntelliJ project settings from the active profile
+ */
+export async function generateIdeConfig(
+  projectDir: string,
+  targets: IdeTarget[]
+): Promise<IdeConfigResult> {
+  return invoke<IdeConfigResult>('generate_ide_config', { projectDir, targets });
+}
+
+// ============================================
+// Trash
+// ============================================
+
+export type TrashKind = 'maven_config' | 'profile' | 'instance' | 'repository' | 'snapshot';
+
+export interface TrashEntry {
+  id: string;
+  kind: TrashKind;
+  label: string;
+  original_path: string | null;
+  trashed_path: string | null;
+  record: unknown | null;
+  deleted_at: string;
+  expires_at: string;
+  size_bytes: number;
+}
+
+/**
+ * List deleted items still in the trash, newest first
+ */
+export async function listTrash(): Promise<TrashEntry[]> {
+  return invoke<TrashEntry[]>('list_trash');
+}
+
+/**
+ * Restore a trashed item to its original location
+ */
+export async function restoreFromTrash(id: string): Promise<TrashEntry> {
+  return invoke<TrashEntry>('restore_from_trash', { id });
+}
+
+/**
+ * Permanently delete one trashed item, or everything when no id is given
+ * @returns Number of items removed
+ */
+export async function emptyTrash(id?: string): Promise<number> {
+  return invoke<number>('empty_trash', { id: id ?? null });
+}
+
+// ============================================
+// Running Operations
+// ============================================
+
+/** Error message of operations that were cancelled */
+export const OPERATION_CANCELLED = 'Operation cancelled';
+
+export interface RunningOperation {
+  /** Operation name, followed by `:` and its arguments when it has any */
+  key: string;
+  started_at: string;
+  /** Requests sharing the result besides the one that started it */
+  waiters: number;
+  cancelled: boolean;
+}
+
+/**
+ * List scans and detections currently in flight
+ */
+export async function listRunningOperations(): Promise<RunningOperation[]> {
+  return invoke<RunningOperation[]>('list_running_operations');
+}
+
+/**
+ * Cancel a running operation by key, or every running call of an operation by name
+ * (e.g. `scan_aem_instances`)
+ * @returns Whether a running operation was found
+ */
+export async function cancelOperation(key: string): Promise<boolean> {
+  return invoke<boolean>('cancel_operation', { key });
+}
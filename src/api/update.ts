@@ -1,16 +1,14 @@
 /**
- * Update API - Tauri updater plugin wrapper
+ * Update API - application updates through the backend
  *
- * Provides a clean API for checking, downloading, and installing application updates
- * using the official Tauri updater plugin with GitHub Releases.
+ * Checks and installs go through the backend commands, so the update channel, deferral,
+ * skipped version and staged rollout apply to every check.
  */
 
-import { check, type Update } from '@tauri-apps/plugin-updater';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { relaunch } from '@tauri-apps/plugin-process';
 
-// Re-export types for convenience
-export type { Update };
-
 /**
  * Update information returned from check
  */
@@ -35,41 +33,31 @@ export interface CheckUpdateResult {
 export type DownloadProgressCallback = (downloaded: number, total: number) => void;
 
 /**
- * Cached Update object from the last check.
- * This is reused for download to avoid multiple check() calls which can cause issues.
- */
-let cachedUpdate: Update | null = null;
-
-/**
- * Check for available updates
+ * Check for available updates on the configured channel
  *
+ * @param manual - Manual checks offer any newer version; automatic ones respect the policy
  * @returns Promise with update availability and info
  */
-export async function checkForUpdate(): Promise<CheckUpdateResult> {
+export async function checkForUpdate(manual: boolean): Promise<CheckUpdateResult> {
   try {
-    const update = await check();
+    const result = await checkAppUpdate(manual);
 
-    // Cache the update object for later use
-    cachedUpdate = update;
-
-    if (!update) {
+    if (!result.version || !result.offer) {
       return { available: false, update: null };
     }
 
     return {
       available: true,
       update: {
-        version: update.version,
-        currentVersion: update.currentVersion,
-        date: update.date || new Date().toISOString(),
-        body: update.body || '',
+        version: result.version,
+        currentVersion: result.current_version,
+        date: result.date || new Date().toISOString(),
+        body: result.notes || '',
       },
     };
   } catch (error) {
-    // Clear cache on error
-    cachedUpdate = null;
     // Re-throw with more context
-    const message = error instanceof Error ? error.message : 'Unknown error checking for updates';
+    const message = error instanceof Error ? error.message : String(error);
     throw new Error(`Failed to check for updates: ${message}`);
   }
 }
@@ -80,70 +68,32 @@ export async function checkForUpdate(): Promise<CheckUpdateResult> {
  * This will download the update, install it, and the app will need to be restarted.
  * Use `relaunchApp()` after this completes to restart.
  *
- * IMPORTANT: This function uses the cached Update object from checkForUpdate().
- * Always call checkForUpdate() before calling this function.
- *
  * @param onProgress - Optional callback for download progress
  */
 export async function downloadAndInstallUpdate(
   onProgress?: DownloadProgressCallback
 ): Promise<void> {
-  // Use cached update object, or fetch a new one if not available
-  let update = cachedUpdate;
-
-  if (!update) {
-    console.warn('[Update] No cached update object, fetching new one...');
-    update = await check();
-  }
-
-  if (!update) {
-    throw new Error('No update available');
-  }
-
-  let downloaded = 0;
-  let contentLength = 0;
-
-  try {
-    await update.downloadAndInstall((event) => {
-      switch (event.event) {
-        case 'Started':
-          contentLength = event.data.contentLength || 0;
-          console.log(`[Update] Download started, size: ${contentLength} bytes`);
-          break;
-        case 'Progress':
-          downloaded += event.data.chunkLength || 0;
-          if (onProgress && contentLength > 0) {
-            onProgress(downloaded, contentLength);
-          }
-          break;
-        case 'Finished':
-          console.log('[Update] Download finished');
-          if (onProgress && contentLength > 0) {
-            onProgress(contentLength, contentLength);
-          }
-          break;
+  const unlisten = await listen<{ downloaded: number; total: number | null }>(
+    UPDATE_PROGRESS_EVENT,
+    (event) => {
+      const { downloaded, total } = event.payload;
+      if (onProgress && total) {
+        onProgress(downloaded, total);
       }
-    });
+    }
+  );
 
-    console.log('[Update] Installation prepared successfully');
-    // Clear cache after successful install preparation
-    cachedUpdate = null;
+  try {
+    const version = await installAppUpdate();
+    console.log(`[Update] Installation of ${version} prepared successfully`);
   } catch (error) {
     console.error('[Update] Download/Install failed:', error);
-    // Clear cache on error
-    cachedUpdate = null;
-    throw error;
+    throw error instanceof Error ? error : new Error(String(error));
+  } finally {
+    unlisten();
   }
 }
 
-/**
- * Clear the cached update object.
- * Call this when the user dismisses the update or when needed to force a fresh check.
- */
-export function clearUpdateCache(): void {
-  cachedUpdate = null;
-}
-
 /**
  * Relaunch the application
  *
@@ -165,3 +115,79 @@ export async function getCurrentVersion(): Promise<string> {
     return 'dev';
   }
 }
+
+// ============================================
+// Update channel and policy (backend controlled)
+// ============================================
+
+export type UpdateChannel = 'stable' | 'beta';
+
+export interface UpdatePolicy {
+  channel: UpdateChannel;
+  /** Automatic checks stay quiet until this time */
+  deferred_until: string | null;
+  skipped_version: string | null;
+  /** Per-install value (0-99) compared with a release's rollout percentage */
+  rollout_bucket: number | null;
+}
+
+export interface AppUpdateCheck {
+  channel: UpdateChannel;
+  current_version: string;
+  version: string | null;
+  date: string | null;
+  notes: string | null;
+  /** False when the update is deferred, skipped or not yet rolled out to this install */
+  offer: boolean;
+  held_back_reason: string | null;
+}
+
+export interface ReleaseNote {
+  version: string;
+  name: string;
+  date: string | null;
+  prerelease: boolean;
+  notes: string;
+  url: string;
+}
+
+/** Event emitted with { downloaded, total } while installAppUpdate downloads */
+export const UPDATE_PROGRESS_EVENT = 'update-download-progress';
+
+export async function getUpdatePolicy(): Promise<UpdatePolicy> {
+  return invoke<UpdatePolicy>('get_update_policy');
+}
+
+export async function setUpdateChannel(channel: UpdateChannel): Promise<UpdatePolicy> {
+  return invoke<UpdatePolicy>('set_update_channel', { channel });
+}
+
+/**
+ * Defer automatic update prompts for a number of days (default 7, 0 clears)
+ */
+export async function deferUpdates(days?: number): Promise<UpdatePolicy> {
+  return invoke<UpdatePolicy>('defer_updates', { days: days ?? null });
+}
+
+export async function skipUpdateVersion(version: string | null): Promise<UpdatePolicy> {
+  return invoke<UpdatePolicy>('skip_update_version', { version });
+}
+
+/**
+ * Check the configured channel. Manual checks ignore deferral, skipped version and rollout.
+ */
+export async function checkAppUpdate(manual: boolean): Promise<AppUpdateCheck> {
+  return invoke<AppUpdateCheck>('check_app_update', { manual });
+}
+
+/**
+ * Download and install the newest version of the channel; returns the installed version.
+ * Call relaunchApp() afterwards.
+ */
+export async function installAppUpdate(): Promise<string> {
+  return invoke<string>('install_app_update');
+}
+
+export async function getReleaseNotes(limit?: number): Promise<ReleaseNote[]> {
+  return invoke<ReleaseNote[]>('get_release_notes', { limit: limit ?? null });
+}
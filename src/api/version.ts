@@ -2,6 +2,7 @@
 // Tauri IPC bindings for Java, Node, and Maven version management
 
 import { invoke } from '@tauri-apps/api/core';
+import type { MessageCode } from './messages';
 
 // ============================================
 // Types
@@ -15,8 +16,15 @@ export type VersionManagerType =
   | 'fnm'
   | 'volta'
   | 'nvmwindows'
+  | 'scoop'
+  | 'chocolatey'
+  | 'winget'
   | 'manual';
 
+// Scoop, Chocolatey and winget (Windows) provide both Java and Node
+const JAVA_MANAGER_TYPES = ['sdkman', 'jenv', 'jabba', 'scoop', 'chocolatey', 'winget'];
+const NODE_MANAGER_TYPES = ['nvm', 'fnm', 'volta', 'nvmwindows', 'scoop', 'chocolatey', 'winget'];
+
 export interface VersionManager {
   id: string;
   name: string;
@@ -49,7 +57,7 @@ export interface InstalledVersion {
   vendor: string | null;
 }
 
-export interface VersionSwitchResult {
+export interface VersionSwitchResult extends Partial<MessageCode> {
   success: boolean;
   previous_version: string | null;
   current_version: string;
@@ -105,18 +113,28 @@ export async function switchJavaVersion(
   });
 }
 
+/** Manager id that downloads Temurin JDKs and Node.js builds through the download cache */
+export const DOWNLOAD_MANAGER_ID = 'download';
+
 /**
  * Install a new Java version (triggers version manager)
  * @param version - Version to install
  * @param vendor - Java vendor (e.g., "temurin", "corretto")
- * @param managerId - Version manager to use
+ * @param managerId - Version manager to use, or DOWNLOAD_MANAGER_ID
+ * @param operationId - Transfer progress of a download is emitted under this id
  */
 export async function installJavaVersion(
   version: string,
   vendor: string,
-  managerId: string
+  managerId: string,
+  operationId?: string
 ): Promise<boolean> {
-  return invoke<boolean>('install_java_version', { version, vendor, managerId });
+  return invoke<boolean>('install_java_version', {
+    version,
+    vendor,
+    managerId,
+    operationId: operationId ?? null,
+  });
 }
 
 // ============================================
@@ -155,10 +173,19 @@ export async function switchNodeVersion(
 /**
  * Install a new Node version (triggers version manager)
  * @param version - Version to install
- * @param managerId - Version manager to use
+ * @param managerId - Version manager to use, or DOWNLOAD_MANAGER_ID
+ * @param operationId - Transfer progress of a download is emitted under this id
  */
-export async function installNodeVersion(version: string, managerId: string): Promise<boolean> {
-  return invoke<boolean>('install_node_version', { version, managerId });
+export async function installNodeVersion(
+  version: string,
+  managerId: string,
+  operationId?: string
+): Promise<boolean> {
+  return invoke<boolean>('install_node_version', {
+    version,
+    managerId,
+    operationId: operationId ?? null,
+  });
 }
 
 // ============================================
@@ -317,6 +344,62 @@ export async function getMavenConfigPath(configId: string): Promise<string> {
   return invoke<string>('get_maven_config_path', { configId });
 }
 
+// ============================================
+// Global npm Packages
+// ============================================
+
+export interface GlobalPackage {
+  name: string;
+  version: string;
+}
+
+export interface GlobalPackageSnapshot {
+  /** Node version the packages were installed for */
+  node_version: string;
+  packages: GlobalPackage[];
+  /** Whether corepack had yarn/pnpm shims enabled */
+  corepack_enabled: boolean;
+  captured_at: string;
+}
+
+export interface GlobalPackageRestoreResult {
+  node_version: string;
+  installed: string[];
+  already_installed: string[];
+  /** [package, error] */
+  failed: [string, string][];
+  corepack_enabled: boolean;
+}
+
+/**
+ * Record the globally installed npm packages of the active Node version
+ * (also done automatically before a profile switches Node)
+ */
+export async function snapshotGlobalPackages(): Promise<GlobalPackageSnapshot> {
+  return invoke<GlobalPackageSnapshot>('snapshot_global_packages');
+}
+
+/**
+ * Recorded global package sets, newest Node version first
+ */
+export async function listGlobalPackageSnapshots(): Promise<GlobalPackageSnapshot[]> {
+  return invoke<GlobalPackageSnapshot[]>('list_global_package_snapshots');
+}
+
+/**
+ * Install the packages recorded for another Node version into the active one
+ * @param packages - Only restore these package names
+ */
+export async function restoreGlobalPackages(
+  fromNodeVersion: string,
+  packages?: string[]
+): Promise<GlobalPackageRestoreResult> {
+  return invoke<GlobalPackageRestoreResult>('restore_global_packages', {
+    fromNodeVersion,
+    packages: packages ?? null,
+  });
+}
+
 // ============================================
 // Convenience Functions
 // ============================================
@@ -326,7 +409,7 @@ export async function getMavenConfigPath(configId: string): Promise<string> {
  */
 export async function getJavaVersionManagers(): Promise<VersionManager[]> {
   const managers = await detectVersionManagers();
-  return managers.filter((m) => ['sdkman', 'jenv', 'jabba'].includes(m.manager_type.toLowerCase()));
+  return managers.filter((m) => JAVA_MANAGER_TYPES.includes(m.manager_type.toLowerCase()));
 }
 
 /**
@@ -335,7 +418,7 @@ export async function getJavaVersionManagers(): Promise<VersionManager[]> {
 export async function getNodeVersionManagers(): Promise<VersionManager[]> {
   const managers = await detectVersionManagers();
   return managers.filter((m) =>
-    ['nvm', 'fnm', 'volta', 'nvmwindows'].includes(m.manager_type.toLowerCase())
+    NODE_MANAGER_TYPES.includes(m.manager_type.toLowerCase())
   );
 }
 
@@ -379,11 +462,11 @@ export async function getAllVersionInfo(): Promise<VersionInfo> {
   ]);
 
   const javaManagers = managers.filter((m) =>
-    ['sdkman', 'jenv', 'jabba'].includes(m.manager_type.toLowerCase())
+    JAVA_MANAGER_TYPES.includes(m.manager_type.toLowerCase())
   );
 
   const nodeManagers = managers.filter((m) =>
-    ['nvm', 'fnm', 'volta', 'nvmwindows'].includes(m.manager_type.toLowerCase())
+    NODE_MANAGER_TYPES.includes(m.manager_type.toLowerCase())
   );
 
   return {
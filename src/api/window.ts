@@ -0,0 +1,180 @@
+// Window API
+// Additional windows scoped to an instance (log viewer, metrics) or a profile (profile editor)
+
+import { invoke } from '@tauri-apps/api/core';
+import type { AemInstanceStatus } from './instance';
+
+export type WindowKind = 'log_viewer' | 'metrics' | 'profile_editor';
+
+export interface WindowContext {
+  label: string;
+  kind: WindowKind;
+  instance_id: string | null;
+  profile_id: string | null;
+}
+
+/**
+ * Open (or focus) a context window. Log viewer and metrics need an instance,
+ * the profile editor a profile.
+ */
+export async function openContextWindow(
+  kind: WindowKind,
+  target: { instanceId?: string; profileId?: string }
+): Promise<WindowContext> {
+  return invoke<WindowContext>('open_context_window', {
+    kind,
+    instanceId: target.instanceId ?? null,
+    profileId: target.profileId ?? null,
+  });
+}
+
+/**
+ * Context of the current window (null in the main window)
+ */
+export async function getWindowContext(): Promise<WindowContext | null> {
+  return invoke<WindowContext | null>('get_window_context');
+}
+
+export async function listContextWindows(): Promise<WindowContext[]> {
+  return invoke<WindowContext[]>('list_context_windows');
+}
+
+export async function closeContextWindow(label: string): Promise<void> {
+  return invoke<void>('close_context_window', { label });
+}
+
+/**
+ * Emit an event to the main window and the windows of one instance or profile only.
+ * Returns the labels of the windows that received it.
+ */
+export async function emitToContextWindows(
+  event: string,
+  payload: unknown,
+  target: { instanceId?: string; profileId?: string } = {}
+): Promise<string[]> {
+  return invoke<string[]>('emit_to_context_windows', {
+    event,
+    payload,
+    instanceId: target.instanceId ?? null,
+    profileId: target.profileId ?? null,
+  });
+}
+
+// ============================================
+// Quit check
+// ============================================
+
+/** Event emitted by the tray Quit item while local instances are still running */
+export const QUIT_REQUESTED_EVENT = 'quit-requested';
+
+export interface RunningInstance {
+  id: string;
+  name: string;
+  port: number;
+  status: AemInstanceStatus;
+}
+
+export type QuitDecision = 'stop_instances' | 'leave_running' | 'cancel';
+
+export interface QuitResult {
+  stopped: string[];
+  failed: [string, string][];
+}
+
+/**
+ * Local instances started by this app that are still running
+ */
+export async function getRunningInstancesForQuit(): Promise<RunningInstance[]> {
+  return invoke<RunningInstance[]>('get_running_instances_for_quit');
+}
+
+/**
+ * Answer the quit prompt. Resolves only when the app stays open
+ * (cancelled, or some instances could not be stopped).
+ */
+export async function resolveQuit(decision: QuitDecision): Promise<QuitResult> {
+  return invoke<QuitResult>('resolve_quit', { decision });
+}
+
+// ============================================
+// Startup recovery
+// ============================================
+
+/** Event emitted at startup when persisted instance states had to be corrected */
+export const STATE_RECOVERED_EVENT = 'state-recovered';
+
+export interface ReconciledInstance {
+  instance_id: string;
+  name: string;
+  previous_status: AemInstanceStatus;
+  status: AemInstanceStatus;
+  previous_process_id: number | null;
+  process_id: number | null;
+  reason: string;
+}
+
+export interface RecoveryReport {
+  timestamp: string;
+  checked: number;
+  reconciled: ReconciledInstance[];
+}
+
+/**
+ * What the startup recovery corrected (null while it is still running)
+ */
+export async function getStartupRecoveryReport(): Promise<RecoveryReport | null> {
+  return invoke<RecoveryReport | null>('get_startup_recovery_report');
+}
+
+/**
+ * Probe all instances and correct persisted statuses now
+ */
+export async function reconcileInstanceStates(): Promise<RecoveryReport> {
+  return invoke<RecoveryReport>('reconcile_instance_states');
+}
+
+// ============================================
+// Data integrity
+// ============================================
+
+/** Event emitted at startup when the integrity check found problems in the stored data */
+export const INTEGRITY_REPORT_EVENT = 'integrity-report';
+
+export type IntegrityIssueKind =
+  | 'unreadable'
+  | 'invalid_json'
+  | 'schema_mismatch'
+  | 'unsupported_schema_version'
+  | 'dangling_reference';
+
+export interface IntegrityIssue {
+  /** Store name, e.g. instances.json, profiles/<id>.json or config/config.json */
+  store: string;
+  kind: IntegrityIssueKind;
+  detail: string;
+  repaired: boolean;
+  /** Backup the store was restored from */
+  restored_from: string | null;
+  /** Where the corrupt file was moved */
+  quarantined_to: string | null;
+}
+
+export interface IntegrityReport {
+  timestamp: string;
+  checked: number;
+  issues: IntegrityIssue[];
+}
+
+/**
+ * What the startup integrity check found
+ */
+export async function getIntegrityReport(): Promise<IntegrityReport | null> {
+  return invoke<IntegrityReport | null>('get_integrity_report');
+}
+
+/**
+ * Check the stored data now, restoring corrupt files from backups
+ */
+export async function runIntegrityCheck(): Promise<IntegrityReport> {
+  return invoke<IntegrityReport>('run_integrity_check');
+}
@@ -1,6 +1,7 @@
 import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { Globe, Check } from 'lucide-react';
+import { saveAppConfig } from '@/api/profile';
 
 interface Language {
   code: string;
@@ -22,6 +23,10 @@ export function LanguageSwitcher() {
 
   const handleLanguageChange = (code: string) => {
     i18n.changeLanguage(code);
+    // Keep backend messages (tray menu, errors) in the same language
+    saveAppConfig({ language: code }).catch((e) =>
+      console.error('Failed to save language to backend:', e)
+    );
     setIsOpen(false);
   };
 
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
 import { useNavigate } from 'react-router-dom';
+import { listen } from '@tauri-apps/api/event';
 import { useTranslation } from 'react-i18next';
 import {
   Server,
@@ -15,8 +16,14 @@ import * as instanceApi from '../../api/instance';
 import { mapApiInstanceToFrontend } from '../../api/mappers';
 import type { AEMInstance, AEMInstanceStatus } from '../../types';
 import type { InstanceStatusResult } from '../../api/instance';
+import type { DashboardInstance } from '../../api/dashboard';
 
-export function AemInstanceCards() {
+interface AemInstanceCardsProps {
+  /** Last detected statuses from the dashboard state */
+  dashboardInstances?: DashboardInstance[];
+}
+
+export function AemInstanceCards({ dashboardInstances }: AemInstanceCardsProps) {
   const { t } = useTranslation();
   const navigate = useNavigate();
   const allInstances = useAemInstances();
@@ -97,27 +104,41 @@ export function AemInstanceCards() {
     }
   }, [activeProfile, updateAemInstance, addNotification, t]);
 
-  // Auto-refresh status based on config
+  // Initial statuses come from the dashboard state instead of a detection per instance,
+  // applied once the instance list is loaded so it does not overwrite them
   useEffect(() => {
-    // Initial status check on mount (always perform once)
-    refreshAllStatuses();
+    if (isLoading || !dashboardInstances) return;
 
-    // Only set up interval if auto status check is enabled
-    if (!config.autoStatusCheck) {
-      return;
+    let latestCheck: string | null = null;
+    for (const inst of dashboardInstances) {
+      updateAemInstance(inst.id, { status: inst.status as AEMInstanceStatus });
+      if (inst.checked_at && (!latestCheck || inst.checked_at > latestCheck)) {
+        latestCheck = inst.checked_at;
+      }
     }
+    setLastStatusCheck(latestCheck);
+  }, [isLoading, dashboardInstances, updateAemInstance]);
 
-    // Set up interval for periodic refresh (interval in seconds, convert to ms)
-    const intervalMs = config.statusCheckInterval * 1000;
-    const intervalId = setInterval(() => {
-      refreshAllStatuses();
-    }, intervalMs);
+  // Afterwards follow the status results pushed by the backend health scheduler
+  useEffect(() => {
+    if (!activeProfile || !config.autoStatusCheck) return;
+
+    const instanceIds = [activeProfile.authorInstanceId, activeProfile.publishInstanceId];
+    const unlisten = listen<InstanceStatusResult[]>(instanceApi.INSTANCE_STATUS_EVENT, (event) => {
+      const newMap = new Map<string, InstanceStatusResult>();
+      for (const result of event.payload) {
+        if (!instanceIds.includes(result.instance_id)) continue;
+        newMap.set(result.instance_id, result);
+        updateAemInstance(result.instance_id, { status: result.status as AEMInstanceStatus });
+      }
+      setStatusResults(newMap);
+      setLastStatusCheck(new Date().toISOString());
+    });
 
-    // Cleanup on unmount
     return () => {
-      clearInterval(intervalId);
+      unlisten.then((fn) => fn());
     };
-  }, [refreshAllStatuses, config.autoStatusCheck, config.statusCheckInterval]);
+  }, [activeProfile, config.autoStatusCheck, updateAemInstance]);
 
   const handleStart = async (instanceId: string, instanceName: string) => {
     // 5-second debounce to prevent multiple terminal windows
@@ -153,7 +174,14 @@ export function AemInstanceCards() {
 
   const handleOpenInBrowser = async (instanceId: string, path: string = '') => {
     try {
-      await instanceApi.openInBrowser(instanceId, path);
+      const result = await instanceApi.openInBrowser(instanceId, path);
+      if (!result.opened) {
+        addNotification({
+          type: 'warning',
+          title: t('instance.notifications.openBrowserFailed'),
+          message: t('dashboard.instanceNotRunning'),
+        });
+      }
     } catch (error) {
       addNotification({
         type: 'error',
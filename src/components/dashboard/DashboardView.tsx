@@ -1,11 +1,33 @@
+import { useState, useEffect, useCallback } from 'react';
 import { useTranslation } from 'react-i18next';
 import { ProfileSwitcher } from './ProfileSwitcher';
 import { StatusCards } from './StatusCards';
 import { QuickActionsPanel } from './QuickActionsPanel';
 import { AemInstanceCards } from './AemInstanceCards';
+import * as dashboardApi from '../../api/dashboard';
+import { useActiveProfile } from '../../store';
 
 export function DashboardView() {
   const { t } = useTranslation();
+  const activeProfile = useActiveProfile();
+  const [dashboardState, setDashboardState] = useState<dashboardApi.DashboardState | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+
+  const loadDashboardState = useCallback(async () => {
+    setIsLoading(true);
+    try {
+      setDashboardState(await dashboardApi.getDashboardState());
+    } catch {
+      // Failed to load dashboard state
+    } finally {
+      setIsLoading(false);
+    }
+  }, []);
+
+  // Reload dashboard state on mount and when active profile changes
+  useEffect(() => {
+    loadDashboardState();
+  }, [loadDashboardState, activeProfile?.id]);
 
   return (
     <div className="space-y-6">
@@ -24,10 +46,10 @@ export function DashboardView() {
       <QuickActionsPanel />
 
       {/* Status Cards - 3 columns */}
-      <StatusCards />
+      <StatusCards state={dashboardState} isLoading={isLoading} />
 
       {/* AEM Instances Section */}
-      <AemInstanceCards />
+      <AemInstanceCards dashboardInstances={dashboardState?.instances} />
     </div>
   );
 }
@@ -73,7 +73,14 @@ export function QuickActionsPanel() {
     }
 
     try {
-      await instanceApi.openInBrowser(activeInstance.id, path);
+      const result = await instanceApi.openInBrowser(activeInstance.id, path);
+      if (!result.opened) {
+        addNotification({
+          type: 'warning',
+          title: t('dashboard.quickActions'),
+          message: t('dashboard.instanceNotRunning', 'AEM 实例未运行'),
+        });
+      }
     } catch (error) {
       addNotification({
         type: 'error',
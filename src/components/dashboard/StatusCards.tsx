@@ -1,8 +1,7 @@
-import { useState, useEffect, useCallback } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
 import { RefreshCw, ExternalLink } from 'lucide-react';
-import * as versionApi from '../../api/version';
+import type { DashboardState } from '../../api/dashboard';
 import { useActiveProfile } from '../../store';
 
 interface StatusCardProps {
@@ -72,50 +71,32 @@ function StatusCard({
   );
 }
 
-export function StatusCards() {
+interface StatusCardsProps {
+  state: DashboardState | null;
+  isLoading: boolean;
+}
+
+export function StatusCards({ state, isLoading }: StatusCardsProps) {
   const { t } = useTranslation();
   const navigate = useNavigate();
   const activeProfile = useActiveProfile();
-  const [versionInfo, setVersionInfo] = useState<versionApi.VersionInfo | null>(null);
-  const [isLoading, setIsLoading] = useState(true);
-
-  const loadVersionInfo = useCallback(async () => {
-    setIsLoading(true);
-    try {
-      const info = await versionApi.getAllVersionInfo();
-      setVersionInfo(info);
-    } catch {
-      // Failed to load version info
-    } finally {
-      setIsLoading(false);
-    }
-  }, []);
-
-  // Reload version info on mount and when active profile changes
-  useEffect(() => {
-    loadVersionInfo();
-  }, [loadVersionInfo, activeProfile?.id]);
 
   // Extract Java major version number
-  const javaVersion = versionInfo?.java.current;
+  const javaVersion = state?.java_version;
   const javaDisplay = javaVersion ? javaVersion.split('.')[0] : '--';
-  const javaSubtext = versionInfo?.java.current
-    ? `${versionInfo.java.current} Corretto`
-    : t('dashboard.stats.notDetected');
+  const javaSubtext = javaVersion ? `${javaVersion} Corretto` : t('dashboard.stats.notDetected');
 
   // Extract Node major version number
-  const nodeVersion = versionInfo?.node.current;
+  const nodeVersion = state?.node_version;
   const nodeDisplay = nodeVersion ? nodeVersion.replace('v', '').split('.')[0] : '--';
-  const nodeSubtext = versionInfo?.node.current
-    ? `${versionInfo.node.current} LTS`
-    : t('dashboard.stats.notDetected');
+  const nodeSubtext = nodeVersion ? `${nodeVersion} LTS` : t('dashboard.stats.notDetected');
 
   // Maven config - prefer profile's maven_config_id over current config name
   const mavenConfigId = activeProfile?.mavenConfigId;
-  const mavenName = versionInfo?.maven.current?.name || 'default';
+  const mavenName = state?.maven_config?.name || 'default';
   // Simplify "Current settings.xml" to just "default" for display
   const mavenDisplay = mavenConfigId || (mavenName.startsWith('Current ') ? 'default' : mavenName);
-  const mavenPath = versionInfo?.maven.current?.path || '~/.m2/settings.xml';
+  const mavenPath = state?.maven_config?.path || '~/.m2/settings.xml';
   const mavenSubtext = mavenPath.split('/').slice(-2).join('/');
 
   return (
@@ -126,7 +107,7 @@ export function StatusCards() {
         label="JAVA"
         value={javaDisplay}
         subtext={javaSubtext}
-        isActive={!!versionInfo?.java.current}
+        isActive={!!javaVersion}
         isLoading={isLoading}
         onClick={() => navigate('/java')}
         t={t}
@@ -138,7 +119,7 @@ export function StatusCards() {
         label="NODE"
         value={nodeDisplay}
         subtext={nodeSubtext}
-        isActive={!!versionInfo?.node.current}
+        isActive={!!nodeVersion}
         isLoading={isLoading}
         onClick={() => navigate('/node')}
         t={t}
@@ -150,7 +131,7 @@ export function StatusCards() {
         label="MAVEN"
         value={mavenDisplay}
         subtext={mavenSubtext}
-        isActive={!!versionInfo?.maven.current}
+        isActive={!!state?.maven_config}
         isLoading={isLoading}
         onClick={() => navigate('/maven')}
         t={t}
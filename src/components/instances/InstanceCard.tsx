@@ -67,6 +67,14 @@ export function InstanceCard({
         </div>
 
         <div className="flex items-center gap-2">
+          {instance.wslDistro && (
+            <span
+              className="px-2 py-0.5 rounded text-xs font-medium bg-orange-100 text-orange-700 dark:bg-orange-900/30 dark:text-orange-300"
+              title={t('instance.card.wsl', { distro: instance.wslDistro })}
+            >
+              WSL
+            </span>
+          )}
           <span
             className={`px-2 py-0.5 rounded text-xs font-medium ${typeColors[instance.instanceType]}`}
           >
@@ -0,0 +1,27 @@
+import { useEffect } from 'react';
+import { Outlet } from 'react-router-dom';
+import { NotificationToast } from '../common/NotificationToast';
+import { initializeTheme } from '../common/ThemeToggle';
+import { useConfig } from '../../store';
+
+/**
+ * Layout of the windows opened for one instance or profile (no sidebar or status bar)
+ */
+export function ContextWindowLayout() {
+  const config = useConfig();
+
+  useEffect(() => {
+    const cleanup = initializeTheme(config.theme);
+    return cleanup;
+  }, [config.theme]);
+
+  return (
+    <div className="flex flex-col h-screen sky-bg">
+      <main className="flex-1 overflow-auto p-6 scrollbar-thin">
+        <Outlet />
+      </main>
+
+      <NotificationToast />
+    </div>
+  );
+}
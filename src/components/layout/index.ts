@@ -2,3 +2,4 @@ export { Sidebar } from './Sidebar';
 export { Header } from './Header';
 export { MainLayout } from './MainLayout';
 export { StatusBar } from './StatusBar';
+export { ContextWindowLayout } from './ContextWindowLayout';
@@ -7,6 +7,7 @@ import { Card, CardHeader, CardContent } from '@/components/common/Card';
 import { Button } from '@/components/common/Button';
 import { useAppStore } from '@/store';
 import * as settingsApi from '@/api/settings';
+import { backendMessage } from '@/api/messages';
 
 export function DataSettings() {
   const { t } = useTranslation();
@@ -25,10 +26,12 @@ export function DataSettings() {
         addNotification({
           type: 'success',
           title: t('settings.data.exportSuccess'),
-          message: t('settings.data.exportedCount', {
-            profiles: result.profiles_count,
-            instances: result.instances_count,
-          }),
+          message:
+            backendMessage(result) ||
+            t('settings.data.exportedCount', {
+              profiles: result.profiles_count,
+              instances: result.instances_count,
+            }),
         });
       } else if (result.error !== '操作已取消') {
         addNotification({
@@ -56,10 +59,12 @@ export function DataSettings() {
         addNotification({
           type: 'success',
           title: t('settings.data.importSuccess'),
-          message: t('settings.data.importedCount', {
-            profiles: result.profiles_imported,
-            instances: result.instances_imported,
-          }),
+          message:
+            backendMessage(result) ||
+            t('settings.data.importedCount', {
+              profiles: result.profiles_imported,
+              instances: result.instances_imported,
+            }),
         });
         // Reload the page to reflect imported data
         window.location.reload();
@@ -91,10 +96,12 @@ export function DataSettings() {
         addNotification({
           type: 'success',
           title: t('settings.data.resetSuccess'),
-          message: t('settings.data.resetCount', {
-            profiles: result.profiles_deleted,
-            instances: result.instances_deleted,
-          }),
+          message:
+            backendMessage(result) ||
+            t('settings.data.resetCount', {
+              profiles: result.profiles_deleted,
+              instances: result.instances_deleted,
+            }),
         });
         setShowResetConfirm(false);
         // Reload the page to reflect reset state
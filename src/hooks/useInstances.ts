@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { useTranslation } from 'react-i18next';
+import { listen } from '@tauri-apps/api/event';
 import { useAppStore, useAemInstances, useIsLoading } from '../store';
 import * as instanceApi from '../api/instance';
 import { mapApiInstanceToFrontend } from '../api/mappers';
@@ -190,7 +191,14 @@ export function useInstanceManager() {
   const openInBrowser = useCallback(
     async (instanceId: string, path?: string) => {
       try {
-        await instanceApi.openInBrowser(instanceId, path);
+        const result = await instanceApi.openInBrowser(instanceId, path);
+        if (!result.opened) {
+          addNotification({
+            type: 'warning',
+            title: t('instance.notifications.openBrowserFailed'),
+            message: t('dashboard.instanceNotRunning'),
+          });
+        }
       } catch (err) {
         addNotification({
           type: 'error',
@@ -267,6 +275,23 @@ export function useInstanceManager() {
     }
   }, [t, updateInstance, addNotification]);
 
+  // Status results pushed by the backend health scheduler
+  useEffect(() => {
+    const unlisten = listen<InstanceStatusResult[]>(instanceApi.INSTANCE_STATUS_EVENT, (event) => {
+      const newMap = new Map<string, InstanceStatusResult>();
+      for (const result of event.payload) {
+        newMap.set(result.instance_id, result);
+        updateInstance(result.instance_id, { status: result.status as AEMInstanceStatus });
+      }
+      setStatusResults(newMap);
+      setLastStatusCheck(new Date().toISOString());
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [updateInstance]);
+
   // Load instances on mount (only once)
   useEffect(() => {
     if (!hasLoadedRef.current) {
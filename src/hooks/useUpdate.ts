@@ -14,7 +14,6 @@
 import { useCallback, useEffect, useRef } from 'react';
 import { useAppStore, useConfig, useUpdateState, useUpdateActions } from '@/store';
 import * as updateApi from '@/api/update';
-import { clearUpdateCache } from '@/api/update';
 import type { UpdateInfo } from '@/api/update';
 import i18n from '@/i18n';
 
@@ -60,14 +59,15 @@ export function useUpdate() {
   /**
    * Check for updates
    *
-   * @param silent - If true, don't show notifications for no updates or errors
+   * @param silent - Automatic check: the update policy applies and no notifications are shown
+   *   for no updates or errors
    */
   const checkUpdate = useCallback(
     async (silent = false) => {
       setUpdateState({ updateChecking: true, updateError: null });
 
       try {
-        const result = await updateApi.checkForUpdate();
+        const result = await updateApi.checkForUpdate(!silent);
 
         // Update last check timestamp
         updateConfig({ lastUpdateCheck: new Date().toISOString() });
@@ -170,7 +170,6 @@ export function useUpdate() {
    * User can check again from Settings.
    */
   const dismissUpdate = useCallback(() => {
-    clearUpdateCache();
     storeDismissUpdate();
   }, [storeDismissUpdate]);
 
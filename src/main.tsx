@@ -13,6 +13,8 @@ function App() {
 
     const setupCloseHandler = async () => {
       const appWindow = getCurrentWindow();
+      // Context windows (logs, metrics, profile editor) close normally
+      if (appWindow.label !== 'main') return;
 
       unlisten = await appWindow.onCloseRequested(async (event) => {
         // Prevent the window from closing
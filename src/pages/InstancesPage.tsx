@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useSearchParams } from 'react-router-dom';
+import { listen } from '@tauri-apps/api/event';
 import { Plus, RefreshCw, Terminal } from 'lucide-react';
 import { Button } from '@/components/common/Button';
 import { ConfirmDialog } from '@/components/common/ConfirmDialog';
@@ -89,27 +90,31 @@ export function InstancesPage() {
     }
   }, [activeProfile, updateInstance]);
 
-  // Auto-refresh status based on config
+  // Initial status check on mount (always perform once)
   useEffect(() => {
-    // Initial status check on mount (always perform once)
     refreshAllStatuses();
+  }, [refreshAllStatuses]);
 
-    // Only set up interval if auto status check is enabled
-    if (!config.autoStatusCheck) {
-      return;
-    }
+  // Afterwards follow the status results pushed by the backend health scheduler
+  useEffect(() => {
+    if (!activeProfile || !config.autoStatusCheck) return;
 
-    // Set up interval for periodic refresh (interval in seconds, convert to ms)
-    const intervalMs = config.statusCheckInterval * 1000;
-    const intervalId = setInterval(() => {
-      refreshAllStatuses();
-    }, intervalMs);
+    const instanceIds = [activeProfile.authorInstanceId, activeProfile.publishInstanceId];
+    const unlisten = listen<InstanceStatusResult[]>(instanceApi.INSTANCE_STATUS_EVENT, (event) => {
+      const newMap = new Map<string, InstanceStatusResult>();
+      for (const result of event.payload) {
+        if (!instanceIds.includes(result.instance_id)) continue;
+        newMap.set(result.instance_id, result);
+        updateInstance(result.instance_id, { status: result.status as AEMInstanceStatus });
+      }
+      setStatusResults(newMap);
+      setLastStatusCheck(new Date().toISOString());
+    });
 
-    // Cleanup on unmount
     return () => {
-      clearInterval(intervalId);
+      unlisten.then((fn) => fn());
     };
-  }, [refreshAllStatuses, config.autoStatusCheck, config.statusCheckInterval]);
+  }, [activeProfile, config.autoStatusCheck, updateInstance]);
 
   const handleRefreshAll = async () => {
     setIsRefreshing(true);
@@ -0,0 +1,121 @@
+import { useState, useEffect, useRef } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useSearchParams } from 'react-router-dom';
+import { listen } from '@tauri-apps/api/event';
+import * as instanceApi from '@/api/instance';
+import type { LogLines } from '@/api/instance';
+
+/** Lines kept in the view; older ones are dropped */
+const MAX_LINES = 5000;
+
+const DEFAULT_LOG = 'error.log';
+
+export function LogViewerWindow() {
+  const { t } = useTranslation();
+  const [searchParams] = useSearchParams();
+  const instanceId = searchParams.get('instance');
+  const [instanceName, setInstanceName] = useState<string | null>(null);
+  const [logs, setLogs] = useState<instanceApi.LogFile[]>([]);
+  const [log, setLog] = useState(DEFAULT_LOG);
+  const [lines, setLines] = useState<string[]>([]);
+  const [droppedLines, setDroppedLines] = useState(0);
+  const bottomRef = useRef<HTMLDivElement>(null);
+
+  useEffect(() => {
+    if (!instanceId) return;
+    instanceApi
+      .getInstance(instanceId)
+      .then((instance) => setInstanceName(instance?.name ?? null))
+      .catch(() => setInstanceName(null));
+    instanceApi
+      .listInstanceLogs(instanceId)
+      .then(setLogs)
+      .catch(() => setLogs([]));
+  }, [instanceId]);
+
+  // Follow the selected log; the tail is stopped when the log changes or the window closes
+  useEffect(() => {
+    if (!instanceId) return;
+
+    let tailId: string | null = null;
+    let cancelled = false;
+    setLines([]);
+    setDroppedLines(0);
+
+    const unlisten = listen<LogLines>(instanceApi.LOG_LINES_EVENT, (event) => {
+      const payload = event.payload;
+      if (payload.instance_id !== instanceId || payload.log !== log) return;
+      setDroppedLines((count) => count + payload.dropped_lines);
+      setLines((current) => {
+        const base = payload.rotated ? [] : current;
+        return [...base, ...payload.lines].slice(-MAX_LINES);
+      });
+    });
+
+    instanceApi
+      .startLogTail(instanceId, log, 500)
+      .then((tail) => {
+        if (cancelled) {
+          instanceApi.stopLogTail(tail.tail_id);
+        } else {
+          tailId = tail.tail_id;
+        }
+      })
+      .catch((error) => {
+        setLines([error instanceof Error ? error.message : String(error)]);
+      });
+
+    return () => {
+      cancelled = true;
+      unlisten.then((fn) => fn());
+      if (tailId) {
+        instanceApi.stopLogTail(tailId);
+      }
+    };
+  }, [instanceId, log]);
+
+  useEffect(() => {
+    bottomRef.current?.scrollIntoView({ block: 'end' });
+  }, [lines]);
+
+  if (!instanceId) {
+    return <p className="text-slate-500 dark:text-gray-400">{t('windows.missingTarget')}</p>;
+  }
+
+  return (
+    <div className="flex flex-col h-full gap-4">
+      <div className="flex items-center justify-between gap-4">
+        <h1 className="text-xl font-bold text-slate-900 dark:text-gray-100 truncate">
+          {t('windows.logs.title', { name: instanceName ?? instanceId })}
+        </h1>
+        <select className="select w-56" value={log} onChange={(e) => setLog(e.target.value)}>
+          {!logs.some((file) => file.name === log) && <option value={log}>{log}</option>}
+          {logs.map((file) => (
+            <option key={file.name} value={file.name}>
+              {file.name === instanceApi.CONSOLE_LOG ? t('windows.logs.console') : file.name}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      {droppedLines > 0 && (
+        <p className="text-amber-600 dark:text-amber-400 text-sm">
+          {t('windows.logs.droppedLines', { count: droppedLines })}
+        </p>
+      )}
+
+      <div className="panel flex-1 overflow-auto p-4 font-mono text-xs leading-relaxed scrollbar-thin">
+        {lines.length === 0 ? (
+          <span className="text-slate-400 dark:text-gray-500">{t('windows.logs.waiting')}</span>
+        ) : (
+          lines.map((line, index) => (
+            <div key={index} className="whitespace-pre-wrap break-all text-slate-700 dark:text-gray-300">
+              {line}
+            </div>
+          ))
+        )}
+        <div ref={bottomRef} />
+      </div>
+    </div>
+  );
+}
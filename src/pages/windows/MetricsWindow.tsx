@@ -0,0 +1,134 @@
+import { useState, useEffect, useCallback } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useSearchParams } from 'react-router-dom';
+import { RefreshCw } from 'lucide-react';
+import * as instanceApi from '@/api/instance';
+
+/** Range shown in the window */
+const RANGE_HOURS = 24;
+
+/** Samples listed below the summary */
+const MAX_POINTS = 48;
+
+export function MetricsWindow() {
+  const { t } = useTranslation();
+  const [searchParams] = useSearchParams();
+  const instanceId = searchParams.get('instance');
+  const [instanceName, setInstanceName] = useState<string | null>(null);
+  const [uptime, setUptime] = useState<instanceApi.UptimeSummary | null>(null);
+  const [samples, setSamples] = useState<instanceApi.MetricSample[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+
+  const loadMetrics = useCallback(async () => {
+    if (!instanceId) return;
+    setIsLoading(true);
+    try {
+      const from = new Date(Date.now() - RANGE_HOURS * 3600 * 1000).toISOString();
+      const [summary, history] = await Promise.all([
+        instanceApi.getInstanceUptime(instanceId, from),
+        instanceApi.getInstanceMetrics(instanceId, from, undefined, MAX_POINTS),
+      ]);
+      setUptime(summary);
+      setSamples(history.reverse());
+    } catch {
+      // Failed to load metrics
+    } finally {
+      setIsLoading(false);
+    }
+  }, [instanceId]);
+
+  useEffect(() => {
+    if (!instanceId) return;
+    instanceApi
+      .getInstance(instanceId)
+      .then((instance) => setInstanceName(instance?.name ?? null))
+      .catch(() => setInstanceName(null));
+    loadMetrics();
+  }, [instanceId, loadMetrics]);
+
+  if (!instanceId) {
+    return <p className="text-slate-500 dark:text-gray-400">{t('windows.missingTarget')}</p>;
+  }
+
+  const summaryItems = uptime
+    ? [
+        { label: t('windows.metrics.uptime'), value: `${uptime.uptime_percentage.toFixed(1)}%` },
+        {
+          label: t('windows.metrics.avgResponseTime'),
+          value: `${Math.round(uptime.avg_response_time_ms)} ms`,
+        },
+        { label: t('windows.metrics.avgHeap'), value: `${Math.round(uptime.avg_heap_used_mb)} MB` },
+        { label: t('windows.metrics.peakHeap'), value: `${Math.round(uptime.peak_heap_used_mb)} MB` },
+      ]
+    : [];
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between gap-4">
+        <h1 className="text-xl font-bold text-slate-900 dark:text-gray-100 truncate">
+          {t('windows.metrics.title', { name: instanceName ?? instanceId })}
+        </h1>
+        <button
+          className="btn-ghost px-3 py-2 text-sm flex items-center gap-2"
+          onClick={loadMetrics}
+          disabled={isLoading}
+        >
+          <RefreshCw size={14} className={isLoading ? 'animate-spin' : ''} />
+          {t('common.refresh')}
+        </button>
+      </div>
+
+      {samples.length === 0 && !isLoading ? (
+        <p className="text-slate-500 dark:text-gray-400">{t('windows.metrics.noData')}</p>
+      ) : (
+        <>
+          <div className="grid grid-cols-2 md:grid-cols-4 gap-4">
+            {summaryItems.map((item) => (
+              <div key={item.label} className="panel p-4">
+                <div className="text-slate-500 dark:text-gray-400 text-xs font-semibold uppercase tracking-wider mb-1">
+                  {item.label}
+                </div>
+                <div className="text-2xl font-bold text-slate-900 dark:text-gray-100">
+                  {item.value}
+                </div>
+              </div>
+            ))}
+          </div>
+
+          <div className="panel p-4 overflow-auto">
+            <table className="w-full text-sm">
+              <thead>
+                <tr className="text-left text-slate-500 dark:text-gray-400">
+                  <th className="py-2 pr-4">{t('windows.metrics.time')}</th>
+                  <th className="py-2 pr-4">{t('windows.metrics.status')}</th>
+                  <th className="py-2 pr-4">{t('windows.metrics.responseTime')}</th>
+                  <th className="py-2 pr-4">{t('windows.metrics.heap')}</th>
+                  <th className="py-2">{t('windows.metrics.bundles')}</th>
+                </tr>
+              </thead>
+              <tbody className="text-slate-700 dark:text-gray-300">
+                {samples.map((sample) => (
+                  <tr key={sample.timestamp} className="border-t border-slate-100 dark:border-gray-800">
+                    <td className="py-2 pr-4">
+                      {new Date(sample.timestamp * 1000).toLocaleString()}
+                    </td>
+                    <td className="py-2 pr-4">
+                      {sample.running ? t('windows.metrics.running') : t('windows.metrics.stopped')}
+                    </td>
+                    <td className="py-2 pr-4">{Math.round(sample.response_time_ms)} ms</td>
+                    <td className="py-2 pr-4">
+                      {Math.round(sample.heap_used_mb)} / {Math.round(sample.heap_max_mb)} MB
+                    </td>
+                    <td className="py-2">
+                      {sample.bundles_active} / {sample.bundles_total}
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
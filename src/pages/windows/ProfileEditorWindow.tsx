@@ -0,0 +1,87 @@
+import { useState, useEffect } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useSearchParams } from 'react-router-dom';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { ProfileForm } from '@/components/profiles';
+import type { ProfileFormData } from '@/components/profiles';
+import { useAppStore } from '@/store';
+import * as profileApi from '@/api/profile';
+
+export function ProfileEditorWindow() {
+  const { t } = useTranslation();
+  const [searchParams] = useSearchParams();
+  const profileId = searchParams.get('profile');
+  const addNotification = useAppStore((s) => s.addNotification);
+  const [profile, setProfile] = useState<profileApi.EnvironmentProfile | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+
+  useEffect(() => {
+    if (!profileId) return;
+    profileApi
+      .getProfile(profileId)
+      .then(setProfile)
+      .catch(() => setProfile(null))
+      .finally(() => setIsLoading(false));
+  }, [profileId]);
+
+  const closeWindow = () => {
+    getCurrentWindow().close();
+  };
+
+  const handleSubmit = async (data: ProfileFormData) => {
+    if (!profile) return;
+
+    try {
+      await profileApi.updateProfile(profile.id, {
+        name: data.name,
+        description: data.description || null,
+        java_version: data.javaVersion,
+        java_manager_id: data.javaManagerId,
+        node_version: data.nodeVersion,
+        node_manager_id: data.nodeManagerId,
+        maven_config_id: data.mavenConfigId,
+        author_instance_id: data.authorInstanceId,
+        publish_instance_id: data.publishInstanceId,
+        env_vars: data.envVars,
+      });
+      closeWindow();
+    } catch (error) {
+      addNotification({
+        type: 'error',
+        title: t('profile.notifications.updateFailed'),
+        message: error instanceof Error ? error.message : t('common.unknown'),
+      });
+    }
+  };
+
+  if (!profileId) {
+    return <p className="text-slate-500 dark:text-gray-400">{t('windows.missingTarget')}</p>;
+  }
+  if (isLoading) {
+    return null;
+  }
+  if (!profile) {
+    return <p className="text-slate-500 dark:text-gray-400">{t('windows.profile.notFound')}</p>;
+  }
+
+  return (
+    <ProfileForm
+      isOpen
+      onClose={closeWindow}
+      onSubmit={handleSubmit}
+      title={t('profile.edit')}
+      initialData={{
+        name: profile.name,
+        description: profile.description || '',
+        javaVersion: profile.java_version,
+        nodeVersion: profile.node_version,
+        javaManagerId: profile.java_manager_id,
+        nodeManagerId: profile.node_manager_id,
+        mavenConfigId: profile.maven_config_id,
+        authorInstanceId: profile.author_instance_id,
+        publishInstanceId: profile.publish_instance_id,
+        envVars: profile.env_vars || {},
+      }}
+    />
+  );
+}
@@ -0,0 +1,3 @@
+export { LogViewerWindow } from './LogViewerWindow';
+export { MetricsWindow } from './MetricsWindow';
+export { ProfileEditorWindow } from './ProfileEditorWindow';
@@ -1,11 +1,12 @@
 import { createBrowserRouter, RouterProvider, Navigate } from 'react-router-dom';
-import { MainLayout } from '@/components/layout';
+import { MainLayout, ContextWindowLayout } from '@/components/layout';
 import { DashboardView } from '@/components/dashboard';
 import { ProfilesPage } from '@/pages/ProfilesPage';
 import { InstancesPage } from '@/pages/InstancesPage';
 import { VersionsPage } from '@/pages/VersionsPage';
 import { SettingsPage } from '@/pages/SettingsPage';
 import { WizardPage } from '@/pages/WizardPage';
+import { LogViewerWindow, MetricsWindow, ProfileEditorWindow } from '@/pages/windows';
 import { ErrorBoundary, RequireSetup } from '@/components/common';
 
 const router = createBrowserRouter([
@@ -13,6 +14,25 @@ const router = createBrowserRouter([
     path: '/wizard',
     element: <WizardPage />,
   },
+  // Windows opened for one instance or profile (see openContextWindow)
+  {
+    path: '/window',
+    element: <ContextWindowLayout />,
+    children: [
+      {
+        path: 'logs',
+        element: <LogViewerWindow />,
+      },
+      {
+        path: 'metrics',
+        element: <MetricsWindow />,
+      },
+      {
+        path: 'profile',
+        element: <ProfileEditorWindow />,
+      },
+    ],
+  },
   {
     path: '/',
     element: (